@@ -0,0 +1,63 @@
+use ezytutor::errors::ErrorBody;
+use reqwest::StatusCode;
+use thiserror::Error;
+
+pub type ClientResult<T> = Result<T, ClientError>;
+
+// Mirrors the server's `AppError` variants, recovered from the status code
+// and `ErrorBody` of a failed response
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+
+    #[error("Authentication error: {0}")]
+    Authentication(String),
+
+    #[error("Authorization error: {0}")]
+    Authorization(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Precondition required: {0}")]
+    PreconditionRequired(String),
+
+    #[error("Server error: {0}")]
+    Server(String),
+
+    #[error("Unexpected response ({status}): {message}")]
+    Unexpected { status: u16, message: String },
+
+    #[error("Not logged in")]
+    NotLoggedIn,
+
+    #[error("Transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+}
+
+impl ClientError {
+    pub(crate) fn from_response(status: StatusCode, body: &str) -> Self {
+        let message = serde_json::from_str::<ErrorBody>(body)
+            .map(|body| body.error)
+            .unwrap_or_else(|_| body.to_string());
+
+        match status {
+            StatusCode::BAD_REQUEST => ClientError::BadRequest(message),
+            StatusCode::UNAUTHORIZED => ClientError::Authentication(message),
+            StatusCode::FORBIDDEN => ClientError::Authorization(message),
+            StatusCode::NOT_FOUND => ClientError::NotFound(message),
+            StatusCode::CONFLICT => ClientError::Conflict(message),
+            StatusCode::PRECONDITION_FAILED => ClientError::PreconditionFailed(message),
+            StatusCode::PRECONDITION_REQUIRED => ClientError::PreconditionRequired(message),
+            status if status.is_server_error() => ClientError::Server(message),
+            status => ClientError::Unexpected { status: status.as_u16(), message },
+        }
+    }
+}
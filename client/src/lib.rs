@@ -0,0 +1,941 @@
+//! Typed async client for the EzyTutor REST API.
+//!
+//! Requests and responses use the server's own `ezytutor::models` types.
+//! After `login` the client keeps the session fresh on its own: tokens close
+//! to expiry are exchanged through `/auth/refresh`, and a request rejected
+//! with 401 triggers one re-login with the stored credentials before it is
+//! retried.
+
+pub mod error;
+
+pub use error::{ClientError, ClientResult};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::Utc;
+use ezytutor::middleware::Claims;
+use ezytutor::models::{
+    AssignmentFileResponse, AssignmentRequest, AssignmentResponse, AuditEntry, AuditLogQuery,
+    BlackoutResponse, BookingActionRequest, BookingPolicyResponse, BookingResponse, BookingStatus,
+    CalendarFeedResponse, CertificateResponse, ChangeRoleRequest, CompleteLessonRequest,
+    CourseOutlineResponse, CourseProgressDetailResponse, CourseProgressResponse, CourseResponse,
+    CreateBlackoutRequest, CreateBookingRequest, CreateCourseRequest, CreateExceptionRequest,
+    CreateLessonRequest, CreateReviewRequest, CreateTutorProfileRequest, CreateUserRequest,
+    DeletedCourseResponse, EnrollmentResponse, EnrollmentStatus, ExceptionResponse,
+    GradeSubmissionRequest, HealthResponse, LessonCompletionResponse, LessonResponse, LoginRequest,
+    LoginResponse, ModerationRequest, ModuleRequest, ModuleResponse, NotificationResponse,
+    OpenSlotsQuery, OpenSlotsResponse, Page, QuizAttemptResponse, QuizRequest, QuizResponse,
+    QuizSheetResponse, QuizSummary, ReorderCurriculumRequest, RescheduleBookingRequest, ReviewEdit,
+    ReviewReplyRequest, ReviewResponse, ScheduleResponse, SetBookingPolicyRequest,
+    SetScheduleRequest, StoredFileResponse, SubmissionResponse, SubmitAssignmentRequest,
+    SubmitQuizRequest, SubmitVerificationRequest, TutorResponse, TutorVerificationResponse,
+    UpdateCalendarFeedRequest, UpdateCourseRequest, UpdateLessonRequest, UpdateReviewRequest,
+    UpdateTutorProfileRequest, UserResponse, UserRole, UserSearchQuery, VerificationDecisionRequest,
+    VerificationEvent, VerificationRequestResponse, VerificationStatus,
+};
+use reqwest::header::{CONTENT_TYPE, IF_MATCH, IF_NONE_MATCH};
+use reqwest::{Method, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+// Tokens expiring within this many seconds are refreshed before use
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+#[derive(Default)]
+struct Session {
+    token: Option<String>,
+    credentials: Option<LoginRequest>,
+}
+
+impl Session {
+    fn store(&mut self, token: String) {
+        self.token = Some(token);
+    }
+}
+
+// A request body: JSON, optionally conditional on the version it edits, or
+// a file sent as is
+enum Body<'a, B: ?Sized> {
+    Json(&'a B),
+    // An update of the version named by `etag`
+    JsonIfMatch { body: &'a B, etag: &'a str },
+    File { content_type: &'a str, content: &'a [u8] },
+}
+
+// Derived impls would require `B: Copy`
+impl<B: ?Sized> Clone for Body<'_, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B: ?Sized> Copy for Body<'_, B> {}
+
+// Reads the `exp` claim without verifying the signature; the server remains
+// the authority on whether a token is valid
+fn token_expiry(token: &str) -> Option<i64> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = URL_SAFE_NO_PAD.decode(payload).ok()?;
+    let claims: Claims = serde_json::from_slice(&bytes).ok()?;
+    Some(claims.exp as i64)
+}
+
+// A multipart/form-data body holding one part named "file"; returns the
+// content type with its boundary and the encoded form
+fn file_form(file_name: &str, content_type: &str, content: &[u8]) -> (String, Vec<u8>) {
+    let boundary = format!("ezytutor-{}", Uuid::new_v4().simple());
+    let mut form = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+        boundary,
+        file_name.replace('"', "%22"),
+        content_type,
+    ).into_bytes();
+    form.extend_from_slice(content);
+    form.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+    (format!("multipart/form-data; boundary={}", boundary), form)
+}
+
+#[derive(Clone)]
+pub struct EzyTutorClient {
+    http: reqwest::Client,
+    base_url: Url,
+    session: Arc<Mutex<Session>>,
+}
+
+impl EzyTutorClient {
+    // `base_url` is the API root, e.g. `http://localhost:8080/api/v1`
+    pub fn new(base_url: &str) -> ClientResult<Self> {
+        Self::with_http_client(base_url, reqwest::Client::new())
+    }
+
+    pub fn with_http_client(base_url: &str, http: reqwest::Client) -> ClientResult<Self> {
+        let base_url = Url::parse(&format!("{}/", base_url.trim_end_matches('/')))
+            .map_err(|e| ClientError::BadRequest(format!("Invalid base URL: {}", e)))?;
+
+        Ok(EzyTutorClient {
+            http,
+            base_url,
+            session: Arc::new(Mutex::new(Session::default())),
+        })
+    }
+
+    // Resumes a session from a previously issued token
+    pub async fn set_token(&self, token: impl Into<String>) {
+        self.session.lock().await.store(token.into());
+    }
+
+    pub async fn token(&self) -> Option<String> {
+        self.session.lock().await.token.clone()
+    }
+
+    pub async fn logout(&self) {
+        *self.session.lock().await = Session::default();
+    }
+
+    // Health
+
+    pub async fn health(&self) -> ClientResult<HealthResponse> {
+        self.send(Method::GET, "health", &[], None::<&()>, false).await
+    }
+
+    // Authentication
+
+    pub async fn register(&self, request: &CreateUserRequest) -> ClientResult<UserResponse> {
+        self.send(Method::POST, "auth/register", &[], Some(request), false).await
+    }
+
+    pub async fn login(&self, email: &str, password: &str) -> ClientResult<LoginResponse> {
+        let credentials = LoginRequest {
+            email: email.to_string(),
+            password: password.to_string(),
+        };
+        let response = self.post_login(&credentials).await?;
+
+        let mut session = self.session.lock().await;
+        session.store(response.token.clone());
+        session.credentials = Some(credentials);
+        Ok(response)
+    }
+
+    pub async fn refresh_token(&self) -> ClientResult<LoginResponse> {
+        let mut session = self.session.lock().await;
+        let token = session.token.clone().ok_or(ClientError::NotLoggedIn)?;
+        let response = self.post_refresh(&token).await?;
+        session.store(response.token.clone());
+        Ok(response)
+    }
+
+    // Courses
+
+    pub async fn list_courses(&self) -> ClientResult<Vec<CourseResponse>> {
+        self.send(Method::GET, "courses", &[], None::<&()>, false).await
+    }
+
+    pub async fn get_course(&self, course_id: Uuid) -> ClientResult<CourseResponse> {
+        self.send(Method::GET, &format!("courses/{}", course_id), &[], None::<&()>, false).await
+    }
+
+    // None while the course is still at the version named by `etag`
+    pub async fn get_course_if_changed(&self, course_id: Uuid, etag: &str) -> ClientResult<Option<CourseResponse>> {
+        let url = self.url(&format!("courses/{}", course_id), &[])?;
+        let response = self.http.get(url).header(IF_NONE_MATCH, etag).send().await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        Ok(Some(Self::check(response).await?.json().await?))
+    }
+
+    pub async fn create_course(&self, request: &CreateCourseRequest) -> ClientResult<CourseResponse> {
+        self.send(Method::POST, "courses", &[], Some(request), true).await
+    }
+
+    // `etag` names the version being edited, `Precondition::entity_tag(course.updated_at)`;
+    // the update fails with PreconditionFailed when the course has changed since
+    pub async fn update_course(&self, course_id: Uuid, etag: &str, request: &UpdateCourseRequest) -> ClientResult<CourseResponse> {
+        let body = Body::JsonIfMatch { body: request, etag };
+        let response = self.execute(Method::PUT, &format!("courses/{}", course_id), &[], Some(body), true).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn delete_course(&self, course_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("courses/{}", course_id), None::<&()>).await
+    }
+
+    pub async fn my_courses(&self) -> ClientResult<Vec<CourseResponse>> {
+        self.send(Method::GET, "my/courses", &[], None::<&()>, true).await
+    }
+
+    pub async fn my_deleted_courses(&self) -> ClientResult<Vec<DeletedCourseResponse>> {
+        self.send(Method::GET, "my/courses/deleted", &[], None::<&()>, true).await
+    }
+
+    pub async fn restore_course(&self, course_id: Uuid) -> ClientResult<CourseResponse> {
+        self.send(Method::POST, &format!("courses/{}/restore", course_id), &[], None::<&()>, true).await
+    }
+
+    // Curriculum
+
+    pub async fn course_outline(&self, course_id: Uuid) -> ClientResult<CourseOutlineResponse> {
+        self.send(Method::GET, &format!("courses/{}/outline", course_id), &[], None::<&()>, false).await
+    }
+
+    pub async fn add_module(&self, course_id: Uuid, request: &ModuleRequest) -> ClientResult<ModuleResponse> {
+        self.send(Method::POST, &format!("courses/{}/modules", course_id), &[], Some(request), true).await
+    }
+
+    pub async fn rename_module(&self, module_id: Uuid, request: &ModuleRequest) -> ClientResult<ModuleResponse> {
+        self.send(Method::PUT, &format!("modules/{}", module_id), &[], Some(request), true).await
+    }
+
+    pub async fn delete_module(&self, module_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("modules/{}", module_id), None::<&()>).await
+    }
+
+    pub async fn add_lesson(&self, module_id: Uuid, request: &CreateLessonRequest) -> ClientResult<LessonResponse> {
+        self.send(Method::POST, &format!("modules/{}/lessons", module_id), &[], Some(request), true).await
+    }
+
+    pub async fn get_lesson(&self, lesson_id: Uuid) -> ClientResult<LessonResponse> {
+        self.send(Method::GET, &format!("lessons/{}", lesson_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn update_lesson(&self, lesson_id: Uuid, request: &UpdateLessonRequest) -> ClientResult<LessonResponse> {
+        self.send(Method::PUT, &format!("lessons/{}", lesson_id), &[], Some(request), true).await
+    }
+
+    pub async fn delete_lesson(&self, lesson_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("lessons/{}", lesson_id), None::<&()>).await
+    }
+
+    pub async fn reorder_curriculum(
+        &self,
+        course_id: Uuid,
+        request: &ReorderCurriculumRequest,
+    ) -> ClientResult<CourseOutlineResponse> {
+        self.send(Method::PUT, &format!("courses/{}/curriculum/order", course_id), &[], Some(request), true).await
+    }
+
+    // Progress
+
+    pub async fn complete_lesson(&self, lesson_id: Uuid, request: &CompleteLessonRequest) -> ClientResult<LessonCompletionResponse> {
+        self.send(Method::POST, &format!("lessons/{}/complete", lesson_id), &[], Some(request), true).await
+    }
+
+    pub async fn reset_lesson(&self, lesson_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("lessons/{}/complete", lesson_id), None::<&()>).await
+    }
+
+    pub async fn my_progress(&self) -> ClientResult<Vec<CourseProgressResponse>> {
+        self.send(Method::GET, "my/progress", &[], None::<&()>, true).await
+    }
+
+    pub async fn my_course_progress(&self, course_id: Uuid) -> ClientResult<CourseProgressDetailResponse> {
+        self.send(Method::GET, &format!("my/progress/{}", course_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn course_progress(&self, course_id: Uuid) -> ClientResult<Vec<CourseProgressResponse>> {
+        self.send(Method::GET, &format!("courses/{}/progress", course_id), &[], None::<&()>, true).await
+    }
+
+    // Files
+
+    // Sent as multipart/form-data, the way a browser form would
+    pub async fn upload_file(&self, file_name: &str, content_type: &str, content: &[u8]) -> ClientResult<StoredFileResponse> {
+        let (form_type, form) = file_form(file_name, content_type, content);
+        let body: Body<()> = Body::File { content_type: &form_type, content: &form };
+        let response = self.execute(Method::POST, "files", &[], Some(body), true).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn my_files(&self) -> ClientResult<Vec<StoredFileResponse>> {
+        self.send(Method::GET, "my/files", &[], None::<&()>, true).await
+    }
+
+    // Fresh metadata, including a new download link
+    pub async fn get_file(&self, file_id: Uuid) -> ClientResult<StoredFileResponse> {
+        self.send(Method::GET, &format!("files/{}", file_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn delete_file(&self, file_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("files/{}", file_id), None::<&()>).await
+    }
+
+    // Fetches a signed `download_url`; no token is sent
+    pub async fn download(&self, download_url: &str) -> ClientResult<Vec<u8>> {
+        let url = Url::parse(download_url)
+            .map_err(|e| ClientError::BadRequest(format!("Invalid download URL: {}", e)))?;
+        let response = Self::check(self.dispatch(Method::GET, url, None::<Body<()>>, None).await?).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    // Avatar
+
+    // Replaces the caller's avatar; the profile comes back with the new image links
+    pub async fn set_avatar(&self, file_name: &str, content_type: &str, content: &[u8]) -> ClientResult<UserResponse> {
+        let (form_type, form) = file_form(file_name, content_type, content);
+        let body: Body<()> = Body::File { content_type: &form_type, content: &form };
+        let response = self.execute(Method::PUT, "my/avatar", &[], Some(body), true).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn delete_avatar(&self) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, "my/avatar", None::<&()>).await
+    }
+
+    // Fetches one of the PNG links in `AvatarUrls`; they are public
+    pub async fn avatar_image(&self, avatar_url: &str) -> ClientResult<Vec<u8>> {
+        let url = self.base_url.join(avatar_url)
+            .map_err(|e| ClientError::BadRequest(format!("Invalid avatar URL: {}", e)))?;
+        let response = Self::check(self.dispatch(Method::GET, url, None::<Body<()>>, None).await?).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    // Certificates
+
+    // Issues the certificate for a completed course, or returns the one already issued
+    pub async fn claim_certificate(&self, course_id: Uuid) -> ClientResult<CertificateResponse> {
+        self.send(Method::POST, &format!("courses/{}/certificate", course_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn my_certificates(&self) -> ClientResult<Vec<CertificateResponse>> {
+        self.send(Method::GET, "my/certificates", &[], None::<&()>, true).await
+    }
+
+    pub async fn verify_certificate(&self, code: &str) -> ClientResult<CertificateResponse> {
+        self.send(Method::GET, &format!("certificates/{}", code), &[], None::<&()>, false).await
+    }
+
+    pub async fn certificate_pdf(&self, code: &str) -> ClientResult<Vec<u8>> {
+        let path = format!("certificates/{}/pdf", code);
+        let response = self.execute(Method::GET, &path, &[], None::<Body<()>>, false).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    // Quizzes
+
+    pub async fn create_quiz(&self, lesson_id: Uuid, request: &QuizRequest) -> ClientResult<QuizResponse> {
+        self.send(Method::POST, &format!("lessons/{}/quizzes", lesson_id), &[], Some(request), true).await
+    }
+
+    pub async fn lesson_quizzes(&self, lesson_id: Uuid) -> ClientResult<Vec<QuizSummary>> {
+        self.send(Method::GET, &format!("lessons/{}/quizzes", lesson_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn get_quiz(&self, quiz_id: Uuid) -> ClientResult<QuizSheetResponse> {
+        self.send(Method::GET, &format!("quizzes/{}", quiz_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn quiz_answer_key(&self, quiz_id: Uuid) -> ClientResult<QuizResponse> {
+        self.send(Method::GET, &format!("quizzes/{}/answer-key", quiz_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn update_quiz(&self, quiz_id: Uuid, request: &QuizRequest) -> ClientResult<QuizResponse> {
+        self.send(Method::PUT, &format!("quizzes/{}", quiz_id), &[], Some(request), true).await
+    }
+
+    pub async fn delete_quiz(&self, quiz_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("quizzes/{}", quiz_id), None::<&()>).await
+    }
+
+    pub async fn start_quiz_attempt(&self, quiz_id: Uuid) -> ClientResult<QuizAttemptResponse> {
+        self.send(Method::POST, &format!("quizzes/{}/attempts", quiz_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn quiz_attempts(&self, quiz_id: Uuid) -> ClientResult<Vec<QuizAttemptResponse>> {
+        self.send(Method::GET, &format!("quizzes/{}/attempts", quiz_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn get_quiz_attempt(&self, attempt_id: Uuid) -> ClientResult<QuizAttemptResponse> {
+        self.send(Method::GET, &format!("quiz-attempts/{}", attempt_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn submit_quiz_attempt(&self, attempt_id: Uuid, request: &SubmitQuizRequest) -> ClientResult<QuizAttemptResponse> {
+        self.send(Method::POST, &format!("quiz-attempts/{}/submit", attempt_id), &[], Some(request), true).await
+    }
+
+    // Assignments
+
+    pub async fn create_assignment(&self, course_id: Uuid, request: &AssignmentRequest) -> ClientResult<AssignmentResponse> {
+        self.send(Method::POST, &format!("courses/{}/assignments", course_id), &[], Some(request), true).await
+    }
+
+    pub async fn course_assignments(&self, course_id: Uuid) -> ClientResult<Vec<AssignmentResponse>> {
+        self.send(Method::GET, &format!("courses/{}/assignments", course_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn get_assignment(&self, assignment_id: Uuid) -> ClientResult<AssignmentResponse> {
+        self.send(Method::GET, &format!("assignments/{}", assignment_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn update_assignment(&self, assignment_id: Uuid, request: &AssignmentRequest) -> ClientResult<AssignmentResponse> {
+        self.send(Method::PUT, &format!("assignments/{}", assignment_id), &[], Some(request), true).await
+    }
+
+    pub async fn delete_assignment(&self, assignment_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("assignments/{}", assignment_id), None::<&()>).await
+    }
+
+    pub async fn upload_assignment_file(
+        &self,
+        assignment_id: Uuid,
+        file_name: &str,
+        content_type: &str,
+        content: &[u8],
+    ) -> ClientResult<AssignmentFileResponse> {
+        let body: Body<()> = Body::File { content_type, content };
+        let path = format!("assignments/{}/files", assignment_id);
+        let response = self.execute(Method::POST, &path, &[("file_name", file_name)], Some(body), true).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn download_assignment_file(&self, file_id: Uuid) -> ClientResult<Vec<u8>> {
+        let path = format!("assignment-files/{}", file_id);
+        let response = self.execute(Method::GET, &path, &[], None::<Body<()>>, true).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    pub async fn submit_assignment(&self, assignment_id: Uuid, request: &SubmitAssignmentRequest) -> ClientResult<SubmissionResponse> {
+        self.send(Method::POST, &format!("assignments/{}/submissions", assignment_id), &[], Some(request), true).await
+    }
+
+    pub async fn assignment_submissions(&self, assignment_id: Uuid) -> ClientResult<Vec<SubmissionResponse>> {
+        self.send(Method::GET, &format!("assignments/{}/submissions", assignment_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn publish_grades(&self, assignment_id: Uuid) -> ClientResult<Vec<SubmissionResponse>> {
+        self.send(Method::POST, &format!("assignments/{}/grades/publish", assignment_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn get_submission(&self, submission_id: Uuid) -> ClientResult<SubmissionResponse> {
+        self.send(Method::GET, &format!("submissions/{}", submission_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn grade_submission(&self, submission_id: Uuid, request: &GradeSubmissionRequest) -> ClientResult<SubmissionResponse> {
+        self.send(Method::PUT, &format!("submissions/{}/grade", submission_id), &[], Some(request), true).await
+    }
+
+    // Notifications
+
+    pub async fn my_notifications(&self, unread_only: bool) -> ClientResult<Vec<NotificationResponse>> {
+        let query: &[(&str, &str)] = if unread_only { &[("unread", "true")] } else { &[] };
+        self.send(Method::GET, "my/notifications", query, None::<&()>, true).await
+    }
+
+    pub async fn mark_notification_read(&self, notification_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::POST, &format!("notifications/{}/read", notification_id), None::<&()>).await
+    }
+
+    // Enrollment
+
+    pub async fn enroll(&self, course_id: Uuid) -> ClientResult<EnrollmentResponse> {
+        self.send(Method::POST, &format!("courses/{}/enroll", course_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn drop_enrollment(&self, course_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("courses/{}/enroll", course_id), None::<&()>).await
+    }
+
+    pub async fn my_enrollments(&self, status: Option<EnrollmentStatus>) -> ClientResult<Vec<EnrollmentResponse>> {
+        let params: Vec<_> = status.map(|status| ("status", status.as_str())).into_iter().collect();
+        self.send(Method::GET, "my/enrollments", &params, None::<&()>, true).await
+    }
+
+    pub async fn course_enrollments(
+        &self,
+        course_id: Uuid,
+        status: Option<EnrollmentStatus>,
+    ) -> ClientResult<Vec<EnrollmentResponse>> {
+        let params: Vec<_> = status.map(|status| ("status", status.as_str())).into_iter().collect();
+        self.send(Method::GET, &format!("courses/{}/enrollments", course_id), &params, None::<&()>, true).await
+    }
+
+    // Tutors
+
+    pub async fn list_tutors(&self) -> ClientResult<Vec<TutorResponse>> {
+        self.send(Method::GET, "tutors", &[], None::<&()>, false).await
+    }
+
+    pub async fn get_tutor(&self, tutor_id: Uuid) -> ClientResult<TutorResponse> {
+        self.send(Method::GET, &format!("tutors/{}", tutor_id), &[], None::<&()>, false).await
+    }
+
+    pub async fn search_tutors(&self, specialization: &str) -> ClientResult<Vec<TutorResponse>> {
+        self.send(Method::GET, "tutors/search", &[("specialization", specialization)], None::<&()>, false).await
+    }
+
+    pub async fn create_tutor_profile(&self, request: &CreateTutorProfileRequest) -> ClientResult<TutorResponse> {
+        self.send(Method::POST, "tutors/profile", &[], Some(request), true).await
+    }
+
+    // `etag` names the version being edited, as for `update_course`
+    pub async fn update_tutor_profile(&self, etag: &str, request: &UpdateTutorProfileRequest) -> ClientResult<TutorResponse> {
+        let body = Body::JsonIfMatch { body: request, etag };
+        let response = self.execute(Method::PUT, "tutors/profile", &[], Some(body), true).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn delete_tutor_profile(&self) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, "tutors/profile", None::<&()>).await
+    }
+
+    pub async fn restore_tutor_profile(&self) -> ClientResult<TutorResponse> {
+        self.send(Method::POST, "tutors/profile/restore", &[], None::<&()>, true).await
+    }
+
+    // Administration (admins only)
+
+    pub async fn search_users(&self, query: &UserSearchQuery) -> ClientResult<Page<UserResponse>> {
+        let page = query.page.map(|page| page.to_string());
+        let per_page = query.per_page.map(|per_page| per_page.to_string());
+        let params: Vec<(&str, &str)> = [
+            ("q", query.q.as_deref()),
+            ("role", query.role.as_ref().map(UserRole::as_str)),
+            ("is_active", query.is_active.map(|is_active| if is_active { "true" } else { "false" })),
+            ("page", page.as_deref()),
+            ("per_page", per_page.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .collect();
+        self.send(Method::GET, "admin/users", &params, None::<&()>, true).await
+    }
+
+    pub async fn admin_get_user(&self, user_id: Uuid) -> ClientResult<UserResponse> {
+        self.send(Method::GET, &format!("admin/users/{}", user_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn change_user_role(&self, user_id: Uuid, request: &ChangeRoleRequest) -> ClientResult<UserResponse> {
+        self.send(Method::PUT, &format!("admin/users/{}/role", user_id), &[], Some(request), true).await
+    }
+
+    pub async fn deactivate_user(&self, user_id: Uuid, request: &ModerationRequest) -> ClientResult<UserResponse> {
+        self.send(Method::POST, &format!("admin/users/{}/deactivate", user_id), &[], Some(request), true).await
+    }
+
+    pub async fn reactivate_user(&self, user_id: Uuid, request: Option<&ModerationRequest>) -> ClientResult<UserResponse> {
+        self.send(Method::POST, &format!("admin/users/{}/reactivate", user_id), &[], request, true).await
+    }
+
+    // Ends every session of the user
+    pub async fn force_logout(&self, user_id: Uuid, request: Option<&ModerationRequest>) -> ClientResult<()> {
+        self.send_empty(Method::POST, &format!("admin/users/{}/logout", user_id), request).await
+    }
+
+    pub async fn take_down_course(&self, course_id: Uuid, request: &ModerationRequest) -> ClientResult<CourseResponse> {
+        self.send(Method::POST, &format!("admin/courses/{}/takedown", course_id), &[], Some(request), true).await
+    }
+
+    pub async fn reinstate_course(&self, course_id: Uuid, request: Option<&ModerationRequest>) -> ClientResult<CourseResponse> {
+        self.send(Method::POST, &format!("admin/courses/{}/reinstate", course_id), &[], request, true).await
+    }
+
+    pub async fn remove_review(&self, review_id: Uuid, request: &ModerationRequest) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("admin/reviews/{}", review_id), Some(request)).await
+    }
+
+    // Newest entries first
+    pub async fn audit_log(&self, query: &AuditLogQuery) -> ClientResult<Page<AuditEntry>> {
+        let actor_id = query.actor_id.map(|id| id.to_string());
+        let target_id = query.target_id.map(|id| id.to_string());
+        let from = query.from.map(|from| from.to_rfc3339());
+        let to = query.to.map(|to| to.to_rfc3339());
+        let page = query.page.map(|page| page.to_string());
+        let per_page = query.per_page.map(|per_page| per_page.to_string());
+        let params: Vec<(&str, &str)> = [
+            ("actor_id", actor_id.as_deref()),
+            ("target_type", query.target_type.as_deref()),
+            ("target_id", target_id.as_deref()),
+            ("from", from.as_deref()),
+            ("to", to.as_deref()),
+            ("page", page.as_deref()),
+            ("per_page", per_page.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .collect();
+        self.send(Method::GET, "admin/audit-log", &params, None::<&()>, true).await
+    }
+
+    // Tutor verification
+
+    pub async fn submit_verification(&self, request: &SubmitVerificationRequest) -> ClientResult<VerificationRequestResponse> {
+        self.send(Method::POST, "my/verification", &[], Some(request), true).await
+    }
+
+    pub async fn my_verification(&self) -> ClientResult<TutorVerificationResponse> {
+        self.send(Method::GET, "my/verification", &[], None::<&()>, true).await
+    }
+
+    // Admins only; pending requests when no status is given
+    pub async fn verification_queue(&self, status: Option<VerificationStatus>) -> ClientResult<Vec<VerificationRequestResponse>> {
+        let params: Vec<_> = status.map(|status| ("status", status.as_str())).into_iter().collect();
+        self.send(Method::GET, "admin/verification-requests", &params, None::<&()>, true).await
+    }
+
+    pub async fn get_verification_request(&self, request_id: Uuid) -> ClientResult<VerificationRequestResponse> {
+        self.send(Method::GET, &format!("admin/verification-requests/{}", request_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn approve_verification(
+        &self,
+        request_id: Uuid,
+        request: Option<&VerificationDecisionRequest>,
+    ) -> ClientResult<VerificationRequestResponse> {
+        self.send(Method::POST, &format!("admin/verification-requests/{}/approve", request_id), &[], request, true).await
+    }
+
+    pub async fn reject_verification(
+        &self,
+        request_id: Uuid,
+        request: &VerificationDecisionRequest,
+    ) -> ClientResult<VerificationRequestResponse> {
+        self.send(Method::POST, &format!("admin/verification-requests/{}/reject", request_id), &[], Some(request), true).await
+    }
+
+    pub async fn revoke_verification(&self, tutor_id: Uuid, request: &VerificationDecisionRequest) -> ClientResult<TutorResponse> {
+        self.send(Method::POST, &format!("admin/tutors/{}/verification/revoke", tutor_id), &[], Some(request), true).await
+    }
+
+    pub async fn verification_history(&self, tutor_id: Uuid) -> ClientResult<Vec<VerificationEvent>> {
+        self.send(Method::GET, &format!("admin/tutors/{}/verification/history", tutor_id), &[], None::<&()>, true).await
+    }
+
+    // Reviews
+
+    pub async fn tutor_reviews(&self, tutor_id: Uuid) -> ClientResult<Vec<ReviewResponse>> {
+        self.send(Method::GET, &format!("tutors/{}/reviews", tutor_id), &[], None::<&()>, false).await
+    }
+
+    pub async fn create_review(&self, tutor_id: Uuid, request: &CreateReviewRequest) -> ClientResult<ReviewResponse> {
+        self.send(Method::POST, &format!("tutors/{}/reviews", tutor_id), &[], Some(request), true).await
+    }
+
+    pub async fn update_review(&self, review_id: Uuid, request: &UpdateReviewRequest) -> ClientResult<ReviewResponse> {
+        self.send(Method::PUT, &format!("reviews/{}", review_id), &[], Some(request), true).await
+    }
+
+    pub async fn delete_review(&self, review_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("reviews/{}", review_id), None::<&()>).await
+    }
+
+    pub async fn review_edits(&self, review_id: Uuid) -> ClientResult<Vec<ReviewEdit>> {
+        self.send(Method::GET, &format!("reviews/{}/edits", review_id), &[], None::<&()>, false).await
+    }
+
+    pub async fn reply_to_review(&self, review_id: Uuid, request: &ReviewReplyRequest) -> ClientResult<ReviewResponse> {
+        self.send(Method::POST, &format!("reviews/{}/reply", review_id), &[], Some(request), true).await
+    }
+
+    // Availability
+
+    pub async fn open_slots(&self, tutor_id: Uuid, query: &OpenSlotsQuery) -> ClientResult<OpenSlotsResponse> {
+        let from = query.from.to_string();
+        let to = query.to.to_string();
+        let mut params = vec![("from", from.as_str()), ("to", to.as_str())];
+        if let Some(tz) = &query.tz {
+            params.push(("tz", tz.as_str()));
+        }
+        self.send(Method::GET, &format!("tutors/{}/availability", tutor_id), &params, None::<&()>, false).await
+    }
+
+    pub async fn my_availability(&self) -> ClientResult<ScheduleResponse> {
+        self.send(Method::GET, "my/availability", &[], None::<&()>, true).await
+    }
+
+    pub async fn set_availability(&self, request: &SetScheduleRequest) -> ClientResult<ScheduleResponse> {
+        self.send(Method::PUT, "my/availability", &[], Some(request), true).await
+    }
+
+    pub async fn add_availability_exception(&self, request: &CreateExceptionRequest) -> ClientResult<ExceptionResponse> {
+        self.send(Method::POST, "my/availability/exceptions", &[], Some(request), true).await
+    }
+
+    pub async fn remove_availability_exception(&self, exception_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("my/availability/exceptions/{}", exception_id), None::<&()>).await
+    }
+
+    pub async fn add_blackout(&self, request: &CreateBlackoutRequest) -> ClientResult<BlackoutResponse> {
+        self.send(Method::POST, "my/availability/blackouts", &[], Some(request), true).await
+    }
+
+    pub async fn remove_blackout(&self, blackout_id: Uuid) -> ClientResult<()> {
+        self.send_empty(Method::DELETE, &format!("my/availability/blackouts/{}", blackout_id), None::<&()>).await
+    }
+
+    // Bookings
+
+    pub async fn create_booking(&self, request: &CreateBookingRequest) -> ClientResult<BookingResponse> {
+        self.send(Method::POST, "bookings", &[], Some(request), true).await
+    }
+
+    pub async fn get_booking(&self, booking_id: Uuid) -> ClientResult<BookingResponse> {
+        self.send(Method::GET, &format!("bookings/{}", booking_id), &[], None::<&()>, true).await
+    }
+
+    pub async fn my_bookings(&self, status: Option<BookingStatus>) -> ClientResult<Vec<BookingResponse>> {
+        let params: Vec<_> = status.map(|status| ("status", status.as_str())).into_iter().collect();
+        self.send(Method::GET, "my/bookings", &params, None::<&()>, true).await
+    }
+
+    pub async fn tutor_bookings(&self, status: Option<BookingStatus>) -> ClientResult<Vec<BookingResponse>> {
+        let params: Vec<_> = status.map(|status| ("status", status.as_str())).into_iter().collect();
+        self.send(Method::GET, "my/tutor/bookings", &params, None::<&()>, true).await
+    }
+
+    pub async fn accept_booking(&self, booking_id: Uuid) -> ClientResult<BookingResponse> {
+        self.booking_action(booking_id, "accept", None).await
+    }
+
+    pub async fn decline_booking(&self, booking_id: Uuid, request: &BookingActionRequest) -> ClientResult<BookingResponse> {
+        self.booking_action(booking_id, "decline", Some(request)).await
+    }
+
+    pub async fn cancel_booking(&self, booking_id: Uuid, request: &BookingActionRequest) -> ClientResult<BookingResponse> {
+        self.booking_action(booking_id, "cancel", Some(request)).await
+    }
+
+    pub async fn complete_booking(&self, booking_id: Uuid) -> ClientResult<BookingResponse> {
+        self.booking_action(booking_id, "complete", None).await
+    }
+
+    pub async fn mark_no_show(&self, booking_id: Uuid) -> ClientResult<BookingResponse> {
+        self.booking_action(booking_id, "no-show", None).await
+    }
+
+    pub async fn reschedule_booking(&self, booking_id: Uuid, request: &RescheduleBookingRequest) -> ClientResult<BookingResponse> {
+        self.send(Method::POST, &format!("bookings/{}/reschedule", booking_id), &[], Some(request), true).await
+    }
+
+    pub async fn booking_policy(&self, tutor_id: Uuid) -> ClientResult<BookingPolicyResponse> {
+        self.send(Method::GET, &format!("tutors/{}/booking-policy", tutor_id), &[], None::<&()>, false).await
+    }
+
+    pub async fn my_booking_policy(&self) -> ClientResult<BookingPolicyResponse> {
+        self.send(Method::GET, "my/booking-policy", &[], None::<&()>, true).await
+    }
+
+    pub async fn set_booking_policy(&self, request: &SetBookingPolicyRequest) -> ClientResult<BookingPolicyResponse> {
+        self.send(Method::PUT, "my/booking-policy", &[], Some(request), true).await
+    }
+
+    async fn booking_action(
+        &self,
+        booking_id: Uuid,
+        action: &str,
+        request: Option<&BookingActionRequest>,
+    ) -> ClientResult<BookingResponse> {
+        self.send(Method::POST, &format!("bookings/{}/{}", booking_id, action), &[], request, true).await
+    }
+
+    // Calendar feed
+
+    pub async fn calendar_feed(&self) -> ClientResult<CalendarFeedResponse> {
+        self.send(Method::GET, "my/calendar-feed", &[], None::<&()>, true).await
+    }
+
+    pub async fn set_calendar_time_zone(&self, request: &UpdateCalendarFeedRequest) -> ClientResult<CalendarFeedResponse> {
+        self.send(Method::PUT, "my/calendar-feed", &[], Some(request), true).await
+    }
+
+    pub async fn rotate_calendar_feed(&self) -> ClientResult<CalendarFeedResponse> {
+        self.send(Method::POST, "my/calendar-feed/rotate", &[], None::<&()>, true).await
+    }
+
+    // Transport
+
+    async fn send<T, B>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<&B>,
+        authenticated: bool,
+    ) -> ClientResult<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let response = self.execute(method, path, query, body.map(Body::Json), authenticated).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn send_empty<B>(&self, method: Method, path: &str, body: Option<&B>) -> ClientResult<()>
+    where
+        B: Serialize + ?Sized,
+    {
+        self.execute(method, path, &[], body.map(Body::Json), true).await?;
+        Ok(())
+    }
+
+    async fn execute<B>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<Body<'_, B>>,
+        authenticated: bool,
+    ) -> ClientResult<reqwest::Response>
+    where
+        B: Serialize + ?Sized,
+    {
+        let url = self.url(path, query)?;
+
+        if !authenticated {
+            return Self::check(self.dispatch(method, url, body, None).await?).await;
+        }
+
+        let token = self.fresh_token().await?;
+        let response = self.dispatch(method.clone(), url.clone(), body, Some(&token)).await?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            if let Some(token) = self.relogin().await? {
+                let retried = self.dispatch(method, url, body, Some(&token)).await?;
+                return Self::check(retried).await;
+            }
+        }
+
+        Self::check(response).await
+    }
+
+    async fn dispatch<B>(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<Body<'_, B>>,
+        token: Option<&str>,
+    ) -> ClientResult<reqwest::Response>
+    where
+        B: Serialize + ?Sized,
+    {
+        let mut request = self.http.request(method, url);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        match body {
+            Some(Body::Json(body)) => request = request.json(body),
+            Some(Body::JsonIfMatch { body, etag }) => request = request.header(IF_MATCH, etag).json(body),
+            Some(Body::File { content_type, content }) => {
+                request = request.header(CONTENT_TYPE, content_type).body(content.to_vec());
+            }
+            None => {}
+        }
+        Ok(request.send().await?)
+    }
+
+    async fn check(response: reqwest::Response) -> ClientResult<reqwest::Response> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(ClientError::from_response(status, &body))
+    }
+
+    fn url(&self, path: &str, query: &[(&str, &str)]) -> ClientResult<Url> {
+        let mut url = self.base_url.join(path)
+            .map_err(|e| ClientError::BadRequest(format!("Invalid path {}: {}", path, e)))?;
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        Ok(url)
+    }
+
+    // Returns a token that is not about to expire, refreshing or logging in
+    // again when needed
+    async fn fresh_token(&self) -> ClientResult<String> {
+        let mut session = self.session.lock().await;
+        let token = session.token.clone().ok_or(ClientError::NotLoggedIn)?;
+
+        let now = Utc::now().timestamp();
+        let expires_at = match token_expiry(&token) {
+            Some(expires_at) => expires_at,
+            None => return Ok(token),
+        };
+        if expires_at - now > REFRESH_MARGIN_SECS {
+            return Ok(token);
+        }
+
+        if expires_at > now {
+            if let Ok(response) = self.post_refresh(&token).await {
+                session.store(response.token.clone());
+                return Ok(response.token);
+            }
+        }
+
+        match session.credentials.clone() {
+            Some(credentials) => {
+                let response = self.post_login(&credentials).await?;
+                session.store(response.token.clone());
+                Ok(response.token)
+            }
+            // Let the server reject it; the caller gets a typed 401
+            None => Ok(token),
+        }
+    }
+
+    async fn relogin(&self) -> ClientResult<Option<String>> {
+        let mut session = self.session.lock().await;
+        let credentials = match session.credentials.clone() {
+            Some(credentials) => credentials,
+            None => return Ok(None),
+        };
+        let response = self.post_login(&credentials).await?;
+        session.store(response.token.clone());
+        Ok(Some(response.token))
+    }
+
+    async fn post_login(&self, credentials: &LoginRequest) -> ClientResult<LoginResponse> {
+        let url = self.url("auth/login", &[])?;
+        let response = Self::check(self.dispatch(Method::POST, url, Some(Body::Json(credentials)), None).await?).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn post_refresh(&self, token: &str) -> ClientResult<LoginResponse> {
+        let url = self.url("auth/refresh", &[])?;
+        let response = Self::check(self.dispatch(Method::POST, url, None::<Body<()>>, Some(token)).await?).await?;
+        Ok(response.json().await?)
+    }
+}
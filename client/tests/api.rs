@@ -0,0 +1,636 @@
+// Runs the client against the real route table backed by the in-memory store
+use actix_web::{web, App, HttpServer};
+use chrono::{Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use ezytutor::handlers::configure_routes;
+use ezytutor::middleware::Claims;
+use ezytutor::models::{
+    AssignmentRequest, AuditLogQuery, BookingStatus, ChangeRoleRequest, CompleteLessonRequest, CreateBookingRequest,
+    CreateCourseRequest, CreateLessonRequest, CreateReviewRequest, CreateTutorProfileRequest, CreateUserRequest,
+    CredentialDocumentRequest, CredentialKind, CriterionScoreRequest, DifficultyLevel, EnrollmentStatus,
+    GradeSubmissionRequest, ModerationRequest, ModuleRequest, OpenSlotsQuery, Precondition, ReviewReplyRequest,
+    ReviewResponse, RubricCriterionRequest, SetScheduleRequest, SubmitAssignmentRequest, SubmitVerificationRequest,
+    UpdateCourseRequest, UpdateReviewRequest, UpdateTutorProfileRequest, UserRole, UserSearchQuery,
+    VerificationAction, VerificationDecisionRequest, VerificationStatus, WeeklyWindow,
+};
+use ezytutor::repositories::{FixtureSet, InMemoryRepository};
+use ezytutor::storage::{FileStore, MemoryStorage};
+use ezytutor_client::{ClientError, EzyTutorClient};
+use image::{DynamicImage, ImageFormat, Rgb, RgbImage};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use std::io::Cursor;
+use uuid::Uuid;
+
+const TUTOR_EMAIL: &str = "maria.garcia@ezytutor.dev";
+const STUDENT_EMAIL: &str = "sam.student@ezytutor.dev";
+const PASSWORD: &str = "password123";
+
+async fn spawn_server() -> (EzyTutorClient, FixtureSet) {
+    let fixtures = FixtureSet::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/seed.json")).unwrap();
+    let repo = InMemoryRepository::new();
+    repo.load_fixtures(&fixtures).await.unwrap();
+    let files = FileStore::new(MemoryStorage::new(), b"test-signing-key");
+
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(repo.clone()))
+            .app_data(web::Data::new(files.clone()))
+            .configure(configure_routes::<InMemoryRepository>)
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let address = server.addrs()[0];
+    actix_rt::spawn(server.run());
+
+    let client = EzyTutorClient::new(&format!("http://{}/api/v1", address)).unwrap();
+    (client, fixtures)
+}
+
+fn token_expiring_in(seconds: i64, user_id: Uuid, email: &str) -> String {
+    let secret = std::env::var("JWT_SECRET")
+        .unwrap_or_else(|_| "your-secret-key-change-in-production".to_string());
+    let claims = Claims {
+        sub: user_id,
+        email: email.to_string(),
+        role: "tutor".to_string(),
+        token_version: 0,
+        exp: (Utc::now() + Duration::seconds(seconds)).timestamp() as usize,
+    };
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_ref())).unwrap()
+}
+
+fn course_request() -> CreateCourseRequest {
+    CreateCourseRequest {
+        title: "Linear Algebra".to_string(),
+        description: "Vectors, matrices and eigenvalues".to_string(),
+        price: 5500,
+        duration_minutes: 60,
+        category: "Mathematics".to_string(),
+        difficulty_level: DifficultyLevel::Intermediate,
+        max_students: None,
+    }
+}
+
+#[actix_rt::test]
+async fn public_endpoints_return_shared_models() {
+    let (client, fixtures) = spawn_server().await;
+
+    assert_eq!(client.health().await.unwrap().status, "healthy");
+
+    let courses = client.list_courses().await.unwrap();
+    assert_eq!(courses[0].tutor.name, "Maria Garcia");
+
+    let tutor = client.get_tutor(fixtures.tutors[0].id).await.unwrap();
+    assert_eq!(tutor.total_reviews, 2);
+    assert_eq!(client.tutor_reviews(tutor.id).await.unwrap().len(), 2);
+    assert_eq!(client.search_tutors("Rust").await.unwrap().len(), 1);
+}
+
+#[actix_rt::test]
+async fn tutor_manages_courses_with_a_session() {
+    let (client, _) = spawn_server().await;
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+
+    let course = client.create_course(&course_request()).await.unwrap();
+    let update = UpdateCourseRequest {
+        title: None,
+        description: None,
+        price: Some(6500),
+        duration_minutes: None,
+        category: None,
+        difficulty_level: None,
+        is_active: None,
+        max_students: None,
+    };
+    let etag = Precondition::entity_tag(course.updated_at);
+    assert_eq!(client.update_course(course.id, &etag, &update).await.unwrap().price, 6500);
+    assert!(client.my_courses().await.unwrap().iter().any(|c| c.id == course.id));
+
+    client.delete_course(course.id).await.unwrap();
+    assert!(matches!(client.get_course(course.id).await, Err(ClientError::NotFound(_))));
+}
+
+#[actix_rt::test]
+async fn error_bodies_map_to_typed_errors() {
+    let (client, fixtures) = spawn_server().await;
+
+    assert!(matches!(client.my_courses().await, Err(ClientError::NotLoggedIn)));
+    assert!(matches!(client.login(STUDENT_EMAIL, "wrong-password").await, Err(ClientError::Authentication(_))));
+
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    let profile = CreateTutorProfileRequest {
+        bio: "A student who would very much like to become a tutor one day soon.".to_string(),
+        specializations: vec![],
+        hourly_rate: 2000,
+        years_experience: 0,
+    };
+    assert!(matches!(client.create_tutor_profile(&profile).await, Err(ClientError::Authorization(_))));
+
+    let review = CreateReviewRequest { rating: 9, comment: None };
+    match client.create_review(fixtures.tutors[0].id, &review).await {
+        Err(ClientError::BadRequest(message)) => assert!(message.starts_with("Validation failed")),
+        other => panic!("expected a validation error, got {:?}", other.map(|r| r.id)),
+    }
+}
+
+#[actix_rt::test]
+async fn admins_cannot_self_register() {
+    let (client, _) = spawn_server().await;
+    let registration = |role| CreateUserRequest {
+        email: "eve@example.com".to_string(),
+        password: "correct-horse".to_string(),
+        first_name: "Eve".to_string(),
+        last_name: "Mallory".to_string(),
+        role,
+    };
+
+    let error = client.register(&registration(UserRole::Admin)).await.unwrap_err();
+    assert!(matches!(error, ClientError::Authorization(_)));
+    assert!(matches!(client.login("eve@example.com", "correct-horse").await, Err(ClientError::Authentication(_))));
+
+    assert_eq!(client.register(&registration(UserRole::Student)).await.unwrap().role, UserRole::Student);
+    client.login("eve@example.com", "correct-horse").await.unwrap();
+    assert!(matches!(client.search_users(&UserSearchQuery::default()).await, Err(ClientError::Authorization(_))));
+}
+
+#[actix_rt::test]
+async fn tokens_close_to_expiry_are_refreshed() {
+    let (client, fixtures) = spawn_server().await;
+    let user = &fixtures.users[1];
+    let expiring = token_expiring_in(30, user.id, &user.email);
+    client.set_token(expiring.clone()).await;
+
+    client.my_courses().await.unwrap();
+    let token = client.token().await.unwrap();
+    assert_ne!(token, expiring);
+}
+
+#[actix_rt::test]
+async fn rejected_tokens_trigger_a_relogin() {
+    let (client, _) = spawn_server().await;
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    client.set_token("not-a-valid-token").await;
+
+    assert!(!client.my_courses().await.unwrap().is_empty());
+    assert_ne!(client.token().await.unwrap(), "not-a-valid-token");
+}
+
+#[actix_rt::test]
+async fn published_availability_is_listed_in_the_requester_zone() {
+    let (client, fixtures) = spawn_server().await;
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+
+    let schedule = SetScheduleRequest {
+        time_zone: "Europe/Madrid".to_string(),
+        slot_minutes: Some(90),
+        windows: vec![WeeklyWindow {
+            weekday: Weekday::Wed,
+            start_time: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(21, 0, 0).unwrap(),
+        }],
+    };
+    assert_eq!(client.set_availability(&schedule).await.unwrap().windows, schedule.windows);
+
+    let query = OpenSlotsQuery {
+        from: NaiveDate::from_ymd_opt(2030, 7, 3).unwrap(),
+        to: NaiveDate::from_ymd_opt(2030, 7, 3).unwrap(),
+        tz: Some("America/Chicago".to_string()),
+    };
+    let response = client.open_slots(fixtures.tutors[0].id, &query).await.unwrap();
+    let starts: Vec<String> = response.slots.iter().map(|slot| slot.start.to_rfc3339()).collect();
+    assert_eq!(starts, vec!["2030-07-03T11:00:00-05:00", "2030-07-03T12:30:00-05:00"]);
+}
+
+#[actix_rt::test]
+async fn booked_slots_are_held_until_the_tutor_responds() {
+    let (client, fixtures) = spawn_server().await;
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    let schedule = SetScheduleRequest {
+        time_zone: "Europe/Madrid".to_string(),
+        slot_minutes: Some(90),
+        windows: vec![WeeklyWindow {
+            weekday: Weekday::Wed,
+            start_time: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(21, 0, 0).unwrap(),
+        }],
+    };
+    client.set_availability(&schedule).await.unwrap();
+
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    let request = CreateBookingRequest {
+        course_id: fixtures.courses[0].id,
+        starts_at: "2030-07-03T16:00:00Z".parse().unwrap(),
+        note: Some("Chapter 3 please".to_string()),
+    };
+    let booking = client.create_booking(&request).await.unwrap();
+    assert_eq!(booking.status, BookingStatus::Requested);
+    assert!(matches!(client.create_booking(&request).await, Err(ClientError::Conflict(_))));
+
+    let day = NaiveDate::from_ymd_opt(2030, 7, 3).unwrap();
+    let query = OpenSlotsQuery { from: day, to: day, tz: None };
+    let slots = client.open_slots(fixtures.tutors[0].id, &query).await.unwrap().slots;
+    assert_eq!(slots.len(), 1);
+    assert_eq!(slots[0].start.to_rfc3339(), "2030-07-03T17:30:00+00:00");
+
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    assert_eq!(client.accept_booking(booking.id).await.unwrap().status, BookingStatus::Confirmed);
+    let confirmed = client.tutor_bookings(Some(BookingStatus::Confirmed)).await.unwrap();
+    assert_eq!(confirmed.len(), 1);
+    assert_eq!(confirmed[0].note.as_deref(), Some("Chapter 3 please"));
+}
+
+#[actix_rt::test]
+async fn students_enroll_and_tutors_see_the_roster() {
+    let (client, fixtures) = spawn_server().await;
+    let course_id = fixtures.courses[0].id;
+
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    let enrollment = client.enroll(course_id).await.unwrap();
+    assert_eq!(enrollment.status, EnrollmentStatus::Active);
+    assert!(matches!(client.enroll(course_id).await, Err(ClientError::Conflict(_))));
+    assert_eq!(client.my_enrollments(Some(EnrollmentStatus::Active)).await.unwrap().len(), 1);
+
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    let roster = client.course_enrollments(course_id, None).await.unwrap();
+    assert_eq!(roster[0].student_email, STUDENT_EMAIL);
+    assert_eq!(client.get_course(course_id).await.unwrap().enrolled_students, 1);
+
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    client.drop_enrollment(course_id).await.unwrap();
+    assert!(client.my_enrollments(Some(EnrollmentStatus::Active)).await.unwrap().is_empty());
+}
+
+#[actix_rt::test]
+async fn uploaded_work_is_graded_and_the_student_notified() {
+    let (client, fixtures) = spawn_server().await;
+    let course_id = fixtures.courses[0].id;
+
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    let assignment = client.create_assignment(course_id, &AssignmentRequest {
+        title: "Problem set 1".to_string(),
+        instructions: String::new(),
+        due_at: Utc::now() + Duration::days(3),
+        accept_late: true,
+        max_submissions: None,
+        rubric: vec![RubricCriterionRequest {
+            id: None,
+            title: "Correctness".to_string(),
+            description: String::new(),
+            max_points: 10,
+        }],
+    }).await.unwrap();
+
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    client.enroll(course_id).await.unwrap();
+    let file = client.upload_assignment_file(assignment.id, "answers.txt", "text/plain", b"x = 2").await.unwrap();
+    let unsupported = client.upload_assignment_file(assignment.id, "answers.exe", "application/octet-stream", b"MZ").await;
+    assert!(matches!(unsupported, Err(ClientError::BadRequest(_))));
+    let submission = client.submit_assignment(assignment.id, &SubmitAssignmentRequest {
+        text: "See attached".to_string(),
+        file_ids: vec![file.id],
+    }).await.unwrap();
+
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    assert_eq!(client.download_assignment_file(file.id).await.unwrap(), b"x = 2");
+    client.grade_submission(submission.id, &GradeSubmissionRequest {
+        scores: vec![CriterionScoreRequest {
+            criterion_id: assignment.rubric[0].id,
+            points: 9,
+            comment: Some("Check the sign".to_string()),
+        }],
+        feedback: None,
+        publish: true,
+    }).await.unwrap();
+
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    let graded = client.get_submission(submission.id).await.unwrap();
+    assert_eq!(graded.grade.map(|grade| grade.score), Some(9));
+    let notifications = client.my_notifications(true).await.unwrap();
+    assert_eq!(notifications.len(), 1);
+    client.mark_notification_read(notifications[0].id).await.unwrap();
+    assert!(client.my_notifications(true).await.unwrap().is_empty());
+}
+
+#[actix_rt::test]
+async fn completing_a_course_earns_a_public_certificate() {
+    let (client, fixtures) = spawn_server().await;
+    let course = &fixtures.courses[0];
+
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    let module = client.add_module(course.id, &ModuleRequest { title: "Week 1".to_string() }).await.unwrap();
+    let lesson = client.add_lesson(module.id, &CreateLessonRequest {
+        title: "Welcome".to_string(),
+        content: String::new(),
+        estimated_minutes: 5,
+        resources: Vec::new(),
+    }).await.unwrap();
+
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    client.enroll(course.id).await.unwrap();
+    client.complete_lesson(lesson.id, &CompleteLessonRequest::default()).await.unwrap();
+    let certificates = client.my_certificates().await.unwrap();
+    assert_eq!(certificates.len(), 1);
+    assert_eq!(client.claim_certificate(course.id).await.unwrap().code, certificates[0].code);
+
+    let verified = client.verify_certificate(&certificates[0].code).await.unwrap();
+    assert_eq!(verified.course_title, course.title);
+    assert!(client.certificate_pdf(&certificates[0].code).await.unwrap().starts_with(b"%PDF-"));
+    let unknown = client.verify_certificate("AAAA-AAAA-AAAA").await;
+    assert!(matches!(unknown, Err(ClientError::NotFound(_))));
+}
+
+#[actix_rt::test]
+async fn uploaded_files_download_through_signed_links() {
+    let (client, _) = spawn_server().await;
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+
+    let file = client.upload_file("syllabus \"v2\".pdf", "application/pdf", b"%PDF-1.4 syllabus").await.unwrap();
+    assert_eq!((file.file_name.as_str(), file.size_bytes), ("syllabus %22v2%22.pdf", 17));
+    let disguised = client.upload_file("notes.pdf", "application/pdf", b"MZ not a pdf").await;
+    assert!(matches!(disguised, Err(ClientError::BadRequest(_))));
+
+    // The link works without a token; a tampered one does not
+    assert_eq!(client.download(&file.download_url).await.unwrap(), b"%PDF-1.4 syllabus");
+    let tampered = file.download_url.replace("expires=", "expires=1");
+    assert!(matches!(client.download(&tampered).await, Err(ClientError::Authorization(_))));
+
+    assert_eq!(client.my_files().await.unwrap().len(), 1);
+    client.delete_file(file.id).await.unwrap();
+    assert!(matches!(client.get_file(file.id).await, Err(ClientError::NotFound(_))));
+    assert!(matches!(client.download(&file.download_url).await, Err(ClientError::NotFound(_))));
+}
+
+#[actix_rt::test]
+async fn avatars_appear_on_profiles_and_reviews() {
+    let (client, fixtures) = spawn_server().await;
+    let tutor_id = fixtures.tutors[0].id;
+    let mut png = Vec::new();
+    DynamicImage::ImageRgb8(RgbImage::from_pixel(600, 400, Rgb([20, 120, 60])))
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    let mislabelled = client.set_avatar("me.jpg", "image/jpeg", &png).await;
+    assert!(matches!(mislabelled, Err(ClientError::BadRequest(_))));
+    let student = client.set_avatar("me.png", "image/png", &png).await.unwrap();
+    let urls = student.avatar.unwrap();
+    let reviews = client.tutor_reviews(tutor_id).await.unwrap();
+    assert!(reviews.iter().any(|review| review.student_avatar.as_ref() == Some(&urls)));
+
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    let tutor = client.set_avatar("portrait.png", "image/png", &png).await.unwrap();
+    assert_eq!(client.get_tutor(tutor_id).await.unwrap().avatar, tutor.avatar);
+
+    let small = image::load_from_memory(&client.avatar_image(&urls.small).await.unwrap()).unwrap();
+    assert_eq!((small.width(), small.height()), (64, 64));
+    let full = image::load_from_memory(&client.avatar_image(&urls.full).await.unwrap()).unwrap();
+    assert_eq!((full.width(), full.height()), (600, 400));
+
+    client.delete_avatar().await.unwrap();
+    assert!(client.get_tutor(tutor_id).await.unwrap().avatar.is_none());
+    let gone = client.avatar_image(&tutor.avatar.unwrap().small).await;
+    assert!(matches!(gone, Err(ClientError::NotFound(_))));
+}
+
+#[actix_rt::test]
+async fn admins_review_tutor_credentials() {
+    let (client, fixtures) = spawn_server().await;
+    let (verified_id, applicant_id) = (fixtures.tutors[0].id, fixtures.tutors[1].id);
+    let reason = |text: &str| VerificationDecisionRequest { reason: Some(text.to_string()) };
+
+    client.login("james.okafor@ezytutor.dev", PASSWORD).await.unwrap();
+    let degree = client.upload_file("degree.pdf", "application/pdf", b"%PDF-1.4 degree").await.unwrap();
+    let submission = SubmitVerificationRequest {
+        documents: vec![CredentialDocumentRequest { kind: CredentialKind::Degree, file_id: degree.id }],
+        note: None,
+    };
+    let request = client.submit_verification(&submission).await.unwrap();
+    assert_eq!(request.status, VerificationStatus::Pending);
+    assert!(client.my_files().await.unwrap().is_empty());
+    assert!(matches!(client.submit_verification(&submission).await, Err(ClientError::Conflict(_))));
+    assert!(matches!(client.verification_queue(None).await, Err(ClientError::Authorization(_))));
+    assert!(matches!(client.approve_verification(request.id, None).await, Err(ClientError::Authorization(_))));
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    assert!(matches!(client.approve_verification(request.id, None).await, Err(ClientError::Authorization(_))));
+    assert!(matches!(client.reject_verification(request.id, &reason("No")).await, Err(ClientError::Authorization(_))));
+
+    client.login("admin@ezytutor.dev", PASSWORD).await.unwrap();
+    let queue = client.verification_queue(None).await.unwrap();
+    assert_eq!(queue.iter().map(|request| request.id).collect::<Vec<_>>(), vec![request.id]);
+    let document = &client.get_verification_request(request.id).await.unwrap().documents[0];
+    assert_eq!(client.download(&document.file.download_url).await.unwrap(), b"%PDF-1.4 degree");
+    let approved = client.approve_verification(request.id, None).await.unwrap();
+    assert_eq!(approved.status, VerificationStatus::Approved);
+    assert!(client.verification_queue(None).await.unwrap().is_empty());
+    assert_eq!(client.verification_queue(Some(VerificationStatus::Approved)).await.unwrap().len(), 1);
+
+    let revoked = client.revoke_verification(verified_id, &reason("Licence expired")).await.unwrap();
+    assert!(!revoked.is_verified);
+    let history = client.verification_history(applicant_id).await.unwrap();
+    let actions: Vec<_> = history.iter().map(|event| event.action).collect();
+    assert_eq!(actions, vec![VerificationAction::Submitted, VerificationAction::Approved]);
+
+    // Verified tutors are listed first
+    let tutors = client.list_tutors().await.unwrap();
+    assert_eq!((tutors[0].id, tutors[0].is_verified), (applicant_id, true));
+
+    client.login("james.okafor@ezytutor.dev", PASSWORD).await.unwrap();
+    let own = client.my_verification().await.unwrap();
+    assert!(own.is_verified);
+    assert_eq!(own.requests[0].reviewed_by, approved.reviewed_by);
+}
+
+#[actix_rt::test]
+async fn admins_moderate_accounts_and_content() {
+    let (client, fixtures) = spawn_server().await;
+    let (course_id, tutor_id) = (fixtures.courses[0].id, fixtures.tutors[0].id);
+    let reason = |text: &str| ModerationRequest { reason: Some(text.to_string()) };
+    let students = UserSearchQuery {
+        q: Some("student".to_string()),
+        role: Some(UserRole::Student),
+        is_active: Some(true),
+        ..UserSearchQuery::default()
+    };
+
+    let sam = client.login(STUDENT_EMAIL, PASSWORD).await.unwrap().user;
+    let sam_token = client.token().await.unwrap();
+    assert!(matches!(client.search_users(&students).await, Err(ClientError::Authorization(_))));
+
+    client.login("admin@ezytutor.dev", PASSWORD).await.unwrap();
+    let page = client.search_users(&students).await.unwrap();
+    assert_eq!((page.total, page.page, page.per_page), (2, 1, 20));
+    assert!(page.items.iter().any(|user| user.id == sam.id));
+
+    // A forced logout invalidates tokens that have not expired yet
+    client.force_logout(sam.id, None).await.unwrap();
+    let noor_search = UserSearchQuery { q: Some("noor".to_string()), ..UserSearchQuery::default() };
+    let noor = client.search_users(&noor_search).await.unwrap().items.remove(0);
+    let deactivated = client.deactivate_user(noor.id, &reason("Harassment")).await.unwrap();
+    assert!(!deactivated.is_active);
+    let promotion = ChangeRoleRequest { role: UserRole::Tutor, reason: None };
+    let promoted = client.change_user_role(sam.id, &promotion).await.unwrap();
+    assert_eq!(promoted.role, UserRole::Tutor);
+
+    let taken_down = client.take_down_course(course_id, &reason("Plagiarised syllabus")).await.unwrap();
+    assert!(taken_down.taken_down_at.is_some());
+    assert!(matches!(client.get_course(course_id).await, Err(ClientError::NotFound(_))));
+    assert!(client.list_courses().await.unwrap().iter().all(|course| course.id != course_id));
+    assert!(client.reinstate_course(course_id, None).await.unwrap().taken_down_at.is_none());
+
+    let reviews = client.tutor_reviews(tutor_id).await.unwrap();
+    client.remove_review(reviews[0].id, &reason("Spam")).await.unwrap();
+    assert_eq!(client.tutor_reviews(tutor_id).await.unwrap().len(), reviews.len() - 1);
+    assert_eq!(client.get_tutor(tutor_id).await.unwrap().total_reviews as usize, reviews.len() - 1);
+
+    client.logout().await;
+    client.set_token(sam_token).await;
+    assert!(matches!(client.my_enrollments(None).await, Err(ClientError::Authentication(_))));
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    let login = client.login("noor.student@ezytutor.dev", PASSWORD).await;
+    assert!(matches!(login, Err(ClientError::Authentication(_))));
+}
+
+#[actix_rt::test]
+async fn course_changes_are_traced_in_the_audit_log() {
+    let (client, fixtures) = spawn_server().await;
+    let course = &fixtures.courses[0];
+
+    let tutor = client.login(TUTOR_EMAIL, PASSWORD).await.unwrap().user;
+    let reprice = UpdateCourseRequest {
+        title: None,
+        description: None,
+        price: Some(course.price + 1000),
+        duration_minutes: None,
+        category: None,
+        difficulty_level: None,
+        is_active: None,
+        max_students: None,
+    };
+    client.update_course(course.id, &Precondition::entity_tag(course.updated_at), &reprice).await.unwrap();
+    let query = AuditLogQuery {
+        target_type: Some("course".to_string()),
+        target_id: Some(course.id),
+        from: Some(Utc::now() - Duration::minutes(5)),
+        ..AuditLogQuery::default()
+    };
+    assert!(matches!(client.audit_log(&query).await, Err(ClientError::Authorization(_))));
+
+    client.login("admin@ezytutor.dev", PASSWORD).await.unwrap();
+    let page = client.audit_log(&query).await.unwrap();
+    assert_eq!(page.total, 1);
+    let entry = &page.items[0];
+    assert_eq!((entry.action.as_str(), entry.actor_id), ("course.updated", Some(tutor.id)));
+    let price = &entry.changes.as_ref().unwrap()["price"];
+    assert_eq!((price["before"].clone(), price["after"].clone()), (course.price.into(), (course.price + 1000).into()));
+    assert_eq!(entry.ip_address.as_deref(), Some("127.0.0.1"));
+    assert!(entry.request_id.is_some());
+}
+
+#[actix_rt::test]
+async fn deleted_courses_and_profiles_can_be_restored() {
+    let (client, fixtures) = spawn_server().await;
+    let tutor = &fixtures.tutors[0];
+    let course = &fixtures.courses[0];
+
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    let empty = CreateCourseRequest {
+        title: "Scratch Course".to_string(),
+        description: "A course nobody has enrolled in yet".to_string(),
+        price: 1000,
+        duration_minutes: 30,
+        category: "Mathematics".to_string(),
+        difficulty_level: DifficultyLevel::Beginner,
+        max_students: None,
+    };
+    let scratch = client.create_course(&empty).await.unwrap();
+    client.delete_course(scratch.id).await.unwrap();
+    assert!(matches!(client.get_course(scratch.id).await, Err(ClientError::NotFound(_))));
+    let deleted = client.my_deleted_courses().await.unwrap();
+    assert_eq!(deleted.iter().map(|course| course.id).collect::<Vec<_>>(), vec![scratch.id]);
+    assert!(deleted[0].restorable_until > deleted[0].deleted_at);
+    assert_eq!(client.restore_course(scratch.id).await.unwrap().title, "Scratch Course");
+    assert!(client.my_deleted_courses().await.unwrap().is_empty());
+
+    // Deleting the profile hides it along with its courses
+    client.delete_tutor_profile().await.unwrap();
+    assert!(matches!(client.get_tutor(tutor.id).await, Err(ClientError::NotFound(_))));
+    assert!(matches!(client.get_course(course.id).await, Err(ClientError::NotFound(_))));
+    assert_eq!(client.restore_tutor_profile().await.unwrap().id, tutor.id);
+    assert_eq!(client.get_course(course.id).await.unwrap().id, course.id);
+}
+
+#[actix_rt::test]
+async fn stale_edits_are_rejected_by_version() {
+    let (client, fixtures) = spawn_server().await;
+    let course = client.get_course(fixtures.courses[0].id).await.unwrap();
+    let etag = Precondition::entity_tag(course.updated_at);
+    assert!(client.get_course_if_changed(course.id, &etag).await.unwrap().is_none());
+
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    let reprice = |price| UpdateCourseRequest {
+        title: None,
+        description: None,
+        price: Some(price),
+        duration_minutes: None,
+        category: None,
+        difficulty_level: None,
+        is_active: None,
+        max_students: None,
+    };
+    let saved = client.update_course(course.id, &etag, &reprice(course.price + 500)).await.unwrap();
+    // A second tab still editing the version it loaded earlier
+    let error = client.update_course(course.id, &etag, &reprice(course.price + 900)).await.unwrap_err();
+    assert!(matches!(error, ClientError::PreconditionFailed(_)));
+    let current = client.get_course_if_changed(course.id, &etag).await.unwrap().unwrap();
+    assert_eq!(current.price, saved.price);
+
+    let tutor = client.get_tutor(fixtures.tutors[0].id).await.unwrap();
+    let etag = Precondition::entity_tag(tutor.updated_at);
+    let away = UpdateTutorProfileRequest {
+        bio: None,
+        specializations: None,
+        hourly_rate: None,
+        years_experience: None,
+        is_available: Some(false),
+    };
+    assert!(!client.update_tutor_profile(&etag, &away).await.unwrap().is_available);
+    let error = client.update_tutor_profile(&etag, &away).await.unwrap_err();
+    assert!(matches!(error, ClientError::PreconditionFailed(_)));
+}
+
+#[actix_rt::test]
+async fn reviews_can_be_revised_and_answered() {
+    let (client, fixtures) = spawn_server().await;
+    let tutor_id = fixtures.tutors[0].id;
+    let own = |reviews: Vec<ReviewResponse>| {
+        reviews.into_iter()
+            .find(|review| review.comment.as_deref() == Some("Finally understood integration by parts!"))
+            .unwrap()
+    };
+    let review = own(client.tutor_reviews(tutor_id).await.unwrap());
+
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    let revision = UpdateReviewRequest { rating: Some(4), comment: None };
+    let revised = client.update_review(review.id, &revision).await.unwrap();
+    assert_eq!((revised.rating, revised.comment.as_deref()), (4, review.comment.as_deref()));
+    let edits = client.review_edits(review.id).await.unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].rating, 5);
+    assert_eq!(client.get_tutor(tutor_id).await.unwrap().rating, Some(4.0));
+    let error = client.reply_to_review(review.id, &ReviewReplyRequest { comment: "Thanks".to_string() }).await.unwrap_err();
+    assert!(matches!(error, ClientError::Authorization(_)));
+
+    client.login(TUTOR_EMAIL, PASSWORD).await.unwrap();
+    let reply = ReviewReplyRequest { comment: "Glad it clicked!".to_string() };
+    client.reply_to_review(review.id, &reply).await.unwrap();
+    let error = client.reply_to_review(review.id, &reply).await.unwrap_err();
+    assert!(matches!(error, ClientError::Conflict(_)));
+    let listed = own(client.tutor_reviews(tutor_id).await.unwrap());
+    assert_eq!(listed.reply.map(|reply| reply.comment), Some(reply.comment));
+    assert!(listed.edited_at.is_some());
+
+    client.login(STUDENT_EMAIL, PASSWORD).await.unwrap();
+    client.delete_review(review.id).await.unwrap();
+    assert_eq!(client.get_tutor(tutor_id).await.unwrap().total_reviews, 1);
+    assert!(matches!(client.review_edits(review.id).await, Err(ClientError::NotFound(_))));
+}
@@ -0,0 +1,10 @@
+-- Revert the initial schema
+DROP TABLE IF EXISTS tutor_reviews;
+DROP TABLE IF EXISTS courses;
+DROP TABLE IF EXISTS tutors;
+DROP TABLE IF EXISTS users;
+
+DROP FUNCTION IF EXISTS update_updated_at_column();
+
+DROP TYPE IF EXISTS difficulty_level;
+DROP TYPE IF EXISTS user_role;
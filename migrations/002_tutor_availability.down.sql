@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS availability_blackouts;
+DROP TABLE IF EXISTS availability_exceptions;
+DROP TABLE IF EXISTS availability_windows;
+DROP TABLE IF EXISTS tutor_schedules;
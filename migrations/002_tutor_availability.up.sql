@@ -0,0 +1,48 @@
+-- Tutor availability: a weekly schedule in the tutor's IANA time zone, plus
+-- dated exceptions and blackout ranges. Times are local wall-clock times.
+CREATE TABLE tutor_schedules (
+    tutor_id UUID PRIMARY KEY REFERENCES tutors(id) ON DELETE CASCADE,
+    time_zone VARCHAR(64) NOT NULL,
+    slot_minutes INTEGER NOT NULL DEFAULT 60 CHECK (slot_minutes BETWEEN 15 AND 240),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE availability_windows (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    tutor_id UUID NOT NULL REFERENCES tutor_schedules(tutor_id) ON DELETE CASCADE,
+    weekday SMALLINT NOT NULL CHECK (weekday BETWEEN 0 AND 6), -- 0 = Monday
+    start_time TIME NOT NULL,
+    end_time TIME NOT NULL,
+    CHECK (start_time < end_time)
+);
+
+CREATE TABLE availability_exceptions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    tutor_id UUID NOT NULL REFERENCES tutor_schedules(tutor_id) ON DELETE CASCADE,
+    date DATE NOT NULL,
+    start_time TIME, -- NULL start and end cover the whole day
+    end_time TIME,
+    is_available BOOLEAN NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK ((start_time IS NULL) = (end_time IS NULL)),
+    CHECK (start_time IS NULL OR start_time < end_time)
+);
+
+CREATE TABLE availability_blackouts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    tutor_id UUID NOT NULL REFERENCES tutor_schedules(tutor_id) ON DELETE CASCADE,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK (start_date <= end_date)
+);
+
+CREATE INDEX idx_availability_windows_tutor ON availability_windows(tutor_id);
+CREATE INDEX idx_availability_exceptions_tutor_date ON availability_exceptions(tutor_id, date);
+CREATE INDEX idx_availability_blackouts_tutor_dates ON availability_blackouts(tutor_id, start_date, end_date);
+
+CREATE TRIGGER update_tutor_schedules_updated_at BEFORE UPDATE ON tutor_schedules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,30 @@
+-- Session bookings. The exclusion constraint stops a tutor from holding two
+-- live bookings whose times overlap, even under concurrent requests.
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+CREATE TYPE booking_status AS ENUM ('requested', 'confirmed', 'cancelled', 'completed', 'no_show');
+
+CREATE TABLE bookings (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    tutor_id UUID NOT NULL REFERENCES tutors(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    starts_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    ends_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    status booking_status NOT NULL DEFAULT 'requested',
+    note TEXT,
+    status_reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK (starts_at < ends_at),
+    CONSTRAINT bookings_no_tutor_overlap EXCLUDE USING gist (
+        tutor_id WITH =,
+        tstzrange(starts_at, ends_at) WITH &&
+    ) WHERE (status IN ('requested', 'confirmed'))
+);
+
+CREATE INDEX idx_bookings_student ON bookings(student_id, starts_at);
+CREATE INDEX idx_bookings_tutor ON bookings(tutor_id, starts_at);
+
+CREATE TRIGGER update_bookings_updated_at BEFORE UPDATE ON bookings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,6 @@
+ALTER TABLE bookings
+    DROP COLUMN IF EXISTS reschedule_count,
+    DROP COLUMN IF EXISTS cancellation_fee,
+    DROP COLUMN IF EXISTS price;
+
+DROP TABLE IF EXISTS booking_policies;
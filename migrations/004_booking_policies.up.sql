@@ -0,0 +1,25 @@
+-- Per-tutor cancellation and rescheduling terms. Tutors without a row get the
+-- service defaults.
+CREATE TABLE booking_policies (
+    tutor_id UUID PRIMARY KEY REFERENCES tutors(id) ON DELETE CASCADE,
+    free_cancel_hours INTEGER NOT NULL CHECK (free_cancel_hours >= 0),
+    late_cancel_fee_percent INTEGER NOT NULL CHECK (late_cancel_fee_percent BETWEEN 0 AND 100),
+    max_reschedules INTEGER NOT NULL CHECK (max_reschedules >= 0),
+    reschedule_notice_hours INTEGER NOT NULL CHECK (reschedule_notice_hours >= 0),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_booking_policies_updated_at BEFORE UPDATE ON booking_policies
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Billing fields: the price is fixed when the booking is made so later course
+-- price changes do not alter fees
+ALTER TABLE bookings
+    ADD COLUMN price INTEGER,
+    ADD COLUMN cancellation_fee INTEGER CHECK (cancellation_fee >= 0),
+    ADD COLUMN reschedule_count INTEGER NOT NULL DEFAULT 0;
+
+UPDATE bookings b SET price = c.price FROM courses c WHERE c.id = b.course_id;
+
+ALTER TABLE bookings ALTER COLUMN price SET NOT NULL;
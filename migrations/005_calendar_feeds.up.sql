@@ -0,0 +1,12 @@
+-- Secret iCalendar feed per user. The token is the only credential calendar
+-- apps send, so rotating it revokes every existing subscription.
+CREATE TABLE calendar_feeds (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    token VARCHAR(64) NOT NULL UNIQUE,
+    time_zone VARCHAR(64) NOT NULL DEFAULT 'UTC',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_calendar_feeds_updated_at BEFORE UPDATE ON calendar_feeds
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS enrollments;
+DROP TYPE IF EXISTS enrollment_status;
+ALTER TABLE courses DROP COLUMN IF EXISTS max_students;
@@ -0,0 +1,25 @@
+-- Course enrollment. A course with max_students set holds that many active
+-- students; later students join a waitlist ordered by requested_at.
+ALTER TABLE courses ADD COLUMN max_students INTEGER CHECK (max_students > 0);
+
+CREATE TYPE enrollment_status AS ENUM ('active', 'waitlisted', 'dropped');
+
+CREATE TABLE enrollments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status enrollment_status NOT NULL,
+    -- When the student last asked to join; orders the waitlist
+    requested_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    enrolled_at TIMESTAMP WITH TIME ZONE,
+    dropped_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE(course_id, student_id)
+);
+
+CREATE INDEX idx_enrollments_course_status ON enrollments(course_id, status, requested_at);
+CREATE INDEX idx_enrollments_student ON enrollments(student_id);
+
+CREATE TRIGGER update_enrollments_updated_at BEFORE UPDATE ON enrollments
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,39 @@
+-- Course curriculum: ordered modules, each holding ordered lessons. Positions
+-- are 1-based and kept contiguous; the unique constraints are deferred so a
+-- reorder can swap positions inside one transaction.
+CREATE TABLE course_modules (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    title VARCHAR(200) NOT NULL,
+    position INTEGER NOT NULL CHECK (position > 0),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE(id, course_id),
+    CONSTRAINT course_modules_position_key UNIQUE(course_id, position) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE TABLE lessons (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    course_id UUID NOT NULL,
+    module_id UUID NOT NULL,
+    title VARCHAR(200) NOT NULL,
+    -- Markdown
+    content TEXT NOT NULL DEFAULT '',
+    estimated_minutes INTEGER NOT NULL CHECK (estimated_minutes > 0),
+    -- [{"title": ..., "url": ...}]
+    resources JSONB NOT NULL DEFAULT '[]',
+    position INTEGER NOT NULL CHECK (position > 0),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    -- Lessons may move between modules, but never to another course
+    FOREIGN KEY (module_id, course_id) REFERENCES course_modules(id, course_id) ON DELETE CASCADE,
+    CONSTRAINT lessons_position_key UNIQUE(module_id, position) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX idx_lessons_course ON lessons(course_id);
+
+CREATE TRIGGER update_course_modules_updated_at BEFORE UPDATE ON course_modules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_lessons_updated_at BEFORE UPDATE ON lessons
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
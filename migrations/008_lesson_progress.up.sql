@@ -0,0 +1,18 @@
+-- One row per lesson a student has completed. Completing the lesson again adds
+-- to minutes_spent; completed_at keeps the first completion.
+CREATE TABLE lesson_completions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    lesson_id UUID NOT NULL REFERENCES lessons(id) ON DELETE CASCADE,
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    minutes_spent INTEGER NOT NULL DEFAULT 0 CHECK (minutes_spent >= 0),
+    completed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE(lesson_id, student_id)
+);
+
+CREATE INDEX idx_lesson_completions_course_student ON lesson_completions(course_id, student_id);
+
+CREATE TRIGGER update_lesson_completions_updated_at BEFORE UPDATE ON lesson_completions
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
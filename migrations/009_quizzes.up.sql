@@ -0,0 +1,43 @@
+-- Quizzes attached to lessons. Questions, including their answer keys, live in
+-- a JSONB array; each attempt keeps the answers given and the graded results,
+-- so history survives later edits to the quiz.
+CREATE TABLE quizzes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    lesson_id UUID NOT NULL REFERENCES lessons(id) ON DELETE CASCADE,
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    title VARCHAR(200) NOT NULL,
+    instructions TEXT NOT NULL DEFAULT '',
+    -- NULL means unlimited
+    max_attempts INTEGER CHECK (max_attempts > 0),
+    time_limit_minutes INTEGER CHECK (time_limit_minutes > 0),
+    questions JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_quizzes_lesson ON quizzes(lesson_id);
+
+CREATE TABLE quiz_attempts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    quiz_id UUID NOT NULL REFERENCES quizzes(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    attempt_number INTEGER NOT NULL CHECK (attempt_number > 0),
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    deadline_at TIMESTAMP WITH TIME ZONE,
+    -- NULL while the attempt is in progress
+    submitted_at TIMESTAMP WITH TIME ZONE,
+    timed_out BOOLEAN NOT NULL DEFAULT false,
+    score INTEGER,
+    max_score INTEGER NOT NULL,
+    answers JSONB NOT NULL DEFAULT '[]',
+    results JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE(quiz_id, student_id, attempt_number)
+);
+
+CREATE TRIGGER update_quizzes_updated_at BEFORE UPDATE ON quizzes
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_quiz_attempts_updated_at BEFORE UPDATE ON quiz_attempts
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS notifications;
+DROP TABLE IF EXISTS assignment_submissions;
+DROP TABLE IF EXISTS assignment_files;
+DROP TABLE IF EXISTS assignments;
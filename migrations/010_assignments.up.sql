@@ -0,0 +1,76 @@
+-- Course assignments graded against a rubric. Criteria live in a JSONB array;
+-- each graded submission keeps its own criterion scores, so grades survive
+-- later edits to the rubric.
+CREATE TABLE assignments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    title VARCHAR(200) NOT NULL,
+    instructions TEXT NOT NULL DEFAULT '',
+    due_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- Late submissions are flagged; when false they are refused
+    accept_late BOOLEAN NOT NULL DEFAULT true,
+    -- NULL means unlimited resubmissions
+    max_submissions INTEGER CHECK (max_submissions > 0),
+    rubric JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_assignments_course ON assignments(course_id, due_at);
+
+-- Files a student uploaded for an assignment, referenced by their submissions
+CREATE TABLE assignment_files (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    assignment_id UUID NOT NULL REFERENCES assignments(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes >= 0),
+    content BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_assignment_files_student ON assignment_files(assignment_id, student_id);
+
+CREATE TABLE assignment_submissions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    assignment_id UUID NOT NULL REFERENCES assignments(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    submission_number INTEGER NOT NULL CHECK (submission_number > 0),
+    text TEXT NOT NULL DEFAULT '',
+    file_ids UUID[] NOT NULL DEFAULT '{}',
+    submitted_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    is_late BOOLEAN NOT NULL DEFAULT false,
+    -- NULL until graded
+    score INTEGER,
+    max_score INTEGER NOT NULL,
+    rubric_scores JSONB NOT NULL DEFAULT '[]',
+    feedback TEXT,
+    graded_at TIMESTAMP WITH TIME ZONE,
+    -- The student sees the grade only once it is published
+    grade_published_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE(assignment_id, student_id, submission_number)
+);
+
+-- In-app notifications, newest first per user
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,
+    title VARCHAR(200) NOT NULL,
+    body TEXT NOT NULL DEFAULT '',
+    -- API path of the thing the notification is about
+    link VARCHAR(500),
+    read_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_notifications_user ON notifications(user_id, created_at DESC);
+
+CREATE TRIGGER update_assignments_updated_at BEFORE UPDATE ON assignments
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_assignment_submissions_updated_at BEFORE UPDATE ON assignment_submissions
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,17 @@
+-- Course completion certificates. Names are copied in at issue time so the
+-- certificate reads the same forever, even if the course is deleted.
+CREATE TABLE certificates (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    -- Public verification code, e.g. 7KQM-2XHD-9TPA
+    code VARCHAR(20) NOT NULL UNIQUE,
+    course_id UUID REFERENCES courses(id) ON DELETE SET NULL,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    student_name VARCHAR(200) NOT NULL,
+    course_title VARCHAR(200) NOT NULL,
+    tutor_name VARCHAR(200) NOT NULL,
+    completed_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    issued_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE(course_id, student_id)
+);
+
+CREATE INDEX idx_certificates_student ON certificates(student_id);
@@ -0,0 +1,14 @@
+-- Uploaded files. The content lives in the configured file store under its
+-- SHA-256, so identical uploads share one stored object.
+CREATE TABLE stored_files (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes > 0),
+    sha256 CHAR(64) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_stored_files_owner ON stored_files(owner_id, created_at);
+CREATE INDEX idx_stored_files_sha256 ON stored_files(sha256);
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS user_avatars;
+ALTER TABLE users DROP COLUMN IF EXISTS avatar_updated_at;
+DELETE FROM stored_files WHERE purpose = 'avatar';
+ALTER TABLE stored_files DROP COLUMN IF EXISTS purpose;
+DROP TYPE IF EXISTS file_purpose;
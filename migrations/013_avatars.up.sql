@@ -0,0 +1,19 @@
+-- Files created by the server for its own use, such as avatar images, are
+-- kept out of the owner's file list and cannot be deleted through it
+CREATE TYPE file_purpose AS ENUM ('upload', 'avatar');
+
+ALTER TABLE stored_files ADD COLUMN purpose file_purpose NOT NULL DEFAULT 'upload';
+
+-- When the current avatar was set; part of its image URLs so caches can
+-- keep each version indefinitely
+ALTER TABLE users ADD COLUMN avatar_updated_at TIMESTAMP WITH TIME ZONE;
+
+-- One metadata-free image per size for each user's current avatar
+CREATE TABLE user_avatars (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    full_file_id UUID NOT NULL REFERENCES stored_files(id),
+    large_file_id UUID NOT NULL REFERENCES stored_files(id),
+    medium_file_id UUID NOT NULL REFERENCES stored_files(id),
+    small_file_id UUID NOT NULL REFERENCES stored_files(id),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,16 @@
+DROP TABLE IF EXISTS verification_events;
+DROP TABLE IF EXISTS verification_documents;
+DROP TABLE IF EXISTS verification_requests;
+DROP TYPE IF EXISTS verification_action;
+DROP TYPE IF EXISTS credential_kind;
+DROP TYPE IF EXISTS verification_status;
+
+-- Enum values cannot be dropped; rebuild the type without 'credential'
+DELETE FROM stored_files WHERE purpose = 'credential';
+ALTER TYPE file_purpose RENAME TO file_purpose_old;
+CREATE TYPE file_purpose AS ENUM ('upload', 'avatar');
+ALTER TABLE stored_files
+    ALTER COLUMN purpose DROP DEFAULT,
+    ALTER COLUMN purpose TYPE file_purpose USING purpose::text::file_purpose,
+    ALTER COLUMN purpose SET DEFAULT 'upload';
+DROP TYPE file_purpose_old;
@@ -0,0 +1,45 @@
+-- Documents attached to a verification request; the tutor can no longer
+-- delete them, and they are kept with the decision
+ALTER TYPE file_purpose ADD VALUE 'credential';
+
+CREATE TYPE verification_status AS ENUM ('pending', 'approved', 'rejected');
+CREATE TYPE credential_kind AS ENUM ('degree', 'certification', 'identity', 'other');
+CREATE TYPE verification_action AS ENUM ('submitted', 'approved', 'rejected', 'revoked');
+
+CREATE TABLE verification_requests (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    tutor_id UUID NOT NULL REFERENCES tutors(id) ON DELETE CASCADE,
+    status verification_status NOT NULL DEFAULT 'pending',
+    note TEXT,
+    submitted_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    decision_reason TEXT
+);
+
+-- A tutor waits on at most one request at a time
+CREATE UNIQUE INDEX idx_verification_requests_pending ON verification_requests(tutor_id) WHERE status = 'pending';
+CREATE INDEX idx_verification_requests_queue ON verification_requests(status, submitted_at);
+
+CREATE TABLE verification_documents (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    request_id UUID NOT NULL REFERENCES verification_requests(id) ON DELETE CASCADE,
+    kind credential_kind NOT NULL,
+    file_id UUID NOT NULL REFERENCES stored_files(id),
+    position INTEGER NOT NULL,
+    UNIQUE (request_id, position)
+);
+
+-- Every change to a tutor's verification, including ones made without a
+-- request; never updated or deleted by the application
+CREATE TABLE verification_events (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    tutor_id UUID NOT NULL REFERENCES tutors(id) ON DELETE CASCADE,
+    request_id UUID REFERENCES verification_requests(id) ON DELETE SET NULL,
+    action verification_action NOT NULL,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_verification_events_tutor ON verification_events(tutor_id, created_at);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS audit_log;
+ALTER TABLE courses DROP COLUMN IF EXISTS taken_down_at;
+ALTER TABLE users DROP COLUMN IF EXISTS token_version;
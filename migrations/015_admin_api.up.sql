@@ -0,0 +1,22 @@
+-- Bumped to end every session of a user; tokens carry the version they
+-- were issued under and stop working once it changes
+ALTER TABLE users ADD COLUMN token_version INTEGER NOT NULL DEFAULT 0;
+
+-- Set while an admin has taken the course down; the owner cannot edit or
+-- republish it until it is reinstated
+ALTER TABLE courses ADD COLUMN taken_down_at TIMESTAMP WITH TIME ZONE;
+
+-- Every action taken through the admin API or CLI; actor_id is NULL for
+-- the CLI. Rows are only ever inserted.
+CREATE TABLE audit_log (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    action VARCHAR(64) NOT NULL,
+    target_type VARCHAR(32) NOT NULL,
+    target_id UUID NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_log_target ON audit_log(target_type, target_id, created_at);
+CREATE INDEX idx_audit_log_actor ON audit_log(actor_id, created_at);
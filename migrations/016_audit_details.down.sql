@@ -0,0 +1,9 @@
+DROP TRIGGER IF EXISTS audit_log_append_only ON audit_log;
+DROP FUNCTION IF EXISTS reject_audit_log_change();
+DROP INDEX IF EXISTS idx_audit_log_created_at;
+ALTER TABLE audit_log DROP COLUMN IF EXISTS request_id;
+ALTER TABLE audit_log DROP COLUMN IF EXISTS ip_address;
+ALTER TABLE audit_log DROP COLUMN IF EXISTS changes;
+UPDATE audit_log SET actor_id = NULL WHERE actor_id NOT IN (SELECT id FROM users);
+ALTER TABLE audit_log ADD CONSTRAINT audit_log_actor_id_fkey
+    FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL;
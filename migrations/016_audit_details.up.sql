@@ -0,0 +1,21 @@
+-- Audit entries outlive the accounts that made them
+ALTER TABLE audit_log DROP CONSTRAINT audit_log_actor_id_fkey;
+
+-- The fields a call changed as {"field": {"before": ..., "after": ...}},
+-- and where the request came from
+ALTER TABLE audit_log ADD COLUMN changes JSONB;
+ALTER TABLE audit_log ADD COLUMN ip_address VARCHAR(64);
+ALTER TABLE audit_log ADD COLUMN request_id VARCHAR(64);
+
+CREATE INDEX idx_audit_log_created_at ON audit_log(created_at);
+
+-- The log is append-only: rows cannot be edited or removed
+CREATE OR REPLACE FUNCTION reject_audit_log_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER audit_log_append_only BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION reject_audit_log_change();
@@ -0,0 +1,6 @@
+DELETE FROM tutors WHERE deleted_at IS NOT NULL;
+DELETE FROM courses WHERE deleted_at IS NOT NULL;
+DROP INDEX IF EXISTS idx_tutors_deleted_at;
+DROP INDEX IF EXISTS idx_courses_deleted_at;
+ALTER TABLE tutors DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE courses DROP COLUMN IF EXISTS deleted_at;
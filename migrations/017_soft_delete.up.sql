@@ -0,0 +1,8 @@
+-- Deleted courses and tutor profiles stay restorable for a retention window;
+-- a background job removes them, and everything hanging off them, once it
+-- has passed
+ALTER TABLE courses ADD COLUMN deleted_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE tutors ADD COLUMN deleted_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_courses_deleted_at ON courses(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_tutors_deleted_at ON tutors(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE tutors DROP COLUMN IF EXISTS rating_sum;
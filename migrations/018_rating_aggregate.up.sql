@@ -0,0 +1,15 @@
+-- Ratings are kept up to date incrementally: each review write adds or
+-- subtracts its stars in the same transaction, so the average is derived
+-- from the running sum instead of rescanning every review
+ALTER TABLE tutors ADD COLUMN rating_sum BIGINT NOT NULL DEFAULT 0;
+
+UPDATE tutors t
+SET rating_sum = r.rating_sum,
+    total_reviews = r.total_reviews,
+    rating = (r.rating_sum::FLOAT8 / r.total_reviews)::REAL
+FROM (
+    SELECT tutor_id, SUM(rating)::INT8 AS rating_sum, COUNT(*)::INT4 AS total_reviews
+    FROM tutor_reviews
+    GROUP BY tutor_id
+) r
+WHERE t.id = r.tutor_id;
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS review_replies;
+DROP TABLE IF EXISTS review_edits;
+ALTER TABLE tutor_reviews DROP COLUMN IF EXISTS edited_at;
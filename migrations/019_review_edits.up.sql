@@ -0,0 +1,20 @@
+-- Authors may revise a review for a while after posting; every version an
+-- edit replaces is kept, oldest first
+ALTER TABLE tutor_reviews ADD COLUMN edited_at TIMESTAMP WITH TIME ZONE;
+
+CREATE TABLE review_edits (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    review_id UUID NOT NULL REFERENCES tutor_reviews(id) ON DELETE CASCADE,
+    rating INTEGER NOT NULL CHECK (rating >= 1 AND rating <= 5),
+    comment TEXT,
+    edited_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_review_edits_review_id ON review_edits(review_id, edited_at);
+
+-- The reviewed tutor's public answer; one per review
+CREATE TABLE review_replies (
+    review_id UUID PRIMARY KEY REFERENCES tutor_reviews(id) ON DELETE CASCADE,
+    comment TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+ALTER TABLE certificates
+    ALTER COLUMN student_name TYPE VARCHAR(200),
+    ALTER COLUMN tutor_name TYPE VARCHAR(200);
@@ -0,0 +1,5 @@
+-- Student and tutor names are first and last name joined by a space, which
+-- can exceed 200 characters
+ALTER TABLE certificates
+    ALTER COLUMN student_name TYPE TEXT,
+    ALTER COLUMN tutor_name TYPE TEXT;
@@ -0,0 +1,16 @@
+-- Files in the file store cannot be moved back inline; they are dropped
+DELETE FROM assignment_files WHERE content IS NULL;
+ALTER TABLE assignment_files
+    DROP CONSTRAINT assignment_files_content_check,
+    DROP COLUMN stored_file_id,
+    ALTER COLUMN content SET NOT NULL;
+
+-- Enum values cannot be dropped; rebuild the type without 'assignment'
+DELETE FROM stored_files WHERE purpose = 'assignment';
+ALTER TYPE file_purpose RENAME TO file_purpose_old;
+CREATE TYPE file_purpose AS ENUM ('upload', 'avatar', 'credential');
+ALTER TABLE stored_files
+    ALTER COLUMN purpose DROP DEFAULT,
+    ALTER COLUMN purpose TYPE file_purpose USING purpose::text::file_purpose,
+    ALTER COLUMN purpose SET DEFAULT 'upload';
+DROP TYPE file_purpose_old;
@@ -0,0 +1,8 @@
+-- Assignment files are kept in the file store like every other upload;
+-- files uploaded before this keep their content inline
+ALTER TYPE file_purpose ADD VALUE 'assignment';
+
+ALTER TABLE assignment_files
+    ADD COLUMN stored_file_id UUID REFERENCES stored_files(id) ON DELETE CASCADE,
+    ALTER COLUMN content DROP NOT NULL,
+    ADD CONSTRAINT assignment_files_content_check CHECK ((content IS NULL) <> (stored_file_id IS NULL));
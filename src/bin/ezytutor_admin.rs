@@ -0,0 +1,325 @@
+// Operator CLI for tasks that have no HTTP endpoint: bootstrapping admins,
+// moderating accounts, running migrations and maintenance jobs
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use serde_json::json;
+use std::process::ExitCode;
+use ezytutor::config::Config;
+use ezytutor::database::{self, DbPool};
+use ezytutor::errors::{AppError, AppResult};
+use ezytutor::models::{PlatformStats, TutorResponse, UserResponse, UserRole};
+use ezytutor::repositories::seed::{self, SeedConfig, SeedScale};
+use ezytutor::repositories::{PgRepository, UserRepository};
+use ezytutor::services::{AdminService, RetentionService};
+
+#[derive(Parser)]
+#[command(name = "ezytutor-admin", version, about = "EzyTutor administration tool")]
+struct Cli {
+    /// Print results as JSON for scripting
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create an admin account
+    CreateAdmin {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        first_name: String,
+        #[arg(long)]
+        last_name: String,
+        /// Read from EZYTUTOR_ADMIN_PASSWORD when omitted
+        #[arg(long, env = "EZYTUTOR_ADMIN_PASSWORD", hide_env_values = true)]
+        password: String,
+    },
+    /// Change a user's role
+    SetRole {
+        /// User id or email
+        user: String,
+        #[arg(value_enum)]
+        role: Role,
+    },
+    /// Block a user from logging in
+    Deactivate {
+        /// User id or email
+        user: String,
+    },
+    /// Restore a deactivated user
+    Reactivate {
+        /// User id or email
+        user: String,
+    },
+    /// Mark a tutor as verified
+    VerifyTutor {
+        /// Tutor profile id, or the tutor's user id or email
+        tutor: String,
+        /// Remove the verified badge instead
+        #[arg(long)]
+        revoke: bool,
+        /// Recorded in the tutor's verification history
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Apply or revert database migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    /// Rebuild tutor ratings and review counts from the reviews table
+    RecomputeRatings,
+    /// Repair only the tutor ratings that no longer match their reviews
+    ReconcileRatings,
+    /// Remove deleted courses and tutor profiles past their retention window
+    PurgeDeleted,
+    /// Print platform statistics
+    Stats,
+    /// Fill an empty database with deterministic synthetic data
+    Seed {
+        #[arg(long, value_enum, default_value_t = Scale::Demo)]
+        scale: Scale,
+        /// RNG seed; the same seed always produces the same rows
+        #[arg(long, default_value_t = 42)]
+        seed: u64,
+        /// Override the number of students for the chosen scale
+        #[arg(long)]
+        students: Option<usize>,
+        /// Override the number of tutors for the chosen scale
+        #[arg(long)]
+        tutors: Option<usize>,
+        /// Override the average number of courses per tutor
+        #[arg(long)]
+        courses_per_tutor: Option<usize>,
+        /// Override the average number of reviews per tutor
+        #[arg(long)]
+        reviews_per_tutor: Option<usize>,
+        /// Password shared by every seeded account
+        #[arg(long, env = "EZYTUTOR_SEED_PASSWORD", default_value = "password123", hide_env_values = true)]
+        password: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// Apply all pending migrations
+    Run,
+    /// Revert the latest migration, or every migration after --to
+    Revert {
+        #[arg(long)]
+        to: Option<i64>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Scale {
+    Demo,
+    Medium,
+    Load,
+}
+
+impl From<Scale> for SeedScale {
+    fn from(scale: Scale) -> Self {
+        match scale {
+            Scale::Demo => SeedScale::Demo,
+            Scale::Medium => SeedScale::Medium,
+            Scale::Load => SeedScale::Load,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Role {
+    Student,
+    Tutor,
+    Admin,
+}
+
+impl From<Role> for UserRole {
+    fn from(role: Role) -> Self {
+        match role {
+            Role::Student => UserRole::Student,
+            Role::Tutor => UserRole::Tutor,
+            Role::Admin => UserRole::Admin,
+        }
+    }
+}
+
+// Pairs the JSON payload with the line shown to humans
+struct Output {
+    json: serde_json::Value,
+    text: String,
+}
+
+impl Output {
+    fn new(value: impl Serialize, text: String) -> AppResult<Self> {
+        let json = serde_json::to_value(value)
+            .map_err(|e| AppError::Internal(format!("Serialization failed: {}", e)))?;
+        Ok(Output { json, text })
+    }
+}
+
+fn describe_user(action: &str, user: &UserResponse) -> String {
+    format!(
+        "{} {} ({}) role={:?} active={}",
+        action, user.email, user.id, user.role, user.is_active
+    )
+}
+
+fn describe_tutor(tutor: &TutorResponse) -> String {
+    format!(
+        "Tutor {} {} ({}) verified={}",
+        tutor.first_name, tutor.last_name, tutor.id, tutor.is_verified
+    )
+}
+
+fn describe_stats(stats: &PlatformStats) -> String {
+    let average = stats.average_rating
+        .map(|rating| format!("{:.2}", rating))
+        .unwrap_or_else(|| "-".to_string());
+
+    [
+        format!("Users:     {} ({} active)", stats.total_users, stats.active_users),
+        format!("  students {}, tutors {}, admins {}", stats.students, stats.tutors, stats.admins),
+        format!(
+            "Tutors:    {} profiles ({} verified, {} available)",
+            stats.tutor_profiles, stats.verified_tutors, stats.available_tutors
+        ),
+        format!("Courses:   {} ({} active)", stats.courses, stats.active_courses),
+        format!("Reviews:   {} (average rating {})", stats.reviews, average),
+    ]
+    .join("\n")
+}
+
+async fn run(command: Command, pool: DbPool) -> AppResult<Output> {
+    let repo = PgRepository::new(pool.clone());
+
+    match command {
+        Command::CreateAdmin { email, first_name, last_name, password } => {
+            let user = AdminService::create_admin(&repo, email, password, first_name, last_name).await?;
+            let text = describe_user("Created admin", &user);
+            Output::new(user, text)
+        }
+        Command::SetRole { user, role } => {
+            let user = AdminService::set_role(&repo, &user, role.into()).await?;
+            let text = describe_user("Updated", &user);
+            Output::new(user, text)
+        }
+        Command::Deactivate { user } => {
+            let user = AdminService::set_active(&repo, &user, false).await?;
+            let text = describe_user("Deactivated", &user);
+            Output::new(user, text)
+        }
+        Command::Reactivate { user } => {
+            let user = AdminService::set_active(&repo, &user, true).await?;
+            let text = describe_user("Reactivated", &user);
+            Output::new(user, text)
+        }
+        Command::VerifyTutor { tutor, revoke, reason } => {
+            let tutor = AdminService::set_tutor_verified(&repo, &tutor, !revoke, reason).await?;
+            let text = describe_tutor(&tutor);
+            Output::new(tutor, text)
+        }
+        Command::Migrate { action: MigrateAction::Run } => {
+            database::run_migrations(&pool).await?;
+            Output::new(json!({ "status": "migrated" }), "Migrations applied".to_string())
+        }
+        Command::Migrate { action: MigrateAction::Revert { to } } => {
+            let version = database::revert_migrations(&pool, to).await?;
+            let text = match version {
+                Some(version) => format!("Reverted to migration {}", version),
+                None => "Reverted all migrations".to_string(),
+            };
+            Output::new(json!({ "status": "reverted", "version": version }), text)
+        }
+        Command::RecomputeRatings => {
+            let tutors = AdminService::recompute_ratings(&repo).await?;
+            Output::new(json!({ "tutors": tutors }), format!("Recomputed ratings for {} tutors", tutors))
+        }
+        Command::ReconcileRatings => {
+            let drift = AdminService::reconcile_ratings(&repo).await?;
+            let text = format!("Repaired ratings for {} tutors", drift.len());
+            Output::new(json!({ "tutors": drift }), text)
+        }
+        Command::PurgeDeleted => {
+            let report = RetentionService::purge_expired(&repo, chrono::Utc::now()).await?;
+            let text = format!(
+                "Purged {} tutor profiles and {} courses",
+                report.tutor_ids.len(), report.course_ids.len()
+            );
+            Output::new(json!({ "tutors": report.tutor_ids, "courses": report.course_ids }), text)
+        }
+        Command::Stats => {
+            let stats = AdminService::platform_stats(&repo).await?;
+            let text = describe_stats(&stats);
+            Output::new(stats, text)
+        }
+        Command::Seed {
+            scale, seed, students, tutors, courses_per_tutor, reviews_per_tutor, password,
+        } => {
+            let marker = format!("admin@{}", seed::SEED_EMAIL_DOMAIN);
+            if repo.find_user_by_email(&marker).await?.is_some() {
+                return Err(AppError::BadRequest("Database already contains seed data".to_string()));
+            }
+
+            let mut config = SeedConfig::for_scale(scale.into(), seed);
+            config.students = students.unwrap_or(config.students);
+            config.tutors = tutors.unwrap_or(config.tutors);
+            config.courses_per_tutor = courses_per_tutor.unwrap_or(config.courses_per_tutor);
+            config.reviews_per_tutor = reviews_per_tutor.unwrap_or(config.reviews_per_tutor);
+
+            // Every seeded account shares one password, so hash it once
+            let password_hash = bcrypt::hash(&password, bcrypt::DEFAULT_COST)
+                .map_err(|e| AppError::Internal(format!("Password hashing failed: {}", e)))?;
+            let fixtures = seed::generate(&config, &password_hash);
+            repo.load_fixtures(&fixtures).await?;
+
+            let counts = json!({
+                "seed": seed,
+                "users": fixtures.users.len(),
+                "tutors": fixtures.tutors.len(),
+                "courses": fixtures.courses.len(),
+                "reviews": fixtures.reviews.len()
+            });
+            let text = format!(
+                "Seeded {} users, {} tutors, {} courses and {} reviews (seed {})",
+                fixtures.users.len(), fixtures.tutors.len(), fixtures.courses.len(), fixtures.reviews.len(), seed
+            );
+            Output::new(counts, text)
+        }
+    }
+}
+
+#[actix_web::main]
+async fn main() -> ExitCode {
+    env_logger::init();
+    let cli = Cli::parse();
+
+    let config = Config::from_env().expect("Failed to load configuration");
+    let result = match database::create_pool(&config.database_url).await {
+        Ok(pool) => run(cli.command, pool).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(output) if cli.json => {
+            println!("{}", output.json);
+            ExitCode::SUCCESS
+        }
+        Ok(output) => {
+            println!("{}", output.text);
+            ExitCode::SUCCESS
+        }
+        Err(e) if cli.json => {
+            eprintln!("{}", json!({ "error": e.to_string() }));
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
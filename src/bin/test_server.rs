@@ -1,23 +1,30 @@
-// Test server that runs without database for basic endpoint testing
-use actix_web::{web, App, HttpServer, middleware::Logger, HttpResponse, Result};
+// Mock server that serves the full API from an in-memory store seeded with
+// JSON fixtures, so frontend work does not need PostgreSQL
+use actix_web::{web, App, HttpServer, middleware::Logger, HttpResponse};
 use actix_cors::Cors;
 use serde_json::json;
-use chrono::Utc;
+use ezytutor::config::Config;
+use ezytutor::errors::AppError;
+use ezytutor::handlers;
+use ezytutor::repositories::{FixtureSet, InMemoryRepository};
+use ezytutor::storage::{FileStore, MemoryStorage};
 
-async fn health_check() -> Result<HttpResponse> {
-    Ok(HttpResponse::Ok().json(json!({
-        "status": "healthy",
-        "service": "EzyTutor API (Test Mode)",
-        "version": "0.1.0",
-        "timestamp": Utc::now().to_rfc3339(),
-        "database": "disabled"
-    })))
-}
+// Restores the store to the fixture state between frontend test runs
+async fn reset_state(
+    repo: web::Data<InMemoryRepository>,
+    fixtures: web::Data<FixtureSet>,
+    storage: web::Data<MemoryStorage>,
+) -> Result<HttpResponse, AppError> {
+    repo.clear();
+    storage.clear();
+    repo.load_fixtures(&fixtures).await?;
 
-async fn test_error() -> Result<HttpResponse> {
-    Ok(HttpResponse::NotFound().json(json!({
-        "error": "This is a test error endpoint",
-        "status": 404
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "reset",
+        "users": fixtures.users.len(),
+        "tutors": fixtures.tutors.len(),
+        "courses": fixtures.courses.len(),
+        "reviews": fixtures.reviews.len()
     })))
 }
 
@@ -25,31 +32,45 @@ async fn test_error() -> Result<HttpResponse> {
 async fn main() -> std::io::Result<()> {
     env_logger::init();
 
-    let host = "127.0.0.1";
-    let port = 8080;
+    let config = Config::from_env().expect("Failed to load configuration");
+    let fixtures_path = std::env::var("FIXTURES_PATH")
+        .unwrap_or_else(|_| "fixtures/seed.json".to_string());
+
+    let fixtures = FixtureSet::from_file(&fixtures_path).expect("Failed to load fixtures");
+    let repository = InMemoryRepository::new();
+    repository.load_fixtures(&fixtures).await.expect("Failed to seed in-memory store");
+
+    println!("🚀 Starting EzyTutor Test Server at {}:{}", config.host, config.port);
+    println!("📦 Loaded fixtures from {}", fixtures_path);
+    println!("📋 Serving the full /api/v1 surface from an in-memory store");
+    println!("   POST /api/v1/test/reset restores the fixture state");
 
-    println!("🚀 Starting EzyTutor Test Server at {}:{}", host, port);
-    println!("📋 Available endpoints:");
-    println!("   GET  /api/v1/health");
-    println!("   GET  /api/v1/test-error");
-    println!("💡 This is a test server without database functionality");
+    let fixtures = web::Data::new(fixtures);
+    // Uploads are kept in memory too, and cleared by a reset
+    let storage = web::Data::new(MemoryStorage::new());
+    let files = web::Data::new(FileStore::new(storage.get_ref().clone(), config.jwt_secret.as_bytes()));
 
-    HttpServer::new(|| {
+    HttpServer::new(move || {
         let cors = Cors::default()
             .allow_any_origin()
             .allow_any_method()
             .allow_any_header();
 
         App::new()
+            .app_data(web::Data::new(repository.clone()))
+            .app_data(fixtures.clone())
+            .app_data(storage.clone())
+            .app_data(files.clone())
             .wrap(cors)
             .wrap(Logger::default())
+            // Registered ahead of the API scope, which would otherwise claim the path
             .service(
-                web::scope("/api/v1")
-                    .route("/health", web::get().to(health_check))
-                    .route("/test-error", web::get().to(test_error))
+                web::scope("/api/v1/test")
+                    .route("/reset", web::post().to(reset_state))
             )
+            .configure(handlers::configure_routes::<InMemoryRepository>)
     })
-    .bind(format!("{}:{}", host, port))?
+    .bind(format!("{}:{}", config.host, config.port))?
     .run()
     .await
 }
@@ -4,9 +4,32 @@ use std::env;
 pub struct Config {
     pub database_url: String,
     pub jwt_secret: String,
+    // Key for signing download links; derived from the JWT secret when unset
+    pub download_signing_key: Option<String>,
     pub host: String,
     pub port: u16,
     pub log_level: String,
+    pub storage: StorageConfig,
+    // How often the server purges expired soft-deleted records
+    pub purge_interval_secs: u64,
+    // How often the server checks tutor ratings against their reviews
+    pub rating_reconcile_interval_secs: u64,
+}
+
+// Where uploaded files are kept
+#[derive(Debug, Clone)]
+pub enum StorageConfig {
+    Local {
+        root: String,
+    },
+    // Any S3-compatible service; objects are addressed path-style, as MinIO expects
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    },
 }
 
 impl Config {
@@ -16,6 +39,7 @@ impl Config {
                 .unwrap_or_else(|_| "postgresql://localhost/ezytutor".to_string()),
             jwt_secret: env::var("JWT_SECRET")
                 .unwrap_or_else(|_| "your-secret-key-change-in-production".to_string()),
+            download_signing_key: env::var("DOWNLOAD_SIGNING_KEY").ok(),
             host: env::var("HOST")
                 .unwrap_or_else(|_| "127.0.0.1".to_string()),
             port: env::var("PORT")
@@ -23,6 +47,34 @@ impl Config {
                 .parse()?,
             log_level: env::var("RUST_LOG")
                 .unwrap_or_else(|_| "info".to_string()),
+            storage: StorageConfig::from_env()?,
+            purge_interval_secs: env::var("PURGE_INTERVAL_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()?,
+            rating_reconcile_interval_secs: env::var("RATING_RECONCILE_INTERVAL_SECS")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse()?,
         })
     }
 }
+
+impl StorageConfig {
+    fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let backend = env::var("STORAGE_BACKEND").unwrap_or_else(|_| "local".to_string());
+        match backend.as_str() {
+            "local" => Ok(StorageConfig::Local {
+                root: env::var("STORAGE_PATH")
+                    .unwrap_or_else(|_| "uploads".to_string()),
+            }),
+            "s3" => Ok(StorageConfig::S3 {
+                endpoint: env::var("S3_ENDPOINT")?,
+                bucket: env::var("S3_BUCKET")?,
+                region: env::var("S3_REGION")
+                    .unwrap_or_else(|_| "us-east-1".to_string()),
+                access_key: env::var("S3_ACCESS_KEY")?,
+                secret_key: env::var("S3_SECRET_KEY")?,
+            }),
+            other => Err(format!("Unknown STORAGE_BACKEND {:?}; expected \"local\" or \"s3\"", other).into()),
+        }
+    }
+}
@@ -1,19 +1,22 @@
+use sqlx::migrate::Migrator;
 use sqlx::{PgPool, Pool, Postgres};
 use crate::errors::AppResult;
 
 pub type DbPool = Pool<Postgres>;
 
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
 pub async fn create_pool(database_url: &str) -> AppResult<DbPool> {
     let pool = PgPool::connect(database_url)
         .await
-        .map_err(|e| crate::errors::AppError::Database(e))?;
+        .map_err(crate::errors::AppError::Database)?;
     
     log::info!("Database connection pool created successfully");
     Ok(pool)
 }
 
 pub async fn run_migrations(pool: &DbPool) -> AppResult<()> {
-    sqlx::migrate!("./migrations")
+    MIGRATOR
         .run(pool)
         .await
         .map_err(|e| crate::errors::AppError::Internal(format!("Migration failed: {}", e)))?;
@@ -21,3 +24,28 @@ pub async fn run_migrations(pool: &DbPool) -> AppResult<()> {
     log::info!("Database migrations completed successfully");
     Ok(())
 }
+
+// Reverts applied migrations newer than `target`. Without a target only the
+// latest migration is reverted. Returns the version left applied, if any.
+pub async fn revert_migrations(pool: &DbPool, target: Option<i64>) -> AppResult<Option<i64>> {
+    let applied: Vec<i64> = sqlx::query_scalar(
+        "SELECT version FROM _sqlx_migrations WHERE success ORDER BY version DESC LIMIT 2"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(crate::errors::AppError::Database)?;
+
+    let target = match target {
+        Some(target) => target,
+        None if applied.is_empty() => return Ok(None),
+        None => applied.get(1).copied().unwrap_or(0),
+    };
+
+    MIGRATOR
+        .undo(pool, target)
+        .await
+        .map_err(|e| crate::errors::AppError::Internal(format!("Migration revert failed: {}", e)))?;
+
+    log::info!("Database migrations reverted to version {}", target);
+    Ok((target > 0).then_some(target))
+}
@@ -1,9 +1,16 @@
 use actix_web::{HttpResponse, ResponseError};
-use serde_json::json;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 pub type AppResult<T> = Result<T, AppError>;
 
+// JSON body returned for every error response
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ErrorBody {
+    pub error: String,
+    pub status: u16,
+}
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Database error: {0}")]
@@ -24,6 +31,15 @@ pub enum AppError {
     #[error("Bad request: {0}")]
     BadRequest(String),
     
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Precondition required: {0}")]
+    PreconditionRequired(String),
+    
     #[error("Internal server error: {0}")]
     Internal(String),
 }
@@ -55,15 +71,27 @@ impl ResponseError for AppError {
                 actix_web::http::StatusCode::BAD_REQUEST,
                 msg.clone(),
             ),
+            AppError::Conflict(msg) => (
+                actix_web::http::StatusCode::CONFLICT,
+                msg.clone(),
+            ),
+            AppError::PreconditionFailed(msg) => (
+                actix_web::http::StatusCode::PRECONDITION_FAILED,
+                msg.clone(),
+            ),
+            AppError::PreconditionRequired(msg) => (
+                actix_web::http::StatusCode::PRECONDITION_REQUIRED,
+                msg.clone(),
+            ),
             AppError::Internal(msg) => (
                 actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
                 msg.clone(),
             ),
         };
 
-        HttpResponse::build(status_code).json(json!({
-            "error": error_message,
-            "status": status_code.as_u16()
-        }))
+        HttpResponse::build(status_code).json(ErrorBody {
+            error: error_message,
+            status: status_code.as_u16(),
+        })
     }
 }
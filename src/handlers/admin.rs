@@ -0,0 +1,162 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::{AuditLogQuery, ChangeRoleRequest, ModerationRequest, UserSearchQuery};
+use crate::repositories::Repository;
+use crate::services::AdminService;
+use crate::middleware::Claims;
+use super::request_context;
+
+pub async fn search_users<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<UserSearchQuery>,
+) -> Result<HttpResponse, AppError> {
+    let users = AdminService::search_users(repo.get_ref(), claims.sub, query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(users))
+}
+
+pub async fn get_user<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let user_id = path.into_inner();
+    let user = AdminService::get_user(repo.get_ref(), claims.sub, user_id).await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+pub async fn search_audit_log<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<AuditLogQuery>,
+) -> Result<HttpResponse, AppError> {
+    let entries = AdminService::search_audit_log(repo.get_ref(), claims.sub, query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+pub async fn change_role<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<ChangeRoleRequest>,
+) -> Result<HttpResponse, AppError> {
+    let user_id = path.into_inner();
+    let user = AdminService::change_role(
+        repo.get_ref(),
+        claims.sub,
+        user_id,
+        request.into_inner(),
+        &request_context(&req),
+    ).await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+pub async fn deactivate_user<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<ModerationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let user_id = path.into_inner();
+    let user = AdminService::deactivate_user(
+        repo.get_ref(),
+        claims.sub,
+        user_id,
+        request.into_inner(),
+        &request_context(&req),
+    ).await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+// The reason body is optional
+pub async fn reactivate_user<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: Option<web::Json<ModerationRequest>>,
+) -> Result<HttpResponse, AppError> {
+    let user_id = path.into_inner();
+    let request = request.map(web::Json::into_inner).unwrap_or_default();
+    let user = AdminService::reactivate_user(
+        repo.get_ref(),
+        claims.sub,
+        user_id,
+        request,
+        &request_context(&req),
+    ).await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+// The reason body is optional
+pub async fn force_logout<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: Option<web::Json<ModerationRequest>>,
+) -> Result<HttpResponse, AppError> {
+    let user_id = path.into_inner();
+    let request = request.map(web::Json::into_inner).unwrap_or_default();
+    AdminService::force_logout(repo.get_ref(), claims.sub, user_id, request, &request_context(&req)).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn take_down_course<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<ModerationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let course = AdminService::take_down_course(
+        repo.get_ref(),
+        claims.sub,
+        course_id,
+        request.into_inner(),
+        &request_context(&req),
+    ).await?;
+    Ok(HttpResponse::Ok().json(course))
+}
+
+// The reason body is optional
+pub async fn reinstate_course<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: Option<web::Json<ModerationRequest>>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let request = request.map(web::Json::into_inner).unwrap_or_default();
+    let course = AdminService::reinstate_course(
+        repo.get_ref(),
+        claims.sub,
+        course_id,
+        request,
+        &request_context(&req),
+    ).await?;
+    Ok(HttpResponse::Ok().json(course))
+}
+
+pub async fn remove_review<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<ModerationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let review_id = path.into_inner();
+    AdminService::remove_review(
+        repo.get_ref(),
+        claims.sub,
+        review_id,
+        request.into_inner(),
+        &request_context(&req),
+    ).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,158 @@
+use actix_web::http::header::{self, ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::{AssignmentRequest, GradeSubmissionRequest, SubmitAssignmentRequest, UploadFileQuery};
+use crate::repositories::Repository;
+use crate::services::file::FileUpload;
+use crate::services::AssignmentService;
+use crate::storage::FileStore;
+use crate::middleware::Claims;
+
+pub async fn create_assignment<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<AssignmentRequest>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let assignment = AssignmentService::create_assignment(repo.get_ref(), course_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Created().json(assignment))
+}
+
+pub async fn list_course_assignments<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let assignments = AssignmentService::list_course_assignments(repo.get_ref(), course_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(assignments))
+}
+
+pub async fn get_assignment<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let assignment_id = path.into_inner();
+    let assignment = AssignmentService::get_assignment(repo.get_ref(), assignment_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(assignment))
+}
+
+pub async fn update_assignment<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<AssignmentRequest>,
+) -> Result<HttpResponse, AppError> {
+    let assignment_id = path.into_inner();
+    let assignment = AssignmentService::update_assignment(repo.get_ref(), assignment_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(assignment))
+}
+
+pub async fn delete_assignment<R: Repository>(
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let assignment_id = path.into_inner();
+    AssignmentService::delete_assignment(repo.get_ref(), files.get_ref(), assignment_id, claims.sub).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// The request body is the raw file; its Content-Type header is the file's type
+pub async fn upload_file<R: Repository>(
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    query: web::Query<UploadFileQuery>,
+    body: web::Bytes,
+) -> Result<HttpResponse, AppError> {
+    let assignment_id = path.into_inner();
+    let content_type = req.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    let upload = FileUpload {
+        file_name: query.into_inner().file_name,
+        content_type,
+        content: body.to_vec(),
+    };
+    let file = AssignmentService::upload_file(repo.get_ref(), files.get_ref(), assignment_id, claims.sub, upload).await?;
+    Ok(HttpResponse::Created().json(file))
+}
+
+pub async fn download_file<R: Repository>(
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let file_id = path.into_inner();
+    let (file, content) = AssignmentService::download_file(repo.get_ref(), files.get_ref(), file_id, claims.sub).await?;
+    Ok(HttpResponse::Ok()
+        .content_type(file.content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(file.file_name)],
+        })
+        .body(content))
+}
+
+pub async fn submit<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<SubmitAssignmentRequest>,
+) -> Result<HttpResponse, AppError> {
+    let assignment_id = path.into_inner();
+    let submission = AssignmentService::submit(repo.get_ref(), assignment_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Created().json(submission))
+}
+
+pub async fn list_submissions<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let assignment_id = path.into_inner();
+    let submissions = AssignmentService::list_submissions(repo.get_ref(), assignment_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(submissions))
+}
+
+pub async fn publish_grades<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let assignment_id = path.into_inner();
+    let submissions = AssignmentService::publish_grades(repo.get_ref(), assignment_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(submissions))
+}
+
+pub async fn get_submission<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let submission_id = path.into_inner();
+    let submission = AssignmentService::get_submission(repo.get_ref(), submission_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(submission))
+}
+
+pub async fn grade_submission<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<GradeSubmissionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let submission_id = path.into_inner();
+    let submission = AssignmentService::grade_submission(repo.get_ref(), submission_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(submission))
+}
@@ -1,21 +1,32 @@
-use actix_web::{web, HttpResponse, Result};
-use crate::database::DbPool;
+use actix_web::{web, HttpRequest, HttpResponse, Result};
 use crate::errors::AppError;
 use crate::models::{CreateUserRequest, LoginRequest};
+use crate::repositories::Repository;
 use crate::services::AuthService;
+use crate::middleware::Claims;
+use super::request_context;
 
-pub async fn register(
-    pool: web::Data<DbPool>,
+pub async fn register<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
     request: web::Json<CreateUserRequest>,
 ) -> Result<HttpResponse, AppError> {
-    let user = AuthService::register_user(&pool, request.into_inner()).await?;
+    let user = AuthService::register_user(repo.get_ref(), request.into_inner(), &request_context(&req)).await?;
     Ok(HttpResponse::Created().json(user))
 }
 
-pub async fn login(
-    pool: web::Data<DbPool>,
+pub async fn login<R: Repository>(
+    repo: web::Data<R>,
     request: web::Json<LoginRequest>,
 ) -> Result<HttpResponse, AppError> {
-    let login_response = AuthService::login_user(&pool, request.into_inner()).await?;
+    let login_response = AuthService::login_user(repo.get_ref(), request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(login_response))
+}
+
+pub async fn refresh_token<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let login_response = AuthService::refresh_token(repo.get_ref(), claims.sub).await?;
     Ok(HttpResponse::Ok().json(login_response))
 }
@@ -0,0 +1,72 @@
+use actix_web::{web, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::{CreateBlackoutRequest, CreateExceptionRequest, OpenSlotsQuery, SetScheduleRequest};
+use crate::repositories::Repository;
+use crate::services::AvailabilityService;
+use crate::middleware::Claims;
+
+pub async fn get_open_slots<R: Repository>(
+    repo: web::Data<R>,
+    path: web::Path<Uuid>,
+    query: web::Query<OpenSlotsQuery>,
+) -> Result<HttpResponse, AppError> {
+    let tutor_id = path.into_inner();
+    let slots = AvailabilityService::get_open_slots(repo.get_ref(), tutor_id, query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(slots))
+}
+
+pub async fn get_schedule<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let schedule = AvailabilityService::get_schedule(repo.get_ref(), claims.sub).await?;
+    Ok(HttpResponse::Ok().json(schedule))
+}
+
+pub async fn set_schedule<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<SetScheduleRequest>,
+) -> Result<HttpResponse, AppError> {
+    let schedule = AvailabilityService::set_schedule(repo.get_ref(), claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(schedule))
+}
+
+pub async fn add_exception<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<CreateExceptionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let exception = AvailabilityService::add_exception(repo.get_ref(), claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Created().json(exception))
+}
+
+pub async fn remove_exception<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let exception_id = path.into_inner();
+    AvailabilityService::remove_exception(repo.get_ref(), claims.sub, exception_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn add_blackout<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<CreateBlackoutRequest>,
+) -> Result<HttpResponse, AppError> {
+    let blackout = AvailabilityService::add_blackout(repo.get_ref(), claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Created().json(blackout))
+}
+
+pub async fn remove_blackout<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let blackout_id = path.into_inner();
+    AvailabilityService::remove_blackout(repo.get_ref(), claims.sub, blackout_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
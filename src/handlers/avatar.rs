@@ -0,0 +1,52 @@
+use actix_multipart::Multipart;
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{web, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::AvatarSize;
+use crate::repositories::Repository;
+use crate::services::avatar::MAX_AVATAR_BYTES;
+use crate::services::AvatarService;
+use crate::storage::FileStore;
+use crate::middleware::Claims;
+use super::file::read_upload;
+
+pub async fn set_avatar<R: Repository>(
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let upload = read_upload(payload, MAX_AVATAR_BYTES).await?;
+    let user = AvatarService::set_avatar(repo.get_ref(), files.get_ref(), claims.sub, upload).await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+pub async fn delete_avatar<R: Repository>(
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    AvatarService::delete_avatar(repo.get_ref(), files.get_ref(), claims.sub).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Public. Avatar URLs carry a version, so whatever sits behind one never
+// changes and may be cached for good.
+pub async fn get_avatar<R: Repository>(
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    path: web::Path<(Uuid, AvatarSize)>,
+) -> Result<HttpResponse, AppError> {
+    let (user_id, size) = path.into_inner();
+    let image = AvatarService::get_avatar_image(repo.get_ref(), files.get_ref(), user_id, size).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("image/png")
+        .insert_header(("X-Content-Type-Options", "nosniff"))
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(31_536_000),
+            CacheDirective::Extension("immutable".to_string(), None),
+        ]))
+        .body(image))
+}
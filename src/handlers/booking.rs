@@ -0,0 +1,139 @@
+use actix_web::{web, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::{
+    BookingActionRequest, BookingListQuery, CreateBookingRequest, RescheduleBookingRequest,
+    SetBookingPolicyRequest,
+};
+use crate::repositories::Repository;
+use crate::services::BookingService;
+use crate::middleware::Claims;
+
+pub async fn create_booking<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<CreateBookingRequest>,
+) -> Result<HttpResponse, AppError> {
+    let booking = BookingService::create_booking(repo.get_ref(), claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Created().json(booking))
+}
+
+pub async fn get_booking<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let booking_id = path.into_inner();
+    let booking = BookingService::get_booking(repo.get_ref(), booking_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(booking))
+}
+
+pub async fn get_student_bookings<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<BookingListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let bookings = BookingService::list_student_bookings(repo.get_ref(), claims.sub, query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(bookings))
+}
+
+pub async fn get_tutor_bookings<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<BookingListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let bookings = BookingService::list_tutor_bookings(repo.get_ref(), claims.sub, query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(bookings))
+}
+
+pub async fn accept_booking<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let booking_id = path.into_inner();
+    let booking = BookingService::accept_booking(repo.get_ref(), booking_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(booking))
+}
+
+// The reason body is optional for declines and cancellations
+pub async fn decline_booking<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: Option<web::Json<BookingActionRequest>>,
+) -> Result<HttpResponse, AppError> {
+    let booking_id = path.into_inner();
+    let request = request.map(web::Json::into_inner).unwrap_or_default();
+    let booking = BookingService::decline_booking(repo.get_ref(), booking_id, claims.sub, request).await?;
+    Ok(HttpResponse::Ok().json(booking))
+}
+
+pub async fn cancel_booking<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: Option<web::Json<BookingActionRequest>>,
+) -> Result<HttpResponse, AppError> {
+    let booking_id = path.into_inner();
+    let request = request.map(web::Json::into_inner).unwrap_or_default();
+    let booking = BookingService::cancel_booking(repo.get_ref(), booking_id, claims.sub, request).await?;
+    Ok(HttpResponse::Ok().json(booking))
+}
+
+pub async fn complete_booking<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let booking_id = path.into_inner();
+    let booking = BookingService::complete_booking(repo.get_ref(), booking_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(booking))
+}
+
+pub async fn mark_no_show<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let booking_id = path.into_inner();
+    let booking = BookingService::mark_no_show(repo.get_ref(), booking_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(booking))
+}
+
+pub async fn reschedule_booking<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<RescheduleBookingRequest>,
+) -> Result<HttpResponse, AppError> {
+    let booking_id = path.into_inner();
+    let booking = BookingService::reschedule_booking(repo.get_ref(), booking_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(booking))
+}
+
+pub async fn get_tutor_policy<R: Repository>(
+    repo: web::Data<R>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let tutor_id = path.into_inner();
+    let policy = BookingService::get_tutor_policy(repo.get_ref(), tutor_id).await?;
+    Ok(HttpResponse::Ok().json(policy))
+}
+
+pub async fn get_own_policy<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let policy = BookingService::get_own_policy(repo.get_ref(), claims.sub).await?;
+    Ok(HttpResponse::Ok().json(policy))
+}
+
+pub async fn set_policy<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<SetBookingPolicyRequest>,
+) -> Result<HttpResponse, AppError> {
+    let policy = BookingService::set_policy(repo.get_ref(), claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(policy))
+}
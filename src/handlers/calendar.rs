@@ -0,0 +1,47 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::errors::AppError;
+use crate::models::UpdateCalendarFeedRequest;
+use crate::repositories::Repository;
+use crate::services::CalendarService;
+use crate::middleware::Claims;
+use super::base_url;
+
+// Public: calendar apps authenticate with the secret token in the path
+pub async fn get_calendar<R: Repository>(
+    repo: web::Data<R>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let token = path.into_inner();
+    let calendar = CalendarService::render_feed(repo.get_ref(), &token).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .body(calendar))
+}
+
+pub async fn get_feed<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let feed = CalendarService::get_feed(repo.get_ref(), claims.sub, &base_url(&req)).await?;
+    Ok(HttpResponse::Ok().json(feed))
+}
+
+pub async fn update_feed<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<UpdateCalendarFeedRequest>,
+) -> Result<HttpResponse, AppError> {
+    let feed = CalendarService::set_time_zone(repo.get_ref(), claims.sub, request.into_inner(), &base_url(&req)).await?;
+    Ok(HttpResponse::Ok().json(feed))
+}
+
+pub async fn rotate_feed<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let feed = CalendarService::rotate_feed(repo.get_ref(), claims.sub, &base_url(&req)).await?;
+    Ok(HttpResponse::Ok().json(feed))
+}
@@ -0,0 +1,54 @@
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{web, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::repositories::Repository;
+use crate::services::CertificateService;
+use crate::middleware::Claims;
+
+// Public: anyone holding a code can check it
+pub async fn verify_certificate<R: Repository>(
+    repo: web::Data<R>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let code = path.into_inner();
+    let certificate = CertificateService::verify(repo.get_ref(), &code).await?;
+    Ok(HttpResponse::Ok().json(certificate))
+}
+
+pub async fn get_certificate_pdf<R: Repository>(
+    repo: web::Data<R>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let code = path.into_inner();
+    let (file_name, pdf) = CertificateService::render_pdf(repo.get_ref(), &code).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Inline,
+            parameters: vec![DispositionParam::Filename(file_name)],
+        })
+        .body(pdf))
+}
+
+pub async fn issue_certificate<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let (certificate, created) = CertificateService::issue(repo.get_ref(), course_id, claims.sub).await?;
+    if created {
+        Ok(HttpResponse::Created().json(certificate))
+    } else {
+        Ok(HttpResponse::Ok().json(certificate))
+    }
+}
+
+pub async fn get_student_certificates<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let certificates = CertificateService::list_for_student(repo.get_ref(), claims.sub).await?;
+    Ok(HttpResponse::Ok().json(certificates))
+}
@@ -1,61 +1,97 @@
-use actix_web::{web, HttpResponse, Result};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
 use uuid::Uuid;
-use crate::database::DbPool;
 use crate::errors::AppError;
 use crate::models::{CreateCourseRequest, UpdateCourseRequest};
+use crate::repositories::Repository;
 use crate::services::CourseService;
 use crate::middleware::Claims;
+use super::{conditional_json, if_match, request_context, tagged_json};
 
-pub async fn create_course(
-    pool: web::Data<DbPool>,
+pub async fn create_course<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
     claims: web::ReqData<Claims>,
     request: web::Json<CreateCourseRequest>,
 ) -> Result<HttpResponse, AppError> {
-    let course = CourseService::create_course(&pool, claims.sub, request.into_inner()).await?;
-    Ok(HttpResponse::Created().json(course))
+    let course = CourseService::create_course(
+        repo.get_ref(),
+        claims.sub,
+        request.into_inner(),
+        &request_context(&req),
+    ).await?;
+    Ok(tagged_json(HttpResponse::Created(), course.updated_at, &course))
 }
 
-pub async fn get_all_courses(
-    pool: web::Data<DbPool>,
+pub async fn get_all_courses<R: Repository>(
+    repo: web::Data<R>,
 ) -> Result<HttpResponse, AppError> {
-    let courses = CourseService::get_all_courses(&pool).await?;
+    let courses = CourseService::get_all_courses(repo.get_ref()).await?;
     Ok(HttpResponse::Ok().json(courses))
 }
 
-pub async fn get_course(
-    pool: web::Data<DbPool>,
+pub async fn get_course<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse, AppError> {
     let course_id = path.into_inner();
-    let course = CourseService::get_course_by_id(&pool, course_id).await?;
-    Ok(HttpResponse::Ok().json(course))
+    let course = CourseService::get_course_by_id(repo.get_ref(), course_id).await?;
+    Ok(conditional_json(&req, course.updated_at, &course))
 }
 
-pub async fn update_course(
-    pool: web::Data<DbPool>,
+pub async fn update_course<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
     claims: web::ReqData<Claims>,
     path: web::Path<Uuid>,
     request: web::Json<UpdateCourseRequest>,
 ) -> Result<HttpResponse, AppError> {
     let course_id = path.into_inner();
-    let course = CourseService::update_course(&pool, course_id, claims.sub, request.into_inner()).await?;
-    Ok(HttpResponse::Ok().json(course))
+    let course = CourseService::update_course(
+        repo.get_ref(),
+        course_id,
+        claims.sub,
+        request.into_inner(),
+        &if_match(&req)?,
+        &request_context(&req),
+    ).await?;
+    Ok(tagged_json(HttpResponse::Ok(), course.updated_at, &course))
 }
 
-pub async fn delete_course(
-    pool: web::Data<DbPool>,
+pub async fn delete_course<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
     claims: web::ReqData<Claims>,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse, AppError> {
     let course_id = path.into_inner();
-    CourseService::delete_course(&pool, course_id, claims.sub).await?;
+    CourseService::delete_course(repo.get_ref(), course_id, claims.sub, &request_context(&req)).await?;
     Ok(HttpResponse::NoContent().finish())
 }
 
-pub async fn get_tutor_courses(
-    pool: web::Data<DbPool>,
+pub async fn restore_course<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let course = CourseService::restore_course(repo.get_ref(), course_id, claims.sub, &request_context(&req)).await?;
+    Ok(HttpResponse::Ok().json(course))
+}
+
+pub async fn get_deleted_courses<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let courses = CourseService::list_deleted_courses(repo.get_ref(), claims.sub).await?;
+    Ok(HttpResponse::Ok().json(courses))
+}
+
+pub async fn get_tutor_courses<R: Repository>(
+    repo: web::Data<R>,
     claims: web::ReqData<Claims>,
 ) -> Result<HttpResponse, AppError> {
-    let courses = CourseService::get_courses_by_tutor(&pool, claims.sub).await?;
+    let courses = CourseService::get_courses_by_tutor(repo.get_ref(), claims.sub).await?;
     Ok(HttpResponse::Ok().json(courses))
 }
@@ -0,0 +1,101 @@
+use actix_web::{web, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::{CreateLessonRequest, ModuleRequest, ReorderCurriculumRequest, UpdateLessonRequest};
+use crate::repositories::Repository;
+use crate::services::CurriculumService;
+use crate::middleware::Claims;
+
+pub async fn get_outline<R: Repository>(
+    repo: web::Data<R>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let outline = CurriculumService::get_outline(repo.get_ref(), course_id).await?;
+    Ok(HttpResponse::Ok().json(outline))
+}
+
+pub async fn reorder<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<ReorderCurriculumRequest>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let outline = CurriculumService::reorder(repo.get_ref(), course_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(outline))
+}
+
+pub async fn add_module<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<ModuleRequest>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let module = CurriculumService::add_module(repo.get_ref(), course_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Created().json(module))
+}
+
+pub async fn rename_module<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<ModuleRequest>,
+) -> Result<HttpResponse, AppError> {
+    let module_id = path.into_inner();
+    let module = CurriculumService::rename_module(repo.get_ref(), module_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(module))
+}
+
+pub async fn delete_module<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let module_id = path.into_inner();
+    CurriculumService::delete_module(repo.get_ref(), module_id, claims.sub).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn add_lesson<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<CreateLessonRequest>,
+) -> Result<HttpResponse, AppError> {
+    let module_id = path.into_inner();
+    let lesson = CurriculumService::add_lesson(repo.get_ref(), module_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Created().json(lesson))
+}
+
+pub async fn get_lesson<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let lesson_id = path.into_inner();
+    let lesson = CurriculumService::get_lesson(repo.get_ref(), lesson_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(lesson))
+}
+
+pub async fn update_lesson<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<UpdateLessonRequest>,
+) -> Result<HttpResponse, AppError> {
+    let lesson_id = path.into_inner();
+    let lesson = CurriculumService::update_lesson(repo.get_ref(), lesson_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(lesson))
+}
+
+pub async fn delete_lesson<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let lesson_id = path.into_inner();
+    CurriculumService::delete_lesson(repo.get_ref(), lesson_id, claims.sub).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
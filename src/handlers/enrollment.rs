@@ -0,0 +1,49 @@
+use actix_web::{web, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::EnrollmentListQuery;
+use crate::repositories::Repository;
+use crate::services::EnrollmentService;
+use crate::middleware::Claims;
+
+pub async fn enroll<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let enrollment = EnrollmentService::enroll(repo.get_ref(), course_id, claims.sub).await?;
+    Ok(HttpResponse::Created().json(enrollment))
+}
+
+pub async fn drop_enrollment<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    EnrollmentService::drop_enrollment(repo.get_ref(), course_id, claims.sub).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn get_student_enrollments<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<EnrollmentListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let enrollments = EnrollmentService::list_student_enrollments(repo.get_ref(), claims.sub, query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(enrollments))
+}
+
+pub async fn get_course_enrollments<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    query: web::Query<EnrollmentListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let enrollments = EnrollmentService::list_course_enrollments(
+        repo.get_ref(), course_id, claims.sub, query.into_inner(),
+    ).await?;
+    Ok(HttpResponse::Ok().json(enrollments))
+}
@@ -0,0 +1,110 @@
+use actix_multipart::Multipart;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use futures_util::StreamExt;
+use uuid::Uuid;
+use crate::errors::{AppError, AppResult};
+use crate::models::DownloadQuery;
+use crate::repositories::Repository;
+use crate::services::file::{FileUpload, MAX_UPLOAD_BYTES};
+use crate::services::FileService;
+use crate::storage::FileStore;
+use crate::middleware::Claims;
+use super::base_url;
+
+// Reads the part named "file", giving up as soon as it grows past `limit`
+pub(crate) async fn read_upload(mut payload: Multipart, limit: usize) -> AppResult<FileUpload> {
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| AppError::BadRequest(format!("Invalid multipart body: {}", e)))?;
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field.content_disposition()
+            .and_then(|disposition| disposition.get_filename())
+            .unwrap_or_default()
+            .to_string();
+        let content_type = field.content_type()
+            .map(|mime| mime.essence_str().to_ascii_lowercase())
+            .unwrap_or_default();
+        let mut content = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| AppError::BadRequest(format!("Invalid multipart body: {}", e)))?;
+            if content.len() + chunk.len() > limit {
+                return Err(AppError::Validation(format!(
+                    "Files must be between 1 byte and {} MB",
+                    limit / (1024 * 1024)
+                )));
+            }
+            content.extend_from_slice(&chunk);
+        }
+        return Ok(FileUpload { file_name, content_type, content });
+    }
+
+    Err(AppError::BadRequest("Expected a multipart/form-data part named \"file\"".to_string()))
+}
+
+pub async fn upload_file<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let upload = read_upload(payload, MAX_UPLOAD_BYTES).await?;
+    let file = FileService::upload(repo.get_ref(), files.get_ref(), claims.sub, upload, &base_url(&req)).await?;
+    Ok(HttpResponse::Created().json(file))
+}
+
+pub async fn get_files<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let stored = FileService::list_files(repo.get_ref(), files.get_ref(), claims.sub, &base_url(&req)).await?;
+    Ok(HttpResponse::Ok().json(stored))
+}
+
+// Metadata with a fresh download link
+pub async fn get_file<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let file_id = path.into_inner();
+    let file = FileService::get_file(repo.get_ref(), files.get_ref(), file_id, claims.sub, &base_url(&req)).await?;
+    Ok(HttpResponse::Ok().json(file))
+}
+
+pub async fn delete_file<R: Repository>(
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let file_id = path.into_inner();
+    FileService::delete_file(repo.get_ref(), files.get_ref(), file_id, claims.sub).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Public: the signed query string stands in for a token
+pub async fn download_file<R: Repository>(
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    path: web::Path<Uuid>,
+    query: web::Query<DownloadQuery>,
+) -> Result<HttpResponse, AppError> {
+    let file_id = path.into_inner();
+    let (file, content) = FileService::download(repo.get_ref(), files.get_ref(), file_id, &query).await?;
+    Ok(HttpResponse::Ok()
+        .content_type(file.content_type)
+        .insert_header(("X-Content-Type-Options", "nosniff"))
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(file.file_name)],
+        })
+        .body(content))
+}
@@ -1,15 +1,15 @@
-use actix_web::{HttpResponse, Result, web};
-use serde_json::json;
+use actix_web::{HttpResponse, Result};
 use chrono::Utc;
 use crate::errors::AppError;
+use crate::models::HealthResponse;
 
 pub async fn health_check() -> Result<HttpResponse> {
-    Ok(HttpResponse::Ok().json(json!({
-        "status": "healthy",
-        "service": "EzyTutor API",
-        "version": "0.1.0",
-        "timestamp": Utc::now().to_rfc3339()
-    })))
+    Ok(HttpResponse::Ok().json(HealthResponse {
+        status: "healthy".to_string(),
+        service: "EzyTutor API".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        timestamp: Utc::now(),
+    }))
 }
 
 // Example endpoint that demonstrates error usage
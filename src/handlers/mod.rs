@@ -1,45 +1,262 @@
 pub mod health;
+pub mod admin;
+pub mod assignment;
 pub mod auth;
+pub mod availability;
+pub mod avatar;
+pub mod booking;
+pub mod calendar;
+pub mod certificate;
 pub mod course;
+pub mod curriculum;
+pub mod enrollment;
+pub mod file;
+pub mod notification;
+pub mod progress;
+pub mod quiz;
 pub mod tutor;
+pub mod verification;
 
-use actix_web::web;
+use actix_web::http::header;
+use actix_web::middleware::from_fn;
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, HttpResponseBuilder};
 use actix_web_httpauth::middleware::HttpAuthentication;
-use crate::middleware::jwt_middleware;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::errors::AppError;
+use crate::middleware::{jwt_middleware, request_id, RequestId};
+use crate::models::{Precondition, RequestContext};
+use crate::repositories::Repository;
+use crate::services::file::MAX_UPLOAD_BYTES;
 
-pub fn configure_routes(cfg: &mut web::ServiceConfig) {
-    let auth = HttpAuthentication::bearer(jwt_middleware);
+// Links in responses point back at the host the request came in on
+pub(crate) fn base_url(req: &HttpRequest) -> String {
+    let info = req.connection_info();
+    format!("{}://{}/api/v1", info.scheme(), info.host())
+}
+
+// Where the request came from, for the audit log. The address honours
+// Forwarded / X-Forwarded-For, which the proxy in front of the API sets.
+pub(crate) fn request_context(req: &HttpRequest) -> RequestContext {
+    RequestContext {
+        ip_address: req.connection_info().realip_remote_addr().map(str::to_string),
+        request_id: req.extensions().get::<RequestId>().map(|id| id.0.clone()),
+    }
+}
+
+// Updates must name the version they were made against, so one client
+// cannot silently overwrite another's changes
+pub(crate) fn if_match(req: &HttpRequest) -> Result<Precondition, AppError> {
+    let value = req.headers()
+        .get(header::IF_MATCH)
+        .ok_or_else(|| AppError::PreconditionRequired("If-Match header is required".to_string()))?
+        .to_str()
+        .map_err(|_| AppError::BadRequest("Invalid If-Match header".to_string()))?;
+    Ok(Precondition::from_header(value))
+}
+
+// JSON body tagged with the version it shows
+pub(crate) fn tagged_json<T: Serialize>(mut builder: HttpResponseBuilder, updated_at: DateTime<Utc>, body: &T) -> HttpResponse {
+    builder.insert_header((header::ETAG, Precondition::entity_tag(updated_at))).json(body)
+}
+
+// Like `tagged_json`, but answers 304 Not Modified when If-None-Match
+// already names the current version. That header compares weakly.
+pub(crate) fn conditional_json<T: Serialize>(req: &HttpRequest, updated_at: DateTime<Utc>, body: &T) -> HttpResponse {
+    let etag = Precondition::entity_tag(updated_at);
+    let fresh = req.headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value.split(',').map(str::trim).any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+        });
+    if fresh {
+        return HttpResponse::NotModified().insert_header((header::ETAG, etag)).finish();
+    }
+    tagged_json(HttpResponse::Ok(), updated_at, body)
+}
+
+pub fn configure_routes<R: Repository>(cfg: &mut web::ServiceConfig) {
+    let auth = HttpAuthentication::bearer(jwt_middleware::<R>);
     
     cfg.service(
         web::scope("/api/v1")
+            .wrap(from_fn(request_id))
             // Public routes
             .route("/health", web::get().to(health::health_check))
             .route("/test-error", web::get().to(health::test_error))
-            .route("/auth/register", web::post().to(auth::register))
-            .route("/auth/login", web::post().to(auth::login))
-            .route("/courses", web::get().to(course::get_all_courses))
-            .route("/courses/{id}", web::get().to(course::get_course))
-            .route("/tutors", web::get().to(tutor::get_all_tutors))
-            .route("/tutors/{id}", web::get().to(tutor::get_tutor))
-            .route("/tutors/{id}/reviews", web::get().to(tutor::get_tutor_reviews))
-            .route("/tutors/search", web::get().to(tutor::search_tutors))
+            .route("/auth/register", web::post().to(auth::register::<R>))
+            .route("/auth/login", web::post().to(auth::login::<R>))
+            .route("/courses", web::get().to(course::get_all_courses::<R>))
+            .route("/courses/{id}", web::get().to(course::get_course::<R>))
+            .route("/courses/{id}/outline", web::get().to(curriculum::get_outline::<R>))
+            .route("/tutors", web::get().to(tutor::get_all_tutors::<R>))
+            .route("/tutors/search", web::get().to(tutor::search_tutors::<R>))
+            .route("/tutors/{id}", web::get().to(tutor::get_tutor::<R>))
+            .route("/tutors/{id}/reviews", web::get().to(tutor::get_tutor_reviews::<R>))
+            .route("/reviews/{id}/edits", web::get().to(tutor::get_review_edits::<R>))
+            .route("/tutors/{id}/availability", web::get().to(availability::get_open_slots::<R>))
+            .route("/tutors/{id}/booking-policy", web::get().to(booking::get_tutor_policy::<R>))
+            .route("/calendar/{token}.ics", web::get().to(calendar::get_calendar::<R>))
+            .route("/certificates/{code}", web::get().to(certificate::verify_certificate::<R>))
+            .route("/certificates/{code}/pdf", web::get().to(certificate::get_certificate_pdf::<R>))
+            .route("/downloads/{id}", web::get().to(file::download_file::<R>))
+            .route("/users/{id}/avatar/{size}", web::get().to(avatar::get_avatar::<R>))
             
             // Protected routes
             .service(
                 web::scope("")
                     .wrap(auth)
+                    // Session management
+                    .route("/auth/refresh", web::post().to(auth::refresh_token::<R>))
+                    
                     // Course management (tutors only)
-                    .route("/courses", web::post().to(course::create_course))
-                    .route("/courses/{id}", web::put().to(course::update_course))
-                    .route("/courses/{id}", web::delete().to(course::delete_course))
-                    .route("/my/courses", web::get().to(course::get_tutor_courses))
+                    .route("/courses", web::post().to(course::create_course::<R>))
+                    .route("/courses/{id}", web::put().to(course::update_course::<R>))
+                    .route("/courses/{id}", web::delete().to(course::delete_course::<R>))
+                    .route("/courses/{id}/restore", web::post().to(course::restore_course::<R>))
+                    .route("/my/courses", web::get().to(course::get_tutor_courses::<R>))
+                    .route("/my/courses/deleted", web::get().to(course::get_deleted_courses::<R>))
+                    .route("/courses/{id}/enrollments", web::get().to(enrollment::get_course_enrollments::<R>))
+
+                    // Curriculum (course owner)
+                    .route("/courses/{id}/modules", web::post().to(curriculum::add_module::<R>))
+                    .route("/courses/{id}/curriculum/order", web::put().to(curriculum::reorder::<R>))
+                    .route("/modules/{id}", web::put().to(curriculum::rename_module::<R>))
+                    .route("/modules/{id}", web::delete().to(curriculum::delete_module::<R>))
+                    .route("/modules/{id}/lessons", web::post().to(curriculum::add_lesson::<R>))
+                    .route("/lessons/{id}", web::put().to(curriculum::update_lesson::<R>))
+                    .route("/lessons/{id}", web::delete().to(curriculum::delete_lesson::<R>))
+
+                    // Lesson content (course owner and enrolled students)
+                    .route("/lessons/{id}", web::get().to(curriculum::get_lesson::<R>))
+
+                    // Enrollment (students only)
+                    .route("/courses/{id}/enroll", web::post().to(enrollment::enroll::<R>))
+                    .route("/courses/{id}/enroll", web::delete().to(enrollment::drop_enrollment::<R>))
+                    .route("/my/enrollments", web::get().to(enrollment::get_student_enrollments::<R>))
+
+                    // Progress
+                    .route("/lessons/{id}/complete", web::post().to(progress::complete_lesson::<R>))
+                    .route("/lessons/{id}/complete", web::delete().to(progress::reset_lesson::<R>))
+                    .route("/my/progress", web::get().to(progress::get_student_dashboard::<R>))
+                    .route("/my/progress/{course_id}", web::get().to(progress::get_student_course_progress::<R>))
+                    .route("/courses/{id}/progress", web::get().to(progress::get_course_dashboard::<R>))
+
+                    // Certificates
+                    .route("/courses/{id}/certificate", web::post().to(certificate::issue_certificate::<R>))
+                    .route("/my/certificates", web::get().to(certificate::get_student_certificates::<R>))
+
+                    // Quizzes
+                    .route("/lessons/{id}/quizzes", web::post().to(quiz::create_quiz::<R>))
+                    .route("/lessons/{id}/quizzes", web::get().to(quiz::list_lesson_quizzes::<R>))
+                    .route("/quizzes/{id}", web::get().to(quiz::get_quiz::<R>))
+                    .route("/quizzes/{id}", web::put().to(quiz::update_quiz::<R>))
+                    .route("/quizzes/{id}", web::delete().to(quiz::delete_quiz::<R>))
+                    .route("/quizzes/{id}/answer-key", web::get().to(quiz::get_answer_key::<R>))
+                    .route("/quizzes/{id}/attempts", web::post().to(quiz::start_attempt::<R>))
+                    .route("/quizzes/{id}/attempts", web::get().to(quiz::list_attempts::<R>))
+                    .route("/quiz-attempts/{id}", web::get().to(quiz::get_attempt::<R>))
+                    .route("/quiz-attempts/{id}/submit", web::post().to(quiz::submit_attempt::<R>))
+
+                    // Assignments
+                    .route("/courses/{id}/assignments", web::post().to(assignment::create_assignment::<R>))
+                    .route("/courses/{id}/assignments", web::get().to(assignment::list_course_assignments::<R>))
+                    .route("/assignments/{id}", web::get().to(assignment::get_assignment::<R>))
+                    .route("/assignments/{id}", web::put().to(assignment::update_assignment::<R>))
+                    .route("/assignments/{id}", web::delete().to(assignment::delete_assignment::<R>))
+                    .service(
+                        web::resource("/assignments/{id}/files")
+                            .app_data(web::PayloadConfig::new(MAX_UPLOAD_BYTES))
+                            .route(web::post().to(assignment::upload_file::<R>))
+                    )
+                    .route("/assignment-files/{id}", web::get().to(assignment::download_file::<R>))
+                    .route("/assignments/{id}/submissions", web::post().to(assignment::submit::<R>))
+                    .route("/assignments/{id}/submissions", web::get().to(assignment::list_submissions::<R>))
+                    .route("/assignments/{id}/grades/publish", web::post().to(assignment::publish_grades::<R>))
+                    .route("/submissions/{id}", web::get().to(assignment::get_submission::<R>))
+                    .route("/submissions/{id}/grade", web::put().to(assignment::grade_submission::<R>))
+
+                    // Files
+                    .route("/files", web::post().to(file::upload_file::<R>))
+                    .route("/my/files", web::get().to(file::get_files::<R>))
+                    .route("/files/{id}", web::get().to(file::get_file::<R>))
+                    .route("/files/{id}", web::delete().to(file::delete_file::<R>))
+
+                    // Avatar
+                    .route("/my/avatar", web::put().to(avatar::set_avatar::<R>))
+                    .route("/my/avatar", web::delete().to(avatar::delete_avatar::<R>))
+
+                    // Notifications
+                    .route("/my/notifications", web::get().to(notification::get_notifications::<R>))
+                    .route("/notifications/{id}/read", web::post().to(notification::mark_read::<R>))
                     
                     // Tutor profile management
-                    .route("/tutors/profile", web::post().to(tutor::create_tutor_profile))
-                    .route("/tutors/profile", web::put().to(tutor::update_tutor_profile))
+                    .route("/tutors/profile", web::post().to(tutor::create_tutor_profile::<R>))
+                    .route("/tutors/profile", web::put().to(tutor::update_tutor_profile::<R>))
+                    .route("/tutors/profile", web::delete().to(tutor::delete_tutor_profile::<R>))
+                    .route("/tutors/profile/restore", web::post().to(tutor::restore_tutor_profile::<R>))
+
+                    // Tutor verification
+                    .route("/my/verification", web::post().to(verification::submit::<R>))
+                    .route("/my/verification", web::get().to(verification::get_own::<R>))
+
+                    // Administration (admins only)
+                    .service(
+                        web::scope("/admin")
+                            .route("/users", web::get().to(admin::search_users::<R>))
+                            .route("/users/{id}", web::get().to(admin::get_user::<R>))
+                            .route("/users/{id}/role", web::put().to(admin::change_role::<R>))
+                            .route("/users/{id}/deactivate", web::post().to(admin::deactivate_user::<R>))
+                            .route("/users/{id}/reactivate", web::post().to(admin::reactivate_user::<R>))
+                            .route("/users/{id}/logout", web::post().to(admin::force_logout::<R>))
+                            .route("/courses/{id}/takedown", web::post().to(admin::take_down_course::<R>))
+                            .route("/courses/{id}/reinstate", web::post().to(admin::reinstate_course::<R>))
+                            .route("/reviews/{id}", web::delete().to(admin::remove_review::<R>))
+                            .route("/audit-log", web::get().to(admin::search_audit_log::<R>))
+                            // Verification review
+                            .route("/verification-requests", web::get().to(verification::list_queue::<R>))
+                            .route("/verification-requests/{id}", web::get().to(verification::get_request::<R>))
+                            .route("/verification-requests/{id}/approve", web::post().to(verification::approve::<R>))
+                            .route("/verification-requests/{id}/reject", web::post().to(verification::reject::<R>))
+                            .route("/tutors/{id}/verification/revoke", web::post().to(verification::revoke::<R>))
+                            .route("/tutors/{id}/verification/history", web::get().to(verification::get_history::<R>))
+                    )
+
+                    // Availability (tutors only)
+                    .route("/my/availability", web::get().to(availability::get_schedule::<R>))
+                    .route("/my/availability", web::put().to(availability::set_schedule::<R>))
+                    .route("/my/availability/exceptions", web::post().to(availability::add_exception::<R>))
+                    .route("/my/availability/exceptions/{id}", web::delete().to(availability::remove_exception::<R>))
+                    .route("/my/availability/blackouts", web::post().to(availability::add_blackout::<R>))
+                    .route("/my/availability/blackouts/{id}", web::delete().to(availability::remove_blackout::<R>))
                     
+                    // Bookings
+                    .route("/bookings", web::post().to(booking::create_booking::<R>))
+                    .route("/bookings/{id}", web::get().to(booking::get_booking::<R>))
+                    .route("/bookings/{id}/accept", web::post().to(booking::accept_booking::<R>))
+                    .route("/bookings/{id}/decline", web::post().to(booking::decline_booking::<R>))
+                    .route("/bookings/{id}/cancel", web::post().to(booking::cancel_booking::<R>))
+                    .route("/bookings/{id}/complete", web::post().to(booking::complete_booking::<R>))
+                    .route("/bookings/{id}/no-show", web::post().to(booking::mark_no_show::<R>))
+                    .route("/bookings/{id}/reschedule", web::post().to(booking::reschedule_booking::<R>))
+                    .route("/my/bookings", web::get().to(booking::get_student_bookings::<R>))
+                    .route("/my/tutor/bookings", web::get().to(booking::get_tutor_bookings::<R>))
+                    .route("/my/booking-policy", web::get().to(booking::get_own_policy::<R>))
+                    .route("/my/booking-policy", web::put().to(booking::set_policy::<R>))
+
+                    // Calendar feed
+                    .route("/my/calendar-feed", web::get().to(calendar::get_feed::<R>))
+                    .route("/my/calendar-feed", web::put().to(calendar::update_feed::<R>))
+                    .route("/my/calendar-feed/rotate", web::post().to(calendar::rotate_feed::<R>))
+
                     // Reviews (students only)
-                    .route("/tutors/{id}/reviews", web::post().to(tutor::create_review))
+                    .route("/tutors/{id}/reviews", web::post().to(tutor::create_review::<R>))
+                    // Authors edit or delete their own review for a while after posting
+                    .route("/reviews/{id}", web::put().to(tutor::update_review::<R>))
+                    .route("/reviews/{id}", web::delete().to(tutor::delete_review::<R>))
+                    // The reviewed tutor's one public reply
+                    .route("/reviews/{id}/reply", web::post().to(tutor::reply_to_review::<R>))
             )
     );
 }
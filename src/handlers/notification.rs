@@ -0,0 +1,26 @@
+use actix_web::{web, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::NotificationListQuery;
+use crate::repositories::Repository;
+use crate::services::NotificationService;
+use crate::middleware::Claims;
+
+pub async fn get_notifications<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<NotificationListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let notifications = NotificationService::list(repo.get_ref(), claims.sub, query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(notifications))
+}
+
+pub async fn mark_read<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let notification_id = path.into_inner();
+    NotificationService::mark_read(repo.get_ref(), notification_id, claims.sub).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
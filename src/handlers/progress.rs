@@ -0,0 +1,58 @@
+use actix_web::{web, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::CompleteLessonRequest;
+use crate::repositories::Repository;
+use crate::services::ProgressService;
+use crate::middleware::Claims;
+
+// The body is optional; without it the lesson's estimate counts as time spent
+pub async fn complete_lesson<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: Option<web::Json<CompleteLessonRequest>>,
+) -> Result<HttpResponse, AppError> {
+    let lesson_id = path.into_inner();
+    let request = request.map(web::Json::into_inner).unwrap_or_default();
+    let completion = ProgressService::complete_lesson(repo.get_ref(), lesson_id, claims.sub, request).await?;
+    Ok(HttpResponse::Ok().json(completion))
+}
+
+pub async fn reset_lesson<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let lesson_id = path.into_inner();
+    ProgressService::reset_lesson(repo.get_ref(), lesson_id, claims.sub).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn get_student_dashboard<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let progress = ProgressService::student_dashboard(repo.get_ref(), claims.sub).await?;
+    Ok(HttpResponse::Ok().json(progress))
+}
+
+pub async fn get_student_course_progress<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let progress = ProgressService::student_course_progress(repo.get_ref(), course_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(progress))
+}
+
+pub async fn get_course_dashboard<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let course_id = path.into_inner();
+    let progress = ProgressService::course_dashboard(repo.get_ref(), course_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(progress))
+}
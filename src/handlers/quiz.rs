@@ -0,0 +1,112 @@
+use actix_web::{web, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::{QuizRequest, SubmitQuizRequest};
+use crate::repositories::Repository;
+use crate::services::QuizService;
+use crate::middleware::Claims;
+
+pub async fn create_quiz<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<QuizRequest>,
+) -> Result<HttpResponse, AppError> {
+    let lesson_id = path.into_inner();
+    let quiz = QuizService::create_quiz(repo.get_ref(), lesson_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Created().json(quiz))
+}
+
+pub async fn list_lesson_quizzes<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let lesson_id = path.into_inner();
+    let quizzes = QuizService::list_lesson_quizzes(repo.get_ref(), lesson_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(quizzes))
+}
+
+// Questions without their answers
+pub async fn get_quiz<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let quiz_id = path.into_inner();
+    let quiz = QuizService::get_quiz_sheet(repo.get_ref(), quiz_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(quiz))
+}
+
+pub async fn get_answer_key<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let quiz_id = path.into_inner();
+    let quiz = QuizService::get_answer_key(repo.get_ref(), quiz_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(quiz))
+}
+
+pub async fn update_quiz<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<QuizRequest>,
+) -> Result<HttpResponse, AppError> {
+    let quiz_id = path.into_inner();
+    let quiz = QuizService::update_quiz(repo.get_ref(), quiz_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(quiz))
+}
+
+pub async fn delete_quiz<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let quiz_id = path.into_inner();
+    QuizService::delete_quiz(repo.get_ref(), quiz_id, claims.sub).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Starts an attempt, or resumes the one still open
+pub async fn start_attempt<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let quiz_id = path.into_inner();
+    let attempt = QuizService::start_attempt(repo.get_ref(), quiz_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(attempt))
+}
+
+pub async fn list_attempts<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let quiz_id = path.into_inner();
+    let attempts = QuizService::list_attempts(repo.get_ref(), quiz_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(attempts))
+}
+
+pub async fn get_attempt<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let attempt_id = path.into_inner();
+    let attempt = QuizService::get_attempt(repo.get_ref(), attempt_id, claims.sub).await?;
+    Ok(HttpResponse::Ok().json(attempt))
+}
+
+pub async fn submit_attempt<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<SubmitQuizRequest>,
+) -> Result<HttpResponse, AppError> {
+    let attempt_id = path.into_inner();
+    let attempt = QuizService::submit_attempt(repo.get_ref(), attempt_id, claims.sub, request.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(attempt))
+}
@@ -1,73 +1,113 @@
-use actix_web::{web, HttpResponse, Result};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
 use uuid::Uuid;
-use crate::database::DbPool;
 use crate::errors::AppError;
-use crate::models::{CreateTutorProfileRequest, UpdateTutorProfileRequest, CreateReviewRequest};
+use crate::models::{CreateTutorProfileRequest, UpdateTutorProfileRequest, CreateReviewRequest, UpdateReviewRequest, ReviewReplyRequest};
+use crate::repositories::Repository;
 use crate::services::TutorService;
 use crate::middleware::Claims;
+use super::{conditional_json, if_match, request_context, tagged_json};
 
-pub async fn create_tutor_profile(
-    pool: web::Data<DbPool>,
+pub async fn create_tutor_profile<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
     claims: web::ReqData<Claims>,
     request: web::Json<CreateTutorProfileRequest>,
 ) -> Result<HttpResponse, AppError> {
-    let tutor = TutorService::create_tutor_profile(&pool, claims.sub, request.into_inner()).await?;
-    Ok(HttpResponse::Created().json(tutor))
+    let tutor = TutorService::create_tutor_profile(
+        repo.get_ref(),
+        claims.sub,
+        request.into_inner(),
+        &request_context(&req),
+    ).await?;
+    Ok(tagged_json(HttpResponse::Created(), tutor.updated_at, &tutor))
 }
 
-pub async fn get_all_tutors(
-    pool: web::Data<DbPool>,
+pub async fn get_all_tutors<R: Repository>(
+    repo: web::Data<R>,
 ) -> Result<HttpResponse, AppError> {
-    let tutors = TutorService::get_all_tutors(&pool).await?;
+    let tutors = TutorService::get_all_tutors(repo.get_ref()).await?;
     Ok(HttpResponse::Ok().json(tutors))
 }
 
-pub async fn get_tutor(
-    pool: web::Data<DbPool>,
+pub async fn get_tutor<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse, AppError> {
     let tutor_id = path.into_inner();
-    let tutor = TutorService::get_tutor_by_id(&pool, tutor_id).await?;
-    Ok(HttpResponse::Ok().json(tutor))
+    let tutor = TutorService::get_tutor_by_id(repo.get_ref(), tutor_id).await?;
+    Ok(conditional_json(&req, tutor.updated_at, &tutor))
 }
 
-pub async fn update_tutor_profile(
-    pool: web::Data<DbPool>,
+pub async fn update_tutor_profile<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
     claims: web::ReqData<Claims>,
     request: web::Json<UpdateTutorProfileRequest>,
 ) -> Result<HttpResponse, AppError> {
-    let tutor = TutorService::update_tutor_profile(&pool, claims.sub, request.into_inner()).await?;
+    let tutor = TutorService::update_tutor_profile(
+        repo.get_ref(),
+        claims.sub,
+        request.into_inner(),
+        &if_match(&req)?,
+        &request_context(&req),
+    ).await?;
+    Ok(tagged_json(HttpResponse::Ok(), tutor.updated_at, &tutor))
+}
+
+pub async fn delete_tutor_profile<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    TutorService::delete_tutor_profile(repo.get_ref(), claims.sub, &request_context(&req)).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn restore_tutor_profile<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let tutor = TutorService::restore_tutor_profile(repo.get_ref(), claims.sub, &request_context(&req)).await?;
     Ok(HttpResponse::Ok().json(tutor))
 }
 
-pub async fn create_review(
-    pool: web::Data<DbPool>,
+pub async fn create_review<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
     claims: web::ReqData<Claims>,
     path: web::Path<Uuid>,
     request: web::Json<CreateReviewRequest>,
 ) -> Result<HttpResponse, AppError> {
     let tutor_id = path.into_inner();
-    let review = TutorService::create_review(&pool, tutor_id, claims.sub, request.into_inner()).await?;
+    let review = TutorService::create_review(
+        repo.get_ref(),
+        tutor_id,
+        claims.sub,
+        request.into_inner(),
+        &request_context(&req),
+    ).await?;
     Ok(HttpResponse::Created().json(review))
 }
 
-pub async fn get_tutor_reviews(
-    pool: web::Data<DbPool>,
+pub async fn get_tutor_reviews<R: Repository>(
+    repo: web::Data<R>,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse, AppError> {
     let tutor_id = path.into_inner();
-    let reviews = TutorService::get_tutor_reviews(&pool, tutor_id).await?;
+    let reviews = TutorService::get_tutor_reviews(repo.get_ref(), tutor_id).await?;
     Ok(HttpResponse::Ok().json(reviews))
 }
 
-pub async fn search_tutors(
-    pool: web::Data<DbPool>,
+pub async fn search_tutors<R: Repository>(
+    repo: web::Data<R>,
     query: web::Query<SearchQuery>,
 ) -> Result<HttpResponse, AppError> {
     let tutors = if let Some(specialization) = &query.specialization {
-        TutorService::search_tutors_by_specialization(&pool, specialization).await?
+        TutorService::search_tutors_by_specialization(repo.get_ref(), specialization).await?
     } else {
-        TutorService::get_all_tutors(&pool).await?
+        TutorService::get_all_tutors(repo.get_ref()).await?
     };
     Ok(HttpResponse::Ok().json(tutors))
 }
@@ -76,3 +116,59 @@ pub async fn search_tutors(
 pub struct SearchQuery {
     pub specialization: Option<String>,
 }
+
+pub async fn update_review<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<UpdateReviewRequest>,
+) -> Result<HttpResponse, AppError> {
+    let review_id = path.into_inner();
+    let review = TutorService::update_review(
+        repo.get_ref(),
+        review_id,
+        claims.sub,
+        request.into_inner(),
+        &request_context(&req),
+    ).await?;
+    Ok(HttpResponse::Ok().json(review))
+}
+
+pub async fn delete_review<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let review_id = path.into_inner();
+    TutorService::delete_review(repo.get_ref(), review_id, claims.sub, &request_context(&req)).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn get_review_edits<R: Repository>(
+    repo: web::Data<R>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let review_id = path.into_inner();
+    let edits = TutorService::get_review_edits(repo.get_ref(), review_id).await?;
+    Ok(HttpResponse::Ok().json(edits))
+}
+
+pub async fn reply_to_review<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    request: web::Json<ReviewReplyRequest>,
+) -> Result<HttpResponse, AppError> {
+    let review_id = path.into_inner();
+    let review = TutorService::reply_to_review(
+        repo.get_ref(),
+        review_id,
+        claims.sub,
+        request.into_inner(),
+        &request_context(&req),
+    ).await?;
+    Ok(HttpResponse::Created().json(review))
+}
@@ -0,0 +1,134 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use uuid::Uuid;
+use crate::errors::AppError;
+use crate::models::{SubmitVerificationRequest, VerificationDecisionRequest, VerificationQueueQuery};
+use crate::repositories::Repository;
+use crate::services::VerificationService;
+use crate::storage::FileStore;
+use crate::middleware::Claims;
+use super::base_url;
+
+pub async fn submit<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<SubmitVerificationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let verification = VerificationService::submit(
+        repo.get_ref(),
+        files.get_ref(),
+        claims.sub,
+        request.into_inner(),
+        &base_url(&req),
+    ).await?;
+    Ok(HttpResponse::Created().json(verification))
+}
+
+pub async fn get_own<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let verification = VerificationService::get_own(repo.get_ref(), files.get_ref(), claims.sub, &base_url(&req)).await?;
+    Ok(HttpResponse::Ok().json(verification))
+}
+
+pub async fn list_queue<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<VerificationQueueQuery>,
+) -> Result<HttpResponse, AppError> {
+    let requests = VerificationService::list_queue(
+        repo.get_ref(),
+        files.get_ref(),
+        claims.sub,
+        query.into_inner(),
+        &base_url(&req),
+    ).await?;
+    Ok(HttpResponse::Ok().json(requests))
+}
+
+pub async fn get_request<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let request_id = path.into_inner();
+    let request = VerificationService::get_request(
+        repo.get_ref(),
+        files.get_ref(),
+        claims.sub,
+        request_id,
+        &base_url(&req),
+    ).await?;
+    Ok(HttpResponse::Ok().json(request))
+}
+
+// The reason body is optional for approvals
+pub async fn approve<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    decision: Option<web::Json<VerificationDecisionRequest>>,
+) -> Result<HttpResponse, AppError> {
+    let request_id = path.into_inner();
+    let decision = decision.map(web::Json::into_inner).unwrap_or_default();
+    let request = VerificationService::approve(
+        repo.get_ref(),
+        files.get_ref(),
+        claims.sub,
+        request_id,
+        decision,
+        &base_url(&req),
+    ).await?;
+    Ok(HttpResponse::Ok().json(request))
+}
+
+pub async fn reject<R: Repository>(
+    req: HttpRequest,
+    repo: web::Data<R>,
+    files: web::Data<FileStore>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    decision: web::Json<VerificationDecisionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let request_id = path.into_inner();
+    let request = VerificationService::reject(
+        repo.get_ref(),
+        files.get_ref(),
+        claims.sub,
+        request_id,
+        decision.into_inner(),
+        &base_url(&req),
+    ).await?;
+    Ok(HttpResponse::Ok().json(request))
+}
+
+pub async fn revoke<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    decision: web::Json<VerificationDecisionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let tutor_id = path.into_inner();
+    let tutor = VerificationService::revoke(repo.get_ref(), claims.sub, tutor_id, decision.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(tutor))
+}
+
+pub async fn get_history<R: Repository>(
+    repo: web::Data<R>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let tutor_id = path.into_inner();
+    let history = VerificationService::history(repo.get_ref(), claims.sub, tutor_id).await?;
+    Ok(HttpResponse::Ok().json(history))
+}
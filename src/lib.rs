@@ -0,0 +1,9 @@
+pub mod config;
+pub mod database;
+pub mod errors;
+pub mod handlers;
+pub mod middleware;
+pub mod models;
+pub mod repositories;
+pub mod services;
+pub mod storage;
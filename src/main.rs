@@ -1,13 +1,10 @@
-mod config;
-mod database;
-mod handlers;
-mod models;
-mod services;
-mod middleware;
-mod errors;
-
 use actix_web::{App, HttpServer, middleware::Logger};
 use actix_cors::Cors;
+use ezytutor::{config, database, handlers};
+use ezytutor::repositories::PgRepository;
+use ezytutor::services::{AdminService, RetentionService};
+use ezytutor::storage::{derive_download_key, FileStore};
+use std::time::Duration;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -28,6 +25,45 @@ async fn main() -> std::io::Result<()> {
     log::info!("Database connected and migrations completed successfully");
     log::info!("Starting EzyTutor server at {}:{}", config.host, config.port);
 
+    let repository = PgRepository::new(pool);
+    let signing_key = match &config.download_signing_key {
+        Some(key) => key.as_bytes().to_vec(),
+        None => derive_download_key(config.jwt_secret.as_bytes()),
+    };
+    let files = FileStore::from_config(&config.storage, &signing_key)
+        .expect("Failed to set up file storage");
+
+    // Purge soft-deleted courses and profiles once their retention window passes
+    let purge_repository = repository.clone();
+    let mut purge_interval = tokio::time::interval(Duration::from_secs(config.purge_interval_secs.max(1)));
+    actix_web::rt::spawn(async move {
+        loop {
+            purge_interval.tick().await;
+            match RetentionService::purge_expired(&purge_repository, chrono::Utc::now()).await {
+                Ok(report) if report.tutor_ids.is_empty() && report.course_ids.is_empty() => {}
+                Ok(report) => log::info!(
+                    "Purged {} deleted tutor profiles and {} deleted courses",
+                    report.tutor_ids.len(), report.course_ids.len()
+                ),
+                Err(e) => log::error!("Purging deleted records failed: {}", e),
+            }
+        }
+    });
+
+    // Repair tutor ratings that drifted from their reviews
+    let reconcile_repository = repository.clone();
+    let mut reconcile_interval = tokio::time::interval(Duration::from_secs(config.rating_reconcile_interval_secs.max(1)));
+    actix_web::rt::spawn(async move {
+        loop {
+            reconcile_interval.tick().await;
+            match AdminService::reconcile_ratings(&reconcile_repository).await {
+                Ok(drift) if drift.is_empty() => {}
+                Ok(drift) => log::warn!("Repaired drifted ratings for {} tutors", drift.len()),
+                Err(e) => log::error!("Reconciling tutor ratings failed: {}", e),
+            }
+        }
+    });
+
     HttpServer::new(move || {
         let cors = Cors::default()
             .allow_any_origin()
@@ -35,10 +71,11 @@ async fn main() -> std::io::Result<()> {
             .allow_any_header();
 
         App::new()
-            .app_data(actix_web::web::Data::new(pool.clone()))
+            .app_data(actix_web::web::Data::new(repository.clone()))
+            .app_data(actix_web::web::Data::new(files.clone()))
             .wrap(cors)
             .wrap(Logger::default())
-            .configure(handlers::configure_routes)
+            .configure(handlers::configure_routes::<PgRepository>)
     })
     .bind(format!("{}:{}", config.host, config.port))?
     .run()
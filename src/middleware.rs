@@ -1,39 +1,92 @@
-use actix_web::{dev::ServiceRequest, Error, HttpMessage};
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpMessage};
 use actix_web_httpauth::extractors::bearer::{BearerAuth, Config};
 use actix_web_httpauth::extractors::AuthenticationError;
 use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use crate::errors::AppError;
+use crate::repositories::UserRepository;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: Uuid,
     pub email: String,
     pub role: String,
+    // The user's token version when the token was issued
+    pub token_version: i32,
     pub exp: usize,
 }
 
-pub async fn jwt_middleware(
+// Besides checking the signature, looks the user up so that deactivated
+// accounts and sessions ended by an admin are refused before their tokens
+// expire
+pub async fn jwt_middleware<R: UserRepository + 'static>(
     req: ServiceRequest,
     credentials: BearerAuth,
 ) -> Result<ServiceRequest, (Error, ServiceRequest)> {
     let jwt_secret = std::env::var("JWT_SECRET")
         .unwrap_or_else(|_| "your-secret-key-change-in-production".to_string());
-    
+
     let token = credentials.token();
-    
-    match decode::<Claims>(
+
+    let claims = match decode::<Claims>(
         token,
         &DecodingKey::from_secret(jwt_secret.as_ref()),
         &Validation::new(Algorithm::HS256),
     ) {
-        Ok(token_data) => {
-            req.extensions_mut().insert(token_data.claims);
+        Ok(token_data) => token_data.claims,
+        Err(_) => return Err(unauthorized(req)),
+    };
+
+    let Some(repo) = req.app_data::<web::Data<R>>().cloned() else {
+        return Err((AppError::Internal("Repository is not configured".to_string()).into(), req));
+    };
+    match repo.find_user_by_id(claims.sub).await {
+        Ok(Some(user)) if user.is_active && user.token_version == claims.token_version => {
+            req.extensions_mut().insert(claims);
             Ok(req)
         }
-        Err(_) => {
-            let config = req.app_data::<Config>().cloned().unwrap_or_default();
-            Err((AuthenticationError::from(config).into(), req))
-        }
+        Ok(_) => Err(unauthorized(req)),
+        Err(e) => Err((e.into(), req)),
+    }
+}
+
+fn unauthorized(req: ServiceRequest) -> (Error, ServiceRequest) {
+    let config = req.app_data::<Config>().cloned().unwrap_or_default();
+    (AuthenticationError::from(config).into(), req)
+}
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// Identifies a request in the audit log and to the caller
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+// Keeps an X-Request-Id set by a proxy or the caller when it looks like an
+// id, otherwise assigns a fresh one, and echoes it on the response
+pub async fn request_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let id = req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| {
+            !value.is_empty()
+                && value.len() <= 64
+                && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_.:".contains(c))
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    req.extensions_mut().insert(RequestId(id.clone()));
+
+    let mut response = next.call(req).await?;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
     }
+    Ok(response)
 }
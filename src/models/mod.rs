@@ -1,9 +1,18 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use sqlx::types::Json;
 use sqlx::FromRow;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, Utc, Weekday};
 use validator::Validate;
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HealthResponse {
+    pub status: String,
+    pub service: String,
+    pub version: String,
+    pub timestamp: DateTime<Utc>,
+}
+
 // User Models
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, sqlx::Type)]
 #[serde(rename_all = "lowercase")]
@@ -14,6 +23,17 @@ pub enum UserRole {
     Admin,
 }
 
+impl UserRole {
+    // Wire name, as used in JSON and query strings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::Student => "student",
+            UserRole::Tutor => "tutor",
+            UserRole::Admin => "admin",
+        }
+    }
+}
+
 #[derive(Debug, FromRow, Serialize, Clone)]
 pub struct User {
     pub id: Uuid,
@@ -23,11 +43,15 @@ pub struct User {
     pub last_name: String,
     pub role: UserRole,
     pub is_active: bool,
+    // Set while the user has an avatar
+    pub avatar_updated_at: Option<DateTime<Utc>>,
+    // Tokens issued under an older version are refused
+    pub token_version: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 pub struct CreateUserRequest {
     #[validate(email)]
     pub email: String,
@@ -40,14 +64,14 @@ pub struct CreateUserRequest {
     pub role: UserRole,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 pub struct LoginRequest {
     #[validate(email)]
     pub email: String,
     pub password: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct UserResponse {
     pub id: Uuid,
     pub email: String,
@@ -55,10 +79,11 @@ pub struct UserResponse {
     pub last_name: String,
     pub role: UserRole,
     pub is_active: bool,
+    pub avatar: Option<AvatarUrls>,
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LoginResponse {
     pub token: String,
     pub user: UserResponse,
@@ -85,11 +110,18 @@ pub struct Course {
     pub category: String,
     pub difficulty_level: DifficultyLevel,
     pub is_active: bool,
+    // Seats for active students; None means unlimited
+    pub max_students: Option<i32>,
+    // Set while an admin has taken the course down
+    pub taken_down_at: Option<DateTime<Utc>>,
+    // Set when the tutor deletes the course; it is purged after the
+    // retention window
+    pub deleted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 pub struct CreateCourseRequest {
     #[validate(length(min = 1, max = 200))]
     pub title: String,
@@ -102,9 +134,12 @@ pub struct CreateCourseRequest {
     #[validate(length(min = 1))]
     pub category: String,
     pub difficulty_level: DifficultyLevel,
+    #[serde(default)]
+    #[validate(range(min = 1, max = 10000))]
+    pub max_students: Option<i32>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 pub struct UpdateCourseRequest {
     #[validate(length(min = 1, max = 200))]
     pub title: Option<String>,
@@ -118,24 +153,68 @@ pub struct UpdateCourseRequest {
     pub category: Option<String>,
     pub difficulty_level: Option<DifficultyLevel>,
     pub is_active: Option<bool>,
+    // Absent leaves the capacity alone, null removes the limit
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub max_students: Option<Option<i32>>,
+}
+
+// Lets an `Option<Option<T>>` field tell an explicit null from a missing key
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
-#[derive(Debug, Serialize)]
+// Course row joined with the owning tutor, so listings need a single query
+#[derive(Debug, FromRow, Clone)]
+pub struct CourseWithTutor {
+    #[sqlx(flatten)]
+    pub course: Course,
+    pub tutor_first_name: String,
+    pub tutor_last_name: String,
+    pub tutor_rating: Option<f32>,
+    pub tutor_is_verified: bool,
+    pub enrolled_students: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TutorSummary {
+    pub id: Uuid,
+    pub name: String,
+    pub rating: Option<f32>,
+    pub is_verified: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CourseResponse {
     pub id: Uuid,
     pub title: String,
     pub description: String,
     pub tutor_id: Uuid,
-    pub tutor_name: Option<String>,
+    pub tutor: TutorSummary,
     pub price: i32,
     pub duration_minutes: i32,
     pub category: String,
     pub difficulty_level: DifficultyLevel,
     pub is_active: bool,
+    pub max_students: Option<i32>,
+    pub enrolled_students: i64,
+    pub taken_down_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+// A course its tutor deleted and can still restore
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeletedCourseResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub deleted_at: DateTime<Utc>,
+    pub restorable_until: DateTime<Utc>,
+}
+
 // Tutor Models
 #[derive(Debug, FromRow, Serialize, Clone)]
 pub struct TutorProfile {
@@ -147,13 +226,18 @@ pub struct TutorProfile {
     pub years_experience: i32,
     pub rating: Option<f32>,
     pub total_reviews: i32,
+    // Stars across all reviews; `rating` is this over `total_reviews`
+    pub rating_sum: i64,
     pub is_verified: bool,
     pub is_available: bool,
+    // Set when the tutor deletes the profile; it is purged, with the
+    // tutor's courses and reviews, after the retention window
+    pub deleted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 pub struct CreateTutorProfileRequest {
     #[validate(length(min = 50, max = 1000))]
     pub bio: String,
@@ -164,7 +248,7 @@ pub struct CreateTutorProfileRequest {
     pub years_experience: i32,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 pub struct UpdateTutorProfileRequest {
     #[validate(length(min = 50, max = 1000))]
     pub bio: Option<String>,
@@ -176,7 +260,18 @@ pub struct UpdateTutorProfileRequest {
     pub is_available: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
+// Tutor row joined with its user account
+#[derive(Debug, FromRow, Clone)]
+pub struct TutorWithUser {
+    #[sqlx(flatten)]
+    pub tutor: TutorProfile,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub avatar_updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TutorResponse {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -191,6 +286,7 @@ pub struct TutorResponse {
     pub total_reviews: i32,
     pub is_verified: bool,
     pub is_available: bool,
+    pub avatar: Option<AvatarUrls>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -204,9 +300,51 @@ pub struct TutorReview {
     pub rating: i32,
     pub comment: Option<String>,
     pub created_at: DateTime<Utc>,
+    // Last time the author changed the review
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+// A version of a review that an edit replaced
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct ReviewEdit {
+    pub id: Uuid,
+    pub review_id: Uuid,
+    pub rating: i32,
+    pub comment: Option<String>,
+    pub edited_at: DateTime<Utc>,
+}
+
+// The reviewed tutor's public answer to a review
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct ReviewReply {
+    pub review_id: Uuid,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// A tutor whose stored rating no longer matches its reviews
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct RatingDrift {
+    pub tutor_id: Uuid,
+    pub stored_rating: Option<f32>,
+    pub stored_total_reviews: i32,
+    pub actual_rating: Option<f32>,
+    pub actual_total_reviews: i32,
+}
+
+// Review row joined with the reviewing student
+#[derive(Debug, FromRow, Clone)]
+pub struct ReviewWithStudent {
+    #[sqlx(flatten)]
+    pub review: TutorReview,
+    pub student_first_name: String,
+    pub student_last_name: String,
+    pub student_avatar_updated_at: Option<DateTime<Utc>>,
+    pub reply_comment: Option<String>,
+    pub reply_created_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 pub struct CreateReviewRequest {
     #[validate(range(min = 1, max = 5))]
     pub rating: i32,
@@ -214,46 +352,1845 @@ pub struct CreateReviewRequest {
     pub comment: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct UpdateReviewRequest {
+    #[validate(range(min = 1, max = 5))]
+    pub rating: Option<i32>,
+    // Absent leaves the comment alone, null removes it
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 500))]
+    pub comment: Option<Option<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct ReviewReplyRequest {
+    #[validate(length(min = 1, max = 1000))]
+    pub comment: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ReviewResponse {
     pub id: Uuid,
     pub tutor_id: Uuid,
     pub student_name: String,
+    pub student_avatar: Option<AvatarUrls>,
     pub rating: i32,
     pub comment: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub reply: Option<ReplyResponse>,
 }
 
-// Conversion implementations
-impl From<User> for UserResponse {
-    fn from(user: User) -> Self {
-        UserResponse {
-            id: user.id,
-            email: user.email,
-            first_name: user.first_name,
-            last_name: user.last_name,
-            role: user.role,
-            is_active: user.is_active,
-            created_at: user.created_at,
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReplyResponse {
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// Availability Models
+// Weekly windows and exceptions are wall-clock times in the schedule's IANA time zone
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct TutorSchedule {
+    pub tutor_id: Uuid,
+    pub time_zone: String,
+    pub slot_minutes: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct AvailabilityWindow {
+    pub id: Uuid,
+    pub tutor_id: Uuid,
+    pub weekday: i16, // 0 = Monday
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+}
+
+// Opens an extra window on a date, or closes part or all of it
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct AvailabilityException {
+    pub id: Uuid,
+    pub tutor_id: Uuid,
+    pub date: NaiveDate,
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+    pub is_available: bool,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Whole days off, both dates inclusive
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct AvailabilityBlackout {
+    pub id: Uuid,
+    pub tutor_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct SetScheduleRequest {
+    #[validate(length(min = 1, max = 64))]
+    pub time_zone: String,
+    #[validate(range(min = 15, max = 240))]
+    pub slot_minutes: Option<i32>,
+    pub windows: Vec<WeeklyWindow>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WeeklyWindow {
+    pub weekday: Weekday,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct CreateExceptionRequest {
+    pub date: NaiveDate,
+    // Omit both to cover the whole day
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+    pub is_available: bool,
+    #[validate(length(max = 500))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct CreateBlackoutRequest {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    #[validate(length(max = 500))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExceptionResponse {
+    pub id: Uuid,
+    pub date: NaiveDate,
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+    pub is_available: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BlackoutResponse {
+    pub id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScheduleResponse {
+    pub tutor_id: Uuid,
+    pub time_zone: String,
+    pub slot_minutes: i32,
+    pub windows: Vec<WeeklyWindow>,
+    pub exceptions: Vec<ExceptionResponse>,
+    pub blackouts: Vec<BlackoutResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenSlotsQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    // IANA name of the requester's time zone; defaults to UTC
+    pub tz: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OpenSlot {
+    pub start: DateTime<FixedOffset>,
+    pub end: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenSlotsResponse {
+    pub tutor_id: Uuid,
+    pub time_zone: String,
+    // None until the tutor publishes a schedule
+    pub tutor_time_zone: Option<String>,
+    pub slots: Vec<OpenSlot>,
+}
+
+// Booking Models
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "booking_status", rename_all = "snake_case")]
+pub enum BookingStatus {
+    Requested,
+    Confirmed,
+    Cancelled,
+    Completed,
+    NoShow,
+}
+
+impl BookingStatus {
+    // Requested and confirmed bookings hold the tutor's time
+    pub fn is_active(self) -> bool {
+        matches!(self, BookingStatus::Requested | BookingStatus::Confirmed)
+    }
+
+    // Wire name, as used in JSON and query strings
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BookingStatus::Requested => "requested",
+            BookingStatus::Confirmed => "confirmed",
+            BookingStatus::Cancelled => "cancelled",
+            BookingStatus::Completed => "completed",
+            BookingStatus::NoShow => "no_show",
         }
     }
 }
 
-impl From<Course> for CourseResponse {
-    fn from(course: Course) -> Self {
-        CourseResponse {
-            id: course.id,
-            title: course.title,
-            description: course.description,
-            tutor_id: course.tutor_id,
-            tutor_name: None, // Will be populated by service layer
-            price: course.price,
-            duration_minutes: course.duration_minutes,
-            category: course.category,
-            difficulty_level: course.difficulty_level,
-            is_active: course.is_active,
-            created_at: course.created_at,
-            updated_at: course.updated_at,
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct Booking {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub tutor_id: Uuid,
+    pub student_id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub status: BookingStatus,
+    pub note: Option<String>,
+    // Why the booking was declined, cancelled or moved
+    pub status_reason: Option<String>,
+    // Course price in cents when the booking was made
+    pub price: i32,
+    // Charged to the student when the booking was cancelled, in cents
+    pub cancellation_fee: Option<i32>,
+    pub reschedule_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Booking row joined with the course, tutor and student it refers to
+#[derive(Debug, FromRow, Clone)]
+pub struct BookingWithDetails {
+    #[sqlx(flatten)]
+    pub booking: Booking,
+    pub course_title: String,
+    pub tutor_user_id: Uuid,
+    pub tutor_first_name: String,
+    pub tutor_last_name: String,
+    pub student_first_name: String,
+    pub student_last_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct CreateBookingRequest {
+    pub course_id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    #[validate(length(max = 500))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+pub struct BookingActionRequest {
+    #[validate(length(max = 500))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct RescheduleBookingRequest {
+    pub starts_at: DateTime<Utc>,
+    #[validate(length(max = 500))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BookingListQuery {
+    pub status: Option<BookingStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BookingResponse {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub course_title: String,
+    pub tutor_id: Uuid,
+    pub tutor_name: String,
+    pub student_id: Uuid,
+    pub student_name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub status: BookingStatus,
+    pub note: Option<String>,
+    pub status_reason: Option<String>,
+    pub price: i32,
+    pub cancellation_fee: Option<i32>,
+    pub reschedule_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// A tutor's cancellation and rescheduling terms
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct BookingPolicy {
+    pub tutor_id: Uuid,
+    // Students may cancel for free until this many hours before the start
+    pub free_cancel_hours: i32,
+    // Share of the price charged for a later cancellation
+    pub late_cancel_fee_percent: i32,
+    pub max_reschedules: i32,
+    // Bookings cannot be moved later than this many hours before the start
+    pub reschedule_notice_hours: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct SetBookingPolicyRequest {
+    #[validate(range(min = 0, max = 720))]
+    pub free_cancel_hours: i32,
+    #[validate(range(min = 0, max = 100))]
+    pub late_cancel_fee_percent: i32,
+    #[validate(range(min = 0, max = 10))]
+    pub max_reschedules: i32,
+    #[validate(range(min = 0, max = 720))]
+    pub reschedule_notice_hours: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BookingPolicyResponse {
+    pub tutor_id: Uuid,
+    pub free_cancel_hours: i32,
+    pub late_cancel_fee_percent: i32,
+    pub max_reschedules: i32,
+    pub reschedule_notice_hours: i32,
+}
+
+// Enrollment Models
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "enrollment_status", rename_all = "lowercase")]
+pub enum EnrollmentStatus {
+    Active,
+    Waitlisted,
+    Dropped,
+}
+
+impl EnrollmentStatus {
+    // Wire name, as used in JSON and query strings
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EnrollmentStatus::Active => "active",
+            EnrollmentStatus::Waitlisted => "waitlisted",
+            EnrollmentStatus::Dropped => "dropped",
+        }
+    }
+}
+
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct Enrollment {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub student_id: Uuid,
+    pub status: EnrollmentStatus,
+    // When the student last asked to join; orders the waitlist
+    pub requested_at: DateTime<Utc>,
+    pub enrolled_at: Option<DateTime<Utc>>,
+    pub dropped_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Enrollment row joined with its course and student
+#[derive(Debug, FromRow, Clone)]
+pub struct EnrollmentWithDetails {
+    #[sqlx(flatten)]
+    pub enrollment: Enrollment,
+    pub course_title: String,
+    pub student_first_name: String,
+    pub student_last_name: String,
+    pub student_email: String,
+    // 1-based place in the queue for waitlisted enrollments
+    pub waitlist_position: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnrollmentListQuery {
+    pub status: Option<EnrollmentStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnrollmentResponse {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub course_title: String,
+    pub student_id: Uuid,
+    pub student_name: String,
+    pub student_email: String,
+    pub status: EnrollmentStatus,
+    pub waitlist_position: Option<i64>,
+    pub requested_at: DateTime<Utc>,
+    pub enrolled_at: Option<DateTime<Utc>>,
+    pub dropped_at: Option<DateTime<Utc>>,
+}
+
+// Curriculum Models
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct CourseModule {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub title: String,
+    // 1-based order within the course
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Validate)]
+pub struct LessonResource {
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+    #[validate(url)]
+    pub url: String,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct Lesson {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub module_id: Uuid,
+    pub title: String,
+    // Markdown
+    pub content: String,
+    pub estimated_minutes: i32,
+    pub resources: Json<Vec<LessonResource>>,
+    // 1-based order within the module
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct ModuleRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct CreateLessonRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+    #[serde(default)]
+    #[validate(length(max = 100000))]
+    pub content: String,
+    #[validate(range(min = 1, max = 600))]
+    pub estimated_minutes: i32,
+    #[serde(default)]
+    #[validate(length(max = 20), nested)]
+    pub resources: Vec<LessonResource>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct UpdateLessonRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub title: Option<String>,
+    #[validate(length(max = 100000))]
+    pub content: Option<String>,
+    #[validate(range(min = 1, max = 600))]
+    pub estimated_minutes: Option<i32>,
+    #[validate(length(max = 20), nested)]
+    pub resources: Option<Vec<LessonResource>>,
+}
+
+// The complete new order: every module of the course, each listing every
+// lesson it should hold. Lessons may move between modules.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReorderCurriculumRequest {
+    pub modules: Vec<ModuleOrder>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModuleOrder {
+    pub id: Uuid,
+    pub lessons: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModuleResponse {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub title: String,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LessonResponse {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub module_id: Uuid,
+    pub title: String,
+    pub content: String,
+    pub estimated_minutes: i32,
+    pub resources: Vec<LessonResource>,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Public course structure, without lesson content
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CourseOutlineResponse {
+    pub course_id: Uuid,
+    pub title: String,
+    pub lesson_count: usize,
+    pub estimated_minutes: i32,
+    pub modules: Vec<ModuleOutline>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModuleOutline {
+    pub id: Uuid,
+    pub title: String,
+    pub position: i32,
+    pub estimated_minutes: i32,
+    pub lessons: Vec<LessonSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LessonSummary {
+    pub id: Uuid,
+    pub title: String,
+    pub position: i32,
+    pub estimated_minutes: i32,
+}
+
+// Progress Models
+#[derive(Debug, FromRow, Clone)]
+pub struct LessonCompletion {
+    pub id: Uuid,
+    pub lesson_id: Uuid,
+    pub course_id: Uuid,
+    pub student_id: Uuid,
+    pub minutes_spent: i32,
+    pub completed_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Lesson totals and completion aggregates for one active enrollment
+#[derive(Debug, FromRow, Clone)]
+pub struct CourseProgress {
+    pub course_id: Uuid,
+    pub course_title: String,
+    pub student_id: Uuid,
+    pub student_first_name: String,
+    pub student_last_name: String,
+    pub student_email: String,
+    pub enrolled_at: Option<DateTime<Utc>>,
+    pub total_lessons: i64,
+    pub estimated_minutes: i64,
+    pub completed_lessons: i64,
+    pub minutes_spent: i64,
+    pub last_completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Validate)]
+pub struct CompleteLessonRequest {
+    // Defaults to the lesson's estimate
+    #[validate(range(min = 0, max = 1440))]
+    pub minutes_spent: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LessonCompletionResponse {
+    pub lesson_id: Uuid,
+    pub course_id: Uuid,
+    pub minutes_spent: i32,
+    pub completed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CourseProgressResponse {
+    pub course_id: Uuid,
+    pub course_title: String,
+    pub student_id: Uuid,
+    pub student_name: String,
+    pub student_email: String,
+    pub enrolled_at: Option<DateTime<Utc>>,
+    pub total_lessons: i64,
+    pub completed_lessons: i64,
+    // Whole percent, rounded down so 100 means every lesson is done
+    pub completion_percent: i32,
+    pub estimated_minutes: i64,
+    pub minutes_spent: i64,
+    pub last_completed_at: Option<DateTime<Utc>>,
+}
+
+// A student's progress in one course, lesson by lesson in course order
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CourseProgressDetailResponse {
+    #[serde(flatten)]
+    pub progress: CourseProgressResponse,
+    pub lessons: Vec<LessonProgress>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LessonProgress {
+    pub lesson_id: Uuid,
+    pub module_id: Uuid,
+    pub title: String,
+    pub estimated_minutes: i32,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub minutes_spent: Option<i32>,
+}
+
+// Quiz Models
+// A question's type and answer key. Choices are zero-based option indices.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QuestionKind {
+    MultipleChoice {
+        options: Vec<String>,
+        correct: usize,
+    },
+    MultiSelect {
+        options: Vec<String>,
+        correct: Vec<usize>,
+    },
+    Numeric {
+        answer: f64,
+        #[serde(default)]
+        tolerance: f64,
+    },
+    ShortText {
+        accepted: Vec<String>,
+        #[serde(default)]
+        case_sensitive: bool,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QuizQuestion {
+    pub id: Uuid,
+    pub prompt: String,
+    pub points: i32,
+    #[serde(flatten)]
+    pub kind: QuestionKind,
+    // Shown with the graded result
+    pub explanation: Option<String>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct Quiz {
+    pub id: Uuid,
+    pub lesson_id: Uuid,
+    pub course_id: Uuid,
+    pub title: String,
+    pub instructions: String,
+    pub max_attempts: Option<i32>,
+    pub time_limit_minutes: Option<i32>,
+    pub questions: Json<Vec<QuizQuestion>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Quiz {
+    pub fn total_points(&self) -> i32 {
+        self.questions.iter().map(|question| question.points).sum()
+    }
+}
+
+// One answer per question; which field counts depends on the question type
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct QuizAnswer {
+    pub question_id: Uuid,
+    #[serde(default)]
+    pub choices: Vec<usize>,
+    pub number: Option<f64>,
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QuestionResult {
+    pub question_id: Uuid,
+    pub correct: bool,
+    pub points_awarded: i32,
+    pub points_possible: i32,
+    pub feedback: Option<String>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct QuizAttempt {
+    pub id: Uuid,
+    pub quiz_id: Uuid,
+    pub student_id: Uuid,
+    pub attempt_number: i32,
+    pub started_at: DateTime<Utc>,
+    pub deadline_at: Option<DateTime<Utc>>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    // Submitted after the deadline, so graded without answers
+    pub timed_out: bool,
+    pub score: Option<i32>,
+    pub max_score: i32,
+    pub answers: Json<Vec<QuizAnswer>>,
+    pub results: Json<Vec<QuestionResult>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct QuestionRequest {
+    // Keeps an existing question's id when replacing a quiz
+    pub id: Option<Uuid>,
+    #[validate(length(min = 1, max = 2000))]
+    pub prompt: String,
+    #[serde(default = "default_points")]
+    #[validate(range(min = 1, max = 100))]
+    pub points: i32,
+    #[serde(flatten)]
+    pub kind: QuestionKind,
+    #[validate(length(max = 2000))]
+    pub explanation: Option<String>,
+}
+
+fn default_points() -> i32 {
+    1
+}
+
+// Creates a quiz, or replaces one wholesale
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct QuizRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+    #[serde(default)]
+    #[validate(length(max = 10000))]
+    pub instructions: String,
+    #[validate(range(min = 1, max = 100))]
+    pub max_attempts: Option<i32>,
+    #[validate(range(min = 1, max = 600))]
+    pub time_limit_minutes: Option<i32>,
+    #[validate(length(min = 1, max = 100), nested)]
+    pub questions: Vec<QuestionRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubmitQuizRequest {
+    pub answers: Vec<QuizAnswer>,
+}
+
+// Full quiz with answer keys, for the course's tutor
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuizResponse {
+    pub id: Uuid,
+    pub lesson_id: Uuid,
+    pub course_id: Uuid,
+    pub title: String,
+    pub instructions: String,
+    pub max_attempts: Option<i32>,
+    pub time_limit_minutes: Option<i32>,
+    pub total_points: i32,
+    pub questions: Vec<QuizQuestion>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// What a student sees: the questions without their answer keys
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuizSheetResponse {
+    pub id: Uuid,
+    pub lesson_id: Uuid,
+    pub title: String,
+    pub instructions: String,
+    pub max_attempts: Option<i32>,
+    pub time_limit_minutes: Option<i32>,
+    pub total_points: i32,
+    pub questions: Vec<QuestionSheet>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuestionSheet {
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub prompt: String,
+    pub points: i32,
+    // Present for choice questions
+    pub options: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuizSummary {
+    pub id: Uuid,
+    pub title: String,
+    pub question_count: usize,
+    pub total_points: i32,
+    pub max_attempts: Option<i32>,
+    pub time_limit_minutes: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuizAttemptResponse {
+    pub id: Uuid,
+    pub quiz_id: Uuid,
+    pub student_id: Uuid,
+    pub attempt_number: i32,
+    pub started_at: DateTime<Utc>,
+    pub deadline_at: Option<DateTime<Utc>>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub timed_out: bool,
+    pub score: Option<i32>,
+    pub max_score: i32,
+    pub percent: Option<i32>,
+    pub answers: Vec<QuizAnswer>,
+    pub results: Vec<QuestionResult>,
+}
+
+// Assignment Models
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RubricCriterion {
+    pub id: Uuid,
+    pub title: String,
+    pub description: String,
+    pub max_points: i32,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct Assignment {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub title: String,
+    pub instructions: String,
+    pub due_at: DateTime<Utc>,
+    pub accept_late: bool,
+    pub max_submissions: Option<i32>,
+    pub rubric: Json<Vec<RubricCriterion>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Assignment {
+    pub fn max_score(&self) -> i32 {
+        self.rubric.iter().map(|criterion| criterion.max_points).sum()
+    }
+}
+
+// File metadata; the content is loaded separately
+#[derive(Debug, FromRow, Clone)]
+pub struct AssignmentFile {
+    pub id: Uuid,
+    pub assignment_id: Uuid,
+    pub student_id: Uuid,
+    // None for files uploaded before assignment files moved to the file
+    // store; their content is kept inline
+    pub stored_file_id: Option<Uuid>,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CriterionScore {
+    pub criterion_id: Uuid,
+    pub points: i32,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct AssignmentSubmission {
+    pub id: Uuid,
+    pub assignment_id: Uuid,
+    pub student_id: Uuid,
+    pub submission_number: i32,
+    pub text: String,
+    pub file_ids: Vec<Uuid>,
+    pub submitted_at: DateTime<Utc>,
+    pub is_late: bool,
+    pub score: Option<i32>,
+    pub max_score: i32,
+    pub rubric_scores: Json<Vec<CriterionScore>>,
+    pub feedback: Option<String>,
+    pub graded_at: Option<DateTime<Utc>>,
+    pub grade_published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct RubricCriterionRequest {
+    // Keeps an existing criterion's id when replacing an assignment
+    pub id: Option<Uuid>,
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+    #[serde(default)]
+    #[validate(length(max = 2000))]
+    pub description: String,
+    #[validate(range(min = 1, max = 1000))]
+    pub max_points: i32,
+}
+
+// Creates an assignment, or replaces one wholesale
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct AssignmentRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+    #[serde(default)]
+    #[validate(length(max = 20000))]
+    pub instructions: String,
+    pub due_at: DateTime<Utc>,
+    #[serde(default = "default_accept_late")]
+    pub accept_late: bool,
+    #[validate(range(min = 1, max = 100))]
+    pub max_submissions: Option<i32>,
+    #[validate(length(min = 1, max = 20), nested)]
+    pub rubric: Vec<RubricCriterionRequest>,
+}
+
+fn default_accept_late() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UploadFileQuery {
+    pub file_name: String,
+}
+
+// Text, previously uploaded files, or both
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+pub struct SubmitAssignmentRequest {
+    #[serde(default)]
+    #[validate(length(max = 50000))]
+    pub text: String,
+    #[serde(default)]
+    #[validate(length(max = 10))]
+    pub file_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct CriterionScoreRequest {
+    pub criterion_id: Uuid,
+    #[validate(range(min = 0))]
+    pub points: i32,
+    #[validate(length(max = 2000))]
+    pub comment: Option<String>,
+}
+
+// Scores every rubric criterion. Unpublished grades stay hidden from the student.
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct GradeSubmissionRequest {
+    #[validate(nested)]
+    pub scores: Vec<CriterionScoreRequest>,
+    #[validate(length(max = 10000))]
+    pub feedback: Option<String>,
+    #[serde(default)]
+    pub publish: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssignmentResponse {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub title: String,
+    pub instructions: String,
+    pub due_at: DateTime<Utc>,
+    pub accept_late: bool,
+    pub max_submissions: Option<i32>,
+    pub max_score: i32,
+    pub rubric: Vec<RubricCriterion>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssignmentFileResponse {
+    pub id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GradeResponse {
+    pub score: i32,
+    pub max_score: i32,
+    pub rubric_scores: Vec<CriterionScore>,
+    pub feedback: Option<String>,
+    pub graded_at: DateTime<Utc>,
+    // None while the grade is a draft
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubmissionResponse {
+    pub id: Uuid,
+    pub assignment_id: Uuid,
+    pub student_id: Uuid,
+    pub submission_number: i32,
+    pub text: String,
+    pub files: Vec<AssignmentFileResponse>,
+    pub submitted_at: DateTime<Utc>,
+    pub is_late: bool,
+    pub grade: Option<GradeResponse>,
+}
+
+// Notification Models
+#[derive(Debug, FromRow, Clone)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: String,
+    pub title: String,
+    pub body: String,
+    pub link: Option<String>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationListQuery {
+    // Only notifications not yet marked read
+    #[serde(default)]
+    pub unread: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationResponse {
+    pub id: Uuid,
+    pub kind: String,
+    pub title: String,
+    pub body: String,
+    pub link: Option<String>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Certificate Models
+#[derive(Debug, FromRow, Clone)]
+pub struct Certificate {
+    pub id: Uuid,
+    pub code: String,
+    // None once the course has been deleted
+    pub course_id: Option<Uuid>,
+    pub student_id: Uuid,
+    pub student_name: String,
+    pub course_title: String,
+    pub tutor_name: String,
+    pub completed_at: DateTime<Utc>,
+    pub issued_at: DateTime<Utc>,
+}
+
+// Also what the public verification endpoint returns
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CertificateResponse {
+    pub code: String,
+    pub course_id: Option<Uuid>,
+    pub student_name: String,
+    pub course_title: String,
+    pub tutor_name: String,
+    pub completed_at: DateTime<Utc>,
+    pub issued_at: DateTime<Utc>,
+}
+
+// Stored File Models
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "file_purpose", rename_all = "lowercase")]
+pub enum FilePurpose {
+    // Uploaded by the owner through the files API
+    Upload,
+    // One size of the owner's avatar
+    Avatar,
+    // Attached to a tutor verification request
+    Credential,
+    // Uploaded by a student for an assignment
+    Assignment,
+}
+
+// Metadata of a stored file; the content is in the file store under `sha256`
+#[derive(Debug, FromRow, Clone)]
+pub struct StoredFile {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub purpose: FilePurpose,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StoredFileResponse {
+    pub id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub created_at: DateTime<Utc>,
+    // Signed link that works without a token until it expires
+    pub download_url: String,
+    pub download_url_expires_at: DateTime<Utc>,
+}
+
+// Signature of a download link; `expires` is a Unix timestamp
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DownloadQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+// Avatar Models
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AvatarSize {
+    // The whole image, at most 1024 pixels on its longer side
+    Full,
+    // Square crops
+    Large,
+    Medium,
+    Small,
+}
+
+impl AvatarSize {
+    pub const ALL: [AvatarSize; 4] = [AvatarSize::Full, AvatarSize::Large, AvatarSize::Medium, AvatarSize::Small];
+
+    // Longest side in pixels
+    pub fn pixels(self) -> u32 {
+        match self {
+            AvatarSize::Full => 1024,
+            AvatarSize::Large => 256,
+            AvatarSize::Medium => 128,
+            AvatarSize::Small => 64,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AvatarSize::Full => "full",
+            AvatarSize::Large => "large",
+            AvatarSize::Medium => "medium",
+            AvatarSize::Small => "small",
+        }
+    }
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct Avatar {
+    pub user_id: Uuid,
+    pub full_file_id: Uuid,
+    pub large_file_id: Uuid,
+    pub medium_file_id: Uuid,
+    pub small_file_id: Uuid,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Avatar {
+    pub fn file_id(&self, size: AvatarSize) -> Uuid {
+        match size {
+            AvatarSize::Full => self.full_file_id,
+            AvatarSize::Large => self.large_file_id,
+            AvatarSize::Medium => self.medium_file_id,
+            AvatarSize::Small => self.small_file_id,
+        }
+    }
+
+    pub fn file_ids(&self) -> [Uuid; 4] {
+        AvatarSize::ALL.map(|size| self.file_id(size))
+    }
+}
+
+// Public image links, one per size
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AvatarUrls {
+    pub full: String,
+    pub large: String,
+    pub medium: String,
+    pub small: String,
+}
+
+impl AvatarUrls {
+    // The version parameter changes with every new avatar, so an image URL
+    // always shows the same picture
+    pub fn new(user_id: Uuid, updated_at: DateTime<Utc>) -> Self {
+        let url = |size: AvatarSize| {
+            format!("/api/v1/users/{}/avatar/{}?v={}", user_id, size.as_str(), updated_at.timestamp_millis())
+        };
+        AvatarUrls {
+            full: url(AvatarSize::Full),
+            large: url(AvatarSize::Large),
+            medium: url(AvatarSize::Medium),
+            small: url(AvatarSize::Small),
+        }
+    }
+}
+
+// Verification Models
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "verification_status", rename_all = "lowercase")]
+pub enum VerificationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl VerificationStatus {
+    // Wire name, as used in JSON and query strings
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VerificationStatus::Pending => "pending",
+            VerificationStatus::Approved => "approved",
+            VerificationStatus::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "credential_kind", rename_all = "lowercase")]
+pub enum CredentialKind {
+    Degree,
+    Certification,
+    Identity,
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "verification_action", rename_all = "lowercase")]
+pub enum VerificationAction {
+    Submitted,
+    Approved,
+    Rejected,
+    Revoked,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct VerificationRequest {
+    pub id: Uuid,
+    pub tutor_id: Uuid,
+    pub status: VerificationStatus,
+    // From the tutor to the reviewer
+    pub note: Option<String>,
+    pub submitted_at: DateTime<Utc>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub decision_reason: Option<String>,
+}
+
+// Request row joined with the tutor who sent it
+#[derive(Debug, FromRow, Clone)]
+pub struct VerificationRequestWithTutor {
+    #[sqlx(flatten)]
+    pub request: VerificationRequest,
+    pub tutor_user_id: Uuid,
+    pub tutor_first_name: String,
+    pub tutor_last_name: String,
+    pub tutor_email: String,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct VerificationDocument {
+    pub id: Uuid,
+    pub request_id: Uuid,
+    pub kind: CredentialKind,
+    pub file_id: Uuid,
+    pub position: i32,
+}
+
+// One change to a tutor's verification; `actor_id` is None for changes
+// made with the admin CLI
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct VerificationEvent {
+    pub id: Uuid,
+    pub tutor_id: Uuid,
+    pub request_id: Option<Uuid>,
+    pub action: VerificationAction,
+    pub actor_id: Option<Uuid>,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// A file previously sent to `POST /files`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CredentialDocumentRequest {
+    pub kind: CredentialKind,
+    pub file_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct SubmitVerificationRequest {
+    #[validate(length(min = 1, max = 10))]
+    pub documents: Vec<CredentialDocumentRequest>,
+    #[validate(length(max = 2000))]
+    pub note: Option<String>,
+}
+
+// Required when rejecting or revoking, optional when approving
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+pub struct VerificationDecisionRequest {
+    #[validate(length(max = 2000))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VerificationQueueQuery {
+    // Pending when omitted
+    pub status: Option<VerificationStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VerificationDocumentResponse {
+    pub kind: CredentialKind,
+    pub file: StoredFileResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VerificationRequestResponse {
+    pub id: Uuid,
+    pub tutor_id: Uuid,
+    pub tutor_name: String,
+    pub tutor_email: String,
+    pub status: VerificationStatus,
+    pub note: Option<String>,
+    pub documents: Vec<VerificationDocumentResponse>,
+    pub submitted_at: DateTime<Utc>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub decision_reason: Option<String>,
+}
+
+// A tutor's own view: current state, every request and the full history
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TutorVerificationResponse {
+    pub tutor_id: Uuid,
+    pub is_verified: bool,
+    pub requests: Vec<VerificationRequestResponse>,
+    pub history: Vec<VerificationEvent>,
+}
+
+// Calendar Models
+#[derive(Debug, FromRow, Clone)]
+pub struct CalendarFeed {
+    pub user_id: Uuid,
+    pub token: String,
+    // Zone the feed's event times are written in
+    pub time_zone: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct UpdateCalendarFeedRequest {
+    #[validate(length(min = 1, max = 64))]
+    pub time_zone: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalendarFeedResponse {
+    pub url: String,
+    pub time_zone: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Admin Models
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct PlatformStats {
+    pub total_users: i64,
+    pub active_users: i64,
+    pub students: i64,
+    pub tutors: i64,
+    pub admins: i64,
+    pub tutor_profiles: i64,
+    pub verified_tutors: i64,
+    pub available_tutors: i64,
+    pub courses: i64,
+    pub active_courses: i64,
+    pub reviews: i64,
+    pub average_rating: Option<f64>,
+}
+
+// One audited change; `actor_id` is None for the admin CLI. `changes` maps
+// each changed field to {"before": ..., "after": ...}
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub changes: Option<serde_json::Value>,
+    pub reason: Option<String>,
+    pub ip_address: Option<String>,
+    pub request_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// The If-Match condition an update was sent with. Record versions are told
+// apart by updated_at, to the microsecond Postgres keeps.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Precondition {
+    // `*`: whatever version is current
+    Any,
+    // Entity tags, one of which must name the current version
+    Tags(Vec<String>),
+}
+
+impl Precondition {
+    // Strong ETag of the version last updated at `updated_at`
+    pub fn entity_tag(updated_at: DateTime<Utc>) -> String {
+        format!("\"{:x}\"", updated_at.timestamp_micros())
+    }
+
+    pub fn from_header(value: &str) -> Self {
+        if value.trim() == "*" {
+            return Precondition::Any;
+        }
+        Precondition::Tags(value.split(',').map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()).collect())
+    }
+
+    // Strong comparison: weak tags never match
+    pub fn matches(&self, updated_at: DateTime<Utc>) -> bool {
+        match self {
+            Precondition::Any => true,
+            Precondition::Tags(tags) => tags.contains(&Self::entity_tag(updated_at)),
+        }
+    }
+}
+
+// Where an audited call came from; empty for the CLI and in tests
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    pub ip_address: Option<String>,
+    pub request_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+pub struct AuditLogQuery {
+    pub actor_id: Option<Uuid>,
+    #[validate(length(max = 32))]
+    pub target_type: Option<String>,
+    pub target_id: Option<Uuid>,
+    // Inclusive start and exclusive end of the time range
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    // 1-based; defaults to the first page of 20
+    #[validate(range(min = 1))]
+    pub page: Option<i64>,
+    #[validate(range(min = 1, max = 100))]
+    pub per_page: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+pub struct UserSearchQuery {
+    // Matched against email and name
+    #[validate(length(max = 200))]
+    pub q: Option<String>,
+    pub role: Option<UserRole>,
+    pub is_active: Option<bool>,
+    // 1-based; defaults to the first page of 20
+    #[validate(range(min = 1))]
+    pub page: Option<i64>,
+    #[validate(range(min = 1, max = 100))]
+    pub per_page: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+pub struct ChangeRoleRequest {
+    pub role: UserRole,
+    #[validate(length(max = 2000))]
+    pub reason: Option<String>,
+}
+
+// Required for deactivations, takedowns and review removals
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+pub struct ModerationRequest {
+    #[validate(length(max = 2000))]
+    pub reason: Option<String>,
+}
+
+// Conversion implementations
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        UserResponse {
+            id: user.id,
+            email: user.email,
+            first_name: user.first_name,
+            last_name: user.last_name,
+            role: user.role,
+            is_active: user.is_active,
+            avatar: user.avatar_updated_at.map(|at| AvatarUrls::new(user.id, at)),
+            created_at: user.created_at,
+        }
+    }
+}
+
+impl From<CourseWithTutor> for CourseResponse {
+    fn from(row: CourseWithTutor) -> Self {
+        let course = row.course;
+        CourseResponse {
+            id: course.id,
+            title: course.title,
+            description: course.description,
+            tutor_id: course.tutor_id,
+            tutor: TutorSummary {
+                id: course.tutor_id,
+                name: format!("{} {}", row.tutor_first_name, row.tutor_last_name),
+                rating: row.tutor_rating,
+                is_verified: row.tutor_is_verified,
+            },
+            price: course.price,
+            duration_minutes: course.duration_minutes,
+            category: course.category,
+            difficulty_level: course.difficulty_level,
+            is_active: course.is_active,
+            max_students: course.max_students,
+            enrolled_students: row.enrolled_students,
+            taken_down_at: course.taken_down_at,
+            created_at: course.created_at,
+            updated_at: course.updated_at,
+        }
+    }
+}
+
+impl From<TutorWithUser> for TutorResponse {
+    fn from(row: TutorWithUser) -> Self {
+        let tutor = row.tutor;
+        TutorResponse {
+            id: tutor.id,
+            user_id: tutor.user_id,
+            first_name: row.first_name,
+            last_name: row.last_name,
+            email: row.email,
+            bio: tutor.bio,
+            specializations: tutor.specializations,
+            hourly_rate: tutor.hourly_rate,
+            years_experience: tutor.years_experience,
+            rating: tutor.rating,
+            total_reviews: tutor.total_reviews,
+            is_verified: tutor.is_verified,
+            is_available: tutor.is_available,
+            avatar: row.avatar_updated_at.map(|at| AvatarUrls::new(tutor.user_id, at)),
+            created_at: tutor.created_at,
+            updated_at: tutor.updated_at,
+        }
+    }
+}
+
+impl From<ReviewWithStudent> for ReviewResponse {
+    fn from(row: ReviewWithStudent) -> Self {
+        let review = row.review;
+        ReviewResponse {
+            id: review.id,
+            tutor_id: review.tutor_id,
+            student_name: format!("{} {}", row.student_first_name, row.student_last_name),
+            student_avatar: row.student_avatar_updated_at.map(|at| AvatarUrls::new(review.student_id, at)),
+            rating: review.rating,
+            comment: review.comment,
+            created_at: review.created_at,
+            edited_at: review.edited_at,
+            reply: row.reply_comment.zip(row.reply_created_at)
+                .map(|(comment, created_at)| ReplyResponse { comment, created_at }),
+        }
+    }
+}
+
+impl From<AvailabilityException> for ExceptionResponse {
+    fn from(exception: AvailabilityException) -> Self {
+        ExceptionResponse {
+            id: exception.id,
+            date: exception.date,
+            start_time: exception.start_time,
+            end_time: exception.end_time,
+            is_available: exception.is_available,
+            reason: exception.reason,
+        }
+    }
+}
+
+impl From<AvailabilityBlackout> for BlackoutResponse {
+    fn from(blackout: AvailabilityBlackout) -> Self {
+        BlackoutResponse {
+            id: blackout.id,
+            start_date: blackout.start_date,
+            end_date: blackout.end_date,
+            reason: blackout.reason,
+        }
+    }
+}
+
+impl From<BookingWithDetails> for BookingResponse {
+    fn from(row: BookingWithDetails) -> Self {
+        let booking = row.booking;
+        BookingResponse {
+            id: booking.id,
+            course_id: booking.course_id,
+            course_title: row.course_title,
+            tutor_id: booking.tutor_id,
+            tutor_name: format!("{} {}", row.tutor_first_name, row.tutor_last_name),
+            student_id: booking.student_id,
+            student_name: format!("{} {}", row.student_first_name, row.student_last_name),
+            starts_at: booking.starts_at,
+            ends_at: booking.ends_at,
+            status: booking.status,
+            note: booking.note,
+            status_reason: booking.status_reason,
+            price: booking.price,
+            cancellation_fee: booking.cancellation_fee,
+            reschedule_count: booking.reschedule_count,
+            created_at: booking.created_at,
+            updated_at: booking.updated_at,
+        }
+    }
+}
+
+impl From<EnrollmentWithDetails> for EnrollmentResponse {
+    fn from(row: EnrollmentWithDetails) -> Self {
+        let enrollment = row.enrollment;
+        EnrollmentResponse {
+            id: enrollment.id,
+            course_id: enrollment.course_id,
+            course_title: row.course_title,
+            student_id: enrollment.student_id,
+            student_name: format!("{} {}", row.student_first_name, row.student_last_name),
+            student_email: row.student_email,
+            status: enrollment.status,
+            waitlist_position: row.waitlist_position,
+            requested_at: enrollment.requested_at,
+            enrolled_at: enrollment.enrolled_at,
+            dropped_at: enrollment.dropped_at,
+        }
+    }
+}
+
+impl From<CourseModule> for ModuleResponse {
+    fn from(module: CourseModule) -> Self {
+        ModuleResponse {
+            id: module.id,
+            course_id: module.course_id,
+            title: module.title,
+            position: module.position,
+            created_at: module.created_at,
+            updated_at: module.updated_at,
+        }
+    }
+}
+
+impl From<Lesson> for LessonResponse {
+    fn from(lesson: Lesson) -> Self {
+        LessonResponse {
+            id: lesson.id,
+            course_id: lesson.course_id,
+            module_id: lesson.module_id,
+            title: lesson.title,
+            content: lesson.content,
+            estimated_minutes: lesson.estimated_minutes,
+            resources: lesson.resources.0,
+            position: lesson.position,
+            created_at: lesson.created_at,
+            updated_at: lesson.updated_at,
+        }
+    }
+}
+
+impl From<LessonCompletion> for LessonCompletionResponse {
+    fn from(completion: LessonCompletion) -> Self {
+        LessonCompletionResponse {
+            lesson_id: completion.lesson_id,
+            course_id: completion.course_id,
+            minutes_spent: completion.minutes_spent,
+            completed_at: completion.completed_at,
+        }
+    }
+}
+
+impl From<CourseProgress> for CourseProgressResponse {
+    fn from(row: CourseProgress) -> Self {
+        let completion_percent = if row.total_lessons > 0 {
+            (row.completed_lessons * 100 / row.total_lessons) as i32
+        } else {
+            0
+        };
+        CourseProgressResponse {
+            course_id: row.course_id,
+            course_title: row.course_title,
+            student_id: row.student_id,
+            student_name: format!("{} {}", row.student_first_name, row.student_last_name),
+            student_email: row.student_email,
+            enrolled_at: row.enrolled_at,
+            total_lessons: row.total_lessons,
+            completed_lessons: row.completed_lessons,
+            completion_percent,
+            estimated_minutes: row.estimated_minutes,
+            minutes_spent: row.minutes_spent,
+            last_completed_at: row.last_completed_at,
+        }
+    }
+}
+
+impl From<Quiz> for QuizResponse {
+    fn from(quiz: Quiz) -> Self {
+        let total_points = quiz.total_points();
+        QuizResponse {
+            id: quiz.id,
+            lesson_id: quiz.lesson_id,
+            course_id: quiz.course_id,
+            title: quiz.title,
+            instructions: quiz.instructions,
+            max_attempts: quiz.max_attempts,
+            time_limit_minutes: quiz.time_limit_minutes,
+            total_points,
+            questions: quiz.questions.0,
+            created_at: quiz.created_at,
+            updated_at: quiz.updated_at,
+        }
+    }
+}
+
+impl From<Quiz> for QuizSheetResponse {
+    fn from(quiz: Quiz) -> Self {
+        let questions = quiz.questions.iter()
+            .map(|question| {
+                let (kind, options) = match &question.kind {
+                    QuestionKind::MultipleChoice { options, .. } => ("multiple_choice", Some(options.clone())),
+                    QuestionKind::MultiSelect { options, .. } => ("multi_select", Some(options.clone())),
+                    QuestionKind::Numeric { .. } => ("numeric", None),
+                    QuestionKind::ShortText { .. } => ("short_text", None),
+                };
+                QuestionSheet {
+                    id: question.id,
+                    kind: kind.to_string(),
+                    prompt: question.prompt.clone(),
+                    points: question.points,
+                    options,
+                }
+            })
+            .collect();
+        QuizSheetResponse {
+            id: quiz.id,
+            lesson_id: quiz.lesson_id,
+            title: quiz.title.clone(),
+            instructions: quiz.instructions.clone(),
+            max_attempts: quiz.max_attempts,
+            time_limit_minutes: quiz.time_limit_minutes,
+            total_points: quiz.total_points(),
+            questions,
+        }
+    }
+}
+
+impl From<Quiz> for QuizSummary {
+    fn from(quiz: Quiz) -> Self {
+        QuizSummary {
+            id: quiz.id,
+            title: quiz.title.clone(),
+            question_count: quiz.questions.len(),
+            total_points: quiz.total_points(),
+            max_attempts: quiz.max_attempts,
+            time_limit_minutes: quiz.time_limit_minutes,
+        }
+    }
+}
+
+impl From<QuizAttempt> for QuizAttemptResponse {
+    fn from(attempt: QuizAttempt) -> Self {
+        let percent = attempt.score
+            .filter(|_| attempt.max_score > 0)
+            .map(|score| score * 100 / attempt.max_score);
+        QuizAttemptResponse {
+            id: attempt.id,
+            quiz_id: attempt.quiz_id,
+            student_id: attempt.student_id,
+            attempt_number: attempt.attempt_number,
+            started_at: attempt.started_at,
+            deadline_at: attempt.deadline_at,
+            submitted_at: attempt.submitted_at,
+            timed_out: attempt.timed_out,
+            score: attempt.score,
+            max_score: attempt.max_score,
+            percent,
+            answers: attempt.answers.0,
+            results: attempt.results.0,
+        }
+    }
+}
+
+impl From<Assignment> for AssignmentResponse {
+    fn from(assignment: Assignment) -> Self {
+        let max_score = assignment.max_score();
+        AssignmentResponse {
+            id: assignment.id,
+            course_id: assignment.course_id,
+            title: assignment.title,
+            instructions: assignment.instructions,
+            due_at: assignment.due_at,
+            accept_late: assignment.accept_late,
+            max_submissions: assignment.max_submissions,
+            max_score,
+            rubric: assignment.rubric.0,
+            created_at: assignment.created_at,
+            updated_at: assignment.updated_at,
+        }
+    }
+}
+
+impl From<AssignmentFile> for AssignmentFileResponse {
+    fn from(file: AssignmentFile) -> Self {
+        AssignmentFileResponse {
+            id: file.id,
+            file_name: file.file_name,
+            content_type: file.content_type,
+            size_bytes: file.size_bytes,
+            created_at: file.created_at,
+        }
+    }
+}
+
+impl From<Notification> for NotificationResponse {
+    fn from(notification: Notification) -> Self {
+        NotificationResponse {
+            id: notification.id,
+            kind: notification.kind,
+            title: notification.title,
+            body: notification.body,
+            link: notification.link,
+            read_at: notification.read_at,
+            created_at: notification.created_at,
+        }
+    }
+}
+
+impl From<Certificate> for CertificateResponse {
+    fn from(certificate: Certificate) -> Self {
+        CertificateResponse {
+            code: certificate.code,
+            course_id: certificate.course_id,
+            student_name: certificate.student_name,
+            course_title: certificate.course_title,
+            tutor_name: certificate.tutor_name,
+            completed_at: certificate.completed_at,
+            issued_at: certificate.issued_at,
+        }
+    }
+}
+
+impl From<BookingPolicy> for BookingPolicyResponse {
+    fn from(policy: BookingPolicy) -> Self {
+        BookingPolicyResponse {
+            tutor_id: policy.tutor_id,
+            free_cancel_hours: policy.free_cancel_hours,
+            late_cancel_fee_percent: policy.late_cancel_fee_percent,
+            max_reschedules: policy.max_reschedules,
+            reschedule_notice_hours: policy.reschedule_notice_hours,
         }
     }
 }
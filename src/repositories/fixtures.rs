@@ -0,0 +1,275 @@
+use bcrypt::hash;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Course, DifficultyLevel, TutorProfile, TutorReview, User, UserRole};
+use super::{CourseRepository, InMemoryRepository, ReviewRepository, TutorRepository, UserRepository};
+
+// Fixture passwords only need to survive a login round trip, so keep hashing cheap
+const FIXTURE_HASH_COST: u32 = 4;
+
+#[derive(Debug, Deserialize)]
+struct FixtureFile {
+    #[serde(default)]
+    users: Vec<UserFixture>,
+    #[serde(default)]
+    tutors: Vec<TutorFixture>,
+    #[serde(default)]
+    courses: Vec<CourseFixture>,
+    #[serde(default)]
+    reviews: Vec<ReviewFixture>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserFixture {
+    id: Option<Uuid>,
+    email: String,
+    password: String,
+    first_name: String,
+    last_name: String,
+    role: UserRole,
+    #[serde(default = "default_true")]
+    is_active: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TutorFixture {
+    id: Option<Uuid>,
+    user_email: String,
+    bio: String,
+    #[serde(default)]
+    specializations: Vec<String>,
+    hourly_rate: i32,
+    #[serde(default)]
+    years_experience: i32,
+    #[serde(default)]
+    is_verified: bool,
+    #[serde(default = "default_true")]
+    is_available: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CourseFixture {
+    id: Option<Uuid>,
+    tutor_email: String,
+    title: String,
+    description: String,
+    price: i32,
+    duration_minutes: i32,
+    category: String,
+    difficulty_level: DifficultyLevel,
+    #[serde(default = "default_true")]
+    is_active: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewFixture {
+    id: Option<Uuid>,
+    tutor_email: String,
+    student_email: String,
+    rating: i32,
+    comment: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// Listings sort newest first, so step timestamps back to keep file order
+fn created_at(now: DateTime<Utc>, index: usize) -> DateTime<Utc> {
+    now - Duration::seconds(index as i64)
+}
+
+fn invalid(message: String) -> AppError {
+    AppError::Internal(format!("Invalid fixtures: {}", message))
+}
+
+// Seed rows resolved from a JSON fixture file. Passwords are hashed and
+// missing ids generated once, so reloading after a reset is cheap and ids
+// stay stable for the lifetime of the process.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureSet {
+    pub users: Vec<User>,
+    pub tutors: Vec<TutorProfile>,
+    pub courses: Vec<Course>,
+    pub reviews: Vec<TutorReview>,
+}
+
+impl FixtureSet {
+    pub fn from_file(path: impl AsRef<Path>) -> AppResult<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| invalid(format!("cannot read {}: {}", path.display(), e)))?;
+        Self::from_json(&contents)
+    }
+
+    pub fn from_json(json: &str) -> AppResult<Self> {
+        let file: FixtureFile = serde_json::from_str(json)
+            .map_err(|e| invalid(e.to_string()))?;
+        let now = Utc::now();
+
+        let mut users = Vec::new();
+        let mut user_ids = HashMap::new();
+        for fixture in file.users {
+            let password_hash = hash(&fixture.password, FIXTURE_HASH_COST)
+                .map_err(|e| invalid(format!("password hashing failed: {}", e)))?;
+            let user = User {
+                id: fixture.id.unwrap_or_else(Uuid::new_v4),
+                email: fixture.email,
+                password_hash,
+                first_name: fixture.first_name,
+                last_name: fixture.last_name,
+                role: fixture.role,
+                is_active: fixture.is_active,
+                avatar_updated_at: None,
+                token_version: 0,
+                created_at: now,
+                updated_at: now,
+            };
+            user_ids.insert(user.email.clone(), user.id);
+            users.push(user);
+        }
+
+        let user_id = |email: &str| {
+            user_ids.get(email).copied()
+                .ok_or_else(|| invalid(format!("unknown user {}", email)))
+        };
+
+        let mut tutors = Vec::new();
+        let mut tutor_ids = HashMap::new();
+        for (index, fixture) in file.tutors.into_iter().enumerate() {
+            let tutor = TutorProfile {
+                id: fixture.id.unwrap_or_else(Uuid::new_v4),
+                user_id: user_id(&fixture.user_email)?,
+                bio: fixture.bio,
+                specializations: fixture.specializations,
+                hourly_rate: fixture.hourly_rate,
+                years_experience: fixture.years_experience,
+                rating: None,
+                total_reviews: 0,
+                rating_sum: 0,
+                is_verified: fixture.is_verified,
+                is_available: fixture.is_available,
+                deleted_at: None,
+                created_at: created_at(now, index),
+                updated_at: now,
+            };
+            tutor_ids.insert(fixture.user_email, tutor.id);
+            tutors.push(tutor);
+        }
+
+        let tutor_id = |email: &str| {
+            tutor_ids.get(email).copied()
+                .ok_or_else(|| invalid(format!("no tutor profile for {}", email)))
+        };
+
+        let courses = file.courses.into_iter()
+            .enumerate()
+            .map(|(index, fixture)| {
+                Ok(Course {
+                    id: fixture.id.unwrap_or_else(Uuid::new_v4),
+                    title: fixture.title,
+                    description: fixture.description,
+                    tutor_id: tutor_id(&fixture.tutor_email)?,
+                    price: fixture.price,
+                    duration_minutes: fixture.duration_minutes,
+                    category: fixture.category,
+                    difficulty_level: fixture.difficulty_level,
+                    is_active: fixture.is_active,
+                    max_students: None,
+                    taken_down_at: None,
+                    deleted_at: None,
+                    created_at: created_at(now, index),
+                    updated_at: now,
+                })
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+
+        let reviews = file.reviews.into_iter()
+            .enumerate()
+            .map(|(index, fixture)| {
+                Ok(TutorReview {
+                    id: fixture.id.unwrap_or_else(Uuid::new_v4),
+                    tutor_id: tutor_id(&fixture.tutor_email)?,
+                    student_id: user_id(&fixture.student_email)?,
+                    rating: fixture.rating,
+                    comment: fixture.comment,
+                    created_at: created_at(now, index),
+                    edited_at: None,
+                })
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+
+        Ok(FixtureSet { users, tutors, courses, reviews })
+    }
+}
+
+impl InMemoryRepository {
+    // Inserts the fixture rows on top of whatever is already stored
+    pub async fn load_fixtures(&self, fixtures: &FixtureSet) -> AppResult<()> {
+        for user in &fixtures.users {
+            self.insert_user(user).await?;
+        }
+        for tutor in &fixtures.tutors {
+            self.insert_tutor(tutor).await?;
+        }
+        for course in &fixtures.courses {
+            self.insert_course(course).await?;
+        }
+        for review in &fixtures.reviews {
+            self.insert_review(review).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{AuthService, CourseService, TutorService};
+    use crate::models::LoginRequest;
+
+    #[actix_rt::test]
+    async fn bundled_seed_file_loads_and_resets() {
+        let fixtures = FixtureSet::from_json(include_str!("../../fixtures/seed.json")).unwrap();
+        let repo = InMemoryRepository::new();
+        repo.load_fixtures(&fixtures).await.unwrap();
+
+        let login = LoginRequest {
+            email: "sam.student@ezytutor.dev".to_string(),
+            password: "password123".to_string(),
+        };
+        assert!(AuthService::login_user(&repo, login).await.is_ok());
+
+        let courses = CourseService::get_all_courses(&repo).await.unwrap();
+        assert_eq!(courses[0].title, "Calculus I Crash Course");
+        let tutor = TutorService::get_tutor_by_id(&repo, fixtures.tutors[0].id).await.unwrap();
+        assert_eq!(tutor.total_reviews, 2);
+
+        repo.clear();
+        assert!(CourseService::get_all_courses(&repo).await.unwrap().is_empty());
+        repo.load_fixtures(&fixtures).await.unwrap();
+        assert_eq!(CourseService::get_all_courses(&repo).await.unwrap().len(), courses.len());
+    }
+
+    #[test]
+    fn unknown_references_are_rejected() {
+        let json = r#"{
+            "courses": [{
+                "tutor_email": "ghost@ezytutor.dev",
+                "title": "Haunted",
+                "description": "Nobody teaches this",
+                "price": 100,
+                "duration_minutes": 30,
+                "category": "Mystery",
+                "difficulty_level": "beginner"
+            }]
+        }"#;
+
+        let error = FixtureSet::from_json(json).unwrap_err();
+        assert!(matches!(error, AppError::Internal(message) if message.contains("ghost@ezytutor.dev")));
+    }
+}
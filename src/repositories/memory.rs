@@ -0,0 +1,1803 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use uuid::Uuid;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Assignment, AssignmentFile, AssignmentSubmission, AuditEntry, AuditLogQuery, Avatar, AvailabilityBlackout, AvailabilityException, AvailabilityWindow, Booking, BookingPolicy, BookingStatus,
+    BookingWithDetails, CalendarFeed, Certificate, Course, CourseModule, CourseProgress, CourseWithTutor, Enrollment, EnrollmentStatus, EnrollmentWithDetails, FilePurpose, Lesson, LessonCompletion, ModuleOrder, Notification, PlatformStats, Quiz, QuizAttempt, RatingDrift, ReviewEdit, ReviewReply, ReviewWithStudent, StoredFile, TutorProfile, TutorReview, TutorSchedule, TutorWithUser,
+    User, UserRole, VerificationDocument, VerificationEvent, VerificationRequest, VerificationRequestWithTutor, VerificationStatus
+};
+use super::{check_complete_order, AssignmentRepository, AuditRepository, AvailabilityRepository, AvatarRepository, BookingRepository, CalendarRepository, CertificateRepository, CourseRepository, CurriculumRepository, EnrollmentRepository, FileRepository, NotificationRepository, ProgressRepository, QuizRepository, ReviewRepository, StatsRepository, TutorRepository, UserRepository, VerificationRepository};
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    users: HashMap<Uuid, User>,
+    tutors: HashMap<Uuid, TutorProfile>,
+    courses: HashMap<Uuid, Course>,
+    reviews: HashMap<Uuid, TutorReview>,
+    review_edits: Vec<ReviewEdit>,
+    review_replies: HashMap<Uuid, ReviewReply>,
+    schedules: HashMap<Uuid, TutorSchedule>,
+    windows: HashMap<Uuid, AvailabilityWindow>,
+    exceptions: HashMap<Uuid, AvailabilityException>,
+    blackouts: HashMap<Uuid, AvailabilityBlackout>,
+    bookings: HashMap<Uuid, Booking>,
+    booking_policies: HashMap<Uuid, BookingPolicy>,
+    calendar_feeds: HashMap<Uuid, CalendarFeed>,
+    enrollments: HashMap<Uuid, Enrollment>,
+    modules: HashMap<Uuid, CourseModule>,
+    lessons: HashMap<Uuid, Lesson>,
+    lesson_completions: HashMap<Uuid, LessonCompletion>,
+    quizzes: HashMap<Uuid, Quiz>,
+    quiz_attempts: HashMap<Uuid, QuizAttempt>,
+    assignments: HashMap<Uuid, Assignment>,
+    assignment_files: HashMap<Uuid, AssignmentFile>,
+    submissions: HashMap<Uuid, AssignmentSubmission>,
+    notifications: HashMap<Uuid, Notification>,
+    certificates: HashMap<Uuid, Certificate>,
+    stored_files: HashMap<Uuid, StoredFile>,
+    avatars: HashMap<Uuid, Avatar>,
+    verification_requests: HashMap<Uuid, VerificationRequest>,
+    verification_documents: HashMap<Uuid, VerificationDocument>,
+    verification_events: Vec<VerificationEvent>,
+    audit_log: Vec<AuditEntry>,
+}
+
+impl MemoryState {
+    // None for deleted profiles, which reads treat as missing
+    fn tutor_with_user(&self, tutor: &TutorProfile) -> Option<TutorWithUser> {
+        if tutor.deleted_at.is_some() {
+            return None;
+        }
+        let user = self.users.get(&tutor.user_id)?;
+        Some(TutorWithUser {
+            tutor: tutor.clone(),
+            first_name: user.first_name.clone(),
+            last_name: user.last_name.clone(),
+            email: user.email.clone(),
+            avatar_updated_at: user.avatar_updated_at,
+        })
+    }
+
+    fn verification_request_with_tutor(&self, request: &VerificationRequest) -> Option<VerificationRequestWithTutor> {
+        let tutor = self.tutors.get(&request.tutor_id)?;
+        let user = self.users.get(&tutor.user_id)?;
+        Some(VerificationRequestWithTutor {
+            request: request.clone(),
+            tutor_user_id: user.id,
+            tutor_first_name: user.first_name.clone(),
+            tutor_last_name: user.last_name.clone(),
+            tutor_email: user.email.clone(),
+        })
+    }
+
+    // None for deleted courses and courses of deleted tutors
+    fn course_with_tutor(&self, course: &Course) -> Option<CourseWithTutor> {
+        if course.deleted_at.is_some() {
+            return None;
+        }
+        let tutor = self.tutors.get(&course.tutor_id).filter(|tutor| tutor.deleted_at.is_none())?;
+        let user = self.users.get(&tutor.user_id)?;
+        Some(CourseWithTutor {
+            course: course.clone(),
+            tutor_first_name: user.first_name.clone(),
+            tutor_last_name: user.last_name.clone(),
+            tutor_rating: tutor.rating,
+            tutor_is_verified: tutor.is_verified,
+            enrolled_students: self.count_active_enrollments(course.id),
+        })
+    }
+
+    fn enrollment_with_details(&self, enrollment: &Enrollment) -> Option<EnrollmentWithDetails> {
+        let course = self.courses.get(&enrollment.course_id)?;
+        let student = self.users.get(&enrollment.student_id)?;
+        let waitlist_position = (enrollment.status == EnrollmentStatus::Waitlisted).then(|| {
+            self.enrollments.values()
+                .filter(|other| {
+                    other.course_id == enrollment.course_id
+                        && other.status == EnrollmentStatus::Waitlisted
+                        && (other.requested_at, other.id) <= (enrollment.requested_at, enrollment.id)
+                })
+                .count() as i64
+        });
+        Some(EnrollmentWithDetails {
+            enrollment: enrollment.clone(),
+            course_title: course.title.clone(),
+            student_first_name: student.first_name.clone(),
+            student_last_name: student.last_name.clone(),
+            student_email: student.email.clone(),
+            waitlist_position,
+        })
+    }
+
+    fn count_active_enrollments(&self, course_id: Uuid) -> i64 {
+        self.enrollments.values()
+            .filter(|enrollment| enrollment.course_id == course_id && enrollment.status == EnrollmentStatus::Active)
+            .count() as i64
+    }
+
+    // Mirrors the Postgres promotion: earliest requests take the free seats
+    fn promote_waitlisted(&mut self, course_id: Uuid, now: DateTime<Utc>) -> Vec<Enrollment> {
+        let Some(course) = self.courses.get(&course_id) else {
+            return Vec::new();
+        };
+        let free = match course.max_students {
+            Some(max) => (i64::from(max) - self.count_active_enrollments(course_id)).max(0) as usize,
+            None => usize::MAX,
+        };
+
+        let mut waiting: Vec<(DateTime<Utc>, Uuid)> = self.enrollments.values()
+            .filter(|enrollment| enrollment.course_id == course_id && enrollment.status == EnrollmentStatus::Waitlisted)
+            .map(|enrollment| (enrollment.requested_at, enrollment.id))
+            .collect();
+        waiting.sort();
+
+        waiting.into_iter()
+            .take(free)
+            .filter_map(|(_, id)| {
+                let enrollment = self.enrollments.get_mut(&id)?;
+                enrollment.status = EnrollmentStatus::Active;
+                enrollment.enrolled_at = Some(now);
+                enrollment.updated_at = now;
+                Some(enrollment.clone())
+            })
+            .collect()
+    }
+
+    // ON DELETE CASCADE for the rows hanging off lessons
+    fn remove_orphaned_lesson_rows(&mut self) {
+        let MemoryState { lessons, lesson_completions, quizzes, quiz_attempts, .. } = self;
+        lesson_completions.retain(|_, completion| lessons.contains_key(&completion.lesson_id));
+        quizzes.retain(|_, quiz| lessons.contains_key(&quiz.lesson_id));
+        quiz_attempts.retain(|_, attempt| quizzes.contains_key(&attempt.quiz_id));
+    }
+
+    // ON DELETE CASCADE for the rows hanging off reviews
+    fn remove_orphaned_review_rows(&mut self) {
+        let MemoryState { reviews, review_edits, review_replies, .. } = self;
+        review_edits.retain(|edit| reviews.contains_key(&edit.review_id));
+        review_replies.retain(|review_id, _| reviews.contains_key(review_id));
+    }
+
+    fn review_with_student(&self, review: &TutorReview) -> Option<ReviewWithStudent> {
+        let student = self.users.get(&review.student_id)?;
+        let reply = self.review_replies.get(&review.id);
+        Some(ReviewWithStudent {
+            review: review.clone(),
+            student_first_name: student.first_name.clone(),
+            student_last_name: student.last_name.clone(),
+            student_avatar_updated_at: student.avatar_updated_at,
+            reply_comment: reply.map(|reply| reply.comment.clone()),
+            reply_created_at: reply.map(|reply| reply.created_at),
+        })
+    }
+
+    // ON DELETE CASCADE for the rows hanging off assignments
+    fn remove_orphaned_assignment_rows(&mut self) {
+        let MemoryState { assignments, assignment_files, submissions, .. } = self;
+        assignment_files.retain(|_, file| assignments.contains_key(&file.assignment_id));
+        submissions.retain(|_, submission| assignments.contains_key(&submission.assignment_id));
+    }
+
+    // DELETE FROM courses, with its ON DELETE CASCADE and SET NULL rules
+    fn remove_course(&mut self, course_id: Uuid) -> bool {
+        self.bookings.retain(|_, booking| booking.course_id != course_id);
+        self.enrollments.retain(|_, enrollment| enrollment.course_id != course_id);
+        self.modules.retain(|_, module| module.course_id != course_id);
+        self.lessons.retain(|_, lesson| lesson.course_id != course_id);
+        self.remove_orphaned_lesson_rows();
+        self.assignments.retain(|_, assignment| assignment.course_id != course_id);
+        self.remove_orphaned_assignment_rows();
+        for certificate in self.certificates.values_mut().filter(|c| c.course_id == Some(course_id)) {
+            certificate.course_id = None;
+        }
+        self.courses.remove(&course_id).is_some()
+    }
+
+    // DELETE FROM tutors, with its ON DELETE CASCADE rules
+    fn remove_tutor(&mut self, tutor_id: Uuid) -> bool {
+        let course_ids: Vec<Uuid> = self.courses.values()
+            .filter(|course| course.tutor_id == tutor_id)
+            .map(|course| course.id)
+            .collect();
+        for course_id in course_ids {
+            self.remove_course(course_id);
+        }
+        self.reviews.retain(|_, review| review.tutor_id != tutor_id);
+        self.remove_orphaned_review_rows();
+        self.bookings.retain(|_, booking| booking.tutor_id != tutor_id);
+        self.booking_policies.remove(&tutor_id);
+        self.schedules.remove(&tutor_id);
+        self.windows.retain(|_, window| window.tutor_id != tutor_id);
+        self.exceptions.retain(|_, exception| exception.tutor_id != tutor_id);
+        self.blackouts.retain(|_, blackout| blackout.tutor_id != tutor_id);
+        self.verification_requests.retain(|_, request| request.tutor_id != tutor_id);
+        let MemoryState { verification_requests, verification_documents, .. } = self;
+        verification_documents.retain(|_, document| verification_requests.contains_key(&document.request_id));
+        self.verification_events.retain(|event| event.tutor_id != tutor_id);
+        self.tutors.remove(&tutor_id).is_some()
+    }
+
+    // Star sum and count over a tutor's stored reviews
+    fn review_totals(&self, tutor_id: Uuid) -> (i64, i32) {
+        self.reviews.values()
+            .filter(|review| review.tutor_id == tutor_id)
+            .fold((0, 0), |(stars, count), review| (stars + i64::from(review.rating), count + 1))
+    }
+
+    fn sorted_modules(&self, course_id: Uuid) -> Vec<CourseModule> {
+        let mut modules: Vec<CourseModule> = self.modules.values()
+            .filter(|module| module.course_id == course_id)
+            .cloned()
+            .collect();
+        modules.sort_by_key(|module| module.position);
+        modules
+    }
+
+    fn course_progress(&self, enrollment: &Enrollment) -> Option<CourseProgress> {
+        let course = self.courses.get(&enrollment.course_id)?;
+        let student = self.users.get(&enrollment.student_id)?;
+        let lessons: Vec<&Lesson> = self.lessons.values()
+            .filter(|lesson| lesson.course_id == enrollment.course_id)
+            .collect();
+        let completions: Vec<&LessonCompletion> = self.lesson_completions.values()
+            .filter(|c| c.course_id == enrollment.course_id && c.student_id == enrollment.student_id)
+            .collect();
+        Some(CourseProgress {
+            course_id: course.id,
+            course_title: course.title.clone(),
+            student_id: student.id,
+            student_first_name: student.first_name.clone(),
+            student_last_name: student.last_name.clone(),
+            student_email: student.email.clone(),
+            enrolled_at: enrollment.enrolled_at,
+            total_lessons: lessons.len() as i64,
+            estimated_minutes: lessons.iter().map(|lesson| i64::from(lesson.estimated_minutes)).sum(),
+            completed_lessons: completions.len() as i64,
+            minutes_spent: completions.iter().map(|c| i64::from(c.minutes_spent)).sum(),
+            last_completed_at: completions.iter().map(|c| c.completed_at).max(),
+        })
+    }
+
+    fn booking_with_details(&self, booking: &Booking) -> Option<BookingWithDetails> {
+        let course = self.courses.get(&booking.course_id)?;
+        let tutor = self.tutors.get(&booking.tutor_id)?;
+        let tutor_user = self.users.get(&tutor.user_id)?;
+        let student = self.users.get(&booking.student_id)?;
+        Some(BookingWithDetails {
+            booking: booking.clone(),
+            course_title: course.title.clone(),
+            tutor_user_id: tutor_user.id,
+            tutor_first_name: tutor_user.first_name.clone(),
+            tutor_last_name: tutor_user.last_name.clone(),
+            student_first_name: student.first_name.clone(),
+            student_last_name: student.last_name.clone(),
+        })
+    }
+
+    fn sorted_bookings<F>(&self, filter: F) -> Vec<BookingWithDetails>
+    where
+        F: Fn(&Booking) -> bool,
+    {
+        let mut bookings: Vec<BookingWithDetails> = self.bookings.values()
+            .filter(|booking| filter(booking))
+            .filter_map(|booking| self.booking_with_details(booking))
+            .collect();
+        bookings.sort_by_key(|row| row.booking.starts_at);
+        bookings
+    }
+
+    // Emulates the bookings_no_tutor_overlap exclusion constraint
+    fn check_booking_overlap(&self, booking: &Booking) -> AppResult<()> {
+        if !booking.status.is_active() {
+            return Ok(());
+        }
+        let overlaps = self.bookings.values().any(|other| {
+            other.id != booking.id
+                && other.tutor_id == booking.tutor_id
+                && other.status.is_active()
+                && other.starts_at < booking.ends_at
+                && other.ends_at > booking.starts_at
+        });
+        if overlaps {
+            return Err(AppError::Conflict("Tutor is already booked for this time".to_string()));
+        }
+        Ok(())
+    }
+
+    // Mirrors `ORDER BY t.rating DESC NULLS LAST, t.created_at DESC`
+    fn sorted_tutors<F>(&self, filter: F) -> Vec<TutorWithUser>
+    where
+        F: Fn(&TutorProfile) -> bool,
+    {
+        let mut tutors: Vec<TutorWithUser> = self.tutors.values()
+            .filter(|tutor| filter(tutor))
+            .filter_map(|tutor| self.tutor_with_user(tutor))
+            .collect();
+        tutors.sort_by(|a, b| {
+            let rating_a = a.tutor.rating.unwrap_or(f32::NEG_INFINITY);
+            let rating_b = b.tutor.rating.unwrap_or(f32::NEG_INFINITY);
+            b.tutor.is_verified.cmp(&a.tutor.is_verified)
+                .then(rating_b.total_cmp(&rating_a))
+                .then(b.tutor.created_at.cmp(&a.tutor.created_at))
+        });
+        tutors
+    }
+
+    fn sorted_courses<F>(&self, filter: F) -> Vec<CourseWithTutor>
+    where
+        F: Fn(&Course) -> bool,
+    {
+        let mut courses: Vec<CourseWithTutor> = self.courses.values()
+            .filter(|course| filter(course))
+            .filter_map(|course| self.course_with_tutor(course))
+            .collect();
+        courses.sort_by_key(|row| Reverse(row.course.created_at));
+        courses
+    }
+}
+
+// Process-local storage backend used by tests and the mock server. Every
+// clone shares the same state.
+#[derive(Clone, Default)]
+pub struct InMemoryRepository {
+    state: Arc<RwLock<MemoryState>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Drops every stored row
+    pub fn clear(&self) {
+        *self.write() = MemoryState::default();
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, MemoryState> {
+        self.state.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, MemoryState> {
+        self.state.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn constraint_violation(message: &str) -> AppError {
+    AppError::Internal(format!("Constraint violation: {}", message))
+}
+
+// Mirrors `(rating_sum::FLOAT8 / total_reviews)::REAL`
+fn average_rating(rating_sum: i64, total_reviews: i32) -> Option<f32> {
+    (total_reviews > 0).then(|| (rating_sum as f64 / f64::from(total_reviews)) as f32)
+}
+
+// Moves a tutor's rating aggregate by `stars` across `reviews` reviews
+fn adjust_rating(tutor: &mut TutorProfile, stars: i64, reviews: i32) {
+    tutor.rating_sum += stars;
+    tutor.total_reviews += reviews;
+    tutor.rating = average_rating(tutor.rating_sum, tutor.total_reviews);
+    tutor.updated_at = Utc::now();
+}
+
+#[async_trait]
+impl UserRepository for InMemoryRepository {
+    async fn find_user_by_id(&self, user_id: Uuid) -> AppResult<Option<User>> {
+        Ok(self.read().users.get(&user_id).cloned())
+    }
+
+    async fn find_user_by_email(&self, email: &str) -> AppResult<Option<User>> {
+        Ok(self.read().users.values().find(|user| user.email == email).cloned())
+    }
+
+    async fn insert_user(&self, user: &User) -> AppResult<()> {
+        let mut state = self.write();
+        if state.users.values().any(|existing| existing.email == user.email) {
+            return Err(constraint_violation("users.email must be unique"));
+        }
+        state.users.insert(user.id, user.clone());
+        Ok(())
+    }
+
+    async fn update_user(&self, user: &User, expected_updated_at: DateTime<Utc>) -> AppResult<bool> {
+        let mut state = self.write();
+        let Some(existing) = state.users.get_mut(&user.id).filter(|existing| existing.updated_at == expected_updated_at) else {
+            return Ok(false);
+        };
+        existing.first_name = user.first_name.clone();
+        existing.last_name = user.last_name.clone();
+        existing.role = user.role.clone();
+        existing.is_active = user.is_active;
+        existing.updated_at = user.updated_at;
+        Ok(true)
+    }
+
+    async fn search_users(
+        &self,
+        text: Option<&str>,
+        role: Option<UserRole>,
+        is_active: Option<bool>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<User>, i64)> {
+        let state = self.read();
+        let text = text.map(str::to_lowercase);
+        let mut users: Vec<&User> = state.users.values()
+            .filter(|user| {
+                text.as_ref().is_none_or(|text| {
+                    let full_name = format!("{} {}", user.first_name, user.last_name).to_lowercase();
+                    user.email.to_lowercase().contains(text) || full_name.contains(text)
+                })
+            })
+            .filter(|user| role.as_ref().is_none_or(|role| &user.role == role))
+            .filter(|user| is_active.is_none_or(|is_active| user.is_active == is_active))
+            .collect();
+        users.sort_by_key(|user| (Reverse(user.created_at), user.id));
+
+        let total = users.len() as i64;
+        let page = users.into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect();
+        Ok((page, total))
+    }
+
+    async fn revoke_sessions(&self, user_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        let Some(user) = state.users.get_mut(&user_id) else {
+            return Ok(false);
+        };
+        user.token_version += 1;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl TutorRepository for InMemoryRepository {
+    async fn find_tutor_by_id(&self, tutor_id: Uuid) -> AppResult<Option<TutorWithUser>> {
+        let state = self.read();
+        Ok(state.tutors.get(&tutor_id).and_then(|tutor| state.tutor_with_user(tutor)))
+    }
+
+    async fn find_tutor_by_user_id(&self, user_id: Uuid) -> AppResult<Option<TutorWithUser>> {
+        let state = self.read();
+        Ok(state.tutors.values()
+            .find(|tutor| tutor.user_id == user_id)
+            .and_then(|tutor| state.tutor_with_user(tutor)))
+    }
+
+    async fn list_available_tutors(&self) -> AppResult<Vec<TutorWithUser>> {
+        Ok(self.read().sorted_tutors(|tutor| tutor.is_available))
+    }
+
+    async fn search_tutors_by_specialization(&self, specialization: &str) -> AppResult<Vec<TutorWithUser>> {
+        Ok(self.read().sorted_tutors(|tutor| {
+            tutor.is_available && tutor.specializations.iter().any(|s| s == specialization)
+        }))
+    }
+
+    async fn list_tutor_ids(&self) -> AppResult<Vec<Uuid>> {
+        let state = self.read();
+        let mut tutors: Vec<&TutorProfile> = state.tutors.values().collect();
+        tutors.sort_by_key(|tutor| tutor.created_at);
+        Ok(tutors.into_iter().map(|tutor| tutor.id).collect())
+    }
+
+    async fn insert_tutor(&self, tutor: &TutorProfile) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.users.contains_key(&tutor.user_id) {
+            return Err(constraint_violation("tutors.user_id must reference users"));
+        }
+        if state.tutors.values().any(|existing| existing.user_id == tutor.user_id) {
+            return Err(constraint_violation("tutors.user_id must be unique"));
+        }
+        state.tutors.insert(tutor.id, tutor.clone());
+        Ok(())
+    }
+
+    async fn update_tutor(&self, tutor: &TutorProfile, expected_updated_at: DateTime<Utc>) -> AppResult<bool> {
+        let mut state = self.write();
+        let Some(existing) = state.tutors.get_mut(&tutor.id).filter(|existing| existing.updated_at == expected_updated_at) else {
+            return Ok(false);
+        };
+        existing.bio = tutor.bio.clone();
+        existing.specializations = tutor.specializations.clone();
+        existing.hourly_rate = tutor.hourly_rate;
+        existing.years_experience = tutor.years_experience;
+        existing.is_available = tutor.is_available;
+        existing.updated_at = tutor.updated_at;
+        Ok(true)
+    }
+
+    async fn delete_tutor(&self, tutor_id: Uuid, deleted_at: DateTime<Utc>) -> AppResult<bool> {
+        let mut state = self.write();
+        let enrolled = state.courses.values()
+            .any(|course| course.tutor_id == tutor_id && state.count_active_enrollments(course.id) > 0);
+        if enrolled {
+            return Ok(false);
+        }
+        match state.tutors.get_mut(&tutor_id) {
+            Some(tutor) if tutor.deleted_at.is_none() => {
+                tutor.deleted_at = Some(deleted_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn find_deleted_tutor_by_user_id(&self, user_id: Uuid) -> AppResult<Option<TutorProfile>> {
+        Ok(self.read().tutors.values()
+            .find(|tutor| tutor.user_id == user_id && tutor.deleted_at.is_some())
+            .cloned())
+    }
+
+    async fn restore_tutor(&self, tutor_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        match state.tutors.get_mut(&tutor_id) {
+            Some(tutor) if tutor.deleted_at.is_some() => {
+                tutor.deleted_at = None;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn purge_deleted_tutors(&self, deleted_before: DateTime<Utc>) -> AppResult<Vec<Uuid>> {
+        let mut state = self.write();
+        let expired: Vec<Uuid> = state.tutors.values()
+            .filter(|tutor| tutor.deleted_at.is_some_and(|deleted_at| deleted_at < deleted_before))
+            .map(|tutor| tutor.id)
+            .collect();
+        for tutor_id in &expired {
+            state.remove_tutor(*tutor_id);
+        }
+        Ok(expired)
+    }
+}
+
+#[async_trait]
+impl CourseRepository for InMemoryRepository {
+    async fn find_course_by_id(&self, course_id: Uuid) -> AppResult<Option<CourseWithTutor>> {
+        let state = self.read();
+        Ok(state.courses.get(&course_id).and_then(|course| state.course_with_tutor(course)))
+    }
+
+    async fn list_active_courses(&self) -> AppResult<Vec<CourseWithTutor>> {
+        Ok(self.read().sorted_courses(|course| course.is_active))
+    }
+
+    async fn list_courses_by_tutor_user(&self, user_id: Uuid) -> AppResult<Vec<CourseWithTutor>> {
+        let state = self.read();
+        let tutor_id = match state.tutors.values().find(|tutor| tutor.user_id == user_id && tutor.deleted_at.is_none()) {
+            Some(tutor) => tutor.id,
+            None => return Ok(Vec::new()),
+        };
+        Ok(state.sorted_courses(|course| course.tutor_id == tutor_id))
+    }
+
+    async fn insert_course(&self, course: &Course) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.tutors.contains_key(&course.tutor_id) {
+            return Err(constraint_violation("courses.tutor_id must reference tutors"));
+        }
+        state.courses.insert(course.id, course.clone());
+        Ok(())
+    }
+
+    async fn update_course(&self, course: &Course, expected_updated_at: DateTime<Utc>) -> AppResult<bool> {
+        let mut state = self.write();
+        let Some(existing) = state.courses.get_mut(&course.id).filter(|existing| existing.updated_at == expected_updated_at) else {
+            return Ok(false);
+        };
+        let (created_at, taken_down_at, deleted_at) = (existing.created_at, existing.taken_down_at, existing.deleted_at);
+        *existing = course.clone();
+        existing.created_at = created_at;
+        existing.taken_down_at = taken_down_at;
+        existing.deleted_at = deleted_at;
+        Ok(true)
+    }
+
+    async fn set_course_taken_down(&self, course_id: Uuid, taken_down_at: Option<DateTime<Utc>>) -> AppResult<bool> {
+        let mut state = self.write();
+        let Some(course) = state.courses.get_mut(&course_id) else {
+            return Ok(false);
+        };
+        course.taken_down_at = taken_down_at;
+        if taken_down_at.is_some() {
+            course.is_active = false;
+        }
+        course.updated_at = Utc::now();
+        Ok(true)
+    }
+
+    async fn delete_course(&self, course_id: Uuid, deleted_at: DateTime<Utc>) -> AppResult<bool> {
+        let mut state = self.write();
+        if state.count_active_enrollments(course_id) > 0 {
+            return Ok(false);
+        }
+        match state.courses.get_mut(&course_id) {
+            Some(course) if course.deleted_at.is_none() => {
+                course.deleted_at = Some(deleted_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn list_deleted_courses(&self, tutor_id: Uuid) -> AppResult<Vec<Course>> {
+        let state = self.read();
+        let mut courses: Vec<Course> = state.courses.values()
+            .filter(|course| course.tutor_id == tutor_id && course.deleted_at.is_some())
+            .cloned()
+            .collect();
+        courses.sort_by_key(|course| Reverse(course.deleted_at));
+        Ok(courses)
+    }
+
+    async fn restore_course(&self, course_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        match state.courses.get_mut(&course_id) {
+            Some(course) if course.deleted_at.is_some() => {
+                course.deleted_at = None;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn purge_deleted_courses(&self, deleted_before: DateTime<Utc>) -> AppResult<Vec<Uuid>> {
+        let mut state = self.write();
+        let expired: Vec<Uuid> = state.courses.values()
+            .filter(|course| course.deleted_at.is_some_and(|deleted_at| deleted_at < deleted_before))
+            .map(|course| course.id)
+            .collect();
+        for course_id in &expired {
+            state.remove_course(*course_id);
+        }
+        Ok(expired)
+    }
+}
+
+#[async_trait]
+impl ReviewRepository for InMemoryRepository {
+    async fn find_review(&self, tutor_id: Uuid, student_id: Uuid) -> AppResult<Option<TutorReview>> {
+        Ok(self.read().reviews.values()
+            .find(|review| review.tutor_id == tutor_id && review.student_id == student_id)
+            .cloned())
+    }
+
+    async fn find_review_by_id(&self, review_id: Uuid) -> AppResult<Option<TutorReview>> {
+        Ok(self.read().reviews.get(&review_id).cloned())
+    }
+
+    async fn find_review_with_student(&self, review_id: Uuid) -> AppResult<Option<ReviewWithStudent>> {
+        let state = self.read();
+        Ok(state.reviews.get(&review_id).and_then(|review| state.review_with_student(review)))
+    }
+
+    async fn list_reviews_for_tutor(&self, tutor_id: Uuid) -> AppResult<Vec<ReviewWithStudent>> {
+        let state = self.read();
+        if state.tutors.get(&tutor_id).is_some_and(|tutor| tutor.deleted_at.is_some()) {
+            return Ok(Vec::new());
+        }
+        let mut reviews: Vec<ReviewWithStudent> = state.reviews.values()
+            .filter(|review| review.tutor_id == tutor_id)
+            .filter_map(|review| state.review_with_student(review))
+            .collect();
+        reviews.sort_by_key(|row| Reverse(row.review.created_at));
+        Ok(reviews)
+    }
+
+    async fn insert_review(&self, review: &TutorReview) -> AppResult<bool> {
+        let mut state = self.write();
+        if !state.tutors.contains_key(&review.tutor_id) || !state.users.contains_key(&review.student_id) {
+            return Err(constraint_violation("tutor_reviews must reference tutors and users"));
+        }
+        if state.reviews.values().any(|r| r.tutor_id == review.tutor_id && r.student_id == review.student_id) {
+            return Ok(false);
+        }
+        if let Some(tutor) = state.tutors.get_mut(&review.tutor_id) {
+            adjust_rating(tutor, i64::from(review.rating), 1);
+        }
+        state.reviews.insert(review.id, review.clone());
+        Ok(true)
+    }
+
+    async fn update_review(&self, review: &TutorReview) -> AppResult<bool> {
+        let mut state = self.write();
+        let Some(existing) = state.reviews.get_mut(&review.id) else {
+            return Ok(false);
+        };
+        let replaced = ReviewEdit {
+            id: Uuid::new_v4(),
+            review_id: review.id,
+            rating: existing.rating,
+            comment: existing.comment.clone(),
+            edited_at: review.edited_at.unwrap_or_else(Utc::now),
+        };
+        let stars = i64::from(review.rating - existing.rating);
+        existing.rating = review.rating;
+        existing.comment = review.comment.clone();
+        existing.edited_at = review.edited_at;
+        let tutor_id = existing.tutor_id;
+        state.review_edits.push(replaced);
+        if let Some(tutor) = state.tutors.get_mut(&tutor_id) {
+            adjust_rating(tutor, stars, 0);
+        }
+        Ok(true)
+    }
+
+    async fn delete_review(&self, review_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        let Some(review) = state.reviews.remove(&review_id) else {
+            return Ok(false);
+        };
+        state.remove_orphaned_review_rows();
+        if let Some(tutor) = state.tutors.get_mut(&review.tutor_id) {
+            adjust_rating(tutor, -i64::from(review.rating), -1);
+        }
+        Ok(true)
+    }
+
+    async fn list_review_edits(&self, review_id: Uuid) -> AppResult<Vec<ReviewEdit>> {
+        Ok(self.read().review_edits.iter()
+            .filter(|edit| edit.review_id == review_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn insert_review_reply(&self, reply: &ReviewReply) -> AppResult<bool> {
+        let mut state = self.write();
+        if !state.reviews.contains_key(&reply.review_id) {
+            return Err(constraint_violation("review_replies must reference tutor_reviews"));
+        }
+        if state.review_replies.contains_key(&reply.review_id) {
+            return Ok(false);
+        }
+        state.review_replies.insert(reply.review_id, reply.clone());
+        Ok(true)
+    }
+
+    async fn refresh_tutor_rating(&self, tutor_id: Uuid) -> AppResult<()> {
+        let mut state = self.write();
+        let (rating_sum, total_reviews) = state.review_totals(tutor_id);
+
+        if let Some(tutor) = state.tutors.get_mut(&tutor_id) {
+            tutor.rating_sum = rating_sum;
+            tutor.total_reviews = total_reviews;
+            tutor.rating = average_rating(rating_sum, total_reviews);
+            tutor.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn list_rating_drift(&self) -> AppResult<Vec<RatingDrift>> {
+        let state = self.read();
+        let mut drift: Vec<RatingDrift> = state.tutors.values()
+            .filter_map(|tutor| {
+                let (rating_sum, total_reviews) = state.review_totals(tutor.id);
+                let actual_rating = average_rating(rating_sum, total_reviews);
+                let in_sync = tutor.rating_sum == rating_sum
+                    && tutor.total_reviews == total_reviews
+                    && tutor.rating == actual_rating;
+                (!in_sync).then_some(RatingDrift {
+                    tutor_id: tutor.id,
+                    stored_rating: tutor.rating,
+                    stored_total_reviews: tutor.total_reviews,
+                    actual_rating,
+                    actual_total_reviews: total_reviews,
+                })
+            })
+            .collect();
+        drift.sort_by_key(|row| row.tutor_id);
+        Ok(drift)
+    }
+}
+
+#[async_trait]
+impl AvailabilityRepository for InMemoryRepository {
+    async fn find_schedule(&self, tutor_id: Uuid) -> AppResult<Option<TutorSchedule>> {
+        Ok(self.read().schedules.get(&tutor_id).cloned())
+    }
+
+    async fn replace_schedule(&self, schedule: &TutorSchedule, windows: &[AvailabilityWindow]) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.tutors.contains_key(&schedule.tutor_id) {
+            return Err(constraint_violation("tutor_schedules.tutor_id must reference a tutor"));
+        }
+
+        let created_at = state.schedules.get(&schedule.tutor_id)
+            .map(|existing| existing.created_at)
+            .unwrap_or(schedule.created_at);
+        state.schedules.insert(schedule.tutor_id, TutorSchedule { created_at, ..schedule.clone() });
+        state.windows.retain(|_, window| window.tutor_id != schedule.tutor_id);
+        for window in windows {
+            state.windows.insert(window.id, window.clone());
+        }
+        Ok(())
+    }
+
+    async fn list_windows(&self, tutor_id: Uuid) -> AppResult<Vec<AvailabilityWindow>> {
+        let mut windows: Vec<AvailabilityWindow> = self.read().windows.values()
+            .filter(|window| window.tutor_id == tutor_id)
+            .cloned()
+            .collect();
+        windows.sort_by_key(|window| (window.weekday, window.start_time));
+        Ok(windows)
+    }
+
+    async fn list_exceptions(&self, tutor_id: Uuid, from: NaiveDate, to: NaiveDate) -> AppResult<Vec<AvailabilityException>> {
+        let mut exceptions: Vec<AvailabilityException> = self.read().exceptions.values()
+            .filter(|exception| exception.tutor_id == tutor_id && exception.date >= from && exception.date <= to)
+            .cloned()
+            .collect();
+        exceptions.sort_by_key(|exception| (exception.date, exception.start_time));
+        Ok(exceptions)
+    }
+
+    async fn insert_exception(&self, exception: &AvailabilityException) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.schedules.contains_key(&exception.tutor_id) {
+            return Err(constraint_violation("availability_exceptions.tutor_id must reference a schedule"));
+        }
+        state.exceptions.insert(exception.id, exception.clone());
+        Ok(())
+    }
+
+    async fn delete_exception(&self, tutor_id: Uuid, exception_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        match state.exceptions.get(&exception_id) {
+            Some(exception) if exception.tutor_id == tutor_id => {
+                state.exceptions.remove(&exception_id);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn list_blackouts(&self, tutor_id: Uuid, from: NaiveDate, to: NaiveDate) -> AppResult<Vec<AvailabilityBlackout>> {
+        let mut blackouts: Vec<AvailabilityBlackout> = self.read().blackouts.values()
+            .filter(|blackout| blackout.tutor_id == tutor_id && blackout.start_date <= to && blackout.end_date >= from)
+            .cloned()
+            .collect();
+        blackouts.sort_by_key(|blackout| blackout.start_date);
+        Ok(blackouts)
+    }
+
+    async fn insert_blackout(&self, blackout: &AvailabilityBlackout) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.schedules.contains_key(&blackout.tutor_id) {
+            return Err(constraint_violation("availability_blackouts.tutor_id must reference a schedule"));
+        }
+        state.blackouts.insert(blackout.id, blackout.clone());
+        Ok(())
+    }
+
+    async fn delete_blackout(&self, tutor_id: Uuid, blackout_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        match state.blackouts.get(&blackout_id) {
+            Some(blackout) if blackout.tutor_id == tutor_id => {
+                state.blackouts.remove(&blackout_id);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+#[async_trait]
+impl BookingRepository for InMemoryRepository {
+    async fn find_booking(&self, booking_id: Uuid) -> AppResult<Option<BookingWithDetails>> {
+        let state = self.read();
+        Ok(state.bookings.get(&booking_id).and_then(|booking| state.booking_with_details(booking)))
+    }
+
+    async fn insert_booking(&self, booking: &Booking) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.courses.contains_key(&booking.course_id) || !state.users.contains_key(&booking.student_id) {
+            return Err(constraint_violation("bookings must reference a course and a student"));
+        }
+        state.check_booking_overlap(booking)?;
+        state.bookings.insert(booking.id, booking.clone());
+        Ok(())
+    }
+
+    async fn update_booking(&self, booking: &Booking, expected_updated_at: DateTime<Utc>) -> AppResult<bool> {
+        let mut state = self.write();
+        if state.bookings.get(&booking.id).map(|existing| existing.updated_at) != Some(expected_updated_at) {
+            return Ok(false);
+        }
+        state.check_booking_overlap(booking)?;
+        let existing = state.bookings.get_mut(&booking.id).unwrap();
+        existing.starts_at = booking.starts_at;
+        existing.ends_at = booking.ends_at;
+        existing.status = booking.status;
+        existing.status_reason = booking.status_reason.clone();
+        existing.cancellation_fee = booking.cancellation_fee;
+        existing.reschedule_count = booking.reschedule_count;
+        existing.updated_at = booking.updated_at;
+        Ok(true)
+    }
+
+    async fn list_bookings_for_student(&self, student_id: Uuid, status: Option<BookingStatus>) -> AppResult<Vec<BookingWithDetails>> {
+        Ok(self.read().sorted_bookings(|booking| {
+            booking.student_id == student_id && status.is_none_or(|status| booking.status == status)
+        }))
+    }
+
+    async fn list_bookings_for_tutor(&self, tutor_id: Uuid, status: Option<BookingStatus>) -> AppResult<Vec<BookingWithDetails>> {
+        Ok(self.read().sorted_bookings(|booking| {
+            booking.tutor_id == tutor_id && status.is_none_or(|status| booking.status == status)
+        }))
+    }
+
+    async fn list_busy_intervals(&self, tutor_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> AppResult<Vec<(DateTime<Utc>, DateTime<Utc>)>> {
+        let mut intervals: Vec<(DateTime<Utc>, DateTime<Utc>)> = self.read().bookings.values()
+            .filter(|booking| {
+                booking.tutor_id == tutor_id
+                    && booking.status.is_active()
+                    && booking.starts_at < to
+                    && booking.ends_at > from
+            })
+            .map(|booking| (booking.starts_at, booking.ends_at))
+            .collect();
+        intervals.sort();
+        Ok(intervals)
+    }
+
+    async fn find_booking_policy(&self, tutor_id: Uuid) -> AppResult<Option<BookingPolicy>> {
+        Ok(self.read().booking_policies.get(&tutor_id).cloned())
+    }
+
+    async fn upsert_booking_policy(&self, policy: &BookingPolicy) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.tutors.contains_key(&policy.tutor_id) {
+            return Err(constraint_violation("booking_policies.tutor_id must reference a tutor"));
+        }
+
+        let created_at = state.booking_policies.get(&policy.tutor_id)
+            .map(|existing| existing.created_at)
+            .unwrap_or(policy.created_at);
+        state.booking_policies.insert(policy.tutor_id, BookingPolicy { created_at, ..policy.clone() });
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EnrollmentRepository for InMemoryRepository {
+    async fn find_enrollment(&self, course_id: Uuid, student_id: Uuid) -> AppResult<Option<EnrollmentWithDetails>> {
+        let state = self.read();
+        Ok(state.enrollments.values()
+            .find(|enrollment| enrollment.course_id == course_id && enrollment.student_id == student_id)
+            .and_then(|enrollment| state.enrollment_with_details(enrollment)))
+    }
+
+    async fn enroll_student(&self, course_id: Uuid, student_id: Uuid, now: DateTime<Utc>) -> AppResult<Enrollment> {
+        let mut state = self.write();
+        let course = state.courses.get(&course_id)
+            .filter(|course| course.is_active && state.course_with_tutor(course).is_some())
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?;
+        if !state.users.contains_key(&student_id) {
+            return Err(constraint_violation("enrollments.student_id must reference a user"));
+        }
+
+        let status = match course.max_students {
+            Some(max) if state.count_active_enrollments(course_id) >= i64::from(max) => EnrollmentStatus::Waitlisted,
+            _ => EnrollmentStatus::Active,
+        };
+        let enrolled_at = (status == EnrollmentStatus::Active).then_some(now);
+
+        let existing = state.enrollments.values()
+            .find(|enrollment| enrollment.course_id == course_id && enrollment.student_id == student_id)
+            .cloned();
+        let enrollment = match existing {
+            Some(existing) if existing.status != EnrollmentStatus::Dropped => {
+                return Err(AppError::Conflict("Student is already enrolled in this course".to_string()));
+            }
+            Some(existing) => Enrollment {
+                status,
+                requested_at: now,
+                enrolled_at,
+                dropped_at: None,
+                updated_at: now,
+                ..existing
+            },
+            None => Enrollment {
+                id: Uuid::new_v4(),
+                course_id,
+                student_id,
+                status,
+                requested_at: now,
+                enrolled_at,
+                dropped_at: None,
+                created_at: now,
+                updated_at: now,
+            },
+        };
+
+        state.enrollments.insert(enrollment.id, enrollment.clone());
+        Ok(enrollment)
+    }
+
+    async fn withdraw_enrollment(&self, enrollment_id: Uuid, now: DateTime<Utc>) -> AppResult<Vec<Enrollment>> {
+        let mut state = self.write();
+        let Some(enrollment) = state.enrollments.get_mut(&enrollment_id) else {
+            return Ok(Vec::new());
+        };
+        if enrollment.status != EnrollmentStatus::Dropped {
+            enrollment.status = EnrollmentStatus::Dropped;
+            enrollment.dropped_at = Some(now);
+            enrollment.updated_at = now;
+        }
+        let course_id = enrollment.course_id;
+        Ok(state.promote_waitlisted(course_id, now))
+    }
+
+    async fn promote_waitlisted(&self, course_id: Uuid, now: DateTime<Utc>) -> AppResult<Vec<Enrollment>> {
+        Ok(self.write().promote_waitlisted(course_id, now))
+    }
+
+    async fn list_enrollments_for_student(&self, student_id: Uuid, status: Option<EnrollmentStatus>) -> AppResult<Vec<EnrollmentWithDetails>> {
+        let state = self.read();
+        let mut enrollments: Vec<EnrollmentWithDetails> = state.enrollments.values()
+            .filter(|enrollment| enrollment.student_id == student_id && status.is_none_or(|s| enrollment.status == s))
+            .filter_map(|enrollment| state.enrollment_with_details(enrollment))
+            .collect();
+        enrollments.sort_by_key(|row| Reverse(row.enrollment.requested_at));
+        Ok(enrollments)
+    }
+
+    async fn list_enrollments_for_course(&self, course_id: Uuid, status: Option<EnrollmentStatus>) -> AppResult<Vec<EnrollmentWithDetails>> {
+        let state = self.read();
+        let mut enrollments: Vec<EnrollmentWithDetails> = state.enrollments.values()
+            .filter(|enrollment| enrollment.course_id == course_id && status.is_none_or(|s| enrollment.status == s))
+            .filter_map(|enrollment| state.enrollment_with_details(enrollment))
+            .collect();
+        enrollments.sort_by_key(|row| (row.enrollment.requested_at, row.enrollment.id));
+        Ok(enrollments)
+    }
+
+    async fn count_active_enrollments(&self, course_id: Uuid) -> AppResult<i64> {
+        Ok(self.read().count_active_enrollments(course_id))
+    }
+}
+
+#[async_trait]
+impl CurriculumRepository for InMemoryRepository {
+    async fn find_module(&self, module_id: Uuid) -> AppResult<Option<CourseModule>> {
+        Ok(self.read().modules.get(&module_id).cloned())
+    }
+
+    async fn find_lesson(&self, lesson_id: Uuid) -> AppResult<Option<Lesson>> {
+        Ok(self.read().lessons.get(&lesson_id).cloned())
+    }
+
+    async fn list_modules(&self, course_id: Uuid) -> AppResult<Vec<CourseModule>> {
+        Ok(self.read().sorted_modules(course_id))
+    }
+
+    async fn list_lessons(&self, course_id: Uuid) -> AppResult<Vec<Lesson>> {
+        let state = self.read();
+        let mut lessons: Vec<Lesson> = state.lessons.values()
+            .filter(|lesson| lesson.course_id == course_id)
+            .cloned()
+            .collect();
+        lessons.sort_by_key(|lesson| {
+            let module_position = state.modules.get(&lesson.module_id).map(|module| module.position);
+            (module_position, lesson.position)
+        });
+        Ok(lessons)
+    }
+
+    async fn append_module(&self, module: &CourseModule) -> AppResult<CourseModule> {
+        let mut state = self.write();
+        if !state.courses.contains_key(&module.course_id) {
+            return Err(AppError::NotFound("Course not found".to_string()));
+        }
+
+        let position = state.modules.values().filter(|other| other.course_id == module.course_id).count() as i32 + 1;
+        let module = CourseModule { position, ..module.clone() };
+        state.modules.insert(module.id, module.clone());
+        Ok(module)
+    }
+
+    async fn update_module(&self, module: &CourseModule) -> AppResult<()> {
+        if let Some(existing) = self.write().modules.get_mut(&module.id) {
+            existing.title = module.title.clone();
+            existing.updated_at = module.updated_at;
+        }
+        Ok(())
+    }
+
+    async fn delete_module(&self, module_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        let Some(module) = state.modules.remove(&module_id) else {
+            return Ok(false);
+        };
+
+        // ON DELETE CASCADE, then close the gap
+        state.lessons.retain(|_, lesson| lesson.module_id != module_id);
+        state.remove_orphaned_lesson_rows();
+        for other in state.modules.values_mut() {
+            if other.course_id == module.course_id && other.position > module.position {
+                other.position -= 1;
+            }
+        }
+        Ok(true)
+    }
+
+    async fn append_lesson(&self, lesson: &Lesson) -> AppResult<Lesson> {
+        let mut state = self.write();
+        if state.modules.get(&lesson.module_id).is_none_or(|module| module.course_id != lesson.course_id) {
+            return Err(constraint_violation("lessons.module_id must reference a module of the same course"));
+        }
+
+        let position = state.lessons.values().filter(|other| other.module_id == lesson.module_id).count() as i32 + 1;
+        let lesson = Lesson { position, ..lesson.clone() };
+        state.lessons.insert(lesson.id, lesson.clone());
+        Ok(lesson)
+    }
+
+    async fn update_lesson(&self, lesson: &Lesson) -> AppResult<()> {
+        if let Some(existing) = self.write().lessons.get_mut(&lesson.id) {
+            *existing = Lesson {
+                module_id: existing.module_id,
+                position: existing.position,
+                created_at: existing.created_at,
+                ..lesson.clone()
+            };
+        }
+        Ok(())
+    }
+
+    async fn delete_lesson(&self, lesson_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        let Some(lesson) = state.lessons.remove(&lesson_id) else {
+            return Ok(false);
+        };
+        state.remove_orphaned_lesson_rows();
+
+        for other in state.lessons.values_mut() {
+            if other.module_id == lesson.module_id && other.position > lesson.position {
+                other.position -= 1;
+            }
+        }
+        Ok(true)
+    }
+
+    async fn reorder_curriculum(&self, course_id: Uuid, order: &[ModuleOrder]) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.courses.contains_key(&course_id) {
+            return Err(AppError::NotFound("Course not found".to_string()));
+        }
+
+        let module_ids: Vec<Uuid> = state.sorted_modules(course_id).iter().map(|module| module.id).collect();
+        let lesson_ids: Vec<Uuid> = state.lessons.values()
+            .filter(|lesson| lesson.course_id == course_id)
+            .map(|lesson| lesson.id)
+            .collect();
+        check_complete_order(order, &module_ids, &lesson_ids)?;
+
+        for (index, entry) in order.iter().enumerate() {
+            if let Some(module) = state.modules.get_mut(&entry.id) {
+                module.position = index as i32 + 1;
+            }
+            for (index, lesson_id) in entry.lessons.iter().enumerate() {
+                if let Some(lesson) = state.lessons.get_mut(lesson_id) {
+                    lesson.module_id = entry.id;
+                    lesson.position = index as i32 + 1;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ProgressRepository for InMemoryRepository {
+    async fn record_lesson_completion(&self, completion: &LessonCompletion) -> AppResult<LessonCompletion> {
+        let mut state = self.write();
+        if state.lessons.get(&completion.lesson_id).is_none_or(|lesson| lesson.course_id != completion.course_id) {
+            return Err(constraint_violation("lesson_completions.lesson_id must reference a lesson of the course"));
+        }
+
+        let existing = state.lesson_completions.values_mut()
+            .find(|other| other.lesson_id == completion.lesson_id && other.student_id == completion.student_id);
+        if let Some(existing) = existing {
+            existing.minutes_spent += completion.minutes_spent;
+            existing.updated_at = completion.updated_at;
+            return Ok(existing.clone());
+        }
+
+        state.lesson_completions.insert(completion.id, completion.clone());
+        Ok(completion.clone())
+    }
+
+    async fn delete_lesson_completion(&self, lesson_id: Uuid, student_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        let before = state.lesson_completions.len();
+        state.lesson_completions.retain(|_, c| !(c.lesson_id == lesson_id && c.student_id == student_id));
+        Ok(state.lesson_completions.len() < before)
+    }
+
+    async fn list_lesson_completions(&self, course_id: Uuid, student_id: Uuid) -> AppResult<Vec<LessonCompletion>> {
+        Ok(self.read().lesson_completions.values()
+            .filter(|c| c.course_id == course_id && c.student_id == student_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_student_progress(&self, student_id: Uuid) -> AppResult<Vec<CourseProgress>> {
+        let state = self.read();
+        let mut progress: Vec<CourseProgress> = state.enrollments.values()
+            .filter(|e| e.student_id == student_id && e.status == EnrollmentStatus::Active)
+            .filter_map(|e| state.course_progress(e))
+            .collect();
+        progress.sort_by_key(|row| Reverse(row.enrolled_at));
+        Ok(progress)
+    }
+
+    async fn list_course_progress(&self, course_id: Uuid) -> AppResult<Vec<CourseProgress>> {
+        let state = self.read();
+        let mut enrollments: Vec<&Enrollment> = state.enrollments.values()
+            .filter(|e| e.course_id == course_id && e.status == EnrollmentStatus::Active)
+            .collect();
+        enrollments.sort_by_key(|e| (e.enrolled_at, e.id));
+        Ok(enrollments.into_iter().filter_map(|e| state.course_progress(e)).collect())
+    }
+}
+
+#[async_trait]
+impl QuizRepository for InMemoryRepository {
+    async fn find_quiz(&self, quiz_id: Uuid) -> AppResult<Option<Quiz>> {
+        Ok(self.read().quizzes.get(&quiz_id).cloned())
+    }
+
+    async fn list_quizzes_for_lesson(&self, lesson_id: Uuid) -> AppResult<Vec<Quiz>> {
+        let mut quizzes: Vec<Quiz> = self.read().quizzes.values()
+            .filter(|quiz| quiz.lesson_id == lesson_id)
+            .cloned()
+            .collect();
+        quizzes.sort_by_key(|quiz| (quiz.created_at, quiz.id));
+        Ok(quizzes)
+    }
+
+    async fn insert_quiz(&self, quiz: &Quiz) -> AppResult<()> {
+        let mut state = self.write();
+        if state.lessons.get(&quiz.lesson_id).is_none_or(|lesson| lesson.course_id != quiz.course_id) {
+            return Err(constraint_violation("quizzes.lesson_id must reference a lesson of the course"));
+        }
+        state.quizzes.insert(quiz.id, quiz.clone());
+        Ok(())
+    }
+
+    async fn update_quiz(&self, quiz: &Quiz) -> AppResult<()> {
+        if let Some(existing) = self.write().quizzes.get_mut(&quiz.id) {
+            *existing = Quiz { created_at: existing.created_at, ..quiz.clone() };
+        }
+        Ok(())
+    }
+
+    async fn delete_quiz(&self, quiz_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        let deleted = state.quizzes.remove(&quiz_id).is_some();
+        state.quiz_attempts.retain(|_, attempt| attempt.quiz_id != quiz_id);
+        Ok(deleted)
+    }
+
+    async fn find_quiz_attempt(&self, attempt_id: Uuid) -> AppResult<Option<QuizAttempt>> {
+        Ok(self.read().quiz_attempts.get(&attempt_id).cloned())
+    }
+
+    async fn list_quiz_attempts(&self, quiz_id: Uuid, student_id: Option<Uuid>) -> AppResult<Vec<QuizAttempt>> {
+        let mut attempts: Vec<QuizAttempt> = self.read().quiz_attempts.values()
+            .filter(|attempt| attempt.quiz_id == quiz_id && student_id.is_none_or(|id| attempt.student_id == id))
+            .cloned()
+            .collect();
+        attempts.sort_by_key(|attempt| (attempt.started_at, attempt.attempt_number));
+        Ok(attempts)
+    }
+
+    async fn start_quiz_attempt(&self, attempt: &QuizAttempt, max_attempts: Option<i32>) -> AppResult<QuizAttempt> {
+        let mut state = self.write();
+        if !state.quizzes.contains_key(&attempt.quiz_id) {
+            return Err(AppError::NotFound("Quiz not found".to_string()));
+        }
+
+        let used = state.quiz_attempts.values()
+            .filter(|other| other.quiz_id == attempt.quiz_id && other.student_id == attempt.student_id)
+            .count() as i32;
+        if let Some(max) = max_attempts.filter(|max| used >= *max) {
+            return Err(AppError::Conflict(format!("All {} attempts at this quiz have been used", max)));
+        }
+
+        let attempt = QuizAttempt { attempt_number: used + 1, ..attempt.clone() };
+        state.quiz_attempts.insert(attempt.id, attempt.clone());
+        Ok(attempt)
+    }
+
+    async fn finish_quiz_attempt(&self, attempt: &QuizAttempt) -> AppResult<bool> {
+        let mut state = self.write();
+        match state.quiz_attempts.get_mut(&attempt.id) {
+            Some(existing) if existing.submitted_at.is_none() => {
+                existing.submitted_at = attempt.submitted_at;
+                existing.timed_out = attempt.timed_out;
+                existing.score = attempt.score;
+                existing.max_score = attempt.max_score;
+                existing.answers = attempt.answers.clone();
+                existing.results = attempt.results.clone();
+                existing.updated_at = attempt.updated_at;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+#[async_trait]
+impl AssignmentRepository for InMemoryRepository {
+    async fn find_assignment(&self, assignment_id: Uuid) -> AppResult<Option<Assignment>> {
+        Ok(self.read().assignments.get(&assignment_id).cloned())
+    }
+
+    async fn list_assignments_for_course(&self, course_id: Uuid) -> AppResult<Vec<Assignment>> {
+        let mut assignments: Vec<Assignment> = self.read().assignments.values()
+            .filter(|assignment| assignment.course_id == course_id)
+            .cloned()
+            .collect();
+        assignments.sort_by_key(|assignment| (assignment.due_at, assignment.id));
+        Ok(assignments)
+    }
+
+    async fn insert_assignment(&self, assignment: &Assignment) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.courses.contains_key(&assignment.course_id) {
+            return Err(constraint_violation("assignments.course_id must reference a course"));
+        }
+        state.assignments.insert(assignment.id, assignment.clone());
+        Ok(())
+    }
+
+    async fn update_assignment(&self, assignment: &Assignment) -> AppResult<()> {
+        if let Some(existing) = self.write().assignments.get_mut(&assignment.id) {
+            *existing = Assignment { created_at: existing.created_at, ..assignment.clone() };
+        }
+        Ok(())
+    }
+
+    async fn delete_assignment(&self, assignment_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        let deleted = state.assignments.remove(&assignment_id).is_some();
+        state.remove_orphaned_assignment_rows();
+        Ok(deleted)
+    }
+
+    async fn insert_assignment_file(&self, file: &AssignmentFile) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.assignments.contains_key(&file.assignment_id) {
+            return Err(constraint_violation("assignment_files.assignment_id must reference an assignment"));
+        }
+        if file.stored_file_id.is_some_and(|id| !state.stored_files.contains_key(&id)) {
+            return Err(constraint_violation("assignment_files.stored_file_id must reference a stored file"));
+        }
+        state.assignment_files.insert(file.id, file.clone());
+        Ok(())
+    }
+
+    async fn find_assignment_file(&self, file_id: Uuid) -> AppResult<Option<AssignmentFile>> {
+        Ok(self.read().assignment_files.get(&file_id).cloned())
+    }
+
+    // Every file here was uploaded to the file store
+    async fn read_assignment_file(&self, _file_id: Uuid) -> AppResult<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    async fn list_files_for_assignment(&self, assignment_id: Uuid) -> AppResult<Vec<AssignmentFile>> {
+        let mut files: Vec<AssignmentFile> = self.read().assignment_files.values()
+            .filter(|file| file.assignment_id == assignment_id)
+            .cloned()
+            .collect();
+        files.sort_by_key(|file| (file.created_at, file.id));
+        Ok(files)
+    }
+
+    async fn list_assignment_files(&self, file_ids: &[Uuid]) -> AppResult<Vec<AssignmentFile>> {
+        let state = self.read();
+        let mut files: Vec<AssignmentFile> = file_ids.iter()
+            .filter_map(|id| state.assignment_files.get(id).cloned())
+            .collect();
+        files.sort_by_key(|file| (file.created_at, file.id));
+        files.dedup_by_key(|file| file.id);
+        Ok(files)
+    }
+
+    async fn find_submission(&self, submission_id: Uuid) -> AppResult<Option<AssignmentSubmission>> {
+        Ok(self.read().submissions.get(&submission_id).cloned())
+    }
+
+    async fn list_submissions(&self, assignment_id: Uuid, student_id: Option<Uuid>) -> AppResult<Vec<AssignmentSubmission>> {
+        let mut submissions: Vec<AssignmentSubmission> = self.read().submissions.values()
+            .filter(|submission| {
+                submission.assignment_id == assignment_id && student_id.is_none_or(|id| submission.student_id == id)
+            })
+            .cloned()
+            .collect();
+        submissions.sort_by_key(|submission| (submission.submitted_at, submission.submission_number));
+        Ok(submissions)
+    }
+
+    async fn insert_submission(
+        &self,
+        submission: &AssignmentSubmission,
+        max_submissions: Option<i32>,
+    ) -> AppResult<AssignmentSubmission> {
+        let mut state = self.write();
+        if !state.assignments.contains_key(&submission.assignment_id) {
+            return Err(AppError::NotFound("Assignment not found".to_string()));
+        }
+
+        let used = state.submissions.values()
+            .filter(|other| other.assignment_id == submission.assignment_id && other.student_id == submission.student_id)
+            .count() as i32;
+        if let Some(max) = max_submissions.filter(|max| used >= *max) {
+            return Err(AppError::Conflict(format!("All {} submissions for this assignment have been used", max)));
+        }
+
+        let submission = AssignmentSubmission { submission_number: used + 1, ..submission.clone() };
+        state.submissions.insert(submission.id, submission.clone());
+        Ok(submission)
+    }
+
+    async fn update_submission_grade(&self, submission: &AssignmentSubmission) -> AppResult<()> {
+        if let Some(existing) = self.write().submissions.get_mut(&submission.id) {
+            existing.score = submission.score;
+            existing.max_score = submission.max_score;
+            existing.rubric_scores = submission.rubric_scores.clone();
+            existing.feedback = submission.feedback.clone();
+            existing.graded_at = submission.graded_at;
+            existing.grade_published_at = submission.grade_published_at;
+            existing.updated_at = submission.updated_at;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for InMemoryRepository {
+    async fn insert_notification(&self, notification: &Notification) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.users.contains_key(&notification.user_id) {
+            return Err(constraint_violation("notifications.user_id must reference a user"));
+        }
+        state.notifications.insert(notification.id, notification.clone());
+        Ok(())
+    }
+
+    async fn list_notifications(&self, user_id: Uuid, unread_only: bool) -> AppResult<Vec<Notification>> {
+        let mut notifications: Vec<Notification> = self.read().notifications.values()
+            .filter(|notification| notification.user_id == user_id && !(unread_only && notification.read_at.is_some()))
+            .cloned()
+            .collect();
+        notifications.sort_by_key(|notification| Reverse((notification.created_at, notification.id)));
+        Ok(notifications)
+    }
+
+    async fn mark_notification_read(&self, notification_id: Uuid, user_id: Uuid, read_at: DateTime<Utc>) -> AppResult<bool> {
+        match self.write().notifications.get_mut(&notification_id) {
+            Some(notification) if notification.user_id == user_id => {
+                notification.read_at.get_or_insert(read_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+#[async_trait]
+impl CertificateRepository for InMemoryRepository {
+    async fn find_certificate_by_code(&self, code: &str) -> AppResult<Option<Certificate>> {
+        Ok(self.read().certificates.values().find(|certificate| certificate.code == code).cloned())
+    }
+
+    async fn find_certificate(&self, course_id: Uuid, student_id: Uuid) -> AppResult<Option<Certificate>> {
+        Ok(self.read().certificates.values()
+            .find(|certificate| certificate.course_id == Some(course_id) && certificate.student_id == student_id)
+            .cloned())
+    }
+
+    async fn list_certificates_for_student(&self, student_id: Uuid) -> AppResult<Vec<Certificate>> {
+        let mut certificates: Vec<Certificate> = self.read().certificates.values()
+            .filter(|certificate| certificate.student_id == student_id)
+            .cloned()
+            .collect();
+        certificates.sort_by_key(|certificate| Reverse((certificate.issued_at, certificate.id)));
+        Ok(certificates)
+    }
+
+    async fn insert_certificate(&self, certificate: &Certificate) -> AppResult<Certificate> {
+        let mut state = self.write();
+        if !state.users.contains_key(&certificate.student_id) {
+            return Err(constraint_violation("certificates.student_id must reference a user"));
+        }
+        if state.certificates.values().any(|other| other.code == certificate.code) {
+            return Err(constraint_violation("certificates.code must be unique"));
+        }
+
+        let existing = state.certificates.values().find(|other| {
+            certificate.course_id.is_some()
+                && other.course_id == certificate.course_id
+                && other.student_id == certificate.student_id
+        });
+        if let Some(existing) = existing {
+            return Ok(existing.clone());
+        }
+
+        state.certificates.insert(certificate.id, certificate.clone());
+        Ok(certificate.clone())
+    }
+}
+
+#[async_trait]
+impl FileRepository for InMemoryRepository {
+    async fn insert_stored_file(&self, file: &StoredFile) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.users.contains_key(&file.owner_id) {
+            return Err(constraint_violation("stored_files.owner_id must reference a user"));
+        }
+        state.stored_files.insert(file.id, file.clone());
+        Ok(())
+    }
+
+    async fn find_stored_file(&self, file_id: Uuid) -> AppResult<Option<StoredFile>> {
+        Ok(self.read().stored_files.get(&file_id).cloned())
+    }
+
+    async fn list_stored_files(&self, owner_id: Uuid) -> AppResult<Vec<StoredFile>> {
+        let mut files: Vec<StoredFile> = self.read().stored_files.values()
+            .filter(|file| file.owner_id == owner_id && file.purpose == FilePurpose::Upload)
+            .cloned()
+            .collect();
+        files.sort_by_key(|file| Reverse((file.created_at, file.id)));
+        Ok(files)
+    }
+
+    async fn delete_stored_file(&self, file_id: Uuid) -> AppResult<bool> {
+        let mut state = self.write();
+        let deleted = state.stored_files.remove(&file_id).is_some();
+        state.assignment_files.retain(|_, file| file.stored_file_id != Some(file_id));
+        Ok(deleted)
+    }
+
+    async fn count_stored_files_with_hash(&self, sha256: &str) -> AppResult<i64> {
+        Ok(self.read().stored_files.values().filter(|file| file.sha256 == sha256).count() as i64)
+    }
+}
+
+#[async_trait]
+impl AvatarRepository for InMemoryRepository {
+    async fn find_avatar(&self, user_id: Uuid) -> AppResult<Option<Avatar>> {
+        Ok(self.read().avatars.get(&user_id).cloned())
+    }
+
+    async fn set_avatar(&self, avatar: &Avatar) -> AppResult<Option<Avatar>> {
+        let mut state = self.write();
+        if avatar.file_ids().iter().any(|file_id| !state.stored_files.contains_key(file_id)) {
+            return Err(constraint_violation("user_avatars file ids must reference stored files"));
+        }
+        let Some(user) = state.users.get_mut(&avatar.user_id) else {
+            return Err(constraint_violation("user_avatars.user_id must reference a user"));
+        };
+        user.avatar_updated_at = Some(avatar.updated_at);
+        Ok(state.avatars.insert(avatar.user_id, avatar.clone()))
+    }
+
+    async fn delete_avatar(&self, user_id: Uuid) -> AppResult<Option<Avatar>> {
+        let mut state = self.write();
+        if let Some(user) = state.users.get_mut(&user_id) {
+            user.avatar_updated_at = None;
+        }
+        Ok(state.avatars.remove(&user_id))
+    }
+}
+
+#[async_trait]
+impl VerificationRepository for InMemoryRepository {
+    async fn insert_verification_request(
+        &self,
+        request: &VerificationRequest,
+        documents: &[VerificationDocument],
+        event: &VerificationEvent,
+    ) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.tutors.contains_key(&request.tutor_id) {
+            return Err(constraint_violation("verification_requests.tutor_id must reference a tutor"));
+        }
+        if request.status == VerificationStatus::Pending
+            && state.verification_requests.values().any(|other| {
+                other.tutor_id == request.tutor_id && other.status == VerificationStatus::Pending
+            })
+        {
+            return Err(constraint_violation("verification_requests allow one pending request per tutor"));
+        }
+        if documents.iter().any(|document| !state.stored_files.contains_key(&document.file_id)) {
+            return Err(constraint_violation("verification_documents.file_id must reference a stored file"));
+        }
+
+        for document in documents {
+            if let Some(file) = state.stored_files.get_mut(&document.file_id) {
+                file.purpose = FilePurpose::Credential;
+            }
+            state.verification_documents.insert(document.id, document.clone());
+        }
+        state.verification_requests.insert(request.id, request.clone());
+        state.verification_events.push(event.clone());
+        Ok(())
+    }
+
+    async fn find_verification_request(&self, request_id: Uuid) -> AppResult<Option<VerificationRequestWithTutor>> {
+        let state = self.read();
+        Ok(state.verification_requests.get(&request_id).and_then(|request| state.verification_request_with_tutor(request)))
+    }
+
+    async fn list_verification_requests(&self, status: VerificationStatus) -> AppResult<Vec<VerificationRequestWithTutor>> {
+        let state = self.read();
+        let mut requests: Vec<VerificationRequestWithTutor> = state.verification_requests.values()
+            .filter(|request| request.status == status)
+            .filter_map(|request| state.verification_request_with_tutor(request))
+            .collect();
+        requests.sort_by_key(|row| (row.request.submitted_at, row.request.id));
+        Ok(requests)
+    }
+
+    async fn list_tutor_verification_requests(&self, tutor_id: Uuid) -> AppResult<Vec<VerificationRequestWithTutor>> {
+        let state = self.read();
+        let mut requests: Vec<VerificationRequestWithTutor> = state.verification_requests.values()
+            .filter(|request| request.tutor_id == tutor_id)
+            .filter_map(|request| state.verification_request_with_tutor(request))
+            .collect();
+        requests.sort_by_key(|row| Reverse((row.request.submitted_at, row.request.id)));
+        Ok(requests)
+    }
+
+    async fn list_verification_documents(&self, request_id: Uuid) -> AppResult<Vec<VerificationDocument>> {
+        let mut documents: Vec<VerificationDocument> = self.read().verification_documents.values()
+            .filter(|document| document.request_id == request_id)
+            .cloned()
+            .collect();
+        documents.sort_by_key(|document| document.position);
+        Ok(documents)
+    }
+
+    async fn decide_verification_request(&self, request: &VerificationRequest, event: &VerificationEvent) -> AppResult<bool> {
+        let mut state = self.write();
+        let Some(existing) = state.verification_requests.get_mut(&request.id) else {
+            return Ok(false);
+        };
+        if existing.status != VerificationStatus::Pending {
+            return Ok(false);
+        }
+
+        existing.status = request.status;
+        existing.reviewed_by = request.reviewed_by;
+        existing.reviewed_at = request.reviewed_at;
+        existing.decision_reason = request.decision_reason.clone();
+        if request.status == VerificationStatus::Approved {
+            if let Some(tutor) = state.tutors.get_mut(&request.tutor_id) {
+                tutor.is_verified = true;
+                tutor.updated_at = event.created_at;
+            }
+        }
+        state.verification_events.push(event.clone());
+        Ok(true)
+    }
+
+    async fn set_tutor_verified(&self, tutor_id: Uuid, is_verified: bool, event: &VerificationEvent) -> AppResult<()> {
+        let mut state = self.write();
+        let Some(tutor) = state.tutors.get_mut(&tutor_id) else {
+            return Err(constraint_violation("verification_events.tutor_id must reference a tutor"));
+        };
+        tutor.is_verified = is_verified;
+        tutor.updated_at = event.created_at;
+        state.verification_events.push(event.clone());
+        Ok(())
+    }
+
+    async fn list_verification_events(&self, tutor_id: Uuid) -> AppResult<Vec<VerificationEvent>> {
+        let mut events: Vec<VerificationEvent> = self.read().verification_events.iter()
+            .filter(|event| event.tutor_id == tutor_id)
+            .cloned()
+            .collect();
+        events.sort_by_key(|event| event.created_at);
+        Ok(events)
+    }
+}
+
+#[async_trait]
+impl CalendarRepository for InMemoryRepository {
+    async fn find_calendar_feed(&self, user_id: Uuid) -> AppResult<Option<CalendarFeed>> {
+        Ok(self.read().calendar_feeds.get(&user_id).cloned())
+    }
+
+    async fn find_calendar_feed_by_token(&self, token: &str) -> AppResult<Option<CalendarFeed>> {
+        Ok(self.read().calendar_feeds.values().find(|feed| feed.token == token).cloned())
+    }
+
+    async fn upsert_calendar_feed(&self, feed: &CalendarFeed) -> AppResult<()> {
+        let mut state = self.write();
+        if !state.users.contains_key(&feed.user_id) {
+            return Err(constraint_violation("calendar_feeds.user_id must reference a user"));
+        }
+        if state.calendar_feeds.values().any(|other| other.user_id != feed.user_id && other.token == feed.token) {
+            return Err(constraint_violation("calendar_feeds.token must be unique"));
+        }
+
+        let created_at = state.calendar_feeds.get(&feed.user_id)
+            .map(|existing| existing.created_at)
+            .unwrap_or(feed.created_at);
+        state.calendar_feeds.insert(feed.user_id, CalendarFeed { created_at, ..feed.clone() });
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StatsRepository for InMemoryRepository {
+    async fn platform_stats(&self) -> AppResult<PlatformStats> {
+        let state = self.read();
+        let count_role = |role: UserRole| state.users.values().filter(|user| user.role == role).count() as i64;
+        let ratings: Vec<i32> = state.reviews.values().map(|review| review.rating).collect();
+        let tutors: Vec<&TutorProfile> = state.tutors.values().filter(|tutor| tutor.deleted_at.is_none()).collect();
+        let courses: Vec<&Course> = state.courses.values().filter(|course| course.deleted_at.is_none()).collect();
+
+        Ok(PlatformStats {
+            total_users: state.users.len() as i64,
+            active_users: state.users.values().filter(|user| user.is_active).count() as i64,
+            students: count_role(UserRole::Student),
+            tutors: count_role(UserRole::Tutor),
+            admins: count_role(UserRole::Admin),
+            tutor_profiles: tutors.len() as i64,
+            verified_tutors: tutors.iter().filter(|tutor| tutor.is_verified).count() as i64,
+            available_tutors: tutors.iter().filter(|tutor| tutor.is_available).count() as i64,
+            courses: courses.len() as i64,
+            active_courses: courses.iter().filter(|course| course.is_active).count() as i64,
+            reviews: ratings.len() as i64,
+            average_rating: if ratings.is_empty() {
+                None
+            } else {
+                Some(ratings.iter().sum::<i32>() as f64 / ratings.len() as f64)
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl AuditRepository for InMemoryRepository {
+    async fn insert_audit_entry(&self, entry: &AuditEntry) -> AppResult<()> {
+        self.write().audit_log.push(entry.clone());
+        Ok(())
+    }
+
+    async fn search_audit_entries(&self, query: &AuditLogQuery, limit: i64, offset: i64) -> AppResult<(Vec<AuditEntry>, i64)> {
+        let state = self.read();
+        let mut entries: Vec<&AuditEntry> = state.audit_log.iter()
+            .filter(|entry| query.actor_id.is_none_or(|actor_id| entry.actor_id == Some(actor_id)))
+            .filter(|entry| query.target_type.as_ref().is_none_or(|target_type| &entry.target_type == target_type))
+            .filter(|entry| query.target_id.is_none_or(|target_id| entry.target_id == target_id))
+            .filter(|entry| query.from.is_none_or(|from| entry.created_at >= from))
+            .filter(|entry| query.to.is_none_or(|to| entry.created_at < to))
+            .collect();
+        entries.sort_by_key(|entry| (Reverse(entry.created_at), Reverse(entry.id)));
+
+        let total = entries.len() as i64;
+        let entries = entries.into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect();
+        Ok((entries, total))
+    }
+}
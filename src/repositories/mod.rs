@@ -0,0 +1,401 @@
+pub mod fixtures;
+pub mod memory;
+pub mod postgres;
+pub mod seed;
+
+pub use fixtures::FixtureSet;
+pub use memory::InMemoryRepository;
+pub use postgres::PgRepository;
+pub use seed::{SeedConfig, SeedScale};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+use std::collections::HashSet;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Assignment, AssignmentFile, AuditEntry, AuditLogQuery, Avatar, AssignmentSubmission, AvailabilityBlackout, AvailabilityException, AvailabilityWindow, Booking, BookingPolicy, BookingStatus,
+    BookingWithDetails, CalendarFeed, Certificate, Course, CourseModule, CourseProgress, CourseWithTutor, Enrollment, EnrollmentStatus,
+    EnrollmentWithDetails, Lesson, LessonCompletion, ModuleOrder, Notification, PlatformStats, Quiz, QuizAttempt, RatingDrift, ReviewEdit, ReviewReply, ReviewWithStudent, StoredFile,
+    TutorProfile, TutorReview, TutorSchedule, TutorWithUser, User, UserRole, VerificationDocument, VerificationEvent, VerificationRequest,
+    VerificationRequestWithTutor, VerificationStatus
+};
+
+#[async_trait]
+pub trait UserRepository {
+    async fn find_user_by_id(&self, user_id: Uuid) -> AppResult<Option<User>>;
+    async fn find_user_by_email(&self, email: &str) -> AppResult<Option<User>>;
+    async fn insert_user(&self, user: &User) -> AppResult<()>;
+    // Writes names, role and active flag. Returns false, writing nothing, when
+    // the stored user is no longer at `expected_updated_at`.
+    async fn update_user(&self, user: &User, expected_updated_at: DateTime<Utc>) -> AppResult<bool>;
+    // Users matching every given filter, newest first, with the total number
+    // of matches; `text` is matched case-insensitively against email and name
+    async fn search_users(
+        &self,
+        text: Option<&str>,
+        role: Option<UserRole>,
+        is_active: Option<bool>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<User>, i64)>;
+    // Bumps the token version so every issued token stops working; returns
+    // false when no such user exists
+    async fn revoke_sessions(&self, user_id: Uuid) -> AppResult<bool>;
+}
+
+// Lookups and listings skip deleted profiles, and courses skip deleted
+// courses as well as courses of deleted tutors
+#[async_trait]
+pub trait TutorRepository {
+    async fn find_tutor_by_id(&self, tutor_id: Uuid) -> AppResult<Option<TutorWithUser>>;
+    async fn find_tutor_by_user_id(&self, user_id: Uuid) -> AppResult<Option<TutorWithUser>>;
+    // Available tutors, verified ones first, then best rated
+    async fn list_available_tutors(&self) -> AppResult<Vec<TutorWithUser>>;
+    // Ordered like `list_available_tutors`
+    async fn search_tutors_by_specialization(&self, specialization: &str) -> AppResult<Vec<TutorWithUser>>;
+    async fn list_tutor_ids(&self) -> AppResult<Vec<Uuid>>;
+    async fn insert_tutor(&self, tutor: &TutorProfile) -> AppResult<()>;
+    // Writes the profile fields; ratings are owned by ReviewRepository and
+    // the verification flag by VerificationRepository. Returns false, writing
+    // nothing, when the stored profile is no longer at `expected_updated_at`.
+    async fn update_tutor(&self, tutor: &TutorProfile, expected_updated_at: DateTime<Utc>) -> AppResult<bool>;
+    // Marks the profile deleted; returns false when no such live profile exists
+    // or students are actively enrolled in any of its courses
+    async fn delete_tutor(&self, tutor_id: Uuid, deleted_at: DateTime<Utc>) -> AppResult<bool>;
+    async fn find_deleted_tutor_by_user_id(&self, user_id: Uuid) -> AppResult<Option<TutorProfile>>;
+    // Returns false when no such deleted profile exists
+    async fn restore_tutor(&self, tutor_id: Uuid) -> AppResult<bool>;
+    // Removes profiles deleted before the cutoff, with everything hanging off
+    // them; returns their ids
+    async fn purge_deleted_tutors(&self, deleted_before: DateTime<Utc>) -> AppResult<Vec<Uuid>>;
+}
+
+#[async_trait]
+pub trait CourseRepository {
+    async fn find_course_by_id(&self, course_id: Uuid) -> AppResult<Option<CourseWithTutor>>;
+    // Active courses, newest first
+    async fn list_active_courses(&self) -> AppResult<Vec<CourseWithTutor>>;
+    // All courses owned by the tutor profile of the given user, newest first
+    async fn list_courses_by_tutor_user(&self, user_id: Uuid) -> AppResult<Vec<CourseWithTutor>>;
+    async fn insert_course(&self, course: &Course) -> AppResult<()>;
+    // Writes everything but the takedown, which only admins change. Returns
+    // false, writing nothing, when the stored course is no longer at
+    // `expected_updated_at`.
+    async fn update_course(&self, course: &Course, expected_updated_at: DateTime<Utc>) -> AppResult<bool>;
+    // Takes the course down, deactivating it, or reinstates it when
+    // `taken_down_at` is None; returns false when no such course exists
+    async fn set_course_taken_down(&self, course_id: Uuid, taken_down_at: Option<DateTime<Utc>>) -> AppResult<bool>;
+    // Marks the course deleted; returns false when no such live course exists
+    // or students are actively enrolled in it
+    async fn delete_course(&self, course_id: Uuid, deleted_at: DateTime<Utc>) -> AppResult<bool>;
+    // Deleted courses of the given tutor profile, most recently deleted first
+    async fn list_deleted_courses(&self, tutor_id: Uuid) -> AppResult<Vec<Course>>;
+    // Returns false when no such deleted course exists
+    async fn restore_course(&self, course_id: Uuid) -> AppResult<bool>;
+    // Removes courses deleted before the cutoff, with everything hanging off
+    // them; returns their ids
+    async fn purge_deleted_courses(&self, deleted_before: DateTime<Utc>) -> AppResult<Vec<Uuid>>;
+}
+
+#[async_trait]
+pub trait ReviewRepository {
+    async fn find_review(&self, tutor_id: Uuid, student_id: Uuid) -> AppResult<Option<TutorReview>>;
+    async fn find_review_by_id(&self, review_id: Uuid) -> AppResult<Option<TutorReview>>;
+    async fn find_review_with_student(&self, review_id: Uuid) -> AppResult<Option<ReviewWithStudent>>;
+    // Reviews for a tutor, newest first; none while the tutor is deleted
+    async fn list_reviews_for_tutor(&self, tutor_id: Uuid) -> AppResult<Vec<ReviewWithStudent>>;
+    // Review writes adjust the tutor's rating aggregate in the same
+    // transaction. Returns false when the student already reviewed the tutor.
+    async fn insert_review(&self, review: &TutorReview) -> AppResult<bool>;
+    // Rewrites rating, comment and edited_at, keeping the replaced version in
+    // the review's edit history; returns false when no such review exists
+    async fn update_review(&self, review: &TutorReview) -> AppResult<bool>;
+    // Returns false when no such review exists; its edits and reply go with it
+    async fn delete_review(&self, review_id: Uuid) -> AppResult<bool>;
+    // Replaced versions, oldest first
+    async fn list_review_edits(&self, review_id: Uuid) -> AppResult<Vec<ReviewEdit>>;
+    // Returns false when the review already has a reply
+    async fn insert_review_reply(&self, reply: &ReviewReply) -> AppResult<bool>;
+    // Recomputes tutors.rating and tutors.total_reviews from the stored reviews
+    async fn refresh_tutor_rating(&self, tutor_id: Uuid) -> AppResult<()>;
+    // Tutors whose aggregate disagrees with their reviews
+    async fn list_rating_drift(&self) -> AppResult<Vec<RatingDrift>>;
+}
+
+#[async_trait]
+pub trait AvailabilityRepository {
+    async fn find_schedule(&self, tutor_id: Uuid) -> AppResult<Option<TutorSchedule>>;
+    // Upserts the schedule and swaps its weekly windows in one step
+    async fn replace_schedule(&self, schedule: &TutorSchedule, windows: &[AvailabilityWindow]) -> AppResult<()>;
+    // Ordered by weekday, then start time
+    async fn list_windows(&self, tutor_id: Uuid) -> AppResult<Vec<AvailabilityWindow>>;
+    // Exceptions dated within `from..=to`, by date then start time
+    async fn list_exceptions(&self, tutor_id: Uuid, from: NaiveDate, to: NaiveDate) -> AppResult<Vec<AvailabilityException>>;
+    async fn insert_exception(&self, exception: &AvailabilityException) -> AppResult<()>;
+    // Returns false when the tutor has no such exception
+    async fn delete_exception(&self, tutor_id: Uuid, exception_id: Uuid) -> AppResult<bool>;
+    // Blackouts overlapping `from..=to`, by start date
+    async fn list_blackouts(&self, tutor_id: Uuid, from: NaiveDate, to: NaiveDate) -> AppResult<Vec<AvailabilityBlackout>>;
+    async fn insert_blackout(&self, blackout: &AvailabilityBlackout) -> AppResult<()>;
+    // Returns false when the tutor has no such blackout
+    async fn delete_blackout(&self, tutor_id: Uuid, blackout_id: Uuid) -> AppResult<bool>;
+}
+
+#[async_trait]
+pub trait BookingRepository {
+    async fn find_booking(&self, booking_id: Uuid) -> AppResult<Option<BookingWithDetails>>;
+    // Fails with `AppError::Conflict` when an active booking of the tutor overlaps
+    async fn insert_booking(&self, booking: &Booking) -> AppResult<()>;
+    // Writes times, status, reason, fee and reschedule count; overlaps fail as
+    // on insert. Returns false, writing nothing, when the stored booking is no
+    // longer at `expected_updated_at`.
+    async fn update_booking(&self, booking: &Booking, expected_updated_at: DateTime<Utc>) -> AppResult<bool>;
+    // Bookings made by a student, soonest first
+    async fn list_bookings_for_student(&self, student_id: Uuid, status: Option<BookingStatus>) -> AppResult<Vec<BookingWithDetails>>;
+    // Bookings of a tutor profile, soonest first
+    async fn list_bookings_for_tutor(&self, tutor_id: Uuid, status: Option<BookingStatus>) -> AppResult<Vec<BookingWithDetails>>;
+    // Times held by the tutor's active bookings that overlap [from, to)
+    async fn list_busy_intervals(&self, tutor_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> AppResult<Vec<(DateTime<Utc>, DateTime<Utc>)>>;
+    async fn find_booking_policy(&self, tutor_id: Uuid) -> AppResult<Option<BookingPolicy>>;
+    // Inserts the policy or replaces the tutor's existing one
+    async fn upsert_booking_policy(&self, policy: &BookingPolicy) -> AppResult<()>;
+}
+
+#[async_trait]
+pub trait EnrollmentRepository {
+    async fn find_enrollment(&self, course_id: Uuid, student_id: Uuid) -> AppResult<Option<EnrollmentWithDetails>>;
+    // Takes a seat when one is free and joins the waitlist otherwise, as one
+    // atomic step per course. A dropped enrollment is reopened; a current one
+    // fails with `AppError::Conflict`. Courses that are inactive or deleted, or
+    // whose tutor profile is deleted, fail with `AppError::NotFound`.
+    async fn enroll_student(&self, course_id: Uuid, student_id: Uuid, now: DateTime<Utc>) -> AppResult<Enrollment>;
+    // Drops an active or waitlisted enrollment and promotes waitlisted
+    // students into any seats that opens; returns the promoted enrollments
+    async fn withdraw_enrollment(&self, enrollment_id: Uuid, now: DateTime<Utc>) -> AppResult<Vec<Enrollment>>;
+    // Fills free seats from the head of the waitlist
+    async fn promote_waitlisted(&self, course_id: Uuid, now: DateTime<Utc>) -> AppResult<Vec<Enrollment>>;
+    // Newest first
+    async fn list_enrollments_for_student(&self, student_id: Uuid, status: Option<EnrollmentStatus>) -> AppResult<Vec<EnrollmentWithDetails>>;
+    // In the order students asked to join
+    async fn list_enrollments_for_course(&self, course_id: Uuid, status: Option<EnrollmentStatus>) -> AppResult<Vec<EnrollmentWithDetails>>;
+    async fn count_active_enrollments(&self, course_id: Uuid) -> AppResult<i64>;
+}
+
+// Positions are assigned by the store and stay contiguous from 1; every
+// change to a course's structure locks the course so concurrent edits queue.
+#[async_trait]
+pub trait CurriculumRepository {
+    async fn find_module(&self, module_id: Uuid) -> AppResult<Option<CourseModule>>;
+    async fn find_lesson(&self, lesson_id: Uuid) -> AppResult<Option<Lesson>>;
+    // In course order
+    async fn list_modules(&self, course_id: Uuid) -> AppResult<Vec<CourseModule>>;
+    // In course order: by module position, then lesson position
+    async fn list_lessons(&self, course_id: Uuid) -> AppResult<Vec<Lesson>>;
+    // Adds the module after the course's last one
+    async fn append_module(&self, module: &CourseModule) -> AppResult<CourseModule>;
+    async fn update_module(&self, module: &CourseModule) -> AppResult<()>;
+    // Also deletes the module's lessons
+    async fn delete_module(&self, module_id: Uuid) -> AppResult<bool>;
+    // Adds the lesson after the module's last one
+    async fn append_lesson(&self, lesson: &Lesson) -> AppResult<Lesson>;
+    async fn update_lesson(&self, lesson: &Lesson) -> AppResult<()>;
+    async fn delete_lesson(&self, lesson_id: Uuid) -> AppResult<bool>;
+    // Applies a complete new order in one step. Fails with
+    // `AppError::BadRequest` unless it names every module and lesson of the
+    // course exactly once.
+    async fn reorder_curriculum(&self, course_id: Uuid, order: &[ModuleOrder]) -> AppResult<()>;
+}
+
+#[async_trait]
+pub trait ProgressRepository {
+    // Inserts the completion, or adds its minutes to an earlier one
+    async fn record_lesson_completion(&self, completion: &LessonCompletion) -> AppResult<LessonCompletion>;
+    async fn delete_lesson_completion(&self, lesson_id: Uuid, student_id: Uuid) -> AppResult<bool>;
+    async fn list_lesson_completions(&self, course_id: Uuid, student_id: Uuid) -> AppResult<Vec<LessonCompletion>>;
+    // One row per active enrollment of the student, newest enrollment first
+    async fn list_student_progress(&self, student_id: Uuid) -> AppResult<Vec<CourseProgress>>;
+    // One row per actively enrolled student, in enrollment order
+    async fn list_course_progress(&self, course_id: Uuid) -> AppResult<Vec<CourseProgress>>;
+}
+
+#[async_trait]
+pub trait QuizRepository {
+    async fn find_quiz(&self, quiz_id: Uuid) -> AppResult<Option<Quiz>>;
+    // Oldest first
+    async fn list_quizzes_for_lesson(&self, lesson_id: Uuid) -> AppResult<Vec<Quiz>>;
+    async fn insert_quiz(&self, quiz: &Quiz) -> AppResult<()>;
+    async fn update_quiz(&self, quiz: &Quiz) -> AppResult<()>;
+    // Also deletes the quiz's attempts
+    async fn delete_quiz(&self, quiz_id: Uuid) -> AppResult<bool>;
+    async fn find_quiz_attempt(&self, attempt_id: Uuid) -> AppResult<Option<QuizAttempt>>;
+    // In the order they were started; all students when `student_id` is None
+    async fn list_quiz_attempts(&self, quiz_id: Uuid, student_id: Option<Uuid>) -> AppResult<Vec<QuizAttempt>>;
+    // Numbers the attempt after the student's previous ones. Fails with
+    // `AppError::Conflict` once `max_attempts` have been used.
+    async fn start_quiz_attempt(&self, attempt: &QuizAttempt, max_attempts: Option<i32>) -> AppResult<QuizAttempt>;
+    // Stores the graded submission; false if the attempt was already submitted
+    async fn finish_quiz_attempt(&self, attempt: &QuizAttempt) -> AppResult<bool>;
+}
+
+// Shared by the backends' reorder_curriculum: the order must be a
+// permutation of the course's current modules and lessons
+pub(crate) fn check_complete_order(order: &[ModuleOrder], module_ids: &[Uuid], lesson_ids: &[Uuid]) -> AppResult<()> {
+    let invalid = || AppError::BadRequest(
+        "Order must list every module and lesson of the course exactly once".to_string(),
+    );
+
+    let ordered_modules: HashSet<Uuid> = order.iter().map(|module| module.id).collect();
+    if ordered_modules.len() != order.len() || ordered_modules != module_ids.iter().copied().collect() {
+        return Err(invalid());
+    }
+
+    let lesson_count: usize = order.iter().map(|module| module.lessons.len()).sum();
+    let ordered_lessons: HashSet<Uuid> = order.iter().flat_map(|module| module.lessons.iter().copied()).collect();
+    if ordered_lessons.len() != lesson_count || ordered_lessons != lesson_ids.iter().copied().collect() {
+        return Err(invalid());
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+pub trait AssignmentRepository {
+    async fn find_assignment(&self, assignment_id: Uuid) -> AppResult<Option<Assignment>>;
+    // Soonest due first
+    async fn list_assignments_for_course(&self, course_id: Uuid) -> AppResult<Vec<Assignment>>;
+    async fn insert_assignment(&self, assignment: &Assignment) -> AppResult<()>;
+    async fn update_assignment(&self, assignment: &Assignment) -> AppResult<()>;
+    // Also deletes the assignment's files and submissions
+    async fn delete_assignment(&self, assignment_id: Uuid) -> AppResult<bool>;
+    async fn insert_assignment_file(&self, file: &AssignmentFile) -> AppResult<()>;
+    async fn find_assignment_file(&self, file_id: Uuid) -> AppResult<Option<AssignmentFile>>;
+    // Inline content of a file uploaded before assignment files moved to the
+    // file store; None for every other file
+    async fn read_assignment_file(&self, file_id: Uuid) -> AppResult<Option<Vec<u8>>>;
+    // Oldest first
+    async fn list_files_for_assignment(&self, assignment_id: Uuid) -> AppResult<Vec<AssignmentFile>>;
+    // Files among `file_ids` in upload order; unknown ids are skipped
+    async fn list_assignment_files(&self, file_ids: &[Uuid]) -> AppResult<Vec<AssignmentFile>>;
+    async fn find_submission(&self, submission_id: Uuid) -> AppResult<Option<AssignmentSubmission>>;
+    // Oldest first; all students when `student_id` is None
+    async fn list_submissions(&self, assignment_id: Uuid, student_id: Option<Uuid>) -> AppResult<Vec<AssignmentSubmission>>;
+    // Numbers the submission after the student's previous ones. Fails with
+    // `AppError::Conflict` once `max_submissions` have been made.
+    async fn insert_submission(
+        &self,
+        submission: &AssignmentSubmission,
+        max_submissions: Option<i32>,
+    ) -> AppResult<AssignmentSubmission>;
+    // Stores the score, rubric scores, feedback and publication time
+    async fn update_submission_grade(&self, submission: &AssignmentSubmission) -> AppResult<()>;
+}
+
+#[async_trait]
+pub trait NotificationRepository {
+    async fn insert_notification(&self, notification: &Notification) -> AppResult<()>;
+    // Newest first
+    async fn list_notifications(&self, user_id: Uuid, unread_only: bool) -> AppResult<Vec<Notification>>;
+    // False if the user has no such notification
+    async fn mark_notification_read(&self, notification_id: Uuid, user_id: Uuid, read_at: DateTime<Utc>) -> AppResult<bool>;
+}
+
+#[async_trait]
+pub trait CertificateRepository {
+    async fn find_certificate_by_code(&self, code: &str) -> AppResult<Option<Certificate>>;
+    async fn find_certificate(&self, course_id: Uuid, student_id: Uuid) -> AppResult<Option<Certificate>>;
+    // Most recently issued first
+    async fn list_certificates_for_student(&self, student_id: Uuid) -> AppResult<Vec<Certificate>>;
+    // Returns the stored certificate: the new one, or the one the student
+    // already holds for the course
+    async fn insert_certificate(&self, certificate: &Certificate) -> AppResult<Certificate>;
+}
+
+#[async_trait]
+pub trait FileRepository {
+    async fn insert_stored_file(&self, file: &StoredFile) -> AppResult<()>;
+    async fn find_stored_file(&self, file_id: Uuid) -> AppResult<Option<StoredFile>>;
+    // The owner's uploads, newest first
+    async fn list_stored_files(&self, owner_id: Uuid) -> AppResult<Vec<StoredFile>>;
+    async fn delete_stored_file(&self, file_id: Uuid) -> AppResult<bool>;
+    // Files still sharing the stored content
+    async fn count_stored_files_with_hash(&self, sha256: &str) -> AppResult<i64>;
+}
+
+#[async_trait]
+pub trait AvatarRepository {
+    async fn find_avatar(&self, user_id: Uuid) -> AppResult<Option<Avatar>>;
+    // Inserts or replaces the user's avatar and stamps the user with its
+    // time; returns the one replaced
+    async fn set_avatar(&self, avatar: &Avatar) -> AppResult<Option<Avatar>>;
+    // Returns the removed avatar
+    async fn delete_avatar(&self, user_id: Uuid) -> AppResult<Option<Avatar>>;
+}
+
+#[async_trait]
+pub trait VerificationRepository {
+    // Stores the request and its documents, moves the documents' files out of
+    // the tutor's uploads and logs the submission, all or nothing
+    async fn insert_verification_request(
+        &self,
+        request: &VerificationRequest,
+        documents: &[VerificationDocument],
+        event: &VerificationEvent,
+    ) -> AppResult<()>;
+    async fn find_verification_request(&self, request_id: Uuid) -> AppResult<Option<VerificationRequestWithTutor>>;
+    // Oldest first, so the queue is worked in order
+    async fn list_verification_requests(&self, status: VerificationStatus) -> AppResult<Vec<VerificationRequestWithTutor>>;
+    // Newest first
+    async fn list_tutor_verification_requests(&self, tutor_id: Uuid) -> AppResult<Vec<VerificationRequestWithTutor>>;
+    // In submission order
+    async fn list_verification_documents(&self, request_id: Uuid) -> AppResult<Vec<VerificationDocument>>;
+    // Records the decision if the request is still pending, verifies the tutor
+    // when approved and logs the event; false if someone decided first
+    async fn decide_verification_request(&self, request: &VerificationRequest, event: &VerificationEvent) -> AppResult<bool>;
+    // Sets the tutor's flag outside the request flow and logs the event
+    async fn set_tutor_verified(&self, tutor_id: Uuid, is_verified: bool, event: &VerificationEvent) -> AppResult<()>;
+    // Oldest first
+    async fn list_verification_events(&self, tutor_id: Uuid) -> AppResult<Vec<VerificationEvent>>;
+}
+
+#[async_trait]
+pub trait CalendarRepository {
+    async fn find_calendar_feed(&self, user_id: Uuid) -> AppResult<Option<CalendarFeed>>;
+    async fn find_calendar_feed_by_token(&self, token: &str) -> AppResult<Option<CalendarFeed>>;
+    // Inserts the user's feed or replaces its token and time zone
+    async fn upsert_calendar_feed(&self, feed: &CalendarFeed) -> AppResult<()>;
+}
+
+#[async_trait]
+pub trait StatsRepository {
+    async fn platform_stats(&self) -> AppResult<PlatformStats>;
+}
+
+#[async_trait]
+pub trait AuditRepository {
+    async fn insert_audit_entry(&self, entry: &AuditEntry) -> AppResult<()>;
+    // Newest first, with the total number of matches; the query's `page` and
+    // `per_page` are left to the caller
+    async fn search_audit_entries(&self, query: &AuditLogQuery, limit: i64, offset: i64) -> AppResult<(Vec<AuditEntry>, i64)>;
+}
+
+// Everything the HTTP layer needs from a storage backend
+pub trait Repository:
+    UserRepository + TutorRepository + CourseRepository + ReviewRepository + AvailabilityRepository
+    + BookingRepository + EnrollmentRepository + CurriculumRepository + ProgressRepository
+    + QuizRepository + AssignmentRepository + NotificationRepository + CertificateRepository
+    + FileRepository + AvatarRepository + VerificationRepository + CalendarRepository + StatsRepository
+    + AuditRepository
+    + Clone + Send + Sync + 'static
+{
+}
+
+impl<T> Repository for T where
+    T: UserRepository + TutorRepository + CourseRepository + ReviewRepository + AvailabilityRepository
+        + BookingRepository + EnrollmentRepository + CurriculumRepository + ProgressRepository
+        + QuizRepository + AssignmentRepository + NotificationRepository + CertificateRepository
+        + FileRepository + AvatarRepository + VerificationRepository + CalendarRepository + StatsRepository
+        + AuditRepository
+        + Clone + Send + Sync + 'static
+{
+}
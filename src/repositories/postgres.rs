@@ -0,0 +1,2773 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+use crate::database::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Assignment, AssignmentFile, AssignmentSubmission, AuditEntry, AuditLogQuery, Avatar, AvailabilityBlackout, AvailabilityException, AvailabilityWindow, Booking, BookingPolicy, BookingStatus,
+    BookingWithDetails, CalendarFeed, Certificate, Course, CourseModule, CourseProgress, CourseWithTutor, DifficultyLevel, Enrollment, EnrollmentStatus,
+    EnrollmentWithDetails, Lesson, LessonCompletion, ModuleOrder, Notification, PlatformStats, Quiz, QuizAttempt, RatingDrift, ReviewEdit, ReviewReply, ReviewWithStudent, StoredFile, TutorProfile, TutorReview, TutorSchedule,
+    TutorWithUser, User, UserRole, VerificationDocument, VerificationEvent, VerificationRequest, VerificationRequestWithTutor,
+    VerificationStatus
+};
+use super::{check_complete_order, FixtureSet, AssignmentRepository, AuditRepository, AvailabilityRepository, AvatarRepository, BookingRepository, CalendarRepository, CertificateRepository, CourseRepository, CurriculumRepository, EnrollmentRepository, FileRepository, NotificationRepository, ProgressRepository, QuizRepository, ReviewRepository, StatsRepository, TutorRepository, UserRepository, VerificationRepository};
+
+// Every course read joins the owning tutor and user so a listing is one query;
+// callers append COURSE_IS_LIVE to skip deleted courses and deleted tutors
+const COURSE_WITH_TUTOR_SELECT: &str = r#"
+    SELECT c.id, c.title, c.description, c.tutor_id, c.price, c.duration_minutes,
+           c.category, c.difficulty_level, c.is_active, c.max_students, c.taken_down_at, c.deleted_at, c.created_at, c.updated_at,
+           u.first_name AS tutor_first_name, u.last_name AS tutor_last_name,
+           t.rating AS tutor_rating, t.is_verified AS tutor_is_verified,
+           (SELECT COUNT(*) FROM enrollments e
+            WHERE e.course_id = c.id AND e.status = 'active') AS enrolled_students
+    FROM courses c
+    JOIN tutors t ON c.tutor_id = t.id
+    JOIN users u ON t.user_id = u.id
+"#;
+
+// Waitlist positions count the waitlisted rows that asked to join no later
+const ENROLLMENT_WITH_DETAILS_SELECT: &str = r#"
+    SELECT e.*, c.title AS course_title,
+           u.first_name AS student_first_name, u.last_name AS student_last_name, u.email AS student_email,
+           CASE WHEN e.status = 'waitlisted' THEN
+               (SELECT COUNT(*) FROM enrollments w
+                WHERE w.course_id = e.course_id AND w.status = 'waitlisted'
+                  AND (w.requested_at, w.id) <= (e.requested_at, e.id))
+           END AS waitlist_position
+    FROM enrollments e
+    JOIN courses c ON e.course_id = c.id
+    JOIN users u ON e.student_id = u.id
+"#;
+
+// Aggregates per active enrollment; lesson totals come from correlated
+// subqueries so the completion join cannot multiply them
+const COURSE_PROGRESS_SELECT: &str = r#"
+    SELECT e.course_id, c.title AS course_title, e.student_id,
+           u.first_name AS student_first_name, u.last_name AS student_last_name, u.email AS student_email,
+           e.enrolled_at,
+           (SELECT COUNT(*) FROM lessons l WHERE l.course_id = e.course_id) AS total_lessons,
+           (SELECT COALESCE(SUM(l.estimated_minutes), 0) FROM lessons l WHERE l.course_id = e.course_id)
+               AS estimated_minutes,
+           COUNT(lc.id) AS completed_lessons,
+           COALESCE(SUM(lc.minutes_spent), 0) AS minutes_spent,
+           MAX(lc.completed_at) AS last_completed_at
+    FROM enrollments e
+    JOIN courses c ON e.course_id = c.id
+    JOIN users u ON e.student_id = u.id
+    LEFT JOIN lesson_completions lc ON lc.course_id = e.course_id AND lc.student_id = e.student_id
+"#;
+
+const COURSE_IS_LIVE: &str = "c.deleted_at IS NULL AND t.deleted_at IS NULL";
+
+const TUTOR_WITH_USER_SELECT: &str = r#"
+    SELECT t.*, u.first_name, u.last_name, u.email, u.avatar_updated_at
+    FROM tutors t
+    JOIN users u ON t.user_id = u.id
+"#;
+
+// Reviews with the reviewing student and the tutor's reply, if any
+const REVIEW_WITH_STUDENT_SELECT: &str = r#"
+    SELECT r.id, r.tutor_id, r.student_id, r.rating, r.comment, r.created_at, r.edited_at,
+           u.first_name AS student_first_name, u.last_name AS student_last_name,
+           u.avatar_updated_at AS student_avatar_updated_at,
+           rr.comment AS reply_comment, rr.created_at AS reply_created_at
+    FROM tutor_reviews r
+    JOIN users u ON r.student_id = u.id
+    LEFT JOIN review_replies rr ON rr.review_id = r.id
+"#;
+
+const USER_SELECT: &str = r#"
+    SELECT id, email, password_hash, first_name, last_name, role, is_active, avatar_updated_at,
+           token_version, created_at, updated_at
+    FROM users
+"#;
+
+// Shared by the page and the count of a user search; $1 is an ILIKE pattern
+const USER_SEARCH_FILTER: &str = r#"
+    WHERE ($1::text IS NULL OR email ILIKE $1 OR first_name || ' ' || last_name ILIKE $1)
+      AND ($2::user_role IS NULL OR role = $2)
+      AND ($3::boolean IS NULL OR is_active = $3)
+"#;
+
+const AUDIT_LOG_FILTER: &str = r#"
+    WHERE ($1::uuid IS NULL OR actor_id = $1)
+      AND ($2::text IS NULL OR target_type = $2)
+      AND ($3::uuid IS NULL OR target_id = $3)
+      AND ($4::timestamptz IS NULL OR created_at >= $4)
+      AND ($5::timestamptz IS NULL OR created_at < $5)
+"#;
+
+const VERIFICATION_REQUEST_SELECT: &str = r#"
+    SELECT r.*, u.id AS tutor_user_id, u.first_name AS tutor_first_name, u.last_name AS tutor_last_name,
+           u.email AS tutor_email
+    FROM verification_requests r
+    JOIN tutors t ON r.tutor_id = t.id
+    JOIN users u ON t.user_id = u.id
+"#;
+
+const BOOKING_WITH_DETAILS_SELECT: &str = r#"
+    SELECT b.*, c.title AS course_title,
+           tu.id AS tutor_user_id, tu.first_name AS tutor_first_name, tu.last_name AS tutor_last_name,
+           su.first_name AS student_first_name, su.last_name AS student_last_name
+    FROM bookings b
+    JOIN courses c ON b.course_id = c.id
+    JOIN tutors t ON b.tutor_id = t.id
+    JOIN users tu ON t.user_id = tu.id
+    JOIN users su ON b.student_id = su.id
+"#;
+
+// SQLSTATE raised by the bookings_no_tutor_overlap exclusion constraint
+const EXCLUSION_VIOLATION: &str = "23P01";
+
+fn booking_write_error(error: sqlx::Error) -> AppError {
+    match &error {
+        sqlx::Error::Database(db) if db.code().as_deref() == Some(EXCLUSION_VIOLATION) => {
+            AppError::Conflict("Tutor is already booked for this time".to_string())
+        }
+        _ => AppError::Database(error),
+    }
+}
+
+// Rows per UNNEST statement when bulk loading fixtures
+const BULK_INSERT_BATCH: usize = 5_000;
+const SPECIALIZATION_SEPARATOR: &str = "|";
+
+#[derive(Clone)]
+pub struct PgRepository {
+    pool: DbPool,
+}
+
+impl PgRepository {
+    pub fn new(pool: DbPool) -> Self {
+        PgRepository { pool }
+    }
+
+    pub fn pool(&self) -> &DbPool {
+        &self.pool
+    }
+
+    // Bulk counterpart of `InMemoryRepository::load_fixtures` for seeding.
+    // Rows go in as UNNEST batches inside one transaction, so a load-test data
+    // set takes seconds rather than one round trip per row.
+    pub async fn load_fixtures(&self, fixtures: &FixtureSet) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for users in fixtures.users.chunks(BULK_INSERT_BATCH) {
+            let roles: Vec<&str> = users.iter()
+                .map(|user| match user.role {
+                    UserRole::Student => "student",
+                    UserRole::Tutor => "tutor",
+                    UserRole::Admin => "admin",
+                })
+                .collect();
+
+            sqlx::query(
+                r#"
+                INSERT INTO users (id, email, password_hash, first_name, last_name, role, is_active, created_at, updated_at)
+                SELECT id, email, password_hash, first_name, last_name, role::user_role, is_active, created_at, updated_at
+                FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::text[], $5::text[], $6::text[],
+                            $7::bool[], $8::timestamptz[], $9::timestamptz[])
+                    AS u(id, email, password_hash, first_name, last_name, role, is_active, created_at, updated_at)
+                "#
+            )
+            .bind(users.iter().map(|u| u.id).collect::<Vec<_>>())
+            .bind(users.iter().map(|u| u.email.as_str()).collect::<Vec<_>>())
+            .bind(users.iter().map(|u| u.password_hash.as_str()).collect::<Vec<_>>())
+            .bind(users.iter().map(|u| u.first_name.as_str()).collect::<Vec<_>>())
+            .bind(users.iter().map(|u| u.last_name.as_str()).collect::<Vec<_>>())
+            .bind(roles)
+            .bind(users.iter().map(|u| u.is_active).collect::<Vec<_>>())
+            .bind(users.iter().map(|u| u.created_at).collect::<Vec<_>>())
+            .bind(users.iter().map(|u| u.updated_at).collect::<Vec<_>>())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for tutors in fixtures.tutors.chunks(BULK_INSERT_BATCH) {
+            // UNNEST cannot take an array of arrays, so specializations travel joined
+            let specializations: Vec<String> = tutors.iter()
+                .map(|t| t.specializations.join(SPECIALIZATION_SEPARATOR))
+                .collect();
+
+            sqlx::query(
+                r#"
+                INSERT INTO tutors (id, user_id, bio, specializations, hourly_rate, years_experience,
+                                    is_verified, is_available, created_at, updated_at)
+                SELECT id, user_id, bio, string_to_array(NULLIF(specializations, ''), $11), hourly_rate,
+                       years_experience, is_verified, is_available, created_at, updated_at
+                FROM UNNEST($1::uuid[], $2::uuid[], $3::text[], $4::text[], $5::int4[], $6::int4[],
+                            $7::bool[], $8::bool[], $9::timestamptz[], $10::timestamptz[])
+                    AS t(id, user_id, bio, specializations, hourly_rate, years_experience,
+                         is_verified, is_available, created_at, updated_at)
+                "#
+            )
+            .bind(tutors.iter().map(|t| t.id).collect::<Vec<_>>())
+            .bind(tutors.iter().map(|t| t.user_id).collect::<Vec<_>>())
+            .bind(tutors.iter().map(|t| t.bio.as_str()).collect::<Vec<_>>())
+            .bind(specializations)
+            .bind(tutors.iter().map(|t| t.hourly_rate).collect::<Vec<_>>())
+            .bind(tutors.iter().map(|t| t.years_experience).collect::<Vec<_>>())
+            .bind(tutors.iter().map(|t| t.is_verified).collect::<Vec<_>>())
+            .bind(tutors.iter().map(|t| t.is_available).collect::<Vec<_>>())
+            .bind(tutors.iter().map(|t| t.created_at).collect::<Vec<_>>())
+            .bind(tutors.iter().map(|t| t.updated_at).collect::<Vec<_>>())
+            .bind(SPECIALIZATION_SEPARATOR)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for courses in fixtures.courses.chunks(BULK_INSERT_BATCH) {
+            let levels: Vec<&str> = courses.iter()
+                .map(|course| match course.difficulty_level {
+                    DifficultyLevel::Beginner => "beginner",
+                    DifficultyLevel::Intermediate => "intermediate",
+                    DifficultyLevel::Advanced => "advanced",
+                })
+                .collect();
+
+            sqlx::query(
+                r#"
+                INSERT INTO courses (id, title, description, tutor_id, price, duration_minutes, category,
+                                     difficulty_level, is_active, created_at, updated_at)
+                SELECT id, title, description, tutor_id, price, duration_minutes, category,
+                       difficulty_level::difficulty_level, is_active, created_at, updated_at
+                FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::uuid[], $5::int4[], $6::int4[], $7::text[],
+                            $8::text[], $9::bool[], $10::timestamptz[], $11::timestamptz[])
+                    AS c(id, title, description, tutor_id, price, duration_minutes, category,
+                         difficulty_level, is_active, created_at, updated_at)
+                "#
+            )
+            .bind(courses.iter().map(|c| c.id).collect::<Vec<_>>())
+            .bind(courses.iter().map(|c| c.title.as_str()).collect::<Vec<_>>())
+            .bind(courses.iter().map(|c| c.description.as_str()).collect::<Vec<_>>())
+            .bind(courses.iter().map(|c| c.tutor_id).collect::<Vec<_>>())
+            .bind(courses.iter().map(|c| c.price).collect::<Vec<_>>())
+            .bind(courses.iter().map(|c| c.duration_minutes).collect::<Vec<_>>())
+            .bind(courses.iter().map(|c| c.category.as_str()).collect::<Vec<_>>())
+            .bind(levels)
+            .bind(courses.iter().map(|c| c.is_active).collect::<Vec<_>>())
+            .bind(courses.iter().map(|c| c.created_at).collect::<Vec<_>>())
+            .bind(courses.iter().map(|c| c.updated_at).collect::<Vec<_>>())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for reviews in fixtures.reviews.chunks(BULK_INSERT_BATCH) {
+            sqlx::query(
+                r#"
+                INSERT INTO tutor_reviews (id, tutor_id, student_id, rating, comment, created_at)
+                SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::int4[], $5::text[], $6::timestamptz[])
+                "#
+            )
+            .bind(reviews.iter().map(|r| r.id).collect::<Vec<_>>())
+            .bind(reviews.iter().map(|r| r.tutor_id).collect::<Vec<_>>())
+            .bind(reviews.iter().map(|r| r.student_id).collect::<Vec<_>>())
+            .bind(reviews.iter().map(|r| r.rating).collect::<Vec<_>>())
+            .bind(reviews.iter().map(|r| r.comment.as_deref()).collect::<Vec<_>>())
+            .bind(reviews.iter().map(|r| r.created_at).collect::<Vec<_>>())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // One aggregate pass instead of refreshing tutors one at a time
+        sqlx::query(
+            r#"
+            UPDATE tutors t
+            SET rating_sum = r.rating_sum, total_reviews = r.total_reviews,
+                rating = (r.rating_sum::FLOAT8 / r.total_reviews)::REAL
+            FROM (
+                SELECT tutor_id, SUM(rating)::INT8 AS rating_sum, COUNT(*)::INT4 AS total_reviews
+                FROM tutor_reviews
+                WHERE tutor_id = ANY($1)
+                GROUP BY tutor_id
+            ) r
+            WHERE t.id = r.tutor_id
+            "#
+        )
+        .bind(fixtures.tutors.iter().map(|t| t.id).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UserRepository for PgRepository {
+    async fn find_user_by_id(&self, user_id: Uuid) -> AppResult<Option<User>> {
+        let user = sqlx::query_as::<_, User>(&format!("{} WHERE id = $1", USER_SELECT))
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(user)
+    }
+
+    async fn find_user_by_email(&self, email: &str) -> AppResult<Option<User>> {
+        let user = sqlx::query_as::<_, User>(&format!("{} WHERE email = $1", USER_SELECT))
+            .bind(email)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(user)
+    }
+
+    async fn insert_user(&self, user: &User) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, email, password_hash, first_name, last_name, role, is_active, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#
+        )
+        .bind(user.id)
+        .bind(&user.email)
+        .bind(&user.password_hash)
+        .bind(&user.first_name)
+        .bind(&user.last_name)
+        .bind(&user.role)
+        .bind(user.is_active)
+        .bind(user.created_at)
+        .bind(user.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_user(&self, user: &User, expected_updated_at: DateTime<Utc>) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users SET first_name = $1, last_name = $2, role = $3, is_active = $4, updated_at = $5
+            WHERE id = $6 AND updated_at = $7
+            "#
+        )
+        .bind(&user.first_name)
+        .bind(&user.last_name)
+        .bind(&user.role)
+        .bind(user.is_active)
+        .bind(user.updated_at)
+        .bind(user.id)
+        .bind(expected_updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn search_users(
+        &self,
+        text: Option<&str>,
+        role: Option<UserRole>,
+        is_active: Option<bool>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<User>, i64)> {
+        // Wildcards typed by the admin are matched literally
+        let pattern = text.map(|text| {
+            let escaped = text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            format!("%{}%", escaped)
+        });
+
+        let users = sqlx::query_as::<_, User>(&format!(
+            "{} {} ORDER BY created_at DESC, id LIMIT $4 OFFSET $5",
+            USER_SELECT, USER_SEARCH_FILTER
+        ))
+        .bind(&pattern)
+        .bind(&role)
+        .bind(is_active)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM users {}", USER_SEARCH_FILTER))
+            .bind(&pattern)
+            .bind(&role)
+            .bind(is_active)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok((users, total))
+    }
+
+    async fn revoke_sessions(&self, user_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("UPDATE users SET token_version = token_version + 1 WHERE id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl TutorRepository for PgRepository {
+    async fn find_tutor_by_id(&self, tutor_id: Uuid) -> AppResult<Option<TutorWithUser>> {
+        let tutor = sqlx::query_as::<_, TutorWithUser>(&format!("{} WHERE t.id = $1 AND t.deleted_at IS NULL", TUTOR_WITH_USER_SELECT))
+            .bind(tutor_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(tutor)
+    }
+
+    async fn find_tutor_by_user_id(&self, user_id: Uuid) -> AppResult<Option<TutorWithUser>> {
+        let tutor = sqlx::query_as::<_, TutorWithUser>(&format!("{} WHERE t.user_id = $1 AND t.deleted_at IS NULL", TUTOR_WITH_USER_SELECT))
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(tutor)
+    }
+
+    async fn list_available_tutors(&self) -> AppResult<Vec<TutorWithUser>> {
+        let query = format!(
+            "{} WHERE t.is_available = true AND t.deleted_at IS NULL ORDER BY t.is_verified DESC, t.rating DESC NULLS LAST, t.created_at DESC",
+            TUTOR_WITH_USER_SELECT
+        );
+
+        let tutors = sqlx::query_as::<_, TutorWithUser>(&query)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(tutors)
+    }
+
+    async fn search_tutors_by_specialization(&self, specialization: &str) -> AppResult<Vec<TutorWithUser>> {
+        let query = format!(
+            r#"
+            {} WHERE t.is_available = true AND $1 = ANY(t.specializations) AND t.deleted_at IS NULL
+            ORDER BY t.is_verified DESC, t.rating DESC NULLS LAST, t.created_at DESC
+            "#,
+            TUTOR_WITH_USER_SELECT
+        );
+
+        let tutors = sqlx::query_as::<_, TutorWithUser>(&query)
+            .bind(specialization)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(tutors)
+    }
+
+    async fn list_tutor_ids(&self) -> AppResult<Vec<Uuid>> {
+        let ids = sqlx::query_scalar("SELECT id FROM tutors ORDER BY created_at")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(ids)
+    }
+
+    async fn insert_tutor(&self, tutor: &TutorProfile) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO tutors (id, user_id, bio, specializations, hourly_rate, years_experience, rating,
+                                total_reviews, rating_sum, is_verified, is_available, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            "#
+        )
+        .bind(tutor.id)
+        .bind(tutor.user_id)
+        .bind(&tutor.bio)
+        .bind(&tutor.specializations)
+        .bind(tutor.hourly_rate)
+        .bind(tutor.years_experience)
+        .bind(tutor.rating)
+        .bind(tutor.total_reviews)
+        .bind(tutor.rating_sum)
+        .bind(tutor.is_verified)
+        .bind(tutor.is_available)
+        .bind(tutor.created_at)
+        .bind(tutor.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_tutor(&self, tutor: &TutorProfile, expected_updated_at: DateTime<Utc>) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tutors
+            SET bio = $1, specializations = $2, hourly_rate = $3, years_experience = $4,
+                is_available = $5, updated_at = $6
+            WHERE id = $7 AND updated_at = $8
+            "#
+        )
+        .bind(&tutor.bio)
+        .bind(&tutor.specializations)
+        .bind(tutor.hourly_rate)
+        .bind(tutor.years_experience)
+        .bind(tutor.is_available)
+        .bind(tutor.updated_at)
+        .bind(tutor.id)
+        .bind(expected_updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_tutor(&self, tutor_id: Uuid, deleted_at: DateTime<Utc>) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        // Holding every course lock keeps enrollments out until the profile is gone
+        sqlx::query("SELECT id FROM courses WHERE tutor_id = $1 ORDER BY id FOR UPDATE")
+            .bind(tutor_id)
+            .fetch_all(&mut *tx)
+            .await?;
+        let result = sqlx::query(
+            r#"
+            UPDATE tutors SET deleted_at = $1
+            WHERE id = $2 AND deleted_at IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM enrollments e JOIN courses c ON c.id = e.course_id
+                  WHERE c.tutor_id = $2 AND e.status = 'active'
+              )
+            "#
+        )
+        .bind(deleted_at)
+        .bind(tutor_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_deleted_tutor_by_user_id(&self, user_id: Uuid) -> AppResult<Option<TutorProfile>> {
+        let tutor = sqlx::query_as::<_, TutorProfile>("SELECT * FROM tutors WHERE user_id = $1 AND deleted_at IS NOT NULL")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(tutor)
+    }
+
+    async fn restore_tutor(&self, tutor_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("UPDATE tutors SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL")
+            .bind(tutor_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // Courses, reviews, bookings and the rest go with the profile through
+    // ON DELETE CASCADE
+    async fn purge_deleted_tutors(&self, deleted_before: DateTime<Utc>) -> AppResult<Vec<Uuid>> {
+        let ids = sqlx::query_scalar("DELETE FROM tutors WHERE deleted_at < $1 RETURNING id")
+            .bind(deleted_before)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(ids)
+    }
+}
+
+#[async_trait]
+impl CourseRepository for PgRepository {
+    async fn find_course_by_id(&self, course_id: Uuid) -> AppResult<Option<CourseWithTutor>> {
+        let course = sqlx::query_as::<_, CourseWithTutor>(&format!("{} WHERE c.id = $1 AND {}", COURSE_WITH_TUTOR_SELECT, COURSE_IS_LIVE))
+            .bind(course_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(course)
+    }
+
+    async fn list_active_courses(&self) -> AppResult<Vec<CourseWithTutor>> {
+        let query = format!(
+            "{} WHERE c.is_active = true AND {} ORDER BY c.created_at DESC",
+            COURSE_WITH_TUTOR_SELECT, COURSE_IS_LIVE
+        );
+
+        let courses = sqlx::query_as::<_, CourseWithTutor>(&query)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(courses)
+    }
+
+    async fn list_courses_by_tutor_user(&self, user_id: Uuid) -> AppResult<Vec<CourseWithTutor>> {
+        let query = format!(
+            "{} WHERE t.user_id = $1 AND {} ORDER BY c.created_at DESC",
+            COURSE_WITH_TUTOR_SELECT, COURSE_IS_LIVE
+        );
+
+        let courses = sqlx::query_as::<_, CourseWithTutor>(&query)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(courses)
+    }
+
+    async fn insert_course(&self, course: &Course) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO courses (id, title, description, tutor_id, price, duration_minutes, category,
+                                 difficulty_level, is_active, max_students, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#
+        )
+        .bind(course.id)
+        .bind(&course.title)
+        .bind(&course.description)
+        .bind(course.tutor_id)
+        .bind(course.price)
+        .bind(course.duration_minutes)
+        .bind(&course.category)
+        .bind(&course.difficulty_level)
+        .bind(course.is_active)
+        .bind(course.max_students)
+        .bind(course.created_at)
+        .bind(course.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_course(&self, course: &Course, expected_updated_at: DateTime<Utc>) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE courses
+            SET title = $1, description = $2, price = $3, duration_minutes = $4, category = $5,
+                difficulty_level = $6, is_active = $7, max_students = $8, updated_at = $9
+            WHERE id = $10 AND updated_at = $11
+            "#
+        )
+        .bind(&course.title)
+        .bind(&course.description)
+        .bind(course.price)
+        .bind(course.duration_minutes)
+        .bind(&course.category)
+        .bind(&course.difficulty_level)
+        .bind(course.is_active)
+        .bind(course.max_students)
+        .bind(course.updated_at)
+        .bind(course.id)
+        .bind(expected_updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_course_taken_down(&self, course_id: Uuid, taken_down_at: Option<DateTime<Utc>>) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE courses
+            SET taken_down_at = $1, is_active = is_active AND $1 IS NULL, updated_at = NOW()
+            WHERE id = $2
+            "#
+        )
+        .bind(taken_down_at)
+        .bind(course_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_course(&self, course_id: Uuid, deleted_at: DateTime<Utc>) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        // Enrollments take the same lock, so none can land after the check
+        lock_course(&mut tx, course_id).await?;
+        let result = sqlx::query(
+            r#"
+            UPDATE courses SET deleted_at = $1
+            WHERE id = $2 AND deleted_at IS NULL
+              AND NOT EXISTS (SELECT 1 FROM enrollments WHERE course_id = $2 AND status = 'active')
+            "#
+        )
+        .bind(deleted_at)
+        .bind(course_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_deleted_courses(&self, tutor_id: Uuid) -> AppResult<Vec<Course>> {
+        let courses = sqlx::query_as::<_, Course>(
+            "SELECT * FROM courses WHERE tutor_id = $1 AND deleted_at IS NOT NULL ORDER BY deleted_at DESC"
+        )
+        .bind(tutor_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(courses)
+    }
+
+    async fn restore_course(&self, course_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("UPDATE courses SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL")
+            .bind(course_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn purge_deleted_courses(&self, deleted_before: DateTime<Utc>) -> AppResult<Vec<Uuid>> {
+        let ids = sqlx::query_scalar("DELETE FROM courses WHERE deleted_at < $1 RETURNING id")
+            .bind(deleted_before)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(ids)
+    }
+}
+
+#[async_trait]
+impl ReviewRepository for PgRepository {
+    async fn find_review(&self, tutor_id: Uuid, student_id: Uuid) -> AppResult<Option<TutorReview>> {
+        let review = sqlx::query_as::<_, TutorReview>(
+            "SELECT id, tutor_id, student_id, rating, comment, created_at, edited_at FROM tutor_reviews WHERE tutor_id = $1 AND student_id = $2"
+        )
+        .bind(tutor_id)
+        .bind(student_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(review)
+    }
+
+    async fn find_review_by_id(&self, review_id: Uuid) -> AppResult<Option<TutorReview>> {
+        let review = sqlx::query_as::<_, TutorReview>(
+            "SELECT id, tutor_id, student_id, rating, comment, created_at, edited_at FROM tutor_reviews WHERE id = $1"
+        )
+        .bind(review_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(review)
+    }
+
+    async fn find_review_with_student(&self, review_id: Uuid) -> AppResult<Option<ReviewWithStudent>> {
+        let query = format!("{} WHERE r.id = $1", REVIEW_WITH_STUDENT_SELECT);
+        let review = sqlx::query_as::<_, ReviewWithStudent>(&query)
+            .bind(review_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(review)
+    }
+
+    async fn list_reviews_for_tutor(&self, tutor_id: Uuid) -> AppResult<Vec<ReviewWithStudent>> {
+        let query = format!(
+            "{} JOIN tutors t ON r.tutor_id = t.id WHERE r.tutor_id = $1 AND t.deleted_at IS NULL ORDER BY r.created_at DESC",
+            REVIEW_WITH_STUDENT_SELECT
+        );
+        let reviews = sqlx::query_as::<_, ReviewWithStudent>(&query)
+            .bind(tutor_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(reviews)
+    }
+
+    async fn insert_review(&self, review: &TutorReview) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        // The unique constraint, not an earlier lookup, decides duplicates
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO tutor_reviews (id, tutor_id, student_id, rating, comment, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (tutor_id, student_id) DO NOTHING
+            "#
+        )
+        .bind(review.id)
+        .bind(review.tutor_id)
+        .bind(review.student_id)
+        .bind(review.rating)
+        .bind(&review.comment)
+        .bind(review.created_at)
+        .execute(&mut *tx)
+        .await?;
+        if inserted.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        adjust_rating(&mut tx, review.tutor_id, i64::from(review.rating), 1).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn update_review(&self, review: &TutorReview) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let previous: Option<(Uuid, i32)> = sqlx::query_as(
+            "SELECT tutor_id, rating FROM tutor_reviews WHERE id = $1 FOR UPDATE"
+        )
+        .bind(review.id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some((tutor_id, previous_rating)) = previous else {
+            return Ok(false);
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO review_edits (id, review_id, rating, comment, edited_at)
+            SELECT $2, id, rating, comment, COALESCE($3, NOW()) FROM tutor_reviews WHERE id = $1
+            "#
+        )
+        .bind(review.id)
+        .bind(Uuid::new_v4())
+        .bind(review.edited_at)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE tutor_reviews SET rating = $2, comment = $3, edited_at = $4 WHERE id = $1")
+            .bind(review.id)
+            .bind(review.rating)
+            .bind(&review.comment)
+            .bind(review.edited_at)
+            .execute(&mut *tx)
+            .await?;
+
+        if review.rating != previous_rating {
+            adjust_rating(&mut tx, tutor_id, i64::from(review.rating - previous_rating), 0).await?;
+        }
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn delete_review(&self, review_id: Uuid) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let deleted: Option<(Uuid, i32)> = sqlx::query_as(
+            "DELETE FROM tutor_reviews WHERE id = $1 RETURNING tutor_id, rating"
+        )
+        .bind(review_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some((tutor_id, rating)) = deleted else {
+            return Ok(false);
+        };
+
+        adjust_rating(&mut tx, tutor_id, -i64::from(rating), -1).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn list_review_edits(&self, review_id: Uuid) -> AppResult<Vec<ReviewEdit>> {
+        let edits = sqlx::query_as::<_, ReviewEdit>(
+            "SELECT * FROM review_edits WHERE review_id = $1 ORDER BY edited_at, id"
+        )
+        .bind(review_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(edits)
+    }
+
+    async fn insert_review_reply(&self, reply: &ReviewReply) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO review_replies (review_id, comment, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (review_id) DO NOTHING
+            "#
+        )
+        .bind(reply.review_id)
+        .bind(&reply.comment)
+        .bind(reply.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn refresh_tutor_rating(&self, tutor_id: Uuid) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE tutors t
+            SET rating_sum = r.rating_sum, total_reviews = r.total_reviews,
+                rating = CASE WHEN r.total_reviews > 0 THEN (r.rating_sum::FLOAT8 / r.total_reviews)::REAL END
+            FROM (
+                SELECT COALESCE(SUM(rating), 0)::INT8 AS rating_sum, COUNT(*)::INT4 AS total_reviews
+                FROM tutor_reviews
+                WHERE tutor_id = $1
+            ) r
+            WHERE t.id = $1
+            "#
+        )
+        .bind(tutor_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_rating_drift(&self) -> AppResult<Vec<RatingDrift>> {
+        let drift = sqlx::query_as::<_, RatingDrift>(
+            r#"
+            SELECT t.id AS tutor_id, t.rating AS stored_rating, t.total_reviews AS stored_total_reviews,
+                   CASE WHEN r.total_reviews > 0 THEN (r.rating_sum::FLOAT8 / r.total_reviews)::REAL END AS actual_rating,
+                   COALESCE(r.total_reviews, 0) AS actual_total_reviews
+            FROM tutors t
+            LEFT JOIN (
+                SELECT tutor_id, SUM(rating)::INT8 AS rating_sum, COUNT(*)::INT4 AS total_reviews
+                FROM tutor_reviews
+                GROUP BY tutor_id
+            ) r ON r.tutor_id = t.id
+            WHERE t.total_reviews <> COALESCE(r.total_reviews, 0)
+               OR t.rating_sum <> COALESCE(r.rating_sum, 0)
+               OR t.rating IS DISTINCT FROM
+                  CASE WHEN r.total_reviews > 0 THEN (r.rating_sum::FLOAT8 / r.total_reviews)::REAL END
+            ORDER BY t.id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(drift)
+    }
+}
+
+#[async_trait]
+impl AvailabilityRepository for PgRepository {
+    async fn find_schedule(&self, tutor_id: Uuid) -> AppResult<Option<TutorSchedule>> {
+        let schedule = sqlx::query_as::<_, TutorSchedule>(
+            "SELECT * FROM tutor_schedules WHERE tutor_id = $1"
+        )
+        .bind(tutor_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(schedule)
+    }
+
+    async fn replace_schedule(&self, schedule: &TutorSchedule, windows: &[AvailabilityWindow]) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO tutor_schedules (tutor_id, time_zone, slot_minutes, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (tutor_id) DO UPDATE
+            SET time_zone = EXCLUDED.time_zone, slot_minutes = EXCLUDED.slot_minutes
+            "#
+        )
+        .bind(schedule.tutor_id)
+        .bind(&schedule.time_zone)
+        .bind(schedule.slot_minutes)
+        .bind(schedule.created_at)
+        .bind(schedule.updated_at)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM availability_windows WHERE tutor_id = $1")
+            .bind(schedule.tutor_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for window in windows {
+            sqlx::query(
+                r#"
+                INSERT INTO availability_windows (id, tutor_id, weekday, start_time, end_time)
+                VALUES ($1, $2, $3, $4, $5)
+                "#
+            )
+            .bind(window.id)
+            .bind(window.tutor_id)
+            .bind(window.weekday)
+            .bind(window.start_time)
+            .bind(window.end_time)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn list_windows(&self, tutor_id: Uuid) -> AppResult<Vec<AvailabilityWindow>> {
+        let windows = sqlx::query_as::<_, AvailabilityWindow>(
+            "SELECT * FROM availability_windows WHERE tutor_id = $1 ORDER BY weekday, start_time"
+        )
+        .bind(tutor_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(windows)
+    }
+
+    async fn list_exceptions(&self, tutor_id: Uuid, from: NaiveDate, to: NaiveDate) -> AppResult<Vec<AvailabilityException>> {
+        let exceptions = sqlx::query_as::<_, AvailabilityException>(
+            r#"
+            SELECT * FROM availability_exceptions
+            WHERE tutor_id = $1 AND date BETWEEN $2 AND $3
+            ORDER BY date, start_time NULLS FIRST
+            "#
+        )
+        .bind(tutor_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(exceptions)
+    }
+
+    async fn insert_exception(&self, exception: &AvailabilityException) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO availability_exceptions (id, tutor_id, date, start_time, end_time, is_available, reason, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#
+        )
+        .bind(exception.id)
+        .bind(exception.tutor_id)
+        .bind(exception.date)
+        .bind(exception.start_time)
+        .bind(exception.end_time)
+        .bind(exception.is_available)
+        .bind(&exception.reason)
+        .bind(exception.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_exception(&self, tutor_id: Uuid, exception_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM availability_exceptions WHERE id = $1 AND tutor_id = $2")
+            .bind(exception_id)
+            .bind(tutor_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_blackouts(&self, tutor_id: Uuid, from: NaiveDate, to: NaiveDate) -> AppResult<Vec<AvailabilityBlackout>> {
+        let blackouts = sqlx::query_as::<_, AvailabilityBlackout>(
+            r#"
+            SELECT * FROM availability_blackouts
+            WHERE tutor_id = $1 AND start_date <= $3 AND end_date >= $2
+            ORDER BY start_date
+            "#
+        )
+        .bind(tutor_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(blackouts)
+    }
+
+    async fn insert_blackout(&self, blackout: &AvailabilityBlackout) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO availability_blackouts (id, tutor_id, start_date, end_date, reason, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#
+        )
+        .bind(blackout.id)
+        .bind(blackout.tutor_id)
+        .bind(blackout.start_date)
+        .bind(blackout.end_date)
+        .bind(&blackout.reason)
+        .bind(blackout.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_blackout(&self, tutor_id: Uuid, blackout_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM availability_blackouts WHERE id = $1 AND tutor_id = $2")
+            .bind(blackout_id)
+            .bind(tutor_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl BookingRepository for PgRepository {
+    async fn find_booking(&self, booking_id: Uuid) -> AppResult<Option<BookingWithDetails>> {
+        let booking = sqlx::query_as::<_, BookingWithDetails>(
+            &format!("{} WHERE b.id = $1", BOOKING_WITH_DETAILS_SELECT)
+        )
+        .bind(booking_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(booking)
+    }
+
+    async fn insert_booking(&self, booking: &Booking) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO bookings (id, course_id, tutor_id, student_id, starts_at, ends_at, status,
+                                  note, status_reason, price, cancellation_fee, reschedule_count,
+                                  created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            "#
+        )
+        .bind(booking.id)
+        .bind(booking.course_id)
+        .bind(booking.tutor_id)
+        .bind(booking.student_id)
+        .bind(booking.starts_at)
+        .bind(booking.ends_at)
+        .bind(booking.status)
+        .bind(&booking.note)
+        .bind(&booking.status_reason)
+        .bind(booking.price)
+        .bind(booking.cancellation_fee)
+        .bind(booking.reschedule_count)
+        .bind(booking.created_at)
+        .bind(booking.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(booking_write_error)?;
+
+        Ok(())
+    }
+
+    async fn update_booking(&self, booking: &Booking, expected_updated_at: DateTime<Utc>) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE bookings
+            SET starts_at = $1, ends_at = $2, status = $3, status_reason = $4,
+                cancellation_fee = $5, reschedule_count = $6, updated_at = $7
+            WHERE id = $8 AND updated_at = $9
+            "#
+        )
+        .bind(booking.starts_at)
+        .bind(booking.ends_at)
+        .bind(booking.status)
+        .bind(&booking.status_reason)
+        .bind(booking.cancellation_fee)
+        .bind(booking.reschedule_count)
+        .bind(booking.updated_at)
+        .bind(booking.id)
+        .bind(expected_updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(booking_write_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_bookings_for_student(&self, student_id: Uuid, status: Option<BookingStatus>) -> AppResult<Vec<BookingWithDetails>> {
+        let bookings = sqlx::query_as::<_, BookingWithDetails>(
+            &format!(
+                "{} WHERE b.student_id = $1 AND ($2::booking_status IS NULL OR b.status = $2) ORDER BY b.starts_at",
+                BOOKING_WITH_DETAILS_SELECT
+            )
+        )
+        .bind(student_id)
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(bookings)
+    }
+
+    async fn list_bookings_for_tutor(&self, tutor_id: Uuid, status: Option<BookingStatus>) -> AppResult<Vec<BookingWithDetails>> {
+        let bookings = sqlx::query_as::<_, BookingWithDetails>(
+            &format!(
+                "{} WHERE b.tutor_id = $1 AND ($2::booking_status IS NULL OR b.status = $2) ORDER BY b.starts_at",
+                BOOKING_WITH_DETAILS_SELECT
+            )
+        )
+        .bind(tutor_id)
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(bookings)
+    }
+
+    async fn list_busy_intervals(&self, tutor_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> AppResult<Vec<(DateTime<Utc>, DateTime<Utc>)>> {
+        let intervals = sqlx::query_as::<_, (DateTime<Utc>, DateTime<Utc>)>(
+            r#"
+            SELECT starts_at, ends_at FROM bookings
+            WHERE tutor_id = $1 AND status IN ('requested', 'confirmed')
+              AND starts_at < $3 AND ends_at > $2
+            ORDER BY starts_at
+            "#
+        )
+        .bind(tutor_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(intervals)
+    }
+
+    async fn find_booking_policy(&self, tutor_id: Uuid) -> AppResult<Option<BookingPolicy>> {
+        let policy = sqlx::query_as::<_, BookingPolicy>(
+            "SELECT * FROM booking_policies WHERE tutor_id = $1"
+        )
+        .bind(tutor_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(policy)
+    }
+
+    async fn upsert_booking_policy(&self, policy: &BookingPolicy) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO booking_policies (tutor_id, free_cancel_hours, late_cancel_fee_percent,
+                                          max_reschedules, reschedule_notice_hours, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (tutor_id) DO UPDATE
+            SET free_cancel_hours = EXCLUDED.free_cancel_hours,
+                late_cancel_fee_percent = EXCLUDED.late_cancel_fee_percent,
+                max_reschedules = EXCLUDED.max_reschedules,
+                reschedule_notice_hours = EXCLUDED.reschedule_notice_hours
+            "#
+        )
+        .bind(policy.tutor_id)
+        .bind(policy.free_cancel_hours)
+        .bind(policy.late_cancel_fee_percent)
+        .bind(policy.max_reschedules)
+        .bind(policy.reschedule_notice_hours)
+        .bind(policy.created_at)
+        .bind(policy.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EnrollmentRepository for PgRepository {
+    async fn find_enrollment(&self, course_id: Uuid, student_id: Uuid) -> AppResult<Option<EnrollmentWithDetails>> {
+        let query = format!("{} WHERE e.course_id = $1 AND e.student_id = $2", ENROLLMENT_WITH_DETAILS_SELECT);
+        let enrollment = sqlx::query_as::<_, EnrollmentWithDetails>(&query)
+            .bind(course_id)
+            .bind(student_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(enrollment)
+    }
+
+    async fn enroll_student(&self, course_id: Uuid, student_id: Uuid, now: DateTime<Utc>) -> AppResult<Enrollment> {
+        let mut tx = self.pool.begin().await?;
+
+        // The course row lock serialises seat counting per course
+        let max_students = lock_open_course(&mut tx, course_id).await?
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?;
+        let active = count_active(&mut tx, course_id).await?;
+        let status = match max_students {
+            Some(max) if active >= i64::from(max) => EnrollmentStatus::Waitlisted,
+            _ => EnrollmentStatus::Active,
+        };
+        let enrolled_at = (status == EnrollmentStatus::Active).then_some(now);
+
+        // Only a dropped row may be reopened
+        let enrollment = sqlx::query_as::<_, Enrollment>(
+            r#"
+            INSERT INTO enrollments (id, course_id, student_id, status, requested_at, enrolled_at,
+                                     created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $5, $5)
+            ON CONFLICT (course_id, student_id) DO UPDATE
+            SET status = EXCLUDED.status, requested_at = EXCLUDED.requested_at,
+                enrolled_at = EXCLUDED.enrolled_at, dropped_at = NULL
+            WHERE enrollments.status = 'dropped'
+            RETURNING *
+            "#
+        )
+        .bind(Uuid::new_v4())
+        .bind(course_id)
+        .bind(student_id)
+        .bind(status)
+        .bind(now)
+        .bind(enrolled_at)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Student is already enrolled in this course".to_string()))?;
+
+        tx.commit().await?;
+        Ok(enrollment)
+    }
+
+    async fn withdraw_enrollment(&self, enrollment_id: Uuid, now: DateTime<Utc>) -> AppResult<Vec<Enrollment>> {
+        let course_id: Option<Uuid> = sqlx::query_scalar("SELECT course_id FROM enrollments WHERE id = $1")
+            .bind(enrollment_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        let Some(course_id) = course_id else {
+            return Ok(Vec::new());
+        };
+
+        let mut tx = self.pool.begin().await?;
+        lock_course(&mut tx, course_id).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE enrollments SET status = 'dropped', dropped_at = $2
+            WHERE id = $1 AND status IN ('active', 'waitlisted')
+            "#
+        )
+        .bind(enrollment_id)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        let promoted = promote_from_waitlist(&mut tx, course_id, now).await?;
+        tx.commit().await?;
+        Ok(promoted)
+    }
+
+    async fn promote_waitlisted(&self, course_id: Uuid, now: DateTime<Utc>) -> AppResult<Vec<Enrollment>> {
+        let mut tx = self.pool.begin().await?;
+        lock_course(&mut tx, course_id).await?;
+        let promoted = promote_from_waitlist(&mut tx, course_id, now).await?;
+        tx.commit().await?;
+        Ok(promoted)
+    }
+
+    async fn list_enrollments_for_student(&self, student_id: Uuid, status: Option<EnrollmentStatus>) -> AppResult<Vec<EnrollmentWithDetails>> {
+        let query = format!(
+            "{} WHERE e.student_id = $1 AND ($2::enrollment_status IS NULL OR e.status = $2) ORDER BY e.requested_at DESC",
+            ENROLLMENT_WITH_DETAILS_SELECT
+        );
+        let enrollments = sqlx::query_as::<_, EnrollmentWithDetails>(&query)
+            .bind(student_id)
+            .bind(status)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(enrollments)
+    }
+
+    async fn list_enrollments_for_course(&self, course_id: Uuid, status: Option<EnrollmentStatus>) -> AppResult<Vec<EnrollmentWithDetails>> {
+        let query = format!(
+            "{} WHERE e.course_id = $1 AND ($2::enrollment_status IS NULL OR e.status = $2) ORDER BY e.requested_at, e.id",
+            ENROLLMENT_WITH_DETAILS_SELECT
+        );
+        let enrollments = sqlx::query_as::<_, EnrollmentWithDetails>(&query)
+            .bind(course_id)
+            .bind(status)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(enrollments)
+    }
+
+    async fn count_active_enrollments(&self, course_id: Uuid) -> AppResult<i64> {
+        Ok(count_active(&mut *self.pool.acquire().await?, course_id).await?)
+    }
+}
+
+// Moves a tutor's rating aggregate by `stars` across `reviews` reviews. The
+// row lock this takes serialises concurrent review writes for the tutor.
+async fn adjust_rating(conn: &mut PgConnection, tutor_id: Uuid, stars: i64, reviews: i32) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE tutors
+        SET rating_sum = rating_sum + $2, total_reviews = total_reviews + $3,
+            rating = CASE WHEN total_reviews + $3 > 0
+                          THEN ((rating_sum + $2)::FLOAT8 / (total_reviews + $3))::REAL END
+        WHERE id = $1
+        "#
+    )
+    .bind(tutor_id)
+    .bind(stars)
+    .bind(reviews)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+// Locks the course row and returns its capacity; None when the course is gone.
+// Enrollment and curriculum changes take this lock to serialise per course.
+async fn lock_course(conn: &mut PgConnection, course_id: Uuid) -> Result<Option<Option<i32>>, sqlx::Error> {
+    sqlx::query_scalar("SELECT max_students FROM courses WHERE id = $1 FOR UPDATE")
+        .bind(course_id)
+        .fetch_optional(conn)
+        .await
+}
+
+// `lock_course` for enrollments: None unless the course is active, not
+// deleted, and offered by a tutor profile that is not deleted either
+async fn lock_open_course(conn: &mut PgConnection, course_id: Uuid) -> Result<Option<Option<i32>>, sqlx::Error> {
+    let max_students = sqlx::query_scalar(
+        "SELECT max_students FROM courses WHERE id = $1 AND deleted_at IS NULL AND is_active FOR UPDATE"
+    )
+    .bind(course_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(max_students) = max_students else {
+        return Ok(None);
+    };
+
+    // Read after the lock is held, so a profile deleted meanwhile is seen
+    let tutor_live: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM courses c JOIN tutors t ON t.id = c.tutor_id
+            WHERE c.id = $1 AND t.deleted_at IS NULL
+        )
+        "#
+    )
+    .bind(course_id)
+    .fetch_one(conn)
+    .await?;
+    Ok(tutor_live.then_some(max_students))
+}
+
+async fn count_active(conn: &mut PgConnection, course_id: Uuid) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM enrollments WHERE course_id = $1 AND status = 'active'")
+        .bind(course_id)
+        .fetch_one(conn)
+        .await
+}
+
+// Moves the head of the waitlist into free seats. Callers hold the course lock.
+async fn promote_from_waitlist(conn: &mut PgConnection, course_id: Uuid, now: DateTime<Utc>) -> Result<Vec<Enrollment>, sqlx::Error> {
+    sqlx::query_as::<_, Enrollment>(
+        r#"
+        UPDATE enrollments SET status = 'active', enrolled_at = $2
+        WHERE id IN (
+            SELECT id FROM enrollments
+            WHERE course_id = $1 AND status = 'waitlisted'
+            ORDER BY requested_at, id
+            -- A NULL limit means no limit, i.e. everyone on the waitlist
+            LIMIT (
+                SELECT CASE WHEN c.max_students IS NOT NULL THEN GREATEST(c.max_students - (
+                    SELECT COUNT(*) FROM enrollments WHERE course_id = $1 AND status = 'active'
+                ), 0) END
+                FROM courses c WHERE c.id = $1
+            )
+        )
+        RETURNING *
+        "#
+    )
+    .bind(course_id)
+    .bind(now)
+    .fetch_all(conn)
+    .await
+}
+
+#[async_trait]
+impl CurriculumRepository for PgRepository {
+    async fn find_module(&self, module_id: Uuid) -> AppResult<Option<CourseModule>> {
+        let module = sqlx::query_as::<_, CourseModule>("SELECT * FROM course_modules WHERE id = $1")
+            .bind(module_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(module)
+    }
+
+    async fn find_lesson(&self, lesson_id: Uuid) -> AppResult<Option<Lesson>> {
+        let lesson = sqlx::query_as::<_, Lesson>("SELECT * FROM lessons WHERE id = $1")
+            .bind(lesson_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(lesson)
+    }
+
+    async fn list_modules(&self, course_id: Uuid) -> AppResult<Vec<CourseModule>> {
+        let modules = sqlx::query_as::<_, CourseModule>(
+            "SELECT * FROM course_modules WHERE course_id = $1 ORDER BY position"
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(modules)
+    }
+
+    async fn list_lessons(&self, course_id: Uuid) -> AppResult<Vec<Lesson>> {
+        let lessons = sqlx::query_as::<_, Lesson>(
+            r#"
+            SELECT l.* FROM lessons l
+            JOIN course_modules m ON l.module_id = m.id
+            WHERE l.course_id = $1
+            ORDER BY m.position, l.position
+            "#
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(lessons)
+    }
+
+    async fn append_module(&self, module: &CourseModule) -> AppResult<CourseModule> {
+        let mut tx = self.pool.begin().await?;
+        lock_course(&mut tx, module.course_id).await?
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?;
+
+        let module = sqlx::query_as::<_, CourseModule>(
+            r#"
+            INSERT INTO course_modules (id, course_id, title, position, created_at, updated_at)
+            SELECT $1, $2, $3, COALESCE(MAX(position), 0) + 1, $4, $5
+            FROM course_modules WHERE course_id = $2
+            RETURNING *
+            "#
+        )
+        .bind(module.id)
+        .bind(module.course_id)
+        .bind(&module.title)
+        .bind(module.created_at)
+        .bind(module.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(module)
+    }
+
+    async fn update_module(&self, module: &CourseModule) -> AppResult<()> {
+        sqlx::query("UPDATE course_modules SET title = $1, updated_at = $2 WHERE id = $3")
+            .bind(&module.title)
+            .bind(module.updated_at)
+            .bind(module.id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_module(&self, module_id: Uuid) -> AppResult<bool> {
+        let Some(module) = self.find_module(module_id).await? else {
+            return Ok(false);
+        };
+
+        let mut tx = self.pool.begin().await?;
+        lock_course(&mut tx, module.course_id).await?;
+
+        let position: Option<i32> = sqlx::query_scalar("DELETE FROM course_modules WHERE id = $1 RETURNING position")
+            .bind(module_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some(position) = position else {
+            return Ok(false);
+        };
+
+        // Close the gap
+        sqlx::query("UPDATE course_modules SET position = position - 1 WHERE course_id = $1 AND position > $2")
+            .bind(module.course_id)
+            .bind(position)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn append_lesson(&self, lesson: &Lesson) -> AppResult<Lesson> {
+        let mut tx = self.pool.begin().await?;
+        lock_course(&mut tx, lesson.course_id).await?
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?;
+
+        let lesson = sqlx::query_as::<_, Lesson>(
+            r#"
+            INSERT INTO lessons (id, course_id, module_id, title, content, estimated_minutes, resources,
+                                 position, created_at, updated_at)
+            SELECT $1, $2, $3, $4, $5, $6, $7, COALESCE(MAX(position), 0) + 1, $8, $9
+            FROM lessons WHERE module_id = $3
+            RETURNING *
+            "#
+        )
+        .bind(lesson.id)
+        .bind(lesson.course_id)
+        .bind(lesson.module_id)
+        .bind(&lesson.title)
+        .bind(&lesson.content)
+        .bind(lesson.estimated_minutes)
+        .bind(&lesson.resources)
+        .bind(lesson.created_at)
+        .bind(lesson.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(lesson)
+    }
+
+    async fn update_lesson(&self, lesson: &Lesson) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE lessons
+            SET title = $1, content = $2, estimated_minutes = $3, resources = $4, updated_at = $5
+            WHERE id = $6
+            "#
+        )
+        .bind(&lesson.title)
+        .bind(&lesson.content)
+        .bind(lesson.estimated_minutes)
+        .bind(&lesson.resources)
+        .bind(lesson.updated_at)
+        .bind(lesson.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_lesson(&self, lesson_id: Uuid) -> AppResult<bool> {
+        let Some(lesson) = self.find_lesson(lesson_id).await? else {
+            return Ok(false);
+        };
+
+        let mut tx = self.pool.begin().await?;
+        lock_course(&mut tx, lesson.course_id).await?;
+
+        // Take the module from the deleted row; a reorder may have moved the lesson
+        let deleted: Option<(Uuid, i32)> = sqlx::query_as(
+            "DELETE FROM lessons WHERE id = $1 RETURNING module_id, position"
+        )
+        .bind(lesson_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some((module_id, position)) = deleted else {
+            return Ok(false);
+        };
+
+        sqlx::query("UPDATE lessons SET position = position - 1 WHERE module_id = $1 AND position > $2")
+            .bind(module_id)
+            .bind(position)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn reorder_curriculum(&self, course_id: Uuid, order: &[ModuleOrder]) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+        lock_course(&mut tx, course_id).await?
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?;
+
+        let module_ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM course_modules WHERE course_id = $1")
+            .bind(course_id)
+            .fetch_all(&mut *tx)
+            .await?;
+        let lesson_ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM lessons WHERE course_id = $1")
+            .bind(course_id)
+            .fetch_all(&mut *tx)
+            .await?;
+        check_complete_order(order, &module_ids, &lesson_ids)?;
+
+        // Positions are only unique at commit, so rows can trade places freely
+        let (ids, positions): (Vec<Uuid>, Vec<i32>) = order.iter()
+            .enumerate()
+            .map(|(index, module)| (module.id, index as i32 + 1))
+            .unzip();
+        sqlx::query(
+            r#"
+            UPDATE course_modules m SET position = o.position
+            FROM UNNEST($1::uuid[], $2::int[]) AS o(id, position)
+            WHERE m.id = o.id AND m.position <> o.position
+            "#
+        )
+        .bind(ids)
+        .bind(positions)
+        .execute(&mut *tx)
+        .await?;
+
+        let mut ids = Vec::new();
+        let mut modules = Vec::new();
+        let mut positions = Vec::new();
+        for module in order {
+            for (index, lesson_id) in module.lessons.iter().enumerate() {
+                ids.push(*lesson_id);
+                modules.push(module.id);
+                positions.push(index as i32 + 1);
+            }
+        }
+        sqlx::query(
+            r#"
+            UPDATE lessons l SET module_id = o.module_id, position = o.position
+            FROM UNNEST($1::uuid[], $2::uuid[], $3::int[]) AS o(id, module_id, position)
+            WHERE l.id = o.id AND (l.module_id <> o.module_id OR l.position <> o.position)
+            "#
+        )
+        .bind(ids)
+        .bind(modules)
+        .bind(positions)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ProgressRepository for PgRepository {
+    async fn record_lesson_completion(&self, completion: &LessonCompletion) -> AppResult<LessonCompletion> {
+        let completion = sqlx::query_as::<_, LessonCompletion>(
+            r#"
+            INSERT INTO lesson_completions (id, lesson_id, course_id, student_id, minutes_spent,
+                                            completed_at, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (lesson_id, student_id) DO UPDATE
+            SET minutes_spent = lesson_completions.minutes_spent + EXCLUDED.minutes_spent
+            RETURNING *
+            "#
+        )
+        .bind(completion.id)
+        .bind(completion.lesson_id)
+        .bind(completion.course_id)
+        .bind(completion.student_id)
+        .bind(completion.minutes_spent)
+        .bind(completion.completed_at)
+        .bind(completion.created_at)
+        .bind(completion.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(completion)
+    }
+
+    async fn delete_lesson_completion(&self, lesson_id: Uuid, student_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM lesson_completions WHERE lesson_id = $1 AND student_id = $2")
+            .bind(lesson_id)
+            .bind(student_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_lesson_completions(&self, course_id: Uuid, student_id: Uuid) -> AppResult<Vec<LessonCompletion>> {
+        let completions = sqlx::query_as::<_, LessonCompletion>(
+            "SELECT * FROM lesson_completions WHERE course_id = $1 AND student_id = $2"
+        )
+        .bind(course_id)
+        .bind(student_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(completions)
+    }
+
+    async fn list_student_progress(&self, student_id: Uuid) -> AppResult<Vec<CourseProgress>> {
+        let query = format!(
+            "{} WHERE e.student_id = $1 AND e.status = 'active' GROUP BY e.id, c.id, u.id ORDER BY e.enrolled_at DESC",
+            COURSE_PROGRESS_SELECT
+        );
+        let progress = sqlx::query_as::<_, CourseProgress>(&query)
+            .bind(student_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(progress)
+    }
+
+    async fn list_course_progress(&self, course_id: Uuid) -> AppResult<Vec<CourseProgress>> {
+        let query = format!(
+            "{} WHERE e.course_id = $1 AND e.status = 'active' GROUP BY e.id, c.id, u.id ORDER BY e.enrolled_at, e.id",
+            COURSE_PROGRESS_SELECT
+        );
+        let progress = sqlx::query_as::<_, CourseProgress>(&query)
+            .bind(course_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(progress)
+    }
+}
+
+#[async_trait]
+impl QuizRepository for PgRepository {
+    async fn find_quiz(&self, quiz_id: Uuid) -> AppResult<Option<Quiz>> {
+        let quiz = sqlx::query_as::<_, Quiz>("SELECT * FROM quizzes WHERE id = $1")
+            .bind(quiz_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(quiz)
+    }
+
+    async fn list_quizzes_for_lesson(&self, lesson_id: Uuid) -> AppResult<Vec<Quiz>> {
+        let quizzes = sqlx::query_as::<_, Quiz>(
+            "SELECT * FROM quizzes WHERE lesson_id = $1 ORDER BY created_at, id"
+        )
+        .bind(lesson_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(quizzes)
+    }
+
+    async fn insert_quiz(&self, quiz: &Quiz) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO quizzes (id, lesson_id, course_id, title, instructions, max_attempts,
+                                 time_limit_minutes, questions, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#
+        )
+        .bind(quiz.id)
+        .bind(quiz.lesson_id)
+        .bind(quiz.course_id)
+        .bind(&quiz.title)
+        .bind(&quiz.instructions)
+        .bind(quiz.max_attempts)
+        .bind(quiz.time_limit_minutes)
+        .bind(&quiz.questions)
+        .bind(quiz.created_at)
+        .bind(quiz.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_quiz(&self, quiz: &Quiz) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE quizzes
+            SET title = $1, instructions = $2, max_attempts = $3, time_limit_minutes = $4,
+                questions = $5, updated_at = $6
+            WHERE id = $7
+            "#
+        )
+        .bind(&quiz.title)
+        .bind(&quiz.instructions)
+        .bind(quiz.max_attempts)
+        .bind(quiz.time_limit_minutes)
+        .bind(&quiz.questions)
+        .bind(quiz.updated_at)
+        .bind(quiz.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_quiz(&self, quiz_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM quizzes WHERE id = $1")
+            .bind(quiz_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_quiz_attempt(&self, attempt_id: Uuid) -> AppResult<Option<QuizAttempt>> {
+        let attempt = sqlx::query_as::<_, QuizAttempt>("SELECT * FROM quiz_attempts WHERE id = $1")
+            .bind(attempt_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(attempt)
+    }
+
+    async fn list_quiz_attempts(&self, quiz_id: Uuid, student_id: Option<Uuid>) -> AppResult<Vec<QuizAttempt>> {
+        let attempts = sqlx::query_as::<_, QuizAttempt>(
+            r#"
+            SELECT * FROM quiz_attempts
+            WHERE quiz_id = $1 AND ($2::uuid IS NULL OR student_id = $2)
+            ORDER BY started_at, attempt_number
+            "#
+        )
+        .bind(quiz_id)
+        .bind(student_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(attempts)
+    }
+
+    async fn start_quiz_attempt(&self, attempt: &QuizAttempt, max_attempts: Option<i32>) -> AppResult<QuizAttempt> {
+        let mut tx = self.pool.begin().await?;
+
+        // The quiz row lock serialises attempt numbering
+        let locked: Option<Uuid> = sqlx::query_scalar("SELECT id FROM quizzes WHERE id = $1 FOR UPDATE")
+            .bind(attempt.quiz_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if locked.is_none() {
+            return Err(AppError::NotFound("Quiz not found".to_string()));
+        }
+
+        let used: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM quiz_attempts WHERE quiz_id = $1 AND student_id = $2"
+        )
+        .bind(attempt.quiz_id)
+        .bind(attempt.student_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if let Some(max) = max_attempts.filter(|max| used >= i64::from(*max)) {
+            return Err(AppError::Conflict(format!("All {} attempts at this quiz have been used", max)));
+        }
+
+        let attempt = sqlx::query_as::<_, QuizAttempt>(
+            r#"
+            INSERT INTO quiz_attempts (id, quiz_id, student_id, attempt_number, started_at, deadline_at,
+                                       max_score, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            "#
+        )
+        .bind(attempt.id)
+        .bind(attempt.quiz_id)
+        .bind(attempt.student_id)
+        .bind(used as i32 + 1)
+        .bind(attempt.started_at)
+        .bind(attempt.deadline_at)
+        .bind(attempt.max_score)
+        .bind(attempt.created_at)
+        .bind(attempt.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(attempt)
+    }
+
+    async fn finish_quiz_attempt(&self, attempt: &QuizAttempt) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE quiz_attempts
+            SET submitted_at = $1, timed_out = $2, score = $3, max_score = $4, answers = $5, results = $6
+            WHERE id = $7 AND submitted_at IS NULL
+            "#
+        )
+        .bind(attempt.submitted_at)
+        .bind(attempt.timed_out)
+        .bind(attempt.score)
+        .bind(attempt.max_score)
+        .bind(&attempt.answers)
+        .bind(&attempt.results)
+        .bind(attempt.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+// Everything but the content, which only downloads need
+const ASSIGNMENT_FILE_COLUMNS: &str =
+    "id, assignment_id, student_id, stored_file_id, file_name, content_type, size_bytes, created_at";
+
+#[async_trait]
+impl AssignmentRepository for PgRepository {
+    async fn find_assignment(&self, assignment_id: Uuid) -> AppResult<Option<Assignment>> {
+        let assignment = sqlx::query_as::<_, Assignment>("SELECT * FROM assignments WHERE id = $1")
+            .bind(assignment_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(assignment)
+    }
+
+    async fn list_assignments_for_course(&self, course_id: Uuid) -> AppResult<Vec<Assignment>> {
+        let assignments = sqlx::query_as::<_, Assignment>(
+            "SELECT * FROM assignments WHERE course_id = $1 ORDER BY due_at, id"
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(assignments)
+    }
+
+    async fn insert_assignment(&self, assignment: &Assignment) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO assignments (id, course_id, title, instructions, due_at, accept_late, max_submissions,
+                                     rubric, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#
+        )
+        .bind(assignment.id)
+        .bind(assignment.course_id)
+        .bind(&assignment.title)
+        .bind(&assignment.instructions)
+        .bind(assignment.due_at)
+        .bind(assignment.accept_late)
+        .bind(assignment.max_submissions)
+        .bind(&assignment.rubric)
+        .bind(assignment.created_at)
+        .bind(assignment.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_assignment(&self, assignment: &Assignment) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE assignments
+            SET title = $1, instructions = $2, due_at = $3, accept_late = $4, max_submissions = $5, rubric = $6
+            WHERE id = $7
+            "#
+        )
+        .bind(&assignment.title)
+        .bind(&assignment.instructions)
+        .bind(assignment.due_at)
+        .bind(assignment.accept_late)
+        .bind(assignment.max_submissions)
+        .bind(&assignment.rubric)
+        .bind(assignment.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_assignment(&self, assignment_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM assignments WHERE id = $1")
+            .bind(assignment_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_assignment_file(&self, file: &AssignmentFile) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO assignment_files (id, assignment_id, student_id, stored_file_id, file_name, content_type,
+                                          size_bytes, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#
+        )
+        .bind(file.id)
+        .bind(file.assignment_id)
+        .bind(file.student_id)
+        .bind(file.stored_file_id)
+        .bind(&file.file_name)
+        .bind(&file.content_type)
+        .bind(file.size_bytes)
+        .bind(file.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_assignment_file(&self, file_id: Uuid) -> AppResult<Option<AssignmentFile>> {
+        let file = sqlx::query_as::<_, AssignmentFile>(
+            &format!("SELECT {} FROM assignment_files WHERE id = $1", ASSIGNMENT_FILE_COLUMNS)
+        )
+        .bind(file_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(file)
+    }
+
+    async fn read_assignment_file(&self, file_id: Uuid) -> AppResult<Option<Vec<u8>>> {
+        let content: Option<Option<Vec<u8>>> = sqlx::query_scalar("SELECT content FROM assignment_files WHERE id = $1")
+            .bind(file_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(content.flatten())
+    }
+
+    async fn list_files_for_assignment(&self, assignment_id: Uuid) -> AppResult<Vec<AssignmentFile>> {
+        let files = sqlx::query_as::<_, AssignmentFile>(
+            &format!(
+                "SELECT {} FROM assignment_files WHERE assignment_id = $1 ORDER BY created_at, id",
+                ASSIGNMENT_FILE_COLUMNS
+            )
+        )
+        .bind(assignment_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(files)
+    }
+
+    async fn list_assignment_files(&self, file_ids: &[Uuid]) -> AppResult<Vec<AssignmentFile>> {
+        let files = sqlx::query_as::<_, AssignmentFile>(
+            &format!(
+                "SELECT {} FROM assignment_files WHERE id = ANY($1) ORDER BY created_at, id",
+                ASSIGNMENT_FILE_COLUMNS
+            )
+        )
+        .bind(file_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(files)
+    }
+
+    async fn find_submission(&self, submission_id: Uuid) -> AppResult<Option<AssignmentSubmission>> {
+        let submission = sqlx::query_as::<_, AssignmentSubmission>(
+            "SELECT * FROM assignment_submissions WHERE id = $1"
+        )
+        .bind(submission_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(submission)
+    }
+
+    async fn list_submissions(&self, assignment_id: Uuid, student_id: Option<Uuid>) -> AppResult<Vec<AssignmentSubmission>> {
+        let submissions = sqlx::query_as::<_, AssignmentSubmission>(
+            r#"
+            SELECT * FROM assignment_submissions
+            WHERE assignment_id = $1 AND ($2::uuid IS NULL OR student_id = $2)
+            ORDER BY submitted_at, submission_number
+            "#
+        )
+        .bind(assignment_id)
+        .bind(student_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(submissions)
+    }
+
+    async fn insert_submission(
+        &self,
+        submission: &AssignmentSubmission,
+        max_submissions: Option<i32>,
+    ) -> AppResult<AssignmentSubmission> {
+        let mut tx = self.pool.begin().await?;
+
+        // The assignment row lock serialises submission numbering
+        let locked: Option<Uuid> = sqlx::query_scalar("SELECT id FROM assignments WHERE id = $1 FOR UPDATE")
+            .bind(submission.assignment_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if locked.is_none() {
+            return Err(AppError::NotFound("Assignment not found".to_string()));
+        }
+
+        let used: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM assignment_submissions WHERE assignment_id = $1 AND student_id = $2"
+        )
+        .bind(submission.assignment_id)
+        .bind(submission.student_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if let Some(max) = max_submissions.filter(|max| used >= i64::from(*max)) {
+            return Err(AppError::Conflict(format!("All {} submissions for this assignment have been used", max)));
+        }
+
+        let submission = sqlx::query_as::<_, AssignmentSubmission>(
+            r#"
+            INSERT INTO assignment_submissions (id, assignment_id, student_id, submission_number, text, file_ids,
+                                                submitted_at, is_late, max_score, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#
+        )
+        .bind(submission.id)
+        .bind(submission.assignment_id)
+        .bind(submission.student_id)
+        .bind(used as i32 + 1)
+        .bind(&submission.text)
+        .bind(&submission.file_ids)
+        .bind(submission.submitted_at)
+        .bind(submission.is_late)
+        .bind(submission.max_score)
+        .bind(submission.created_at)
+        .bind(submission.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(submission)
+    }
+
+    async fn update_submission_grade(&self, submission: &AssignmentSubmission) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE assignment_submissions
+            SET score = $1, max_score = $2, rubric_scores = $3, feedback = $4, graded_at = $5,
+                grade_published_at = $6
+            WHERE id = $7
+            "#
+        )
+        .bind(submission.score)
+        .bind(submission.max_score)
+        .bind(&submission.rubric_scores)
+        .bind(&submission.feedback)
+        .bind(submission.graded_at)
+        .bind(submission.grade_published_at)
+        .bind(submission.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for PgRepository {
+    async fn insert_notification(&self, notification: &Notification) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO notifications (id, user_id, kind, title, body, link, read_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#
+        )
+        .bind(notification.id)
+        .bind(notification.user_id)
+        .bind(&notification.kind)
+        .bind(&notification.title)
+        .bind(&notification.body)
+        .bind(&notification.link)
+        .bind(notification.read_at)
+        .bind(notification.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_notifications(&self, user_id: Uuid, unread_only: bool) -> AppResult<Vec<Notification>> {
+        let notifications = sqlx::query_as::<_, Notification>(
+            r#"
+            SELECT * FROM notifications
+            WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+            ORDER BY created_at DESC, id DESC
+            "#
+        )
+        .bind(user_id)
+        .bind(unread_only)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(notifications)
+    }
+
+    async fn mark_notification_read(&self, notification_id: Uuid, user_id: Uuid, read_at: DateTime<Utc>) -> AppResult<bool> {
+        let result = sqlx::query(
+            "UPDATE notifications SET read_at = COALESCE(read_at, $1) WHERE id = $2 AND user_id = $3"
+        )
+        .bind(read_at)
+        .bind(notification_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl CertificateRepository for PgRepository {
+    async fn find_certificate_by_code(&self, code: &str) -> AppResult<Option<Certificate>> {
+        let certificate = sqlx::query_as::<_, Certificate>("SELECT * FROM certificates WHERE code = $1")
+            .bind(code)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(certificate)
+    }
+
+    async fn find_certificate(&self, course_id: Uuid, student_id: Uuid) -> AppResult<Option<Certificate>> {
+        let certificate = sqlx::query_as::<_, Certificate>(
+            "SELECT * FROM certificates WHERE course_id = $1 AND student_id = $2"
+        )
+        .bind(course_id)
+        .bind(student_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(certificate)
+    }
+
+    async fn list_certificates_for_student(&self, student_id: Uuid) -> AppResult<Vec<Certificate>> {
+        let certificates = sqlx::query_as::<_, Certificate>(
+            "SELECT * FROM certificates WHERE student_id = $1 ORDER BY issued_at DESC, id DESC"
+        )
+        .bind(student_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(certificates)
+    }
+
+    async fn insert_certificate(&self, certificate: &Certificate) -> AppResult<Certificate> {
+        let inserted = sqlx::query_as::<_, Certificate>(
+            r#"
+            INSERT INTO certificates (id, code, course_id, student_id, student_name, course_title, tutor_name,
+                                      completed_at, issued_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (course_id, student_id) DO NOTHING
+            RETURNING *
+            "#
+        )
+        .bind(certificate.id)
+        .bind(&certificate.code)
+        .bind(certificate.course_id)
+        .bind(certificate.student_id)
+        .bind(&certificate.student_name)
+        .bind(&certificate.course_title)
+        .bind(&certificate.tutor_name)
+        .bind(certificate.completed_at)
+        .bind(certificate.issued_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match inserted {
+            Some(certificate) => Ok(certificate),
+            // Issued concurrently; keep the first one
+            None => sqlx::query_as::<_, Certificate>(
+                "SELECT * FROM certificates WHERE course_id = $1 AND student_id = $2"
+            )
+            .bind(certificate.course_id)
+            .bind(certificate.student_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::from),
+        }
+    }
+}
+
+#[async_trait]
+impl FileRepository for PgRepository {
+    async fn insert_stored_file(&self, file: &StoredFile) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO stored_files (id, owner_id, purpose, file_name, content_type, size_bytes, sha256, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#
+        )
+        .bind(file.id)
+        .bind(file.owner_id)
+        .bind(file.purpose)
+        .bind(&file.file_name)
+        .bind(&file.content_type)
+        .bind(file.size_bytes)
+        .bind(&file.sha256)
+        .bind(file.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_stored_file(&self, file_id: Uuid) -> AppResult<Option<StoredFile>> {
+        let file = sqlx::query_as::<_, StoredFile>("SELECT * FROM stored_files WHERE id = $1")
+            .bind(file_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(file)
+    }
+
+    async fn list_stored_files(&self, owner_id: Uuid) -> AppResult<Vec<StoredFile>> {
+        let files = sqlx::query_as::<_, StoredFile>(
+            r#"
+            SELECT * FROM stored_files
+            WHERE owner_id = $1 AND purpose = 'upload'
+            ORDER BY created_at DESC, id DESC
+            "#
+        )
+        .bind(owner_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(files)
+    }
+
+    async fn delete_stored_file(&self, file_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM stored_files WHERE id = $1")
+            .bind(file_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn count_stored_files_with_hash(&self, sha256: &str) -> AppResult<i64> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM stored_files WHERE sha256 = $1")
+            .bind(sha256)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl AvatarRepository for PgRepository {
+    async fn find_avatar(&self, user_id: Uuid) -> AppResult<Option<Avatar>> {
+        let avatar = sqlx::query_as::<_, Avatar>("SELECT * FROM user_avatars WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(avatar)
+    }
+
+    async fn set_avatar(&self, avatar: &Avatar) -> AppResult<Option<Avatar>> {
+        let mut tx = self.pool.begin().await?;
+
+        let previous = sqlx::query_as::<_, Avatar>("SELECT * FROM user_avatars WHERE user_id = $1 FOR UPDATE")
+            .bind(avatar.user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_avatars (user_id, full_file_id, large_file_id, medium_file_id, small_file_id, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_id) DO UPDATE
+            SET full_file_id = EXCLUDED.full_file_id, large_file_id = EXCLUDED.large_file_id,
+                medium_file_id = EXCLUDED.medium_file_id, small_file_id = EXCLUDED.small_file_id,
+                updated_at = EXCLUDED.updated_at
+            "#
+        )
+        .bind(avatar.user_id)
+        .bind(avatar.full_file_id)
+        .bind(avatar.large_file_id)
+        .bind(avatar.medium_file_id)
+        .bind(avatar.small_file_id)
+        .bind(avatar.updated_at)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE users SET avatar_updated_at = $2 WHERE id = $1")
+            .bind(avatar.user_id)
+            .bind(avatar.updated_at)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(previous)
+    }
+
+    async fn delete_avatar(&self, user_id: Uuid) -> AppResult<Option<Avatar>> {
+        let mut tx = self.pool.begin().await?;
+
+        let removed = sqlx::query_as::<_, Avatar>("DELETE FROM user_avatars WHERE user_id = $1 RETURNING *")
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        sqlx::query("UPDATE users SET avatar_updated_at = NULL WHERE id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(removed)
+    }
+}
+
+// Appends to the verification history inside the caller's transaction
+async fn insert_verification_event(conn: &mut PgConnection, event: &VerificationEvent) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO verification_events (id, tutor_id, request_id, action, actor_id, reason, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#
+    )
+    .bind(event.id)
+    .bind(event.tutor_id)
+    .bind(event.request_id)
+    .bind(event.action)
+    .bind(event.actor_id)
+    .bind(&event.reason)
+    .bind(event.created_at)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl VerificationRepository for PgRepository {
+    async fn insert_verification_request(
+        &self,
+        request: &VerificationRequest,
+        documents: &[VerificationDocument],
+        event: &VerificationEvent,
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO verification_requests (id, tutor_id, status, note, submitted_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#
+        )
+        .bind(request.id)
+        .bind(request.tutor_id)
+        .bind(request.status)
+        .bind(&request.note)
+        .bind(request.submitted_at)
+        .execute(&mut *tx)
+        .await?;
+
+        for document in documents {
+            sqlx::query(
+                r#"
+                INSERT INTO verification_documents (id, request_id, kind, file_id, position)
+                VALUES ($1, $2, $3, $4, $5)
+                "#
+            )
+            .bind(document.id)
+            .bind(document.request_id)
+            .bind(document.kind)
+            .bind(document.file_id)
+            .bind(document.position)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query("UPDATE stored_files SET purpose = 'credential' WHERE id = ANY($1)")
+            .bind(documents.iter().map(|document| document.file_id).collect::<Vec<_>>())
+            .execute(&mut *tx)
+            .await?;
+
+        insert_verification_event(&mut tx, event).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_verification_request(&self, request_id: Uuid) -> AppResult<Option<VerificationRequestWithTutor>> {
+        let request = sqlx::query_as::<_, VerificationRequestWithTutor>(
+            &format!("{} WHERE r.id = $1", VERIFICATION_REQUEST_SELECT)
+        )
+        .bind(request_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn list_verification_requests(&self, status: VerificationStatus) -> AppResult<Vec<VerificationRequestWithTutor>> {
+        let requests = sqlx::query_as::<_, VerificationRequestWithTutor>(
+            &format!("{} WHERE r.status = $1 ORDER BY r.submitted_at, r.id", VERIFICATION_REQUEST_SELECT)
+        )
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(requests)
+    }
+
+    async fn list_tutor_verification_requests(&self, tutor_id: Uuid) -> AppResult<Vec<VerificationRequestWithTutor>> {
+        let requests = sqlx::query_as::<_, VerificationRequestWithTutor>(
+            &format!("{} WHERE r.tutor_id = $1 ORDER BY r.submitted_at DESC, r.id DESC", VERIFICATION_REQUEST_SELECT)
+        )
+        .bind(tutor_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(requests)
+    }
+
+    async fn list_verification_documents(&self, request_id: Uuid) -> AppResult<Vec<VerificationDocument>> {
+        let documents = sqlx::query_as::<_, VerificationDocument>(
+            "SELECT * FROM verification_documents WHERE request_id = $1 ORDER BY position"
+        )
+        .bind(request_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(documents)
+    }
+
+    async fn decide_verification_request(&self, request: &VerificationRequest, event: &VerificationEvent) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE verification_requests
+            SET status = $2, reviewed_by = $3, reviewed_at = $4, decision_reason = $5
+            WHERE id = $1 AND status = 'pending'
+            "#
+        )
+        .bind(request.id)
+        .bind(request.status)
+        .bind(request.reviewed_by)
+        .bind(request.reviewed_at)
+        .bind(&request.decision_reason)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        if request.status == VerificationStatus::Approved {
+            sqlx::query("UPDATE tutors SET is_verified = true, updated_at = $2 WHERE id = $1")
+                .bind(request.tutor_id)
+                .bind(event.created_at)
+                .execute(&mut *tx)
+                .await?;
+        }
+        insert_verification_event(&mut tx, event).await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn set_tutor_verified(&self, tutor_id: Uuid, is_verified: bool, event: &VerificationEvent) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("UPDATE tutors SET is_verified = $2, updated_at = $3 WHERE id = $1")
+            .bind(tutor_id)
+            .bind(is_verified)
+            .bind(event.created_at)
+            .execute(&mut *tx)
+            .await?;
+        insert_verification_event(&mut tx, event).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn list_verification_events(&self, tutor_id: Uuid) -> AppResult<Vec<VerificationEvent>> {
+        let events = sqlx::query_as::<_, VerificationEvent>(
+            "SELECT * FROM verification_events WHERE tutor_id = $1 ORDER BY created_at, id"
+        )
+        .bind(tutor_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+}
+
+#[async_trait]
+impl CalendarRepository for PgRepository {
+    async fn find_calendar_feed(&self, user_id: Uuid) -> AppResult<Option<CalendarFeed>> {
+        let feed = sqlx::query_as::<_, CalendarFeed>(
+            "SELECT * FROM calendar_feeds WHERE user_id = $1"
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(feed)
+    }
+
+    async fn find_calendar_feed_by_token(&self, token: &str) -> AppResult<Option<CalendarFeed>> {
+        let feed = sqlx::query_as::<_, CalendarFeed>(
+            "SELECT * FROM calendar_feeds WHERE token = $1"
+        )
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(feed)
+    }
+
+    async fn upsert_calendar_feed(&self, feed: &CalendarFeed) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO calendar_feeds (user_id, token, time_zone, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id) DO UPDATE
+            SET token = EXCLUDED.token, time_zone = EXCLUDED.time_zone
+            "#
+        )
+        .bind(feed.user_id)
+        .bind(&feed.token)
+        .bind(&feed.time_zone)
+        .bind(feed.created_at)
+        .bind(feed.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StatsRepository for PgRepository {
+    async fn platform_stats(&self) -> AppResult<PlatformStats> {
+        let stats = sqlx::query_as::<_, PlatformStats>(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM users) AS total_users,
+                (SELECT COUNT(*) FROM users WHERE is_active) AS active_users,
+                (SELECT COUNT(*) FROM users WHERE role = 'student') AS students,
+                (SELECT COUNT(*) FROM users WHERE role = 'tutor') AS tutors,
+                (SELECT COUNT(*) FROM users WHERE role = 'admin') AS admins,
+                (SELECT COUNT(*) FROM tutors WHERE deleted_at IS NULL) AS tutor_profiles,
+                (SELECT COUNT(*) FROM tutors WHERE deleted_at IS NULL AND is_verified) AS verified_tutors,
+                (SELECT COUNT(*) FROM tutors WHERE deleted_at IS NULL AND is_available) AS available_tutors,
+                (SELECT COUNT(*) FROM courses WHERE deleted_at IS NULL) AS courses,
+                (SELECT COUNT(*) FROM courses WHERE deleted_at IS NULL AND is_active) AS active_courses,
+                (SELECT COUNT(*) FROM tutor_reviews) AS reviews,
+                (SELECT AVG(rating)::FLOAT8 FROM tutor_reviews) AS average_rating
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(stats)
+    }
+}
+
+#[async_trait]
+impl AuditRepository for PgRepository {
+    async fn insert_audit_entry(&self, entry: &AuditEntry) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (
+                id, actor_id, action, target_type, target_id, changes, reason, ip_address, request_id, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#
+        )
+        .bind(entry.id)
+        .bind(entry.actor_id)
+        .bind(&entry.action)
+        .bind(&entry.target_type)
+        .bind(entry.target_id)
+        .bind(&entry.changes)
+        .bind(&entry.reason)
+        .bind(&entry.ip_address)
+        .bind(&entry.request_id)
+        .bind(entry.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn search_audit_entries(&self, query: &AuditLogQuery, limit: i64, offset: i64) -> AppResult<(Vec<AuditEntry>, i64)> {
+        let entries = sqlx::query_as::<_, AuditEntry>(&format!(
+            "SELECT * FROM audit_log {} ORDER BY created_at DESC, id DESC LIMIT $6 OFFSET $7",
+            AUDIT_LOG_FILTER
+        ))
+        .bind(query.actor_id)
+        .bind(&query.target_type)
+        .bind(query.target_id)
+        .bind(query.from)
+        .bind(query.to)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM audit_log {}", AUDIT_LOG_FILTER))
+            .bind(query.actor_id)
+            .bind(&query.target_type)
+            .bind(query.target_id)
+            .bind(query.from)
+            .bind(query.to)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok((entries, total))
+    }
+}
@@ -0,0 +1,379 @@
+// Synthetic data for demo and load-test environments. Generation is driven
+// by a seeded ChaCha RNG, whose output is stable across platforms and
+// releases, so the same seed always yields the same ids, names and ratings.
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::seq::index::sample;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use uuid::{Builder, Uuid};
+use crate::models::{Course, DifficultyLevel, TutorProfile, TutorReview, User, UserRole};
+use super::FixtureSet;
+
+pub const SEED_EMAIL_DOMAIN: &str = "seed.ezytutor.dev";
+
+const FIRST_NAMES: &[&str] = &[
+    "Amara", "Ben", "Carla", "Dev", "Elena", "Farid", "Grace", "Hiro", "Ines", "Jonas",
+    "Kemi", "Liam", "Mei", "Nadia", "Omar", "Priya", "Quinn", "Rosa", "Sven", "Tariq",
+    "Uma", "Victor", "Wen", "Ximena", "Yusuf", "Zoe", "Aiden", "Bianca", "Chen", "Dara",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Adeyemi", "Bauer", "Castillo", "Dubois", "Eriksen", "Fischer", "Gupta", "Hassan",
+    "Ivanova", "Jensen", "Kowalski", "Lopez", "Moreau", "Nakamura", "Okafor", "Petrov",
+    "Quintero", "Rossi", "Silva", "Tanaka", "Usman", "Varga", "Walsh", "Xu", "Yamada", "Zhang",
+];
+
+// Each category lists the specializations a tutor in it can hold
+const CATEGORIES: &[(&str, &[&str])] = &[
+    ("Mathematics", &["Algebra", "Calculus", "Statistics", "Geometry", "Linear Algebra"]),
+    ("Computer Science", &["Rust", "Python", "Algorithms", "Web Development", "Databases"]),
+    ("Physics", &["Mechanics", "Electromagnetism", "Quantum Physics", "Thermodynamics"]),
+    ("Chemistry", &["Organic Chemistry", "Biochemistry", "Analytical Chemistry"]),
+    ("Languages", &["Spanish", "French", "Mandarin", "English Writing"]),
+    ("Music", &["Piano", "Guitar", "Music Theory"]),
+    ("Business", &["Accounting", "Marketing", "Economics"]),
+];
+
+const COURSE_TITLES: &[&str] = &[
+    "Introduction to {}",
+    "{} Crash Course",
+    "Mastering {}",
+    "{} for Exam Preparation",
+    "Practical {}",
+    "{} Workshop",
+    "{} from First Principles",
+];
+
+const DURATIONS: &[i32] = &[30, 45, 60, 90, 120];
+
+const POSITIVE_COMMENTS: &[&str] = &[
+    "Explains things clearly and patiently.",
+    "Sessions were well structured and I improved quickly.",
+    "Great examples, I finally understand the topic.",
+    "Always prepared and very encouraging.",
+];
+
+const MIXED_COMMENTS: &[&str] = &[
+    "Knowledgeable, but sessions sometimes felt rushed.",
+    "Helpful overall, though I wanted more practice problems.",
+];
+
+const NEGATIVE_COMMENTS: &[&str] = &[
+    "Hard to follow and often late.",
+    "Did not cover what was promised.",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedScale {
+    // A handful of rows to click through
+    Demo,
+    // Enough rows for pagination and search to matter
+    Medium,
+    // Hundreds of thousands of rows for load testing
+    Load,
+}
+
+#[derive(Debug, Clone)]
+pub struct SeedConfig {
+    pub seed: u64,
+    pub students: usize,
+    pub tutors: usize,
+    // Averages; each tutor gets between zero and twice as many
+    pub courses_per_tutor: usize,
+    pub reviews_per_tutor: usize,
+}
+
+impl SeedConfig {
+    pub fn for_scale(scale: SeedScale, seed: u64) -> Self {
+        let (students, tutors, courses_per_tutor, reviews_per_tutor) = match scale {
+            SeedScale::Demo => (40, 12, 3, 6),
+            SeedScale::Medium => (5_000, 500, 4, 10),
+            SeedScale::Load => (200_000, 20_000, 5, 12),
+        };
+
+        SeedConfig { seed, students, tutors, courses_per_tutor, reviews_per_tutor }
+    }
+}
+
+struct Generator {
+    rng: ChaCha8Rng,
+    // Fixed so that timestamps are as reproducible as everything else
+    epoch: DateTime<Utc>,
+}
+
+impl Generator {
+    fn uuid(&mut self) -> Uuid {
+        Builder::from_random_bytes(self.rng.gen()).into_uuid()
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        items.choose(&mut self.rng).expect("seed word lists are not empty")
+    }
+
+    fn after(&mut self, start: DateTime<Utc>, max_days: i64) -> DateTime<Utc> {
+        start + Duration::seconds(self.rng.gen_range(0..max_days * 86_400))
+    }
+
+    fn user(&mut self, index: usize, role: UserRole, password_hash: &str) -> User {
+        let first_name = self.pick(FIRST_NAMES).to_string();
+        let last_name = self.pick(LAST_NAMES).to_string();
+        let created_at = self.after(self.epoch, 365);
+
+        User {
+            id: self.uuid(),
+            email: format!(
+                "{}.{}.{}@{}",
+                first_name.to_lowercase(), last_name.to_lowercase(), index, SEED_EMAIL_DOMAIN
+            ),
+            password_hash: password_hash.to_string(),
+            first_name,
+            last_name,
+            role,
+            // A few dormant accounts keep the active filters honest
+            is_active: self.rng.gen_bool(0.97),
+            avatar_updated_at: None,
+            token_version: 0,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    fn tutor(&mut self, user: &User) -> (TutorProfile, Vec<&'static str>) {
+        let primary = self.rng.gen_range(0..CATEGORIES.len());
+        let (_, subjects) = CATEGORIES[primary];
+        let count = self.rng.gen_range(1..=subjects.len().min(3));
+        let mut specializations: Vec<&str> = subjects.choose_multiple(&mut self.rng, count).copied().collect();
+        let mut categories = vec![CATEGORIES[primary].0];
+
+        // Some tutors also teach outside their main field
+        if self.rng.gen_bool(0.25) {
+            let (category, subjects) = CATEGORIES[self.rng.gen_range(0..CATEGORIES.len())];
+            let subject = *self.pick(subjects);
+            if !specializations.contains(&subject) {
+                specializations.push(subject);
+                if !categories.contains(&category) {
+                    categories.push(category);
+                }
+            }
+        }
+
+        let years_experience = self.rng.gen_range(0..=25);
+        // Experience pushes the rate up, in steps of $5
+        let hourly_rate = (15 + years_experience * 3 + self.rng.gen_range(0..=40)) / 5 * 500;
+        let created_at = self.after(user.created_at, 30);
+
+        let profile = TutorProfile {
+            id: self.uuid(),
+            user_id: user.id,
+            bio: format!(
+                "{} has {} years of experience teaching {}. Lessons focus on solid fundamentals, worked examples and steady practice.",
+                user.first_name,
+                years_experience,
+                specializations.join(", ")
+            ),
+            specializations: specializations.iter().map(|s| s.to_string()).collect(),
+            hourly_rate,
+            years_experience,
+            rating: None,
+            total_reviews: 0,
+            rating_sum: 0,
+            is_verified: self.rng.gen_bool(0.6),
+            is_available: self.rng.gen_bool(0.85),
+            deleted_at: None,
+            created_at,
+            updated_at: created_at,
+        };
+
+        (profile, categories)
+    }
+
+    fn course(&mut self, tutor: &TutorProfile, categories: &[&str]) -> Course {
+        let category = *self.pick(categories);
+        let subjects = CATEGORIES.iter()
+            .find(|(name, _)| *name == category)
+            .map(|(_, subjects)| *subjects)
+            .unwrap_or_default();
+        // Prefer subjects the tutor lists, falling back to the whole category
+        let taught: Vec<&str> = subjects.iter()
+            .copied()
+            .filter(|subject| tutor.specializations.iter().any(|s| s == subject))
+            .collect();
+        let subject = if taught.is_empty() { *self.pick(subjects) } else { *self.pick(&taught) };
+
+        let difficulty_level = match self.rng.gen_range(0..100) {
+            0..=44 => DifficultyLevel::Beginner,
+            45..=79 => DifficultyLevel::Intermediate,
+            _ => DifficultyLevel::Advanced,
+        };
+        let level = match difficulty_level {
+            DifficultyLevel::Beginner => "beginner",
+            DifficultyLevel::Intermediate => "intermediate",
+            DifficultyLevel::Advanced => "advanced",
+        };
+        let duration_minutes = *self.pick(DURATIONS);
+        let price = tutor.hourly_rate * duration_minutes / 60 / 100 * 100;
+        let created_at = self.after(tutor.created_at, 60);
+
+        Course {
+            id: self.uuid(),
+            title: self.pick(COURSE_TITLES).replace("{}", subject),
+            description: format!(
+                "{} at the {} level: core concepts, guided exercises and a final review session.",
+                subject, level
+            ),
+            tutor_id: tutor.id,
+            price,
+            duration_minutes,
+            category: category.to_string(),
+            difficulty_level,
+            is_active: self.rng.gen_bool(0.95),
+            max_students: None,
+            taken_down_at: None,
+            deleted_at: None,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    // Ratings cluster around a per-tutor quality level, which gives the
+    // familiar skew towards four and five stars with a thin tail of bad reviews
+    fn rating(&mut self, quality: f64) -> i32 {
+        let noise = self.rng.gen_range(-0.9..0.9) + self.rng.gen_range(-0.9..0.9);
+        (quality + noise).round().clamp(1.0, 5.0) as i32
+    }
+
+    fn comment(&mut self, rating: i32) -> Option<String> {
+        if self.rng.gen_bool(0.3) {
+            return None;
+        }
+        let pool = match rating {
+            4..=5 => POSITIVE_COMMENTS,
+            3 => MIXED_COMMENTS,
+            _ => NEGATIVE_COMMENTS,
+        };
+        Some(self.pick(pool).to_string())
+    }
+}
+
+// Builds a complete data set: one admin, the configured students and tutors,
+// courses for each tutor and reviews from distinct students. Every account
+// shares `password_hash`, so callers hash the seed password once.
+pub fn generate(config: &SeedConfig, password_hash: &str) -> FixtureSet {
+    let mut generator = Generator {
+        rng: ChaCha8Rng::seed_from_u64(config.seed),
+        epoch: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+    };
+
+    let mut users = Vec::with_capacity(1 + config.students + config.tutors);
+    let mut admin = generator.user(0, UserRole::Admin, password_hash);
+    admin.email = format!("admin@{}", SEED_EMAIL_DOMAIN);
+    admin.is_active = true;
+    users.push(admin);
+
+    let students: Vec<User> = (1..=config.students)
+        .map(|index| generator.user(index, UserRole::Student, password_hash))
+        .collect();
+
+    let mut tutors = Vec::with_capacity(config.tutors);
+    let mut courses = Vec::with_capacity(config.tutors * config.courses_per_tutor);
+    let mut reviews = Vec::with_capacity(config.tutors * config.reviews_per_tutor);
+
+    for index in 0..config.tutors {
+        let user = generator.user(config.students + 1 + index, UserRole::Tutor, password_hash);
+        let (tutor, categories) = generator.tutor(&user);
+
+        let course_count = generator.rng.gen_range(0..=config.courses_per_tutor * 2);
+        for _ in 0..course_count {
+            courses.push(generator.course(&tutor, &categories));
+        }
+
+        let quality = generator.rng.gen_range(3.4..5.2);
+        let review_count = generator.rng.gen_range(0..=config.reviews_per_tutor * 2).min(students.len());
+        for student_index in sample(&mut generator.rng, students.len(), review_count) {
+            let student = &students[student_index];
+            let rating = generator.rating(quality);
+            let created_at = generator.after(tutor.created_at.max(student.created_at), 90);
+            reviews.push(TutorReview {
+                id: generator.uuid(),
+                tutor_id: tutor.id,
+                student_id: student.id,
+                rating,
+                comment: generator.comment(rating),
+                created_at,
+                edited_at: None,
+            });
+        }
+
+        users.push(user);
+        tutors.push(tutor);
+    }
+
+    users.extend(students);
+    FixtureSet { users, tutors, courses, reviews }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use crate::repositories::InMemoryRepository;
+    use crate::services::{CourseService, TutorService};
+
+    fn demo(seed: u64) -> FixtureSet {
+        generate(&SeedConfig::for_scale(SeedScale::Demo, seed), "not-a-real-hash")
+    }
+
+    #[test]
+    fn same_seed_generates_same_data() {
+        let (first, second) = (demo(7), demo(7));
+        let ids = |set: &FixtureSet| {
+            set.users.iter().map(|u| u.id)
+                .chain(set.courses.iter().map(|c| c.id))
+                .chain(set.reviews.iter().map(|r| r.id))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(&first), ids(&second));
+        assert_eq!(first.users[5].email, second.users[5].email);
+        assert_eq!(first.courses[0].created_at, second.courses[0].created_at);
+        assert_ne!(ids(&first), ids(&demo(8)));
+    }
+
+    #[test]
+    fn generated_rows_respect_schema_rules() {
+        let set = demo(42);
+
+        let emails: HashSet<_> = set.users.iter().map(|u| &u.email).collect();
+        assert_eq!(emails.len(), set.users.len());
+
+        let pairs: HashSet<_> = set.reviews.iter().map(|r| (r.tutor_id, r.student_id)).collect();
+        assert_eq!(pairs.len(), set.reviews.len());
+        assert!(set.reviews.iter().all(|r| (1..=5).contains(&r.rating)));
+
+        let categories: HashSet<_> = set.courses.iter().map(|c| c.category.as_str()).collect();
+        assert!(categories.len() > 3);
+        for level in [DifficultyLevel::Beginner, DifficultyLevel::Intermediate, DifficultyLevel::Advanced] {
+            assert!(set.courses.iter().any(|c| c.difficulty_level == level));
+        }
+
+        // Most reviews should be favourable, but not all of them
+        let favourable = set.reviews.iter().filter(|r| r.rating >= 4).count();
+        assert!(favourable * 2 > set.reviews.len());
+        assert!(favourable < set.reviews.len());
+    }
+
+    #[actix_rt::test]
+    async fn generated_set_loads_into_the_services() {
+        let set = demo(1);
+        let repo = InMemoryRepository::new();
+        repo.load_fixtures(&set).await.unwrap();
+
+        let active = set.courses.iter().filter(|c| c.is_active).count();
+        assert_eq!(CourseService::get_all_courses(&repo).await.unwrap().len(), active);
+
+        let reviewed = set.reviews[0].tutor_id;
+        let tutor = TutorService::get_tutor_by_id(&repo, reviewed).await.unwrap();
+        assert!(tutor.rating.is_some());
+    }
+}
@@ -0,0 +1,926 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    AuditEntry, AuditLogQuery, ChangeRoleRequest, CourseResponse, CourseWithTutor, CreateUserRequest, ModerationRequest,
+    Page, PlatformStats, RatingDrift, RequestContext, TutorResponse, User, UserResponse, UserRole, UserSearchQuery,
+    VerificationAction, VerificationEvent
+};
+use crate::repositories::{
+    AuditRepository, CourseRepository, NotificationRepository, ReviewRepository, StatsRepository, TutorRepository,
+    UserRepository, VerificationRepository
+};
+use crate::services::audit::{self, AuditTarget};
+use crate::services::{AuditService, AuthService, NotificationService, TutorService};
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+
+const MY_COURSES_PATH: &str = "/api/v1/my/courses";
+
+// Operator tasks behind the `/admin` API and the `ezytutor-admin` binary.
+// The CLI addresses users by id or email so the operator can pass whichever
+// is at hand; API calls are checked against the caller's role. Every change
+// is written to the audit log, with no actor for the CLI.
+pub struct AdminService;
+
+impl AdminService {
+    pub async fn create_admin<R: UserRepository + AuditRepository>(
+        repo: &R,
+        email: String,
+        password: String,
+        first_name: String,
+        last_name: String,
+    ) -> AppResult<UserResponse> {
+        let request = CreateUserRequest {
+            email,
+            password,
+            first_name,
+            last_name,
+            role: UserRole::Admin,
+        };
+
+        AuthService::create_user(repo, request, &RequestContext::default()).await
+    }
+
+    pub async fn find_user<R: UserRepository>(repo: &R, identifier: &str) -> AppResult<User> {
+        let user = match Uuid::parse_str(identifier) {
+            Ok(id) => repo.find_user_by_id(id).await?,
+            Err(_) => repo.find_user_by_email(identifier).await?,
+        };
+
+        user.ok_or_else(|| AppError::NotFound(format!("User {} not found", identifier)))
+    }
+
+    pub async fn set_role<R: UserRepository + AuditRepository>(
+        repo: &R,
+        identifier: &str,
+        role: UserRole,
+    ) -> AppResult<UserResponse> {
+        let user = Self::find_user(repo, identifier).await?;
+        Self::apply_role(repo, &RequestContext::default(), None, user, role, None).await
+    }
+
+    pub async fn set_active<R: UserRepository + AuditRepository>(
+        repo: &R,
+        identifier: &str,
+        is_active: bool,
+    ) -> AppResult<UserResponse> {
+        let user = Self::find_user(repo, identifier).await?;
+        Self::apply_active(repo, &RequestContext::default(), None, user, is_active, None).await
+    }
+
+    // Accepts a tutor profile id, or the id or email of the tutor's user account.
+    // Bypasses the request queue but still lands in the verification history.
+    pub async fn set_tutor_verified<R>(
+        repo: &R,
+        identifier: &str,
+        is_verified: bool,
+        reason: Option<String>,
+    ) -> AppResult<TutorResponse>
+    where
+        R: UserRepository + TutorRepository + VerificationRepository + AuditRepository,
+    {
+        let profile = match Uuid::parse_str(identifier) {
+            Ok(id) => repo.find_tutor_by_id(id).await?,
+            Err(_) => None,
+        };
+
+        let tutor = match profile {
+            Some(found) => found.tutor,
+            None => {
+                let user = Self::find_user(repo, identifier).await?;
+                repo.find_tutor_by_user_id(user.id).await?
+                    .ok_or_else(|| AppError::NotFound(format!("No tutor profile for {}", identifier)))?
+                    .tutor
+            }
+        };
+
+        let event = VerificationEvent {
+            id: Uuid::new_v4(),
+            tutor_id: tutor.id,
+            request_id: None,
+            action: if is_verified { VerificationAction::Approved } else { VerificationAction::Revoked },
+            actor_id: None,
+            reason: reason.clone(),
+            created_at: Utc::now(),
+        };
+        repo.set_tutor_verified(tutor.id, is_verified, &event).await?;
+
+        let changes = audit::changes(
+            Some(&json!({ "is_verified": tutor.is_verified })),
+            Some(&json!({ "is_verified": is_verified })),
+        );
+        let action = if is_verified { "tutor.verified" } else { "tutor.unverified" };
+        let target = AuditTarget::Tutor(tutor.id);
+        AuditService::record(repo, &RequestContext::default(), None, action, target, changes, reason).await?;
+
+        TutorService::get_tutor_by_id(repo, tutor.id).await
+    }
+
+    // Rebuilds every tutor's rating and review count from the reviews table;
+    // the tutors whose figures change are audited
+    pub async fn recompute_ratings<R: TutorRepository + ReviewRepository + AuditRepository>(repo: &R) -> AppResult<usize> {
+        let drift = repo.list_rating_drift().await?;
+        let tutor_ids = repo.list_tutor_ids().await?;
+
+        for tutor_id in &tutor_ids {
+            repo.refresh_tutor_rating(*tutor_id).await?;
+        }
+        for tutor in &drift {
+            Self::record_rating_fix(repo, "tutor.rating_recomputed", tutor).await?;
+        }
+
+        Ok(tutor_ids.len())
+    }
+
+    // Finds tutors whose incrementally kept rating no longer matches their
+    // reviews, e.g. after reviews went with a deleted account, and rebuilds
+    // just those
+    pub async fn reconcile_ratings<R: ReviewRepository + AuditRepository>(repo: &R) -> AppResult<Vec<RatingDrift>> {
+        let drift = repo.list_rating_drift().await?;
+
+        for tutor in &drift {
+            repo.refresh_tutor_rating(tutor.tutor_id).await?;
+            Self::record_rating_fix(repo, "tutor.rating_reconciled", tutor).await?;
+        }
+
+        Ok(drift)
+    }
+
+    async fn record_rating_fix<R: AuditRepository>(repo: &R, action: &str, tutor: &RatingDrift) -> AppResult<()> {
+        let changes = audit::changes(
+            Some(&json!({ "rating": tutor.stored_rating, "total_reviews": tutor.stored_total_reviews })),
+            Some(&json!({ "rating": tutor.actual_rating, "total_reviews": tutor.actual_total_reviews })),
+        );
+        let target = AuditTarget::Tutor(tutor.tutor_id);
+        AuditService::record(repo, &RequestContext::default(), None, action, target, changes, None).await
+    }
+
+    pub async fn platform_stats<R: StatsRepository>(repo: &R) -> AppResult<PlatformStats> {
+        repo.platform_stats().await
+    }
+
+    // Admin API
+
+    // Newest accounts first
+    pub async fn search_users<R: UserRepository>(
+        repo: &R,
+        admin_id: Uuid,
+        query: UserSearchQuery,
+    ) -> AppResult<Page<UserResponse>> {
+        Self::require_admin(repo, admin_id).await?;
+        // Validate input
+        query.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let page = query.page.unwrap_or(1);
+        let per_page = query.per_page.unwrap_or(DEFAULT_PAGE_SIZE);
+        let text = query.q.as_deref().map(str::trim).filter(|text| !text.is_empty());
+        let offset = (page - 1).saturating_mul(per_page);
+
+        let (users, total) = repo.search_users(text, query.role, query.is_active, per_page, offset).await?;
+        Ok(Page {
+            items: users.into_iter().map(UserResponse::from).collect(),
+            page,
+            per_page,
+            total,
+        })
+    }
+
+    pub async fn get_user<R: UserRepository>(repo: &R, admin_id: Uuid, user_id: Uuid) -> AppResult<UserResponse> {
+        Self::require_admin(repo, admin_id).await?;
+        let user = AuthService::get_user_by_id(repo, user_id).await?;
+        Ok(UserResponse::from(user))
+    }
+
+    // Newest entries first
+    pub async fn search_audit_log<R: UserRepository + AuditRepository>(
+        repo: &R,
+        admin_id: Uuid,
+        query: AuditLogQuery,
+    ) -> AppResult<Page<AuditEntry>> {
+        Self::require_admin(repo, admin_id).await?;
+        // Validate input
+        query.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        if let Some(target_type) = &query.target_type {
+            if !AuditTarget::TYPES.contains(&target_type.as_str()) {
+                return Err(AppError::Validation(format!(
+                    "target_type must be one of: {}",
+                    AuditTarget::TYPES.join(", ")
+                )));
+            }
+        }
+        if let (Some(from), Some(to)) = (query.from, query.to) {
+            if from >= to {
+                return Err(AppError::Validation("from must be before to".to_string()));
+            }
+        }
+
+        let page = query.page.unwrap_or(1);
+        let per_page = query.per_page.unwrap_or(DEFAULT_PAGE_SIZE);
+        let offset = (page - 1).saturating_mul(per_page);
+
+        let (items, total) = repo.search_audit_entries(&query, per_page, offset).await?;
+        Ok(Page { items, page, per_page, total })
+    }
+
+    pub async fn change_role<R: UserRepository + AuditRepository>(
+        repo: &R,
+        admin_id: Uuid,
+        user_id: Uuid,
+        request: ChangeRoleRequest,
+        context: &RequestContext,
+    ) -> AppResult<UserResponse> {
+        Self::require_admin(repo, admin_id).await?;
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        // Keeps the last admin from locking everyone out by accident
+        if user_id == admin_id {
+            return Err(AppError::BadRequest("Admins cannot change their own role".to_string()));
+        }
+
+        let user = AuthService::get_user_by_id(repo, user_id).await?;
+        Self::apply_role(repo, context, Some(admin_id), user, request.role, optional_reason(request.reason)).await
+    }
+
+    // Signs the user out everywhere and refuses new logins
+    pub async fn deactivate_user<R: UserRepository + AuditRepository>(
+        repo: &R,
+        admin_id: Uuid,
+        user_id: Uuid,
+        request: ModerationRequest,
+        context: &RequestContext,
+    ) -> AppResult<UserResponse> {
+        Self::require_admin(repo, admin_id).await?;
+        let reason = required_reason(request, "deactivate an account")?;
+        if user_id == admin_id {
+            return Err(AppError::BadRequest("Admins cannot deactivate their own account".to_string()));
+        }
+
+        let user = AuthService::get_user_by_id(repo, user_id).await?;
+        if !user.is_active {
+            return Err(AppError::Conflict("Account is already deactivated".to_string()));
+        }
+        Self::apply_active(repo, context, Some(admin_id), user, false, Some(reason)).await
+    }
+
+    pub async fn reactivate_user<R: UserRepository + AuditRepository>(
+        repo: &R,
+        admin_id: Uuid,
+        user_id: Uuid,
+        request: ModerationRequest,
+        context: &RequestContext,
+    ) -> AppResult<UserResponse> {
+        Self::require_admin(repo, admin_id).await?;
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let user = AuthService::get_user_by_id(repo, user_id).await?;
+        if user.is_active {
+            return Err(AppError::Conflict("Account is already active".to_string()));
+        }
+        Self::apply_active(repo, context, Some(admin_id), user, true, optional_reason(request.reason)).await
+    }
+
+    // Every token issued so far stops working; the user can log in again
+    pub async fn force_logout<R: UserRepository + AuditRepository>(
+        repo: &R,
+        admin_id: Uuid,
+        user_id: Uuid,
+        request: ModerationRequest,
+        context: &RequestContext,
+    ) -> AppResult<()> {
+        Self::require_admin(repo, admin_id).await?;
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        if !repo.revoke_sessions(user_id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+        let reason = optional_reason(request.reason);
+        let target = AuditTarget::User(user_id);
+        AuditService::record(repo, context, Some(admin_id), "user.logged_out", target, None, reason).await
+    }
+
+    // Hides the course from listings and locks it against edits by its tutor
+    pub async fn take_down_course<R>(
+        repo: &R,
+        admin_id: Uuid,
+        course_id: Uuid,
+        request: ModerationRequest,
+        context: &RequestContext,
+    ) -> AppResult<CourseResponse>
+    where
+        R: UserRepository + TutorRepository + CourseRepository + NotificationRepository + AuditRepository,
+    {
+        Self::require_admin(repo, admin_id).await?;
+        let reason = required_reason(request, "take a course down")?;
+
+        let course = Self::find_course(repo, course_id).await?.course;
+        if course.taken_down_at.is_some() {
+            return Err(AppError::Conflict("Course is already taken down".to_string()));
+        }
+
+        repo.set_course_taken_down(course_id, Some(Utc::now())).await?;
+        let updated = Self::find_course(repo, course_id).await?;
+        let changes = audit::changes(Some(&course), Some(&updated.course));
+        let target = AuditTarget::Course(course_id);
+        AuditService::record(repo, context, Some(admin_id), "course.taken_down", target, changes, Some(reason.clone())).await?;
+        Self::notify_course_owner(
+            repo,
+            course.tutor_id,
+            "course_taken_down",
+            "Course taken down".to_string(),
+            format!("\"{}\" was taken down by an administrator: {}", course.title, reason),
+        ).await?;
+
+        Ok(CourseResponse::from(updated))
+    }
+
+    // The course stays inactive until its tutor publishes it again
+    pub async fn reinstate_course<R>(
+        repo: &R,
+        admin_id: Uuid,
+        course_id: Uuid,
+        request: ModerationRequest,
+        context: &RequestContext,
+    ) -> AppResult<CourseResponse>
+    where
+        R: UserRepository + TutorRepository + CourseRepository + NotificationRepository + AuditRepository,
+    {
+        Self::require_admin(repo, admin_id).await?;
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let course = Self::find_course(repo, course_id).await?.course;
+        if course.taken_down_at.is_none() {
+            return Err(AppError::Conflict("Course is not taken down".to_string()));
+        }
+
+        repo.set_course_taken_down(course_id, None).await?;
+        let updated = Self::find_course(repo, course_id).await?;
+        let changes = audit::changes(Some(&course), Some(&updated.course));
+        let reason = optional_reason(request.reason);
+        let target = AuditTarget::Course(course_id);
+        AuditService::record(repo, context, Some(admin_id), "course.reinstated", target, changes, reason).await?;
+        Self::notify_course_owner(
+            repo,
+            course.tutor_id,
+            "course_reinstated",
+            "Course reinstated".to_string(),
+            format!("\"{}\" was reinstated and can be published again.", course.title),
+        ).await?;
+
+        Ok(CourseResponse::from(updated))
+    }
+
+    // The tutor's rating is recomputed without the review
+    pub async fn remove_review<R>(
+        repo: &R,
+        admin_id: Uuid,
+        review_id: Uuid,
+        request: ModerationRequest,
+        context: &RequestContext,
+    ) -> AppResult<()>
+    where
+        R: UserRepository + ReviewRepository + NotificationRepository + AuditRepository,
+    {
+        Self::require_admin(repo, admin_id).await?;
+        let reason = required_reason(request, "remove a review")?;
+
+        let review = repo.find_review_by_id(review_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Review not found".to_string()))?;
+        if !repo.delete_review(review_id).await? {
+            return Err(AppError::NotFound("Review not found".to_string()));
+        }
+
+        let changes = audit::changes(Some(&review), None);
+        let target = AuditTarget::Review(review_id);
+        AuditService::record(repo, context, Some(admin_id), "review.removed", target, changes, Some(reason.clone())).await?;
+        NotificationService::notify(
+            repo,
+            review.student_id,
+            "review_removed",
+            "Review removed".to_string(),
+            format!("Your review was removed by an administrator: {}", reason),
+            Some(format!("/api/v1/tutors/{}", review.tutor_id)),
+        ).await
+    }
+
+    async fn require_admin<R: UserRepository>(repo: &R, user_id: Uuid) -> AppResult<()> {
+        if !AuthService::verify_user_role(repo, user_id, UserRole::Admin).await? {
+            return Err(AppError::Authorization("Admin access required".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn apply_role<R: UserRepository + AuditRepository>(
+        repo: &R,
+        context: &RequestContext,
+        actor_id: Option<Uuid>,
+        mut user: User,
+        role: UserRole,
+        reason: Option<String>,
+    ) -> AppResult<UserResponse> {
+        let before = user.clone();
+        user.role = role;
+        user.updated_at = Utc::now();
+
+        if !repo.update_user(&user, before.updated_at).await? {
+            return Err(AppError::Conflict("User has changed since it was read".to_string()));
+        }
+        let changes = audit::changes(Some(&before), Some(&user));
+        let target = AuditTarget::User(user.id);
+        AuditService::record(repo, context, actor_id, "user.role_changed", target, changes, reason).await?;
+        Ok(UserResponse::from(user))
+    }
+
+    // Deactivation also ends the user's sessions
+    async fn apply_active<R: UserRepository + AuditRepository>(
+        repo: &R,
+        context: &RequestContext,
+        actor_id: Option<Uuid>,
+        mut user: User,
+        is_active: bool,
+        reason: Option<String>,
+    ) -> AppResult<UserResponse> {
+        let before = user.clone();
+        user.is_active = is_active;
+        user.updated_at = Utc::now();
+
+        if !repo.update_user(&user, before.updated_at).await? {
+            return Err(AppError::Conflict("User has changed since it was read".to_string()));
+        }
+        if !is_active {
+            repo.revoke_sessions(user.id).await?;
+        }
+        let action = if is_active { "user.reactivated" } else { "user.deactivated" };
+        let changes = audit::changes(Some(&before), Some(&user));
+        let target = AuditTarget::User(user.id);
+        AuditService::record(repo, context, actor_id, action, target, changes, reason).await?;
+        Ok(UserResponse::from(user))
+    }
+
+    async fn notify_course_owner<R: TutorRepository + NotificationRepository>(
+        repo: &R,
+        tutor_id: Uuid,
+        kind: &str,
+        title: String,
+        body: String,
+    ) -> AppResult<()> {
+        let tutor = repo.find_tutor_by_id(tutor_id)
+            .await?
+            .ok_or_else(|| AppError::Internal(format!("Tutor {} is missing", tutor_id)))?;
+        NotificationService::notify(repo, tutor.tutor.user_id, kind, title, body, Some(MY_COURSES_PATH.to_string())).await
+    }
+
+    // Admins see taken-down courses, which the public lookup hides
+    async fn find_course<R: CourseRepository>(repo: &R, course_id: Uuid) -> AppResult<CourseWithTutor> {
+        repo.find_course_by_id(course_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))
+    }
+}
+
+fn optional_reason(reason: Option<String>) -> Option<String> {
+    reason.map(|reason| reason.trim().to_string()).filter(|reason| !reason.is_empty())
+}
+
+// `action` completes "A reason is required to ..."
+fn required_reason(request: ModerationRequest, action: &str) -> AppResult<String> {
+    // Validate input
+    request.validate()
+        .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+    optional_reason(request.reason)
+        .ok_or_else(|| AppError::Validation(format!("A reason is required to {}", action)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        CreateCourseRequest, CreateReviewRequest, DifficultyLevel, LoginRequest, Precondition, TutorProfile, TutorReview,
+        UpdateCourseRequest
+    };
+    use crate::repositories::{AuditRepository, InMemoryRepository, NotificationRepository};
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use crate::services::CourseService;
+
+    fn reason(text: &str) -> ModerationRequest {
+        ModerationRequest { reason: Some(text.to_string()) }
+    }
+
+    // Oldest first
+    async fn audit_trail(repo: &InMemoryRepository, target_type: &str, target_id: Uuid) -> Vec<(Option<Uuid>, String)> {
+        let query = AuditLogQuery {
+            target_type: Some(target_type.to_string()),
+            target_id: Some(target_id),
+            ..AuditLogQuery::default()
+        };
+        let (entries, _) = repo.search_audit_entries(&query, 100, 0).await.unwrap();
+        entries.into_iter().rev().map(|entry| (entry.actor_id, entry.action)).collect()
+    }
+
+    #[actix_rt::test]
+    async fn created_admins_can_log_in() {
+        let repo = InMemoryRepository::new();
+        let admin = AdminService::create_admin(
+            &repo,
+            "ops@ezytutor.test".to_string(),
+            "correct-horse".to_string(),
+            "Ops".to_string(),
+            "Team".to_string(),
+        ).await.unwrap();
+        assert_eq!(admin.role, UserRole::Admin);
+
+        let login = LoginRequest {
+            email: "ops@ezytutor.test".to_string(),
+            password: "correct-horse".to_string(),
+        };
+        assert!(AuthService::login_user(&repo, login).await.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn users_are_addressed_by_id_or_email() {
+        let repo = InMemoryRepository::new();
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        let promoted = AdminService::set_role(&repo, &student.email, UserRole::Tutor).await.unwrap();
+        assert_eq!(promoted.role, UserRole::Tutor);
+
+        let deactivated = AdminService::set_active(&repo, &student.id.to_string(), false).await.unwrap();
+        assert!(!deactivated.is_active);
+        assert!(!repo.find_user_by_id(student.id).await.unwrap().unwrap().is_active);
+
+        let missing = AdminService::set_active(&repo, "nobody@ezytutor.test", true).await;
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+
+        // CLI changes are logged without an actor
+        let trail = audit_trail(&repo, "user", student.id).await;
+        assert_eq!(trail, vec![(None, "user.role_changed".to_string()), (None, "user.deactivated".to_string())]);
+    }
+
+    #[actix_rt::test]
+    async fn admins_search_and_manage_accounts() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let admin = insert_user(&repo, UserRole::Admin).await;
+        let mut students = Vec::new();
+        for _ in 0..3 {
+            students.push(insert_user(&repo, UserRole::Student).await);
+        }
+        let (tutor, _) = insert_tutor(&repo, &["Art"]).await;
+
+        let search = |q: Option<&str>, role: Option<UserRole>, page: i64| UserSearchQuery {
+            q: q.map(str::to_string),
+            role,
+            is_active: None,
+            page: Some(page),
+            per_page: Some(2),
+        };
+        let error = AdminService::search_users(&repo, tutor.id, search(None, None, 1)).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+
+        let first = AdminService::search_users(&repo, admin.id, search(Some(" test student "), None, 1)).await.unwrap();
+        let second = AdminService::search_users(&repo, admin.id, search(Some("STUDENT"), Some(UserRole::Student), 2))
+            .await
+            .unwrap();
+        assert_eq!((first.total, first.items.len(), second.items.len()), (3, 2, 1));
+        let mut found: Vec<Uuid> = first.items.iter().chain(&second.items).map(|user| user.id).collect();
+        found.sort();
+        let mut expected: Vec<Uuid> = students.iter().map(|user| user.id).collect();
+        expected.sort();
+        assert_eq!(found, expected);
+        let by_email = AdminService::search_users(&repo, admin.id, search(Some(&tutor.email), None, 1)).await.unwrap();
+        assert_eq!(by_email.items[0].id, tutor.id);
+
+        // Admins cannot demote or lock out themselves
+        let promote = ChangeRoleRequest { role: UserRole::Tutor, reason: Some("Teaches now".to_string()) };
+        let error = AdminService::change_role(&repo, admin.id, admin.id, promote.clone(), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+        let target = &students[0];
+        let promoted = AdminService::change_role(&repo, admin.id, target.id, promote, &context).await.unwrap();
+        assert_eq!(promoted.role, UserRole::Tutor);
+
+        let error = AdminService::deactivate_user(&repo, admin.id, target.id, ModerationRequest::default(), &context)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+        let deactivated = AdminService::deactivate_user(&repo, admin.id, target.id, reason("Spam"), &context).await.unwrap();
+        assert!(!deactivated.is_active);
+        let stored = repo.find_user_by_id(target.id).await.unwrap().unwrap();
+        assert_eq!(stored.token_version, 1);
+        let error = AdminService::deactivate_user(&repo, admin.id, target.id, reason("Spam"), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        AdminService::reactivate_user(&repo, admin.id, target.id, ModerationRequest::default(), &context).await.unwrap();
+        AdminService::force_logout(&repo, admin.id, target.id, ModerationRequest::default(), &context).await.unwrap();
+        assert_eq!(repo.find_user_by_id(target.id).await.unwrap().unwrap().token_version, 2);
+        let error = AdminService::force_logout(&repo, admin.id, Uuid::new_v4(), ModerationRequest::default(), &context)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        let actions: Vec<String> = audit_trail(&repo, "user", target.id).await.into_iter()
+            .map(|(actor, action)| {
+                assert_eq!(actor, Some(admin.id));
+                action
+            })
+            .collect();
+        assert_eq!(actions, ["user.role_changed", "user.deactivated", "user.reactivated", "user.logged_out"]);
+    }
+
+    #[actix_rt::test]
+    async fn account_changes_from_a_stale_read_are_refused() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let admin = insert_user(&repo, UserRole::Admin).await;
+        let other_admin = insert_user(&repo, UserRole::Admin).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        // One admin reads the account to change its role while another
+        // deactivates it
+        let stale = AuthService::get_user_by_id(&repo, student.id).await.unwrap();
+        AdminService::deactivate_user(&repo, other_admin.id, student.id, reason("Spam"), &context).await.unwrap();
+
+        let error = AdminService::apply_role(&repo, &context, Some(admin.id), stale, UserRole::Tutor, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+        let stored = repo.find_user_by_id(student.id).await.unwrap().unwrap();
+        assert_eq!((stored.role, stored.is_active), (UserRole::Student, false));
+        let actions: Vec<String> = audit_trail(&repo, "user", student.id).await.into_iter().map(|(_, action)| action).collect();
+        assert_eq!(actions, ["user.deactivated"]);
+
+        // A fresh read goes through and leaves the account deactivated
+        let promote = ChangeRoleRequest { role: UserRole::Tutor, reason: None };
+        let promoted = AdminService::change_role(&repo, admin.id, student.id, promote, &context).await.unwrap();
+        assert_eq!((promoted.role, promoted.is_active), (UserRole::Tutor, false));
+    }
+
+    #[actix_rt::test]
+    async fn taken_down_courses_are_hidden_and_locked() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let admin = insert_user(&repo, UserRole::Admin).await;
+        let (tutor, _) = insert_tutor(&repo, &["History"]).await;
+        let course = CourseService::create_course(&repo, tutor.id, CreateCourseRequest {
+            title: "Ancient Rome".to_string(),
+            description: "From the republic to the empire".to_string(),
+            price: 2000,
+            duration_minutes: 60,
+            category: "History".to_string(),
+            difficulty_level: DifficultyLevel::Beginner,
+            max_students: None,
+        }, &context).await.unwrap();
+
+        let error = AdminService::take_down_course(&repo, admin.id, course.id, ModerationRequest::default(), &context)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+        let taken_down = AdminService::take_down_course(&repo, admin.id, course.id, reason("Copyrighted material"), &context)
+            .await
+            .unwrap();
+        assert!(!taken_down.is_active && taken_down.taken_down_at.is_some());
+
+        let error = CourseService::get_course_by_id(&repo, course.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        assert!(CourseService::get_all_courses(&repo).await.unwrap().is_empty());
+        assert_eq!(CourseService::get_courses_by_tutor(&repo, tutor.id).await.unwrap().len(), 1);
+        let publish = UpdateCourseRequest {
+            title: None,
+            description: None,
+            price: None,
+            duration_minutes: None,
+            category: None,
+            difficulty_level: None,
+            is_active: Some(true),
+            max_students: None,
+        };
+        let error = CourseService::update_course(&repo, course.id, tutor.id, publish.clone(), &Precondition::Any, &context)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        // Reinstated courses stay hidden until the tutor publishes them
+        let reinstated = AdminService::reinstate_course(&repo, admin.id, course.id, ModerationRequest::default(), &context)
+            .await
+            .unwrap();
+        assert!(!reinstated.is_active && reinstated.taken_down_at.is_none());
+        let error = AdminService::reinstate_course(&repo, admin.id, course.id, ModerationRequest::default(), &context)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+        let published = CourseService::update_course(&repo, course.id, tutor.id, publish, &Precondition::Any, &context)
+            .await
+            .unwrap();
+        assert!(published.is_active);
+
+        let kinds: Vec<_> = repo.list_notifications(tutor.id, false).await.unwrap().into_iter().map(|n| n.kind).collect();
+        assert!(kinds.contains(&"course_taken_down".to_string()) && kinds.contains(&"course_reinstated".to_string()));
+        let trail = audit_trail(&repo, "course", course.id).await;
+        assert_eq!(trail, vec![
+            (Some(tutor.id), "course.created".to_string()),
+            (Some(admin.id), "course.taken_down".to_string()),
+            (Some(admin.id), "course.reinstated".to_string()),
+            (Some(tutor.id), "course.updated".to_string()),
+        ]);
+    }
+
+    #[actix_rt::test]
+    async fn drifted_ratings_are_found_and_repaired() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (_, steady) = insert_tutor(&repo, &["Music"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        let review = CreateReviewRequest { rating: 4, comment: None };
+        TutorService::create_review(&repo, steady.id, student.id, review, &context).await.unwrap();
+
+        // Stored aggregate still counts reviews that are gone
+        let owner = insert_user(&repo, UserRole::Tutor).await;
+        let drifted = TutorProfile {
+            id: Uuid::new_v4(),
+            user_id: owner.id,
+            rating: Some(2.0),
+            total_reviews: 3,
+            rating_sum: 6,
+            ..steady.clone()
+        };
+        repo.insert_tutor(&drifted).await.unwrap();
+
+        let repaired = AdminService::reconcile_ratings(&repo).await.unwrap();
+        assert_eq!(repaired.len(), 1);
+        assert_eq!(repaired[0].tutor_id, drifted.id);
+        assert_eq!((repaired[0].stored_total_reviews, repaired[0].actual_total_reviews), (3, 0));
+
+        let tutor = repo.find_tutor_by_id(drifted.id).await.unwrap().unwrap().tutor;
+        assert_eq!((tutor.rating, tutor.total_reviews, tutor.rating_sum), (None, 0, 0));
+        let tutor = repo.find_tutor_by_id(steady.id).await.unwrap().unwrap().tutor;
+        assert_eq!((tutor.rating, tutor.total_reviews), (Some(4.0), 1));
+        assert_eq!(audit_trail(&repo, "tutor", drifted.id).await, vec![(None, "tutor.rating_reconciled".to_string())]);
+        assert!(AdminService::reconcile_ratings(&repo).await.unwrap().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn removed_reviews_no_longer_count_toward_ratings() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let admin = insert_user(&repo, UserRole::Admin).await;
+        let (_, tutor) = insert_tutor(&repo, &["Music"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        let review = TutorReview {
+            id: Uuid::new_v4(),
+            tutor_id: tutor.id,
+            student_id: student.id,
+            rating: 1,
+            comment: Some("Buy cheap watches at ...".to_string()),
+            created_at: Utc::now(),
+            edited_at: None,
+        };
+        repo.insert_review(&review).await.unwrap();
+
+        let error = AdminService::remove_review(&repo, student.id, review.id, reason("Spam"), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+        AdminService::remove_review(&repo, admin.id, review.id, reason("Spam"), &context).await.unwrap();
+
+        let tutor = repo.find_tutor_by_id(tutor.id).await.unwrap().unwrap().tutor;
+        assert_eq!((tutor.rating, tutor.total_reviews), (None, 0));
+        let notifications = repo.list_notifications(student.id, false).await.unwrap();
+        assert_eq!(notifications[0].kind, "review_removed");
+        assert_eq!(audit_trail(&repo, "review", review.id).await, vec![(Some(admin.id), "review.removed".to_string())]);
+        let error = AdminService::remove_review(&repo, admin.id, review.id, reason("Spam"), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+    }
+
+    #[actix_rt::test]
+    async fn audit_log_records_changes_and_where_they_came_from() {
+        let repo = InMemoryRepository::new();
+        let admin = insert_user(&repo, UserRole::Admin).await;
+        let (tutor, _) = insert_tutor(&repo, &["Chemistry"]).await;
+        let context = RequestContext {
+            ip_address: Some("203.0.113.7".to_string()),
+            request_id: Some("req-42".to_string()),
+        };
+        let started = Utc::now();
+
+        let course = CourseService::create_course(&repo, tutor.id, CreateCourseRequest {
+            title: "Organic Chemistry".to_string(),
+            description: "Carbon compounds and their reactions".to_string(),
+            price: 3000,
+            duration_minutes: 60,
+            category: "Science".to_string(),
+            difficulty_level: DifficultyLevel::Intermediate,
+            max_students: None,
+        }, &context).await.unwrap();
+        let reprice = UpdateCourseRequest {
+            title: None,
+            description: None,
+            price: Some(4500),
+            duration_minutes: None,
+            category: None,
+            difficulty_level: None,
+            is_active: None,
+            max_students: None,
+        };
+        CourseService::update_course(&repo, course.id, tutor.id, reprice, &Precondition::Any, &context).await.unwrap();
+
+        let by_target = AuditLogQuery {
+            target_type: Some("course".to_string()),
+            target_id: Some(course.id),
+            ..AuditLogQuery::default()
+        };
+        let page = AdminService::search_audit_log(&repo, admin.id, by_target).await.unwrap();
+        assert_eq!(page.total, 2);
+        let update = &page.items[0];
+        assert_eq!((update.action.as_str(), update.actor_id), ("course.updated", Some(tutor.id)));
+        assert_eq!(update.changes, Some(serde_json::json!({ "price": { "before": 3000, "after": 4500 } })));
+        assert_eq!(update.ip_address.as_deref(), Some("203.0.113.7"));
+        assert_eq!(update.request_id.as_deref(), Some("req-42"));
+        let created = page.items[1].changes.as_ref().unwrap();
+        assert_eq!(created["title"]["after"], "Organic Chemistry");
+
+        let in_range = AuditLogQuery { actor_id: Some(tutor.id), from: Some(started), ..AuditLogQuery::default() };
+        assert_eq!(AdminService::search_audit_log(&repo, admin.id, in_range).await.unwrap().total, 2);
+        let before = AuditLogQuery { actor_id: Some(tutor.id), to: Some(started), ..AuditLogQuery::default() };
+        assert_eq!(AdminService::search_audit_log(&repo, admin.id, before).await.unwrap().total, 0);
+
+        let error = AdminService::search_audit_log(&repo, tutor.id, AuditLogQuery::default()).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+        let unknown = AuditLogQuery { target_type: Some("lesson".to_string()), ..AuditLogQuery::default() };
+        let error = AdminService::search_audit_log(&repo, admin.id, unknown).await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+        let backwards = AuditLogQuery { from: Some(Utc::now()), to: Some(started), ..AuditLogQuery::default() };
+        let error = AdminService::search_audit_log(&repo, admin.id, backwards).await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+    }
+
+    #[actix_rt::test]
+    async fn tutors_are_verified_by_profile_or_account() {
+        let repo = InMemoryRepository::new();
+        let (user, tutor) = insert_tutor(&repo, &["Calculus"]).await;
+
+        let verified = AdminService::set_tutor_verified(&repo, &tutor.id.to_string(), true, None).await.unwrap();
+        assert!(verified.is_verified);
+
+        let reason = Some("Licence expired".to_string());
+        let revoked = AdminService::set_tutor_verified(&repo, &user.email, false, reason.clone()).await.unwrap();
+        assert!(!revoked.is_verified);
+        let history = repo.list_verification_events(tutor.id).await.unwrap();
+        let actions: Vec<_> = history.iter().map(|event| (event.action, event.reason.clone())).collect();
+        assert_eq!(actions, vec![(VerificationAction::Approved, None), (VerificationAction::Revoked, reason)]);
+        assert_eq!(
+            audit_trail(&repo, "tutor", tutor.id).await,
+            vec![(None, "tutor.verified".to_string()), (None, "tutor.unverified".to_string())]
+        );
+
+        let student = insert_user(&repo, UserRole::Student).await;
+        let error = AdminService::set_tutor_verified(&repo, &student.email, true, None).await;
+        assert!(matches!(error, Err(AppError::NotFound(_))));
+    }
+
+    #[actix_rt::test]
+    async fn ratings_are_recomputed_and_counted_in_stats() {
+        let repo = InMemoryRepository::new();
+        let (_, tutor) = insert_tutor(&repo, &["Physics"]).await;
+        for rating in [3, 5] {
+            let student = insert_user(&repo, UserRole::Student).await;
+            repo.insert_review(&TutorReview {
+                id: Uuid::new_v4(),
+                tutor_id: tutor.id,
+                student_id: student.id,
+                rating,
+                comment: None,
+                created_at: Utc::now(),
+                edited_at: None,
+            }).await.unwrap();
+        }
+
+        assert_eq!(AdminService::recompute_ratings(&repo).await.unwrap(), 1);
+        let tutor = repo.find_tutor_by_id(tutor.id).await.unwrap().unwrap().tutor;
+        assert_eq!(tutor.rating, Some(4.0));
+        assert_eq!(tutor.total_reviews, 2);
+
+        let stats = AdminService::platform_stats(&repo).await.unwrap();
+        assert_eq!(stats.total_users, 3);
+        assert_eq!(stats.students, 2);
+        assert_eq!(stats.tutor_profiles, 1);
+        assert_eq!(stats.reviews, 2);
+        assert_eq!(stats.average_rating, Some(4.0));
+
+        // Only tutors whose figures change are audited
+        let owner = insert_user(&repo, UserRole::Tutor).await;
+        let drifted = TutorProfile { id: Uuid::new_v4(), user_id: owner.id, total_reviews: 7, ..tutor.clone() };
+        repo.insert_tutor(&drifted).await.unwrap();
+        assert_eq!(AdminService::recompute_ratings(&repo).await.unwrap(), 2);
+        assert_eq!(audit_trail(&repo, "tutor", drifted.id).await, vec![(None, "tutor.rating_recomputed".to_string())]);
+        assert!(audit_trail(&repo, "tutor", tutor.id).await.is_empty());
+    }
+}
@@ -0,0 +1,705 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Assignment, AssignmentFile, AssignmentFileResponse, AssignmentRequest, AssignmentResponse,
+    AssignmentSubmission, CriterionScore, EnrollmentStatus, FilePurpose, GradeResponse, GradeSubmissionRequest,
+    RubricCriterion, RubricCriterionRequest, SubmissionResponse, SubmitAssignmentRequest,
+};
+use crate::repositories::{
+    AssignmentRepository, CourseRepository, EnrollmentRepository, FileRepository, NotificationRepository,
+    TutorRepository,
+};
+use crate::services::file::FileUpload;
+use crate::services::{CourseService, FileService, NotificationService};
+use crate::storage::FileStore;
+use chrono::Utc;
+use sqlx::types::Json;
+use std::collections::HashSet;
+use uuid::Uuid;
+use validator::Validate;
+
+const ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "application/pdf",
+    "application/zip",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "image/jpeg",
+    "image/png",
+    "text/markdown",
+    "text/plain",
+];
+
+// Tutors set assignments with a due date and a rubric. Enrolled students
+// submit text and files, possibly late and possibly several times; the tutor
+// grades each submission and publishes the grade, which notifies the student.
+pub struct AssignmentService;
+
+impl AssignmentService {
+    pub async fn create_assignment<R: TutorRepository + CourseRepository + AssignmentRepository>(
+        repo: &R,
+        course_id: Uuid,
+        tutor_id: Uuid,
+        request: AssignmentRequest,
+    ) -> AppResult<AssignmentResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        Self::check_rubric(&request.rubric)?;
+
+        CourseService::find_owned_course(repo, course_id, tutor_id).await?;
+
+        let now = Utc::now();
+        let assignment = Assignment {
+            id: Uuid::new_v4(),
+            course_id,
+            title: request.title,
+            instructions: request.instructions,
+            due_at: request.due_at,
+            accept_late: request.accept_late,
+            max_submissions: request.max_submissions,
+            rubric: Json(Self::build_rubric(request.rubric, &[])),
+            created_at: now,
+            updated_at: now,
+        };
+        repo.insert_assignment(&assignment).await?;
+
+        Ok(AssignmentResponse::from(assignment))
+    }
+
+    // Replaces the assignment; criteria sent with an existing id keep it.
+    // Grades already given keep the scores they were given.
+    pub async fn update_assignment<R: TutorRepository + CourseRepository + AssignmentRepository>(
+        repo: &R,
+        assignment_id: Uuid,
+        tutor_id: Uuid,
+        request: AssignmentRequest,
+    ) -> AppResult<AssignmentResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        Self::check_rubric(&request.rubric)?;
+
+        let mut assignment = Self::find_owned_assignment(repo, assignment_id, tutor_id).await?;
+        assignment.title = request.title;
+        assignment.instructions = request.instructions;
+        assignment.due_at = request.due_at;
+        assignment.accept_late = request.accept_late;
+        assignment.max_submissions = request.max_submissions;
+        assignment.rubric = Json(Self::build_rubric(request.rubric, &assignment.rubric));
+        assignment.updated_at = Utc::now();
+
+        repo.update_assignment(&assignment).await?;
+
+        Ok(AssignmentResponse::from(assignment))
+    }
+
+    pub async fn delete_assignment<R>(repo: &R, files: &FileStore, assignment_id: Uuid, tutor_id: Uuid) -> AppResult<()>
+    where
+        R: TutorRepository + CourseRepository + AssignmentRepository + FileRepository,
+    {
+        Self::find_owned_assignment(repo, assignment_id, tutor_id).await?;
+
+        let uploads = repo.list_files_for_assignment(assignment_id).await?;
+        if !repo.delete_assignment(assignment_id).await? {
+            return Err(AppError::NotFound("Assignment not found or access denied".to_string()));
+        }
+
+        // The files' rows went with the assignment; their content goes too
+        for stored_file_id in uploads.iter().filter_map(|file| file.stored_file_id) {
+            if let Some(stored) = repo.find_stored_file(stored_file_id).await? {
+                FileService::remove(repo, files, &stored).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn list_course_assignments<R>(repo: &R, course_id: Uuid, user_id: Uuid) -> AppResult<Vec<AssignmentResponse>>
+    where
+        R: TutorRepository + CourseRepository + EnrollmentRepository + AssignmentRepository,
+    {
+        repo.find_course_by_id(course_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?;
+        Self::check_access(repo, course_id, user_id).await?;
+
+        let assignments = repo.list_assignments_for_course(course_id).await?;
+        Ok(assignments.into_iter().map(AssignmentResponse::from).collect())
+    }
+
+    pub async fn get_assignment<R>(repo: &R, assignment_id: Uuid, user_id: Uuid) -> AppResult<AssignmentResponse>
+    where
+        R: TutorRepository + CourseRepository + EnrollmentRepository + AssignmentRepository,
+    {
+        let assignment = Self::find_assignment(repo, assignment_id).await?;
+        Self::check_access(repo, assignment.course_id, user_id).await?;
+
+        Ok(AssignmentResponse::from(assignment))
+    }
+
+    // Stores a file for the student to attach to a submission
+    pub async fn upload_file<R: EnrollmentRepository + AssignmentRepository + FileRepository>(
+        repo: &R,
+        files: &FileStore,
+        assignment_id: Uuid,
+        student_id: Uuid,
+        upload: FileUpload,
+    ) -> AppResult<AssignmentFileResponse> {
+        let assignment = Self::find_assignment(repo, assignment_id).await?;
+        Self::check_enrolled(repo, assignment.course_id, student_id).await?;
+
+        let stored = FileService::store(
+            repo,
+            files,
+            student_id,
+            FilePurpose::Assignment,
+            upload,
+            ALLOWED_CONTENT_TYPES,
+        ).await?;
+        let file = AssignmentFile {
+            id: Uuid::new_v4(),
+            assignment_id,
+            student_id,
+            stored_file_id: Some(stored.id),
+            file_name: stored.file_name.clone(),
+            content_type: stored.content_type.clone(),
+            size_bytes: stored.size_bytes,
+            created_at: stored.created_at,
+        };
+        // The assignment may have been deleted since it was read
+        if let Err(error) = repo.insert_assignment_file(&file).await {
+            FileService::remove(repo, files, &stored).await?;
+            return Err(error);
+        }
+
+        Ok(AssignmentFileResponse::from(file))
+    }
+
+    // For the student who uploaded the file and the course's tutor
+    pub async fn download_file<R>(
+        repo: &R,
+        files: &FileStore,
+        file_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<(AssignmentFile, Vec<u8>)>
+    where
+        R: TutorRepository + CourseRepository + AssignmentRepository + FileRepository,
+    {
+        let not_found = || AppError::NotFound("File not found".to_string());
+
+        let file = repo.find_assignment_file(file_id).await?.ok_or_else(not_found)?;
+        if file.student_id != user_id {
+            let assignment = Self::find_assignment(repo, file.assignment_id).await?;
+            if !Self::is_owner(repo, assignment.course_id, user_id).await? {
+                return Err(not_found());
+            }
+        }
+
+        let content = match file.stored_file_id {
+            Some(stored_file_id) => {
+                let stored = repo.find_stored_file(stored_file_id).await?.ok_or_else(not_found)?;
+                FileService::content(files, &stored).await?
+            }
+            None => repo.read_assignment_file(file_id).await?.ok_or_else(not_found)?,
+        };
+        Ok((file, content))
+    }
+
+    // Each call is a new submission; earlier ones and their grades stay in the history
+    pub async fn submit<R: EnrollmentRepository + AssignmentRepository>(
+        repo: &R,
+        assignment_id: Uuid,
+        student_id: Uuid,
+        request: SubmitAssignmentRequest,
+    ) -> AppResult<SubmissionResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let assignment = Self::find_assignment(repo, assignment_id).await?;
+        Self::check_enrolled(repo, assignment.course_id, student_id).await?;
+
+        let text = request.text.trim().to_string();
+        let file_ids: Vec<Uuid> = request.file_ids.iter()
+            .copied()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if text.is_empty() && file_ids.is_empty() {
+            return Err(AppError::Validation("A submission needs text or at least one file".to_string()));
+        }
+        let files = repo.list_assignment_files(&file_ids).await?;
+        let own_files = files.iter()
+            .filter(|file| file.assignment_id == assignment_id && file.student_id == student_id)
+            .count();
+        if own_files != file_ids.len() {
+            return Err(AppError::BadRequest("Files must be uploaded for this assignment first".to_string()));
+        }
+
+        let now = Utc::now();
+        let is_late = now > assignment.due_at;
+        if is_late && !assignment.accept_late {
+            return Err(AppError::Conflict("The due date for this assignment has passed".to_string()));
+        }
+
+        let submission = repo.insert_submission(&AssignmentSubmission {
+            id: Uuid::new_v4(),
+            assignment_id,
+            student_id,
+            submission_number: 0,
+            text,
+            file_ids: files.iter().map(|file| file.id).collect(),
+            submitted_at: now,
+            is_late,
+            score: None,
+            max_score: assignment.max_score(),
+            rubric_scores: Json(Vec::new()),
+            feedback: None,
+            graded_at: None,
+            grade_published_at: None,
+            created_at: now,
+            updated_at: now,
+        }, assignment.max_submissions).await?;
+
+        Ok(Self::submission_response(submission, files, false))
+    }
+
+    // Every student's submissions for the tutor, otherwise the caller's own
+    pub async fn list_submissions<R>(repo: &R, assignment_id: Uuid, user_id: Uuid) -> AppResult<Vec<SubmissionResponse>>
+    where
+        R: TutorRepository + CourseRepository + AssignmentRepository,
+    {
+        let assignment = Self::find_assignment(repo, assignment_id).await?;
+        let is_owner = Self::is_owner(repo, assignment.course_id, user_id).await?;
+        let student_id = if is_owner { None } else { Some(user_id) };
+
+        let mut responses = Vec::new();
+        for submission in repo.list_submissions(assignment_id, student_id).await? {
+            let files = repo.list_assignment_files(&submission.file_ids).await?;
+            responses.push(Self::submission_response(submission, files, is_owner));
+        }
+
+        Ok(responses)
+    }
+
+    pub async fn get_submission<R>(repo: &R, submission_id: Uuid, user_id: Uuid) -> AppResult<SubmissionResponse>
+    where
+        R: TutorRepository + CourseRepository + AssignmentRepository,
+    {
+        let not_found = || AppError::NotFound("Submission not found".to_string());
+
+        let submission = repo.find_submission(submission_id).await?.ok_or_else(not_found)?;
+        let assignment = Self::find_assignment(repo, submission.assignment_id).await?;
+        let is_owner = Self::is_owner(repo, assignment.course_id, user_id).await?;
+        if submission.student_id != user_id && !is_owner {
+            return Err(not_found());
+        }
+
+        let files = repo.list_assignment_files(&submission.file_ids).await?;
+        Ok(Self::submission_response(submission, files, is_owner))
+    }
+
+    // Scores every rubric criterion. The grade stays a draft unless `publish`
+    // is set; once published, regrades are published straight away.
+    pub async fn grade_submission<R>(
+        repo: &R,
+        submission_id: Uuid,
+        tutor_id: Uuid,
+        request: GradeSubmissionRequest,
+    ) -> AppResult<SubmissionResponse>
+    where
+        R: TutorRepository + CourseRepository + AssignmentRepository + NotificationRepository,
+    {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let not_found = || AppError::NotFound("Submission not found or access denied".to_string());
+        let mut submission = repo.find_submission(submission_id).await?.ok_or_else(not_found)?;
+        let assignment = Self::find_owned_assignment(repo, submission.assignment_id, tutor_id)
+            .await
+            .map_err(|e| match e {
+                AppError::NotFound(_) => not_found(),
+                e => e,
+            })?;
+
+        let mut rubric_scores = Vec::new();
+        for criterion in assignment.rubric.iter() {
+            let mut scores = request.scores.iter().filter(|score| score.criterion_id == criterion.id);
+            let (Some(score), None) = (scores.next(), scores.next()) else {
+                return Err(AppError::Validation(format!(
+                    "Criterion \"{}\" must be scored exactly once",
+                    criterion.title
+                )));
+            };
+            if score.points > criterion.max_points {
+                return Err(AppError::Validation(format!(
+                    "Criterion \"{}\" is worth at most {} points",
+                    criterion.title, criterion.max_points
+                )));
+            }
+            rubric_scores.push(CriterionScore {
+                criterion_id: criterion.id,
+                points: score.points,
+                comment: score.comment.clone(),
+            });
+        }
+        if request.scores.len() != rubric_scores.len() {
+            return Err(AppError::Validation("Scores must match the assignment's rubric".to_string()));
+        }
+
+        let now = Utc::now();
+        submission.score = Some(rubric_scores.iter().map(|score| score.points).sum());
+        submission.max_score = assignment.max_score();
+        submission.rubric_scores = Json(rubric_scores);
+        submission.feedback = request.feedback;
+        submission.graded_at = Some(now);
+        let publish = request.publish || submission.grade_published_at.is_some();
+        if publish {
+            submission.grade_published_at = Some(now);
+        }
+        submission.updated_at = now;
+
+        repo.update_submission_grade(&submission).await?;
+        if publish {
+            Self::notify_published(repo, &assignment, &submission).await?;
+        }
+
+        let files = repo.list_assignment_files(&submission.file_ids).await?;
+        Ok(Self::submission_response(submission, files, true))
+    }
+
+    // Publishes every draft grade of the assignment at once
+    pub async fn publish_grades<R>(repo: &R, assignment_id: Uuid, tutor_id: Uuid) -> AppResult<Vec<SubmissionResponse>>
+    where
+        R: TutorRepository + CourseRepository + AssignmentRepository + NotificationRepository,
+    {
+        let assignment = Self::find_owned_assignment(repo, assignment_id, tutor_id).await?;
+
+        let now = Utc::now();
+        let mut published = Vec::new();
+        for mut submission in repo.list_submissions(assignment_id, None).await? {
+            if submission.graded_at.is_none() || submission.grade_published_at.is_some() {
+                continue;
+            }
+            submission.grade_published_at = Some(now);
+            submission.updated_at = now;
+            repo.update_submission_grade(&submission).await?;
+            Self::notify_published(repo, &assignment, &submission).await?;
+
+            let files = repo.list_assignment_files(&submission.file_ids).await?;
+            published.push(Self::submission_response(submission, files, true));
+        }
+
+        Ok(published)
+    }
+
+    async fn notify_published<R: NotificationRepository>(
+        repo: &R,
+        assignment: &Assignment,
+        submission: &AssignmentSubmission,
+    ) -> AppResult<()> {
+        NotificationService::notify(
+            repo,
+            submission.student_id,
+            "grade_published",
+            format!("Grade published: {}", assignment.title),
+            format!(
+                "Submission {} scored {} of {} points.",
+                submission.submission_number,
+                submission.score.unwrap_or_default(),
+                submission.max_score
+            ),
+            Some(format!("/api/v1/submissions/{}", submission.id)),
+        ).await
+    }
+
+    // Draft grades are only shown to the tutor
+    fn submission_response(
+        submission: AssignmentSubmission,
+        files: Vec<AssignmentFile>,
+        include_drafts: bool,
+    ) -> SubmissionResponse {
+        let visible = include_drafts || submission.grade_published_at.is_some();
+        let grade = match (submission.score, submission.graded_at) {
+            (Some(score), Some(graded_at)) if visible => Some(GradeResponse {
+                score,
+                max_score: submission.max_score,
+                rubric_scores: submission.rubric_scores.0,
+                feedback: submission.feedback,
+                graded_at,
+                published_at: submission.grade_published_at,
+            }),
+            _ => None,
+        };
+
+        SubmissionResponse {
+            id: submission.id,
+            assignment_id: submission.assignment_id,
+            student_id: submission.student_id,
+            submission_number: submission.submission_number,
+            text: submission.text,
+            files: files.into_iter().map(AssignmentFileResponse::from).collect(),
+            submitted_at: submission.submitted_at,
+            is_late: submission.is_late,
+            grade,
+        }
+    }
+
+    fn check_rubric(rubric: &[RubricCriterionRequest]) -> AppResult<()> {
+        let ids: Vec<Uuid> = rubric.iter().filter_map(|criterion| criterion.id).collect();
+        if ids.iter().collect::<HashSet<_>>().len() != ids.len() {
+            return Err(AppError::Validation("Validation failed: criterion ids must be distinct".to_string()));
+        }
+        Ok(())
+    }
+
+    // Ids of criteria the rubric already had are kept; anything else gets a fresh one
+    fn build_rubric(requests: Vec<RubricCriterionRequest>, existing: &[RubricCriterion]) -> Vec<RubricCriterion> {
+        requests.into_iter()
+            .map(|request| RubricCriterion {
+                id: request.id
+                    .filter(|id| existing.iter().any(|criterion| criterion.id == *id))
+                    .unwrap_or_else(Uuid::new_v4),
+                title: request.title,
+                description: request.description,
+                max_points: request.max_points,
+            })
+            .collect()
+    }
+
+    async fn find_assignment<R: AssignmentRepository>(repo: &R, assignment_id: Uuid) -> AppResult<Assignment> {
+        repo.find_assignment(assignment_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Assignment not found".to_string()))
+    }
+
+    // Loads an assignment, treating assignments of another tutor's course as missing
+    async fn find_owned_assignment<R: TutorRepository + CourseRepository + AssignmentRepository>(
+        repo: &R,
+        assignment_id: Uuid,
+        tutor_id: Uuid,
+    ) -> AppResult<Assignment> {
+        let not_found = || AppError::NotFound("Assignment not found or access denied".to_string());
+
+        let assignment = repo.find_assignment(assignment_id).await?.ok_or_else(not_found)?;
+        CourseService::find_owned_course(repo, assignment.course_id, tutor_id)
+            .await
+            .map_err(|e| match e {
+                AppError::NotFound(_) => not_found(),
+                e => e,
+            })?;
+
+        Ok(assignment)
+    }
+
+    async fn is_owner<R: TutorRepository + CourseRepository>(repo: &R, course_id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        match CourseService::find_owned_course(repo, course_id, user_id).await {
+            Ok(_) => Ok(true),
+            Err(AppError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    // The course's tutor or one of its active students
+    async fn check_access<R>(repo: &R, course_id: Uuid, user_id: Uuid) -> AppResult<()>
+    where
+        R: TutorRepository + CourseRepository + EnrollmentRepository,
+    {
+        if Self::is_owner(repo, course_id, user_id).await? {
+            return Ok(());
+        }
+        Self::check_enrolled(repo, course_id, user_id).await
+    }
+
+    async fn check_enrolled<R: EnrollmentRepository>(repo: &R, course_id: Uuid, student_id: Uuid) -> AppResult<()> {
+        let enrolled = repo.find_enrollment(course_id, student_id)
+            .await?
+            .is_some_and(|row| row.enrollment.status == EnrollmentStatus::Active);
+        if !enrolled {
+            return Err(AppError::Authorization("Enroll in this course to see its assignments".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        CreateCourseRequest, CriterionScoreRequest, DifficultyLevel, NotificationListQuery, RequestContext, UserRole,
+    };
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use crate::services::EnrollmentService;
+    use crate::storage::MemoryStorage;
+    use chrono::{DateTime, Duration};
+
+    fn upload(file_name: &str, content_type: &str, content: &[u8]) -> FileUpload {
+        FileUpload {
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+            content: content.to_vec(),
+        }
+    }
+
+    fn criterion(title: &str, max_points: i32) -> RubricCriterionRequest {
+        RubricCriterionRequest {
+            id: None,
+            title: title.to_string(),
+            description: String::new(),
+            max_points,
+        }
+    }
+
+    fn assignment_request(due_at: DateTime<Utc>, accept_late: bool, max_submissions: Option<i32>) -> AssignmentRequest {
+        AssignmentRequest {
+            title: "Essay".to_string(),
+            instructions: "Write about energy".to_string(),
+            due_at,
+            accept_late,
+            max_submissions,
+            rubric: vec![criterion("Content", 6), criterion("Style", 4)],
+        }
+    }
+
+    // The tutor, an enrolled student and the course they share
+    async fn course_with_student(repo: &InMemoryRepository) -> (Uuid, Uuid, Uuid) {
+        let (user, _) = insert_tutor(repo, &["Physics"]).await;
+        let course = CourseService::create_course(repo, user.id, CreateCourseRequest {
+            title: "Energy".to_string(),
+            description: "Work and power".to_string(),
+            price: 2000,
+            duration_minutes: 60,
+            category: "Science".to_string(),
+            difficulty_level: DifficultyLevel::Beginner,
+            max_students: None,
+        }, &RequestContext::default()).await.unwrap();
+        let student = insert_user(repo, UserRole::Student).await;
+        EnrollmentService::enroll(repo, course.id, student.id).await.unwrap();
+        (user.id, student.id, course.id)
+    }
+
+    fn text(text: &str) -> SubmitAssignmentRequest {
+        SubmitAssignmentRequest { text: text.to_string(), file_ids: Vec::new() }
+    }
+
+    fn scores(assignment: &AssignmentResponse, points: &[i32]) -> GradeSubmissionRequest {
+        GradeSubmissionRequest {
+            scores: assignment.rubric.iter()
+                .zip(points)
+                .map(|(criterion, &points)| CriterionScoreRequest {
+                    criterion_id: criterion.id,
+                    points,
+                    comment: None,
+                })
+                .collect(),
+            feedback: Some("Good work".to_string()),
+            publish: false,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn submissions_are_flagged_late_and_limited() {
+        let repo = InMemoryRepository::new();
+        let files = FileStore::new(MemoryStorage::new(), b"secret");
+        let (tutor_id, student_id, course_id) = course_with_student(&repo).await;
+        let classmate = insert_user(&repo, UserRole::Student).await;
+        EnrollmentService::enroll(&repo, course_id, classmate.id).await.unwrap();
+        let overdue = Utc::now() - Duration::days(1);
+        let assignment = AssignmentService::create_assignment(&repo, course_id, tutor_id, assignment_request(overdue, true, Some(2)))
+            .await
+            .unwrap();
+
+        let upload_file = |name: &str, content_type: &str, content: &[u8]| {
+            AssignmentService::upload_file(&repo, &files, assignment.id, student_id, upload(name, content_type, content))
+        };
+        let error = upload_file("run.exe", "application/x-msdownload", b"MZ").await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+        // The content has to match the declared type
+        let error = upload_file("essay.pdf", "application/pdf", b"MZ").await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+        let file = upload_file("C:\\essays\\essay.txt", "text/plain", b"Energy is").await.unwrap();
+        assert_eq!((file.file_name.as_str(), file.size_bytes), ("essay.txt", 9));
+
+        // Files belong to the student who uploaded them
+        let request = SubmitAssignmentRequest { text: String::new(), file_ids: vec![file.id] };
+        let error = AssignmentService::submit(&repo, assignment.id, classmate.id, request.clone()).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+        let error = AssignmentService::download_file(&repo, &files, file.id, classmate.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        let first = AssignmentService::submit(&repo, assignment.id, student_id, request).await.unwrap();
+        assert!(first.is_late);
+        assert_eq!(first.files.len(), 1);
+        let (_, content) = AssignmentService::download_file(&repo, &files, file.id, tutor_id).await.unwrap();
+        assert_eq!(content, b"Energy is");
+
+        let second = AssignmentService::submit(&repo, assignment.id, student_id, text("Revised")).await.unwrap();
+        assert_eq!(second.submission_number, 2);
+        let error = AssignmentService::submit(&repo, assignment.id, student_id, text("Again")).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        // Closed assignments refuse late work
+        let closed = AssignmentService::create_assignment(&repo, course_id, tutor_id, assignment_request(overdue, false, None))
+            .await
+            .unwrap();
+        let error = AssignmentService::submit(&repo, closed.id, student_id, text("Sorry")).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        // Deleting the assignment removes its files from the store
+        let stored_file_id = repo.find_assignment_file(file.id).await.unwrap().unwrap().stored_file_id.unwrap();
+        AssignmentService::delete_assignment(&repo, &files, assignment.id, tutor_id).await.unwrap();
+        assert!(repo.find_stored_file(stored_file_id).await.unwrap().is_none());
+    }
+
+    #[actix_rt::test]
+    async fn published_grades_reach_the_student() {
+        let repo = InMemoryRepository::new();
+        let (tutor_id, student_id, course_id) = course_with_student(&repo).await;
+        let (other_tutor, _) = insert_tutor(&repo, &["Music"]).await;
+        let due = Utc::now() + Duration::days(7);
+        let assignment = AssignmentService::create_assignment(&repo, course_id, tutor_id, assignment_request(due, true, None))
+            .await
+            .unwrap();
+        let submission = AssignmentService::submit(&repo, assignment.id, student_id, text("Essay")).await.unwrap();
+        assert!(!submission.is_late);
+
+        let error = AssignmentService::grade_submission(&repo, submission.id, tutor_id, scores(&assignment, &[5]))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+        let error = AssignmentService::grade_submission(&repo, submission.id, tutor_id, scores(&assignment, &[7, 4]))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+        let error = AssignmentService::grade_submission(&repo, submission.id, other_tutor.id, scores(&assignment, &[5, 3]))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        // Drafts are for the tutor only
+        let draft = AssignmentService::grade_submission(&repo, submission.id, tutor_id, scores(&assignment, &[5, 3]))
+            .await
+            .unwrap();
+        assert_eq!(draft.grade.map(|grade| (grade.score, grade.max_score)), Some((8, 10)));
+        let seen = AssignmentService::get_submission(&repo, submission.id, student_id).await.unwrap();
+        assert!(seen.grade.is_none());
+        let unread = || NotificationListQuery { unread: true };
+        assert!(NotificationService::list(&repo, student_id, unread()).await.unwrap().is_empty());
+
+        let published = AssignmentService::publish_grades(&repo, assignment.id, tutor_id).await.unwrap();
+        assert_eq!(published.len(), 1);
+        let seen = AssignmentService::list_submissions(&repo, assignment.id, student_id).await.unwrap();
+        assert_eq!(seen[0].grade.as_ref().map(|grade| grade.score), Some(8));
+        let inbox = NotificationService::list(&repo, student_id, unread()).await.unwrap();
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].kind, "grade_published");
+
+        // Regrading a published grade publishes again
+        NotificationService::mark_read(&repo, inbox[0].id, student_id).await.unwrap();
+        AssignmentService::grade_submission(&repo, submission.id, tutor_id, scores(&assignment, &[6, 4])).await.unwrap();
+        let inbox = NotificationService::list(&repo, student_id, unread()).await.unwrap();
+        assert_eq!(inbox.len(), 1);
+        let seen = AssignmentService::get_submission(&repo, submission.id, student_id).await.unwrap();
+        assert_eq!(seen.grade.map(|grade| grade.score), Some(10));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::errors::AppResult;
+use crate::models::{AuditEntry, RequestContext};
+use crate::repositories::AuditRepository;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+// Left out of change sets: secrets, and timestamps every write touches
+const UNAUDITED_FIELDS: &[&str] = &["password_hash", "created_at", "updated_at", "edited_at"];
+
+// What an audited call acted on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuditTarget {
+    User(Uuid),
+    Tutor(Uuid),
+    Course(Uuid),
+    Review(Uuid),
+}
+
+impl AuditTarget {
+    pub const TYPES: &'static [&'static str] = &["course", "review", "tutor", "user"];
+
+    // Wire name, as used in JSON and query strings
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            AuditTarget::User(_) => "user",
+            AuditTarget::Tutor(_) => "tutor",
+            AuditTarget::Course(_) => "course",
+            AuditTarget::Review(_) => "review",
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        match *self {
+            AuditTarget::User(id) | AuditTarget::Tutor(id) | AuditTarget::Course(id) | AuditTarget::Review(id) => id,
+        }
+    }
+}
+
+// Append-only record of changes to accounts, profiles, courses and reviews.
+// Entries are written after the change they describe has been stored and
+// are never updated or removed.
+pub struct AuditService;
+
+impl AuditService {
+    pub async fn record<R: AuditRepository>(
+        repo: &R,
+        context: &RequestContext,
+        actor_id: Option<Uuid>,
+        action: &str,
+        target: AuditTarget,
+        changes: Option<Value>,
+        reason: Option<String>,
+    ) -> AppResult<()> {
+        repo.insert_audit_entry(&AuditEntry {
+            id: Uuid::new_v4(),
+            actor_id,
+            action: action.to_string(),
+            target_type: target.type_name().to_string(),
+            target_id: target.id(),
+            changes,
+            reason,
+            ip_address: context.ip_address.clone(),
+            request_id: context.request_id.clone(),
+            created_at: Utc::now(),
+        }).await
+    }
+}
+
+// The fields that differ between two versions of a record, as
+// {"field": {"before": ..., "after": ...}}. None stands for a record that
+// does not exist yet or no longer exists.
+pub fn changes<T: Serialize>(before: Option<&T>, after: Option<&T>) -> Option<Value> {
+    let fields = |record: Option<&T>| match record.map(serde_json::to_value) {
+        Some(Ok(Value::Object(fields))) => fields,
+        _ => Map::new(),
+    };
+    let (before, after) = (fields(before), fields(after));
+
+    let mut changed = Map::new();
+    for name in before.keys().chain(after.keys()) {
+        if UNAUDITED_FIELDS.contains(&name.as_str()) || changed.contains_key(name) {
+            continue;
+        }
+        let old = before.get(name).unwrap_or(&Value::Null);
+        let new = after.get(name).unwrap_or(&Value::Null);
+        if old != new {
+            changed.insert(name.clone(), json!({ "before": old, "after": new }));
+        }
+    }
+    (!changed.is_empty()).then_some(Value::Object(changed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Account {
+        email: String,
+        password_hash: String,
+        price: i32,
+        updated_at: i64,
+    }
+
+    fn account(price: i32, updated_at: i64) -> Account {
+        Account {
+            email: "sam@example.com".to_string(),
+            password_hash: format!("hash-{}", updated_at),
+            price,
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn change_sets_hold_only_changed_fields_and_no_secrets() {
+        let updated = changes(Some(&account(3000, 1)), Some(&account(4500, 2))).unwrap();
+        assert_eq!(updated, json!({ "price": { "before": 3000, "after": 4500 } }));
+
+        let created = changes(None, Some(&account(3000, 1))).unwrap();
+        assert_eq!(created["email"], json!({ "before": null, "after": "sam@example.com" }));
+        assert!(created.get("password_hash").is_none());
+
+        let deleted = changes(Some(&account(3000, 1)), None).unwrap();
+        assert_eq!(deleted["price"], json!({ "before": 3000, "after": null }));
+
+        assert!(changes(Some(&account(3000, 1)), Some(&account(3000, 2))).is_none());
+    }
+}
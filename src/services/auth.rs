@@ -1,30 +1,44 @@
-use crate::database::DbPool;
 use crate::errors::{AppError, AppResult};
-use crate::models::{User, CreateUserRequest, LoginRequest, LoginResponse, UserResponse, UserRole};
+use crate::models::{User, CreateUserRequest, LoginRequest, LoginResponse, RequestContext, UserResponse, UserRole};
 use crate::middleware::Claims;
+use crate::repositories::{AuditRepository, UserRepository};
+use crate::services::audit::{self, AuditService, AuditTarget};
 use bcrypt::{hash, verify, DEFAULT_COST};
 use chrono::{Duration, Utc};
 use jsonwebtoken::{encode, EncodingKey, Header};
-use sqlx::Row;
 use uuid::Uuid;
 use validator::Validate;
 
 pub struct AuthService;
 
 impl AuthService {
-    pub async fn register_user(
-        pool: &DbPool,
+    // Public sign-up; admin accounts only come from the admin CLI or a
+    // role change by another admin
+    pub async fn register_user<R: UserRepository + AuditRepository>(
+        repo: &R,
         request: CreateUserRequest,
+        context: &RequestContext,
+    ) -> AppResult<UserResponse> {
+        if request.role == UserRole::Admin {
+            return Err(AppError::Authorization("Admin accounts cannot be self-registered".to_string()));
+        }
+
+        Self::create_user(repo, request, context).await
+    }
+
+    // Creates an account with whatever role the request names. The new user
+    // is recorded as the actor of their own registration.
+    pub(crate) async fn create_user<R: UserRepository + AuditRepository>(
+        repo: &R,
+        request: CreateUserRequest,
+        context: &RequestContext,
     ) -> AppResult<UserResponse> {
         // Validate input
         request.validate()
             .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
 
         // Check if user already exists
-        let existing_user = sqlx::query("SELECT id FROM users WHERE email = $1")
-            .bind(&request.email)
-            .fetch_optional(pool)
-            .await?;
+        let existing_user = repo.find_user_by_email(&request.email).await?;
 
         if existing_user.is_some() {
             return Err(AppError::BadRequest("User with this email already exists".to_string()));
@@ -35,40 +49,32 @@ impl AuthService {
             .map_err(|e| AppError::Internal(format!("Password hashing failed: {}", e)))?;
 
         // Insert user
-        let user_id = Uuid::new_v4();
         let now = Utc::now();
-
-        sqlx::query(
-            r#"
-            INSERT INTO users (id, email, password_hash, first_name, last_name, role, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-            "#
-        )
-        .bind(user_id)
-        .bind(&request.email)
-        .bind(&password_hash)
-        .bind(&request.first_name)
-        .bind(&request.last_name)
-        .bind(&request.role)
-        .bind(now)
-        .bind(now)
-        .execute(pool)
-        .await?;
-
-        // Return user response
-        Ok(UserResponse {
-            id: user_id,
+        let user = User {
+            id: Uuid::new_v4(),
             email: request.email,
+            password_hash,
             first_name: request.first_name,
             last_name: request.last_name,
             role: request.role,
             is_active: true,
+            avatar_updated_at: None,
+            token_version: 0,
             created_at: now,
-        })
+            updated_at: now,
+        };
+
+        repo.insert_user(&user).await?;
+        let changes = audit::changes(None, Some(&user));
+        let target = AuditTarget::User(user.id);
+        AuditService::record(repo, context, Some(user.id), "user.registered", target, changes, None).await?;
+
+        // Return user response
+        Ok(UserResponse::from(user))
     }
 
-    pub async fn login_user(
-        pool: &DbPool,
+    pub async fn login_user<R: UserRepository>(
+        repo: &R,
         request: LoginRequest,
     ) -> AppResult<LoginResponse> {
         // Validate input
@@ -76,62 +82,55 @@ impl AuthService {
             .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
 
         // Find user by email
-        let user_row = sqlx::query(
-            "SELECT id, email, password_hash, first_name, last_name, role, is_active, created_at FROM users WHERE email = $1"
-        )
-        .bind(&request.email)
-        .fetch_optional(pool)
-        .await?;
+        let user = repo.find_user_by_email(&request.email).await?;
 
-        let user_row = user_row.ok_or_else(|| {
+        let user = user.ok_or_else(|| {
             AppError::Authentication("Invalid email or password".to_string())
         })?;
 
         // Check if user is active
-        let is_active: bool = user_row.get("is_active");
-        if !is_active {
+        if !user.is_active {
             return Err(AppError::Authentication("Account is deactivated".to_string()));
         }
 
         // Verify password
-        let password_hash: String = user_row.get("password_hash");
-        let password_valid = verify(&request.password, &password_hash)
+        let password_valid = verify(&request.password, &user.password_hash)
             .map_err(|e| AppError::Internal(format!("Password verification failed: {}", e)))?;
 
         if !password_valid {
             return Err(AppError::Authentication("Invalid email or password".to_string()));
         }
 
-        // Create user response
-        let user = UserResponse {
-            id: user_row.get("id"),
-            email: user_row.get("email"),
-            first_name: user_row.get("first_name"),
-            last_name: user_row.get("last_name"),
-            role: user_row.get("role"),
-            is_active,
-            created_at: user_row.get("created_at"),
-        };
-
         // Generate JWT token
         let token = Self::generate_jwt_token(&user)?;
 
-        Ok(LoginResponse { token, user })
+        Ok(LoginResponse { token, user: UserResponse::from(user) })
     }
 
-    pub async fn get_user_by_id(pool: &DbPool, user_id: Uuid) -> AppResult<User> {
-        let user = sqlx::query_as::<_, User>(
-            "SELECT id, email, password_hash, first_name, last_name, role, is_active, created_at, updated_at FROM users WHERE id = $1"
-        )
-        .bind(user_id)
-        .fetch_optional(pool)
-        .await?
-        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    // Issues a fresh token for an already authenticated user
+    pub async fn refresh_token<R: UserRepository>(repo: &R, user_id: Uuid) -> AppResult<LoginResponse> {
+        let user = repo.find_user_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::Authentication("Account no longer exists".to_string()))?;
+
+        if !user.is_active {
+            return Err(AppError::Authentication("Account is deactivated".to_string()));
+        }
+
+        let token = Self::generate_jwt_token(&user)?;
+
+        Ok(LoginResponse { token, user: UserResponse::from(user) })
+    }
+
+    pub async fn get_user_by_id<R: UserRepository>(repo: &R, user_id: Uuid) -> AppResult<User> {
+        let user = repo.find_user_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
 
         Ok(user)
     }
 
-    fn generate_jwt_token(user: &UserResponse) -> AppResult<String> {
+    fn generate_jwt_token(user: &User) -> AppResult<String> {
         let jwt_secret = std::env::var("JWT_SECRET")
             .unwrap_or_else(|_| "your-secret-key-change-in-production".to_string());
 
@@ -144,6 +143,7 @@ impl AuthService {
             sub: user.id,
             email: user.email.clone(),
             role: format!("{:?}", user.role).to_lowercase(),
+            token_version: user.token_version,
             exp: expiration,
         };
 
@@ -157,12 +157,12 @@ impl AuthService {
         Ok(token)
     }
 
-    pub async fn verify_user_role(
-        pool: &DbPool,
+    pub async fn verify_user_role<R: UserRepository>(
+        repo: &R,
         user_id: Uuid,
         required_role: UserRole,
     ) -> AppResult<bool> {
-        let user = Self::get_user_by_id(pool, user_id).await?;
+        let user = Self::get_user_by_id(repo, user_id).await?;
         
         // Admin can access everything
         if user.role == UserRole::Admin {
@@ -173,3 +173,115 @@ impl AuthService {
         Ok(user.role == required_role)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::insert_user;
+
+    fn registration(email: &str) -> CreateUserRequest {
+        CreateUserRequest {
+            email: email.to_string(),
+            password: "correct-horse".to_string(),
+            first_name: "Ada".to_string(),
+            last_name: "Lovelace".to_string(),
+            role: UserRole::Student,
+        }
+    }
+
+    fn login(email: &str, password: &str) -> LoginRequest {
+        LoginRequest {
+            email: email.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn register_then_login_issues_token() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let user = AuthService::register_user(&repo, registration("ada@example.com"), &context).await.unwrap();
+        assert_eq!(user.role, UserRole::Student);
+        assert!(user.is_active);
+
+        let response = AuthService::login_user(&repo, login("ada@example.com", "correct-horse")).await.unwrap();
+        assert_eq!(response.user.id, user.id);
+        assert!(!response.token.is_empty());
+
+        let error = AuthService::login_user(&repo, login("ada@example.com", "wrong-password")).await.unwrap_err();
+        assert!(matches!(error, AppError::Authentication(_)));
+    }
+
+    #[actix_rt::test]
+    async fn refresh_requires_active_account() {
+        let repo = InMemoryRepository::new();
+        let user = insert_user(&repo, UserRole::Tutor).await;
+        let response = AuthService::refresh_token(&repo, user.id).await.unwrap();
+        assert_eq!(response.user.email, user.email);
+
+        let error = AuthService::refresh_token(&repo, Uuid::new_v4()).await.unwrap_err();
+        assert!(matches!(error, AppError::Authentication(_)));
+    }
+
+    #[actix_rt::test]
+    async fn register_rejects_invalid_input() {
+        let repo = InMemoryRepository::new();
+        let mut request = registration("not-an-email");
+        request.password = "short".to_string();
+
+        let error = AuthService::register_user(&repo, request, &RequestContext::default()).await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+    }
+
+    #[actix_rt::test]
+    async fn register_refuses_admin_accounts() {
+        let repo = InMemoryRepository::new();
+        let mut request = registration("eve@example.com");
+        request.role = UserRole::Admin;
+
+        let error = AuthService::register_user(&repo, request, &RequestContext::default()).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+        assert!(repo.find_user_by_email("eve@example.com").await.unwrap().is_none());
+    }
+
+    #[actix_rt::test]
+    async fn register_rejects_duplicate_email() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let existing = insert_user(&repo, UserRole::Student).await;
+
+        let error = AuthService::register_user(&repo, registration(&existing.email), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+    }
+
+    #[actix_rt::test]
+    async fn login_rejects_unknown_and_deactivated_accounts() {
+        let repo = InMemoryRepository::new();
+        let error = AuthService::login_user(&repo, login("nobody@example.com", "whatever")).await.unwrap_err();
+        assert!(matches!(error, AppError::Authentication(_)));
+
+        let mut user = insert_user(&repo, UserRole::Student).await;
+        user.id = Uuid::new_v4();
+        user.email = "inactive@example.com".to_string();
+        user.is_active = false;
+        repo.insert_user(&user).await.unwrap();
+
+        let error = AuthService::login_user(&repo, login(&user.email, "whatever")).await.unwrap_err();
+        assert!(matches!(error, AppError::Authentication(message) if message == "Account is deactivated"));
+    }
+
+    #[actix_rt::test]
+    async fn admins_satisfy_every_role_check() {
+        let repo = InMemoryRepository::new();
+        let admin = insert_user(&repo, UserRole::Admin).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        assert!(AuthService::verify_user_role(&repo, admin.id, UserRole::Tutor).await.unwrap());
+        assert!(AuthService::verify_user_role(&repo, student.id, UserRole::Student).await.unwrap());
+        assert!(!AuthService::verify_user_role(&repo, student.id, UserRole::Tutor).await.unwrap());
+
+        let error = AuthService::verify_user_role(&repo, Uuid::new_v4(), UserRole::Student).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+    }
+}
@@ -0,0 +1,593 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    AvailabilityBlackout, AvailabilityException, AvailabilityWindow, BlackoutResponse,
+    CreateBlackoutRequest, CreateExceptionRequest, ExceptionResponse, OpenSlot, OpenSlotsQuery,
+    OpenSlotsResponse, ScheduleResponse, SetScheduleRequest, TutorSchedule, WeeklyWindow,
+};
+use crate::repositories::{AvailabilityRepository, BookingRepository, TutorRepository};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use uuid::Uuid;
+use validator::Validate;
+
+const DEFAULT_SLOT_MINUTES: i32 = 60;
+// Longest date range a single open-slots request may cover
+const MAX_SLOT_RANGE_DAYS: i64 = 31;
+// Years open-slots requests may ask about. Far enough inside chrono's limits
+// that moving between time zones and a day past `to` cannot overflow.
+const SLOT_YEARS: std::ops::RangeInclusive<i32> = 1900..=9999;
+
+// Half-open span of time, [start, end)
+pub(crate) type Interval = (DateTime<Utc>, DateTime<Utc>);
+
+pub struct AvailabilityService;
+
+impl AvailabilityService {
+    pub async fn get_schedule<R: TutorRepository + AvailabilityRepository>(
+        repo: &R,
+        user_id: Uuid,
+    ) -> AppResult<ScheduleResponse> {
+        let tutor_id = Self::own_tutor_id(repo, user_id).await?;
+        let schedule = repo.find_schedule(tutor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Availability schedule not set".to_string()))?;
+
+        Self::schedule_response(repo, schedule).await
+    }
+
+    pub async fn set_schedule<R: TutorRepository + AvailabilityRepository>(
+        repo: &R,
+        user_id: Uuid,
+        request: SetScheduleRequest,
+    ) -> AppResult<ScheduleResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        parse_time_zone(&request.time_zone)?;
+        for window in &request.windows {
+            check_times(window.start_time, window.end_time)?;
+        }
+
+        let tutor_id = Self::own_tutor_id(repo, user_id).await?;
+        let now = Utc::now();
+        let schedule = TutorSchedule {
+            tutor_id,
+            time_zone: request.time_zone,
+            slot_minutes: request.slot_minutes.unwrap_or(DEFAULT_SLOT_MINUTES),
+            created_at: now,
+            updated_at: now,
+        };
+        let windows: Vec<AvailabilityWindow> = request.windows.into_iter()
+            .map(|window| AvailabilityWindow {
+                id: Uuid::new_v4(),
+                tutor_id,
+                weekday: window.weekday.num_days_from_monday() as i16,
+                start_time: window.start_time,
+                end_time: window.end_time,
+            })
+            .collect();
+
+        repo.replace_schedule(&schedule, &windows).await?;
+        Self::get_schedule(repo, user_id).await
+    }
+
+    pub async fn add_exception<R: TutorRepository + AvailabilityRepository>(
+        repo: &R,
+        user_id: Uuid,
+        request: CreateExceptionRequest,
+    ) -> AppResult<ExceptionResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        match (request.start_time, request.end_time) {
+            (Some(start), Some(end)) => check_times(start, end)?,
+            (None, None) => {}
+            _ => return Err(AppError::BadRequest(
+                "Exception start and end times must be given together".to_string()
+            )),
+        }
+
+        let tutor_id = Self::own_schedule(repo, user_id).await?.tutor_id;
+        let exception = AvailabilityException {
+            id: Uuid::new_v4(),
+            tutor_id,
+            date: request.date,
+            start_time: request.start_time,
+            end_time: request.end_time,
+            is_available: request.is_available,
+            reason: request.reason,
+            created_at: Utc::now(),
+        };
+
+        repo.insert_exception(&exception).await?;
+        Ok(ExceptionResponse::from(exception))
+    }
+
+    pub async fn remove_exception<R: TutorRepository + AvailabilityRepository>(
+        repo: &R,
+        user_id: Uuid,
+        exception_id: Uuid,
+    ) -> AppResult<()> {
+        let tutor_id = Self::own_tutor_id(repo, user_id).await?;
+        if !repo.delete_exception(tutor_id, exception_id).await? {
+            return Err(AppError::NotFound("Availability exception not found".to_string()));
+        }
+        Ok(())
+    }
+
+    pub async fn add_blackout<R: TutorRepository + AvailabilityRepository>(
+        repo: &R,
+        user_id: Uuid,
+        request: CreateBlackoutRequest,
+    ) -> AppResult<BlackoutResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        if request.start_date > request.end_date {
+            return Err(AppError::BadRequest("Blackout must not end before it starts".to_string()));
+        }
+
+        let tutor_id = Self::own_schedule(repo, user_id).await?.tutor_id;
+        let blackout = AvailabilityBlackout {
+            id: Uuid::new_v4(),
+            tutor_id,
+            start_date: request.start_date,
+            end_date: request.end_date,
+            reason: request.reason,
+            created_at: Utc::now(),
+        };
+
+        repo.insert_blackout(&blackout).await?;
+        Ok(BlackoutResponse::from(blackout))
+    }
+
+    pub async fn remove_blackout<R: TutorRepository + AvailabilityRepository>(
+        repo: &R,
+        user_id: Uuid,
+        blackout_id: Uuid,
+    ) -> AppResult<()> {
+        let tutor_id = Self::own_tutor_id(repo, user_id).await?;
+        if !repo.delete_blackout(tutor_id, blackout_id).await? {
+            return Err(AppError::NotFound("Blackout not found".to_string()));
+        }
+        Ok(())
+    }
+
+    // Open slots between two dates inclusive, where both the dates and the
+    // returned times are in the requester's time zone
+    pub async fn get_open_slots<R: TutorRepository + AvailabilityRepository + BookingRepository>(
+        repo: &R,
+        tutor_id: Uuid,
+        query: OpenSlotsQuery,
+    ) -> AppResult<OpenSlotsResponse> {
+        let requester_zone = match &query.tz {
+            Some(name) => parse_time_zone(name)?,
+            None => Tz::UTC,
+        };
+        if query.from > query.to {
+            return Err(AppError::BadRequest("`from` must not be after `to`".to_string()));
+        }
+        if !SLOT_YEARS.contains(&query.from.year()) || !SLOT_YEARS.contains(&query.to.year()) {
+            return Err(AppError::BadRequest(format!(
+                "Dates must fall between the years {} and {}", SLOT_YEARS.start(), SLOT_YEARS.end()
+            )));
+        }
+        if (query.to - query.from).num_days() >= MAX_SLOT_RANGE_DAYS {
+            return Err(AppError::BadRequest(format!(
+                "Date range must not exceed {} days", MAX_SLOT_RANGE_DAYS
+            )));
+        }
+
+        let tutor = repo.find_tutor_by_id(tutor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor not found".to_string()))?;
+
+        let mut response = OpenSlotsResponse {
+            tutor_id,
+            time_zone: requester_zone.name().to_string(),
+            tutor_time_zone: None,
+            slots: Vec::new(),
+        };
+        let schedule = match repo.find_schedule(tutor_id).await? {
+            Some(schedule) => schedule,
+            None => return Ok(response),
+        };
+        response.tutor_time_zone = Some(schedule.time_zone.clone());
+
+        if !tutor.tutor.is_available {
+            return Ok(response);
+        }
+
+        let range = (
+            local_instant(requester_zone, query.from, NaiveTime::MIN),
+            local_instant(requester_zone, query.to + Duration::days(1), NaiveTime::MIN),
+        );
+        let plan = SchedulePlan::load(repo, &schedule, range).await?;
+        response.slots = plan.open_slots(range, Utc::now())
+            .into_iter()
+            .map(|(start, end)| OpenSlot {
+                start: start.with_timezone(&requester_zone).fixed_offset(),
+                end: end.with_timezone(&requester_zone).fixed_offset(),
+            })
+            .collect();
+
+        Ok(response)
+    }
+
+    async fn own_tutor_id<R: TutorRepository>(repo: &R, user_id: Uuid) -> AppResult<Uuid> {
+        let tutor = repo.find_tutor_by_user_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor profile not found".to_string()))?;
+        Ok(tutor.tutor.id)
+    }
+
+    async fn own_schedule<R: TutorRepository + AvailabilityRepository>(
+        repo: &R,
+        user_id: Uuid,
+    ) -> AppResult<TutorSchedule> {
+        let tutor_id = Self::own_tutor_id(repo, user_id).await?;
+        repo.find_schedule(tutor_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Set a weekly schedule first".to_string()))
+    }
+
+    async fn schedule_response<R: AvailabilityRepository>(
+        repo: &R,
+        schedule: TutorSchedule,
+    ) -> AppResult<ScheduleResponse> {
+        // Owners see what is still ahead of them
+        let today = Utc::now().with_timezone(&parse_time_zone(&schedule.time_zone)?).date_naive();
+        let windows = repo.list_windows(schedule.tutor_id).await?;
+        let exceptions = repo.list_exceptions(schedule.tutor_id, today, NaiveDate::MAX).await?;
+        let blackouts = repo.list_blackouts(schedule.tutor_id, today, NaiveDate::MAX).await?;
+
+        Ok(ScheduleResponse {
+            tutor_id: schedule.tutor_id,
+            time_zone: schedule.time_zone,
+            slot_minutes: schedule.slot_minutes,
+            windows: windows.into_iter()
+                .map(|window| WeeklyWindow {
+                    weekday: weekday(window.weekday),
+                    start_time: window.start_time,
+                    end_time: window.end_time,
+                })
+                .collect(),
+            exceptions: exceptions.into_iter().map(ExceptionResponse::from).collect(),
+            blackouts: blackouts.into_iter().map(BlackoutResponse::from).collect(),
+        })
+    }
+}
+
+pub(crate) fn parse_time_zone(name: &str) -> AppResult<Tz> {
+    name.parse::<Tz>()
+        .map_err(|_| AppError::BadRequest(format!("Unknown time zone: {}", name)))
+}
+
+fn check_times(start: NaiveTime, end: NaiveTime) -> AppResult<()> {
+    if start >= end {
+        return Err(AppError::BadRequest("Start time must be before end time".to_string()));
+    }
+    Ok(())
+}
+
+fn weekday(index: i16) -> Weekday {
+    Weekday::try_from(index as u8).unwrap_or(Weekday::Mon)
+}
+
+// Resolves a wall-clock time to an instant. Times repeated when clocks go back
+// take their first occurrence; times skipped when clocks go forward move to the
+// moment the clocks land on.
+fn local_instant(zone: Tz, date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+    let local = date.and_time(time);
+    let mut candidate = local;
+    loop {
+        if let Some(instant) = zone.from_local_datetime(&candidate).earliest() {
+            return instant.with_timezone(&Utc);
+        }
+        candidate += Duration::minutes(1);
+    }
+}
+
+// Sorts and joins overlapping or touching intervals
+fn merge(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.sort();
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+// Removes every blocked span from the open intervals; both inputs merged
+fn subtract(open: Vec<Interval>, blocked: &[Interval]) -> Vec<Interval> {
+    let mut result = Vec::new();
+    for (mut start, end) in open {
+        for &(block_start, block_end) in blocked {
+            if block_end <= start || block_start >= end {
+                continue;
+            }
+            if block_start > start {
+                result.push((start, block_start));
+            }
+            start = start.max(block_end);
+        }
+        if start < end {
+            result.push((start, end));
+        }
+    }
+    result
+}
+
+// A tutor's schedule rules and booked time, expanded into concrete instants
+// on demand
+pub(crate) struct SchedulePlan {
+    pub zone: Tz,
+    pub slot_minutes: i32,
+    pub windows: Vec<AvailabilityWindow>,
+    pub exceptions: Vec<AvailabilityException>,
+    pub blackouts: Vec<AvailabilityBlackout>,
+    // Held by active bookings
+    pub busy: Vec<Interval>,
+}
+
+impl SchedulePlan {
+    // Loads everything that can affect availability within `range`
+    pub async fn load<R: AvailabilityRepository + BookingRepository>(
+        repo: &R,
+        schedule: &TutorSchedule,
+        range: Interval,
+    ) -> AppResult<Self> {
+        let zone = parse_time_zone(&schedule.time_zone)?;
+        // The range can start and end on different dates for the tutor
+        let first_day = range.0.with_timezone(&zone).date_naive();
+        let last_day = range.1.with_timezone(&zone).date_naive();
+
+        Ok(SchedulePlan {
+            zone,
+            slot_minutes: schedule.slot_minutes,
+            windows: repo.list_windows(schedule.tutor_id).await?,
+            exceptions: repo.list_exceptions(schedule.tutor_id, first_day, last_day).await?,
+            blackouts: repo.list_blackouts(schedule.tutor_id, first_day, last_day).await?,
+            busy: repo.list_busy_intervals(schedule.tutor_id, range.0, range.1).await?,
+        })
+    }
+
+    fn whole_day(&self, date: NaiveDate) -> Interval {
+        (
+            local_instant(self.zone, date, NaiveTime::MIN),
+            local_instant(self.zone, date + Duration::days(1), NaiveTime::MIN),
+        )
+    }
+
+    fn local_span(&self, date: NaiveDate, start: NaiveTime, end: NaiveTime) -> Interval {
+        (local_instant(self.zone, date, start), local_instant(self.zone, date, end))
+    }
+
+    // Free time around `range`: schedule windows and opening exceptions,
+    // minus closing exceptions, blackouts and booked time
+    pub fn open_intervals(&self, range: Interval) -> Vec<Interval> {
+        let first_day = range.0.with_timezone(&self.zone).date_naive();
+        let last_day = range.1.with_timezone(&self.zone).date_naive();
+
+        let mut open = Vec::new();
+        let mut blocked = self.busy.clone();
+        let mut date = first_day;
+        while date <= last_day {
+            let weekday = date.weekday().num_days_from_monday() as i16;
+            for window in self.windows.iter().filter(|window| window.weekday == weekday) {
+                open.push(self.local_span(date, window.start_time, window.end_time));
+            }
+            for exception in self.exceptions.iter().filter(|exception| exception.date == date) {
+                let span = match (exception.start_time, exception.end_time) {
+                    (Some(start), Some(end)) => self.local_span(date, start, end),
+                    _ => self.whole_day(date),
+                };
+                if exception.is_available {
+                    open.push(span);
+                } else {
+                    blocked.push(span);
+                }
+            }
+            if self.blackouts.iter().any(|b| b.start_date <= date && b.end_date >= date) {
+                blocked.push(self.whole_day(date));
+            }
+            date += Duration::days(1);
+        }
+
+        subtract(merge(open), &merge(blocked))
+    }
+
+    // Whether the whole of `span` is free
+    pub fn covers(&self, span: Interval) -> bool {
+        self.open_intervals(span)
+            .iter()
+            .any(|&(start, end)| start <= span.0 && end >= span.1)
+    }
+
+    // Slots of `slot_minutes` inside `range` that start no earlier than `now`.
+    // Slots are aligned to the start of each open stretch and measured in
+    // elapsed time, so a DST change never produces a duplicate or short slot.
+    pub fn open_slots(&self, range: Interval, now: DateTime<Utc>) -> Vec<Interval> {
+        let step = Duration::minutes(self.slot_minutes as i64);
+        let earliest = range.0.max(now);
+        let mut slots = Vec::new();
+        for (start, end) in self.open_intervals(range) {
+            let mut slot_start = start;
+            while slot_start + step <= end {
+                if slot_start >= earliest && slot_start + step <= range.1 {
+                    slots.push((slot_start, slot_start + step));
+                }
+                slot_start += step;
+            }
+        }
+        slots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserRole;
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use chrono::Timelike;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn schedule(time_zone: &str, windows: Vec<WeeklyWindow>) -> SetScheduleRequest {
+        SetScheduleRequest {
+            time_zone: time_zone.to_string(),
+            slot_minutes: None,
+            windows,
+        }
+    }
+
+    fn window(weekday: Weekday, start: NaiveTime, end: NaiveTime) -> WeeklyWindow {
+        WeeklyWindow { weekday, start_time: start, end_time: end }
+    }
+
+    fn slots_query(from: NaiveDate, to: NaiveDate, tz: &str) -> OpenSlotsQuery {
+        OpenSlotsQuery { from, to, tz: Some(tz.to_string()) }
+    }
+
+    #[actix_rt::test]
+    async fn slots_follow_the_tutor_zone_across_dst() {
+        let repo = InMemoryRepository::new();
+        let (user, tutor) = insert_tutor(&repo, &["Calculus"]).await;
+        let request = schedule("America/New_York", vec![window(Weekday::Sun, time(9, 0), time(11, 0))]);
+        AvailabilityService::set_schedule(&repo, user.id, request).await.unwrap();
+
+        // New York springs forward on 2030-03-10, London only on 2030-03-31
+        let query = slots_query(date(2030, 3, 3), date(2030, 3, 10), "Europe/London");
+        let response = AvailabilityService::get_open_slots(&repo, tutor.id, query).await.unwrap();
+        let starts: Vec<String> = response.slots.iter().map(|slot| slot.start.to_rfc3339()).collect();
+
+        assert_eq!(response.tutor_time_zone.as_deref(), Some("America/New_York"));
+        assert_eq!(starts, vec![
+            "2030-03-03T14:00:00+00:00",
+            "2030-03-03T15:00:00+00:00",
+            "2030-03-10T13:00:00+00:00",
+            "2030-03-10T14:00:00+00:00",
+        ]);
+    }
+
+    #[test]
+    fn skipped_and_repeated_hours_do_not_distort_slots() {
+        let zone: Tz = "America/New_York".parse().unwrap();
+        let windows = vec![
+            // 02:00-02:30 does not exist on 2030-03-10; 01:00-02:00 happens twice on 2030-11-03
+            AvailabilityWindow { id: Uuid::new_v4(), tutor_id: Uuid::nil(), weekday: 6, start_time: time(1, 0), end_time: time(4, 0) },
+        ];
+        let plan = SchedulePlan { zone, slot_minutes: 60, windows, exceptions: vec![], blackouts: vec![], busy: vec![] };
+        let epoch = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+
+        let spring = plan.open_slots(plan.whole_day(date(2030, 3, 10)), epoch);
+        // 01:00 EST to 04:00 EDT is two real hours
+        assert_eq!(spring.len(), 2);
+        assert_eq!(spring[1].1 - spring[0].0, Duration::hours(2));
+
+        let autumn = plan.open_slots(plan.whole_day(date(2030, 11, 3)), epoch);
+        // 01:00 EDT to 04:00 EST is four real hours
+        assert_eq!(autumn.len(), 4);
+        assert!(autumn.windows(2).all(|pair| pair[0].1 == pair[1].0));
+    }
+
+    #[actix_rt::test]
+    async fn exceptions_and_blackouts_reshape_the_week() {
+        let repo = InMemoryRepository::new();
+        let (user, tutor) = insert_tutor(&repo, &["Physics"]).await;
+        let weekdays = [Weekday::Mon, Weekday::Tue, Weekday::Wed]
+            .into_iter()
+            .map(|day| window(day, time(9, 0), time(12, 0)))
+            .collect();
+        AvailabilityService::set_schedule(&repo, user.id, schedule("UTC", weekdays)).await.unwrap();
+
+        // 2030-06-03 is a Monday
+        let blocked = CreateExceptionRequest {
+            date: date(2030, 6, 3),
+            start_time: Some(time(10, 0)),
+            end_time: Some(time(11, 0)),
+            is_available: false,
+            reason: Some("Dentist".to_string()),
+        };
+        let extra = CreateExceptionRequest {
+            date: date(2030, 6, 8),
+            start_time: Some(time(14, 0)),
+            end_time: Some(time(16, 0)),
+            is_available: true,
+            reason: None,
+        };
+        let vacation = CreateBlackoutRequest { start_date: date(2030, 6, 4), end_date: date(2030, 6, 5), reason: None };
+        AvailabilityService::add_exception(&repo, user.id, blocked).await.unwrap();
+        AvailabilityService::add_exception(&repo, user.id, extra).await.unwrap();
+        let blackout = AvailabilityService::add_blackout(&repo, user.id, vacation).await.unwrap();
+
+        let query = slots_query(date(2030, 6, 3), date(2030, 6, 9), "UTC");
+        let slots = AvailabilityService::get_open_slots(&repo, tutor.id, query.clone()).await.unwrap().slots;
+        let hours: Vec<(u32, u32)> = slots.iter().map(|slot| (slot.start.day(), slot.start.hour())).collect();
+        assert_eq!(hours, vec![(3, 9), (3, 11), (8, 14), (8, 15)]);
+
+        AvailabilityService::remove_blackout(&repo, user.id, blackout.id).await.unwrap();
+        let slots = AvailabilityService::get_open_slots(&repo, tutor.id, query).await.unwrap().slots;
+        assert_eq!(slots.len(), 10);
+
+        let owner_view = AvailabilityService::get_schedule(&repo, user.id).await.unwrap();
+        assert_eq!(owner_view.windows.len(), 3);
+        assert_eq!(owner_view.exceptions.len(), 2);
+        assert!(owner_view.blackouts.is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn invalid_schedules_and_queries_are_rejected() {
+        let repo = InMemoryRepository::new();
+        let (user, tutor) = insert_tutor(&repo, &["Chemistry"]).await;
+
+        let unknown_zone = schedule("Mars/Olympus_Mons", vec![]);
+        assert!(matches!(
+            AvailabilityService::set_schedule(&repo, user.id, unknown_zone).await,
+            Err(AppError::BadRequest(_))
+        ));
+        let backwards = schedule("UTC", vec![window(Weekday::Fri, time(17, 0), time(9, 0))]);
+        assert!(matches!(
+            AvailabilityService::set_schedule(&repo, user.id, backwards).await,
+            Err(AppError::BadRequest(_))
+        ));
+
+        let student = insert_user(&repo, UserRole::Student).await;
+        assert!(matches!(
+            AvailabilityService::set_schedule(&repo, student.id, schedule("UTC", vec![])).await,
+            Err(AppError::NotFound(_))
+        ));
+
+        // No schedule yet: the tutor simply has no open slots
+        let query = slots_query(date(2030, 1, 1), date(2030, 1, 7), "UTC");
+        let response = AvailabilityService::get_open_slots(&repo, tutor.id, query).await.unwrap();
+        assert!(response.slots.is_empty());
+        assert!(response.tutor_time_zone.is_none());
+
+        let too_long = slots_query(date(2030, 1, 1), date(2030, 3, 1), "UTC");
+        assert!(matches!(
+            AvailabilityService::get_open_slots(&repo, tutor.id, too_long).await,
+            Err(AppError::BadRequest(_))
+        ));
+
+        // Dates at the edge of the calendar are refused rather than overflowing
+        let request = schedule("Pacific/Kiritimati", vec![window(Weekday::Mon, time(9, 0), time(17, 0))]);
+        AvailabilityService::set_schedule(&repo, user.id, request).await.unwrap();
+        for (from, to) in [(NaiveDate::MAX, NaiveDate::MAX), (NaiveDate::MIN, NaiveDate::MIN)] {
+            assert!(matches!(
+                AvailabilityService::get_open_slots(&repo, tutor.id, slots_query(from, to, "Etc/GMT+12")).await,
+                Err(AppError::BadRequest(_))
+            ));
+        }
+    }
+}
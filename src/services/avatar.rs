@@ -0,0 +1,261 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{Avatar, AvatarSize, FilePurpose, UserResponse};
+use crate::repositories::{AvatarRepository, FileRepository, UserRepository};
+use crate::services::file::FileUpload;
+use crate::services::{AuthService, FileService};
+use crate::storage::FileStore;
+use chrono::Utc;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+use uuid::Uuid;
+
+pub const MAX_AVATAR_BYTES: usize = 5 * 1024 * 1024;
+
+pub const AVATAR_CONTENT_TYPES: &[&str] = &["image/gif", "image/jpeg", "image/png", "image/webp"];
+
+// Larger images are refused before their pixels are decoded
+const MAX_AVATAR_DIMENSION: u32 = 8192;
+
+// Avatars are decoded and re-encoded as PNG in every size. Only pixels
+// survive the round trip, so camera metadata such as EXIF location never
+// reaches the stored files; the EXIF orientation is applied first so
+// photos still show upright.
+pub struct AvatarService;
+
+impl AvatarService {
+    pub async fn set_avatar<R: UserRepository + FileRepository + AvatarRepository>(
+        repo: &R,
+        files: &FileStore,
+        user_id: Uuid,
+        upload: FileUpload,
+    ) -> AppResult<UserResponse> {
+        if !AVATAR_CONTENT_TYPES.contains(&upload.content_type.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Avatars must be one of: {}",
+                AVATAR_CONTENT_TYPES.join(", ")
+            )));
+        }
+        if upload.content.is_empty() || upload.content.len() > MAX_AVATAR_BYTES {
+            return Err(AppError::Validation(format!(
+                "Avatars must be between 1 byte and {} MB",
+                MAX_AVATAR_BYTES / (1024 * 1024)
+            )));
+        }
+        AuthService::get_user_by_id(repo, user_id).await?;
+
+        // Decoding and resizing are CPU bound; keep them off the async workers
+        let renditions = tokio::task::spawn_blocking(move || render(&upload.content, &upload.content_type))
+            .await
+            .map_err(|e| AppError::Internal(format!("Avatar processing failed: {}", e)))??;
+
+        let mut file_ids = Vec::with_capacity(renditions.len());
+        for (size, content) in renditions {
+            let rendition = FileUpload {
+                file_name: format!("avatar-{}.png", size.as_str()),
+                content_type: "image/png".to_string(),
+                content,
+            };
+            let file = FileService::store(repo, files, user_id, FilePurpose::Avatar, rendition, &["image/png"]).await?;
+            file_ids.push(file.id);
+        }
+
+        let avatar = Avatar {
+            user_id,
+            full_file_id: file_ids[0],
+            large_file_id: file_ids[1],
+            medium_file_id: file_ids[2],
+            small_file_id: file_ids[3],
+            updated_at: Utc::now(),
+        };
+        if let Some(previous) = repo.set_avatar(&avatar).await? {
+            Self::remove_files(repo, files, &previous).await?;
+        }
+
+        let user = AuthService::get_user_by_id(repo, user_id).await?;
+        Ok(UserResponse::from(user))
+    }
+
+    pub async fn delete_avatar<R: FileRepository + AvatarRepository>(
+        repo: &R,
+        files: &FileStore,
+        user_id: Uuid,
+    ) -> AppResult<()> {
+        let avatar = repo.delete_avatar(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Avatar not found".to_string()))?;
+        Self::remove_files(repo, files, &avatar).await
+    }
+
+    // Public: avatars appear next to names across the site
+    pub async fn get_avatar_image<R: FileRepository + AvatarRepository>(
+        repo: &R,
+        files: &FileStore,
+        user_id: Uuid,
+        size: AvatarSize,
+    ) -> AppResult<Vec<u8>> {
+        let avatar = repo.find_avatar(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Avatar not found".to_string()))?;
+        let file = repo.find_stored_file(avatar.file_id(size))
+            .await?
+            .ok_or_else(|| AppError::Internal(format!("Avatar file of user {} is missing", user_id)))?;
+        FileService::content(files, &file).await
+    }
+
+    async fn remove_files<R: FileRepository>(repo: &R, files: &FileStore, avatar: &Avatar) -> AppResult<()> {
+        for file_id in avatar.file_ids() {
+            if let Some(file) = repo.find_stored_file(file_id).await? {
+                FileService::remove(repo, files, &file).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// One PNG per size, in `AvatarSize::ALL` order
+fn render(content: &[u8], content_type: &str) -> AppResult<Vec<(AvatarSize, Vec<u8>)>> {
+    let invalid = || AppError::Validation(format!("File content is not a valid {} image", content_type));
+
+    let mut reader = ImageReader::new(Cursor::new(content)).with_guessed_format().map_err(|_| invalid())?;
+    if reader.format() != ImageFormat::from_mime_type(content_type) {
+        return Err(invalid());
+    }
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_AVATAR_DIMENSION);
+    limits.max_image_height = Some(MAX_AVATAR_DIMENSION);
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder().map_err(|_| invalid())?;
+    let orientation = decoder.orientation().map_err(|_| invalid())?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(|_| invalid())?;
+    image.apply_orientation(orientation);
+    let image = if image.color().has_alpha() {
+        DynamicImage::ImageRgba8(image.into_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(image.into_rgb8())
+    };
+
+    AvatarSize::ALL
+        .into_iter()
+        .map(|size| {
+            let pixels = size.pixels();
+            let rendition = match size {
+                AvatarSize::Full if image.width() <= pixels && image.height() <= pixels => image.clone(),
+                AvatarSize::Full => image.resize(pixels, pixels, FilterType::Lanczos3),
+                _ => image.resize_to_fill(pixels, pixels, FilterType::Lanczos3),
+            };
+            let mut png = Vec::new();
+            rendition
+                .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+                .map_err(|e| AppError::Internal(format!("Avatar encoding failed: {}", e)))?;
+            Ok((size, png))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserRole;
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::insert_user;
+    use crate::storage::MemoryStorage;
+    use image::{Rgb, RgbImage};
+
+    fn upload(content_type: &str, content: Vec<u8>) -> FileUpload {
+        FileUpload {
+            file_name: "me.jpg".to_string(),
+            content_type: content_type.to_string(),
+            content,
+        }
+    }
+
+    // A 40x20 JPEG whose EXIF block says to rotate it a quarter turn and
+    // carries a camera serial number
+    fn rotated_photo() -> Vec<u8> {
+        let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(40, 20, Rgb([200, 40, 40])));
+        let mut jpeg = Vec::new();
+        image.write_to(&mut Cursor::new(&mut jpeg), ImageFormat::Jpeg).unwrap();
+
+        let serial = b"SN-0042\0";
+        let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x02".to_vec();
+        // Orientation (0x0112): SHORT 6, rotate 90 degrees clockwise
+        exif.extend_from_slice(b"\x01\x12\0\x03\0\0\0\x01\0\x06\0\0");
+        // BodySerialNumber (0xA431): ASCII stored after the directory
+        exif.extend_from_slice(b"\xA4\x31\0\x02\0\0\0\x08\0\0\0\x26");
+        exif.extend_from_slice(b"\0\0\0\0");
+        exif.extend_from_slice(serial);
+
+        let mut photo = jpeg[..2].to_vec();
+        photo.extend_from_slice(b"\xFF\xE1");
+        photo.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+        photo.extend_from_slice(&exif);
+        photo.extend_from_slice(&jpeg[2..]);
+        photo
+    }
+
+    fn dimensions(png: &[u8]) -> (u32, u32) {
+        let image = image::load_from_memory_with_format(png, ImageFormat::Png).unwrap();
+        (image.width(), image.height())
+    }
+
+    #[actix_rt::test]
+    async fn avatars_are_upright_resized_and_free_of_metadata() {
+        let repo = InMemoryRepository::new();
+        let storage = MemoryStorage::new();
+        let files = FileStore::new(storage.clone(), b"secret");
+        let user = insert_user(&repo, UserRole::Student).await;
+
+        let photo = rotated_photo();
+        assert!(photo.windows(7).any(|window| window == b"SN-0042"));
+        let response = AvatarService::set_avatar(&repo, &files, user.id, upload("image/jpeg", photo)).await.unwrap();
+        let urls = response.avatar.unwrap();
+        assert!(urls.small.starts_with(&format!("/api/v1/users/{}/avatar/small?v=", user.id)));
+
+        let full = AvatarService::get_avatar_image(&repo, &files, user.id, AvatarSize::Full).await.unwrap();
+        assert_eq!(dimensions(&full), (20, 40));
+        assert!(!full.windows(4).any(|window| window == b"Exif" || window == b"eXIf"));
+        assert!(!full.windows(7).any(|window| window == b"SN-0042"));
+        for size in [AvatarSize::Large, AvatarSize::Medium, AvatarSize::Small] {
+            let png = AvatarService::get_avatar_image(&repo, &files, user.id, size).await.unwrap();
+            assert_eq!(dimensions(&png), (size.pixels(), size.pixels()));
+        }
+
+        // Renditions stay out of the user's own file list
+        assert!(FileService::list_files(&repo, &files, user.id, "http://localhost").await.unwrap().is_empty());
+
+        // A new avatar replaces the old files; removing it clears the links
+        let square = DynamicImage::ImageRgb8(RgbImage::from_pixel(300, 300, Rgb([0, 90, 200])));
+        let mut png = Vec::new();
+        square.write_to(&mut Cursor::new(&mut png), ImageFormat::Png).unwrap();
+        AvatarService::set_avatar(&repo, &files, user.id, upload("image/png", png)).await.unwrap();
+        assert_eq!(storage.object_count(), 4);
+
+        AvatarService::delete_avatar(&repo, &files, user.id).await.unwrap();
+        assert_eq!(storage.object_count(), 0);
+        let user = AuthService::get_user_by_id(&repo, user.id).await.unwrap();
+        assert!(UserResponse::from(user).avatar.is_none());
+    }
+
+    #[actix_rt::test]
+    async fn avatars_must_be_images_of_their_declared_type() {
+        let repo = InMemoryRepository::new();
+        let files = FileStore::new(MemoryStorage::new(), b"secret");
+        let user = insert_user(&repo, UserRole::Tutor).await;
+
+        let rejected = [
+            upload("image/png", b"\x89PNG\r\n\x1A\nnot really".to_vec()),
+            upload("image/png", rotated_photo()),
+            upload("application/pdf", b"%PDF-1.4".to_vec()),
+            upload("image/jpeg", Vec::new()),
+        ];
+        for bad in rejected {
+            let error = AvatarService::set_avatar(&repo, &files, user.id, bad).await.unwrap_err();
+            assert!(matches!(error, AppError::Validation(_)));
+        }
+
+        let error = AvatarService::get_avatar_image(&repo, &files, user.id, AvatarSize::Small).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+    }
+}
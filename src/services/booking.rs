@@ -0,0 +1,731 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Booking, BookingActionRequest, BookingListQuery, BookingPolicy, BookingPolicyResponse,
+    BookingResponse, BookingStatus, BookingWithDetails, CreateBookingRequest,
+    RescheduleBookingRequest, SetBookingPolicyRequest, UserRole,
+};
+use crate::repositories::{
+    AvailabilityRepository, BookingRepository, CourseRepository, TutorRepository, UserRepository,
+};
+use crate::services::availability::SchedulePlan;
+use crate::services::AuthService;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use validator::Validate;
+
+// Terms for tutors who have not set their own policy
+const DEFAULT_FREE_CANCEL_HOURS: i32 = 24;
+const DEFAULT_LATE_CANCEL_FEE_PERCENT: i32 = 50;
+const DEFAULT_MAX_RESCHEDULES: i32 = 2;
+const DEFAULT_RESCHEDULE_NOTICE_HOURS: i32 = 24;
+
+// Which side of a booking the caller is on
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Party {
+    Student,
+    Tutor,
+}
+
+// Students request sessions inside a tutor's open time; the tutor then accepts
+// or declines. Requested and confirmed bookings hold the slot, and the database
+// rejects any second active booking that overlaps it.
+pub struct BookingService;
+
+impl BookingService {
+    pub async fn create_booking<R>(
+        repo: &R,
+        student_id: Uuid,
+        request: CreateBookingRequest,
+    ) -> AppResult<BookingResponse>
+    where
+        R: UserRepository + TutorRepository + CourseRepository + AvailabilityRepository + BookingRepository,
+    {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        if !AuthService::verify_user_role(repo, student_id, UserRole::Student).await? {
+            return Err(AppError::Authorization("Only students can book sessions".to_string()));
+        }
+
+        let course = repo.find_course_by_id(request.course_id)
+            .await?
+            .filter(|course| course.course.is_active)
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?
+            .course;
+
+        let now = Utc::now();
+        if request.starts_at <= now {
+            return Err(AppError::BadRequest("Sessions must be booked in the future".to_string()));
+        }
+        let span = (request.starts_at, request.starts_at + Duration::minutes(course.duration_minutes as i64));
+        Self::check_open_time(repo, course.tutor_id, span, None).await?;
+
+        let booking = Booking {
+            id: Uuid::new_v4(),
+            course_id: course.id,
+            tutor_id: course.tutor_id,
+            student_id,
+            starts_at: span.0,
+            ends_at: span.1,
+            status: BookingStatus::Requested,
+            note: request.note,
+            status_reason: None,
+            price: course.price,
+            cancellation_fee: None,
+            reschedule_count: 0,
+            created_at: now,
+            updated_at: now,
+        };
+
+        // Two students racing for the same slot are settled by the insert
+        repo.insert_booking(&booking).await?;
+        Self::get_booking(repo, booking.id, student_id).await
+    }
+
+    pub async fn get_booking<R: BookingRepository>(
+        repo: &R,
+        booking_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<BookingResponse> {
+        let (row, _) = Self::find_participant_booking(repo, booking_id, user_id).await?;
+        Ok(BookingResponse::from(row))
+    }
+
+    pub async fn list_student_bookings<R: BookingRepository>(
+        repo: &R,
+        student_id: Uuid,
+        query: BookingListQuery,
+    ) -> AppResult<Vec<BookingResponse>> {
+        let bookings = repo.list_bookings_for_student(student_id, query.status).await?;
+        Ok(bookings.into_iter().map(BookingResponse::from).collect())
+    }
+
+    pub async fn list_tutor_bookings<R: TutorRepository + BookingRepository>(
+        repo: &R,
+        user_id: Uuid,
+        query: BookingListQuery,
+    ) -> AppResult<Vec<BookingResponse>> {
+        let tutor_id = Self::own_tutor_id(repo, user_id).await?;
+        let bookings = repo.list_bookings_for_tutor(tutor_id, query.status).await?;
+        Ok(bookings.into_iter().map(BookingResponse::from).collect())
+    }
+
+    pub async fn accept_booking<R: BookingRepository>(
+        repo: &R,
+        booking_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<BookingResponse> {
+        let row = Self::find_tutor_booking(repo, booking_id, user_id).await?;
+        if row.booking.status != BookingStatus::Requested {
+            return Err(AppError::Conflict("Only requested bookings can be accepted".to_string()));
+        }
+        if row.booking.starts_at <= Utc::now() {
+            return Err(AppError::BadRequest("Booking has already started".to_string()));
+        }
+
+        Self::save_status(repo, row, BookingStatus::Confirmed, None).await
+    }
+
+    pub async fn decline_booking<R: BookingRepository>(
+        repo: &R,
+        booking_id: Uuid,
+        user_id: Uuid,
+        request: BookingActionRequest,
+    ) -> AppResult<BookingResponse> {
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let row = Self::find_tutor_booking(repo, booking_id, user_id).await?;
+        if row.booking.status != BookingStatus::Requested {
+            return Err(AppError::Conflict("Only requested bookings can be declined".to_string()));
+        }
+
+        Self::save_status(repo, row, BookingStatus::Cancelled, request.reason).await
+    }
+
+    // Either participant may call off a booking until it starts. A student who
+    // cancels a confirmed booking inside the tutor's free-cancel window pays
+    // the late fee; cancellations by the tutor are always free.
+    pub async fn cancel_booking<R: BookingRepository>(
+        repo: &R,
+        booking_id: Uuid,
+        user_id: Uuid,
+        request: BookingActionRequest,
+    ) -> AppResult<BookingResponse> {
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let (row, party) = Self::find_participant_booking(repo, booking_id, user_id).await?;
+        Self::cancel(repo, row, party, request.reason).await
+    }
+
+    // The fee depends on the status it was worked out from, so both are
+    // written by the one update that only applies if `row` is still current
+    async fn cancel<R: BookingRepository>(
+        repo: &R,
+        mut row: BookingWithDetails,
+        party: Party,
+        reason: Option<String>,
+    ) -> AppResult<BookingResponse> {
+        if !row.booking.status.is_active() {
+            return Err(AppError::Conflict("Booking is no longer active".to_string()));
+        }
+        let now = Utc::now();
+        if row.booking.starts_at <= now {
+            return Err(AppError::BadRequest("Bookings cannot be cancelled once they start".to_string()));
+        }
+
+        let policy = Self::policy_for(repo, row.booking.tutor_id).await?;
+        let fee = match (party, row.booking.status) {
+            (Party::Student, BookingStatus::Confirmed) => cancellation_fee(&policy, &row.booking, now),
+            _ => 0,
+        };
+        row.booking.cancellation_fee = Some(fee);
+
+        Self::save_status(repo, row, BookingStatus::Cancelled, reason).await
+    }
+
+    // Moves an active booking to another open time. Each tutor's policy caps
+    // how often and how late a booking can be moved. A move by the student
+    // needs the tutor's approval again; a move by the tutor stays confirmed.
+    pub async fn reschedule_booking<R>(
+        repo: &R,
+        booking_id: Uuid,
+        user_id: Uuid,
+        request: RescheduleBookingRequest,
+    ) -> AppResult<BookingResponse>
+    where
+        R: TutorRepository + AvailabilityRepository + BookingRepository,
+    {
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let (mut row, party) = Self::find_participant_booking(repo, booking_id, user_id).await?;
+        if !row.booking.status.is_active() {
+            return Err(AppError::Conflict("Booking is no longer active".to_string()));
+        }
+
+        let policy = Self::policy_for(repo, row.booking.tutor_id).await?;
+        if row.booking.reschedule_count >= policy.max_reschedules {
+            return Err(AppError::Conflict(format!(
+                "Booking has reached the reschedule limit of {}", policy.max_reschedules
+            )));
+        }
+        let now = Utc::now();
+        if row.booking.starts_at - Duration::hours(policy.reschedule_notice_hours as i64) < now {
+            return Err(AppError::BadRequest(format!(
+                "Bookings must be rescheduled at least {} hours before they start",
+                policy.reschedule_notice_hours
+            )));
+        }
+        if request.starts_at <= now {
+            return Err(AppError::BadRequest("Sessions must be booked in the future".to_string()));
+        }
+
+        let current = (row.booking.starts_at, row.booking.ends_at);
+        let span = (request.starts_at, request.starts_at + (current.1 - current.0));
+        Self::check_open_time(repo, row.booking.tutor_id, span, Some(current)).await?;
+
+        row.booking.starts_at = span.0;
+        row.booking.ends_at = span.1;
+        row.booking.reschedule_count += 1;
+        let status = match party {
+            Party::Student => BookingStatus::Requested,
+            Party::Tutor => BookingStatus::Confirmed,
+        };
+
+        Self::save_status(repo, row, status, request.reason).await
+    }
+
+    // The tutor's terms as shown to students before they book
+    pub async fn get_tutor_policy<R: TutorRepository + BookingRepository>(
+        repo: &R,
+        tutor_id: Uuid,
+    ) -> AppResult<BookingPolicyResponse> {
+        repo.find_tutor_by_id(tutor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor not found".to_string()))?;
+
+        Ok(BookingPolicyResponse::from(Self::policy_for(repo, tutor_id).await?))
+    }
+
+    pub async fn get_own_policy<R: TutorRepository + BookingRepository>(
+        repo: &R,
+        user_id: Uuid,
+    ) -> AppResult<BookingPolicyResponse> {
+        let tutor_id = Self::own_tutor_id(repo, user_id).await?;
+        Ok(BookingPolicyResponse::from(Self::policy_for(repo, tutor_id).await?))
+    }
+
+    // New terms apply to existing bookings as well as future ones
+    pub async fn set_policy<R: TutorRepository + BookingRepository>(
+        repo: &R,
+        user_id: Uuid,
+        request: SetBookingPolicyRequest,
+    ) -> AppResult<BookingPolicyResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let tutor_id = Self::own_tutor_id(repo, user_id).await?;
+        let now = Utc::now();
+        let policy = BookingPolicy {
+            tutor_id,
+            free_cancel_hours: request.free_cancel_hours,
+            late_cancel_fee_percent: request.late_cancel_fee_percent,
+            max_reschedules: request.max_reschedules,
+            reschedule_notice_hours: request.reschedule_notice_hours,
+            created_at: now,
+            updated_at: now,
+        };
+
+        repo.upsert_booking_policy(&policy).await?;
+        Ok(BookingPolicyResponse::from(policy))
+    }
+
+    pub async fn complete_booking<R: BookingRepository>(
+        repo: &R,
+        booking_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<BookingResponse> {
+        let row = Self::find_tutor_booking(repo, booking_id, user_id).await?;
+        if row.booking.status != BookingStatus::Confirmed {
+            return Err(AppError::Conflict("Only confirmed bookings can be completed".to_string()));
+        }
+        if row.booking.ends_at > Utc::now() {
+            return Err(AppError::BadRequest("Session has not ended yet".to_string()));
+        }
+
+        Self::save_status(repo, row, BookingStatus::Completed, None).await
+    }
+
+    pub async fn mark_no_show<R: BookingRepository>(
+        repo: &R,
+        booking_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<BookingResponse> {
+        let row = Self::find_tutor_booking(repo, booking_id, user_id).await?;
+        if row.booking.status != BookingStatus::Confirmed {
+            return Err(AppError::Conflict("Only confirmed bookings can be marked as no-show".to_string()));
+        }
+        if row.booking.starts_at > Utc::now() {
+            return Err(AppError::BadRequest("Session has not started yet".to_string()));
+        }
+
+        Self::save_status(repo, row, BookingStatus::NoShow, None).await
+    }
+
+    // The whole session has to fit inside the tutor's open time. `moving` is
+    // the booking's current time when it is being rescheduled, which must not
+    // count against itself.
+    async fn check_open_time<R: TutorRepository + AvailabilityRepository + BookingRepository>(
+        repo: &R,
+        tutor_id: Uuid,
+        span: (DateTime<Utc>, DateTime<Utc>),
+        moving: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    ) -> AppResult<()> {
+        let tutor = repo.find_tutor_by_id(tutor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor not found".to_string()))?;
+        let schedule = match repo.find_schedule(tutor_id).await? {
+            Some(schedule) if tutor.tutor.is_available => schedule,
+            _ => return Err(AppError::BadRequest("Tutor is not taking bookings".to_string())),
+        };
+
+        let mut plan = SchedulePlan::load(repo, &schedule, span).await?;
+        if let Some(current) = moving {
+            if let Some(index) = plan.busy.iter().position(|busy| *busy == current) {
+                plan.busy.remove(index);
+            }
+        }
+        if !plan.covers(span) {
+            return Err(AppError::Conflict("Requested time is not available".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn policy_for<R: BookingRepository>(repo: &R, tutor_id: Uuid) -> AppResult<BookingPolicy> {
+        let policy = repo.find_booking_policy(tutor_id).await?;
+        Ok(policy.unwrap_or_else(|| {
+            let now = Utc::now();
+            BookingPolicy {
+                tutor_id,
+                free_cancel_hours: DEFAULT_FREE_CANCEL_HOURS,
+                late_cancel_fee_percent: DEFAULT_LATE_CANCEL_FEE_PERCENT,
+                max_reschedules: DEFAULT_MAX_RESCHEDULES,
+                reschedule_notice_hours: DEFAULT_RESCHEDULE_NOTICE_HOURS,
+                created_at: now,
+                updated_at: now,
+            }
+        }))
+    }
+
+    async fn own_tutor_id<R: TutorRepository>(repo: &R, user_id: Uuid) -> AppResult<Uuid> {
+        let tutor = repo.find_tutor_by_user_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor profile not found".to_string()))?;
+        Ok(tutor.tutor.id)
+    }
+
+    // Bookings are hidden from everyone but the student and the tutor
+    async fn find_participant_booking<R: BookingRepository>(
+        repo: &R,
+        booking_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<(BookingWithDetails, Party)> {
+        match repo.find_booking(booking_id).await? {
+            Some(row) if row.booking.student_id == user_id => Ok((row, Party::Student)),
+            Some(row) if row.tutor_user_id == user_id => Ok((row, Party::Tutor)),
+            _ => Err(AppError::NotFound("Booking not found or access denied".to_string())),
+        }
+    }
+
+    async fn find_tutor_booking<R: BookingRepository>(
+        repo: &R,
+        booking_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<BookingWithDetails> {
+        match Self::find_participant_booking(repo, booking_id, user_id).await? {
+            (row, Party::Tutor) => Ok(row),
+            (_, Party::Student) => Err(AppError::Authorization(
+                "Only the tutor can perform this action".to_string()
+            )),
+        }
+    }
+
+    async fn save_status<R: BookingRepository>(
+        repo: &R,
+        mut row: BookingWithDetails,
+        status: BookingStatus,
+        reason: Option<String>,
+    ) -> AppResult<BookingResponse> {
+        let expected_updated_at = row.booking.updated_at;
+        row.booking.status = status;
+        row.booking.status_reason = reason;
+        row.booking.updated_at = Utc::now();
+
+        // The checks above ran against `row`; a booking changed since then is
+        // left alone
+        if !repo.update_booking(&row.booking, expected_updated_at).await? {
+            return Err(AppError::Conflict("Booking has changed since it was read".to_string()));
+        }
+        Ok(BookingResponse::from(row))
+    }
+}
+
+// Late fee in cents for cancelling at `now`, rounded to the nearest cent
+fn cancellation_fee(policy: &BookingPolicy, booking: &Booking, now: DateTime<Utc>) -> i32 {
+    if booking.starts_at - Duration::hours(policy.free_cancel_hours as i64) >= now {
+        return 0;
+    }
+    let fee = (booking.price as i64 * policy.late_cancel_fee_percent as i64 + 50) / 100;
+    fee as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        CourseResponse, CreateCourseRequest, DifficultyLevel, RequestContext, SetScheduleRequest, User, WeeklyWindow,
+    };
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use crate::services::{AvailabilityService, CourseService};
+    use chrono::{DateTime, Duration, NaiveTime, Timelike, Weekday};
+
+    // A tutor open around the clock in UTC, with one hour-long course
+    async fn open_tutor(repo: &InMemoryRepository) -> (User, CourseResponse) {
+        let (user, _) = insert_tutor(repo, &["Chemistry"]).await;
+        let windows = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun]
+            .into_iter()
+            .map(|weekday| WeeklyWindow {
+                weekday,
+                start_time: NaiveTime::MIN,
+                end_time: NaiveTime::from_hms_opt(23, 59, 0).unwrap(),
+            })
+            .collect();
+        AvailabilityService::set_schedule(repo, user.id, SetScheduleRequest {
+            time_zone: "UTC".to_string(),
+            slot_minutes: None,
+            windows,
+        }).await.unwrap();
+
+        let course = CourseService::create_course(repo, user.id, CreateCourseRequest {
+            title: "Organic Chemistry".to_string(),
+            description: "Reaction mechanisms".to_string(),
+            price: 3000,
+            duration_minutes: 60,
+            category: "Science".to_string(),
+            difficulty_level: DifficultyLevel::Intermediate,
+            max_students: None,
+        }, &RequestContext::default()).await.unwrap();
+        (user, course)
+    }
+
+    fn tomorrow_at(hour: u32) -> DateTime<Utc> {
+        (Utc::now() + Duration::days(1))
+            .with_hour(hour).unwrap()
+            .with_minute(0).unwrap()
+            .with_second(0).unwrap()
+            .with_nanosecond(0).unwrap()
+    }
+
+    fn request(course_id: Uuid, starts_at: DateTime<Utc>) -> CreateBookingRequest {
+        CreateBookingRequest { course_id, starts_at, note: None }
+    }
+
+    #[actix_rt::test]
+    async fn requested_bookings_move_through_the_workflow() {
+        let repo = InMemoryRepository::new();
+        let (tutor, course) = open_tutor(&repo).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        let booking = BookingService::create_booking(&repo, student.id, request(course.id, tomorrow_at(10)))
+            .await.unwrap();
+        assert_eq!(booking.status, BookingStatus::Requested);
+        assert_eq!(booking.ends_at - booking.starts_at, Duration::minutes(60));
+
+        // Students cannot confirm their own requests
+        let error = BookingService::accept_booking(&repo, booking.id, student.id).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+
+        let confirmed = BookingService::accept_booking(&repo, booking.id, tutor.id).await.unwrap();
+        assert_eq!(confirmed.status, BookingStatus::Confirmed);
+        let error = BookingService::accept_booking(&repo, booking.id, tutor.id).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        // The session has not happened yet
+        let error = BookingService::complete_booking(&repo, booking.id, tutor.id).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+
+        let reason = BookingActionRequest { reason: Some("Exam moved".to_string()) };
+        let cancelled = BookingService::cancel_booking(&repo, booking.id, student.id, reason).await.unwrap();
+        assert_eq!(cancelled.status, BookingStatus::Cancelled);
+        assert_eq!(cancelled.status_reason.as_deref(), Some("Exam moved"));
+
+        let student_view = BookingService::list_student_bookings(&repo, student.id, BookingListQuery {
+            status: Some(BookingStatus::Cancelled),
+        }).await.unwrap();
+        assert_eq!(student_view.len(), 1);
+        let tutor_view = BookingService::list_tutor_bookings(&repo, tutor.id, BookingListQuery {
+            status: Some(BookingStatus::Requested),
+        }).await.unwrap();
+        assert!(tutor_view.is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn writes_based_on_a_stale_read_are_refused() {
+        let repo = InMemoryRepository::new();
+        let (tutor, course) = open_tutor(&repo).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        let booking = BookingService::create_booking(&repo, student.id, request(course.id, tomorrow_at(10)))
+            .await.unwrap();
+        let stale = repo.find_booking(booking.id).await.unwrap().unwrap();
+        BookingService::accept_booking(&repo, booking.id, tutor.id).await.unwrap();
+
+        let error = BookingService::save_status(&repo, stale, BookingStatus::Cancelled, None).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+        let current = BookingService::get_booking(&repo, booking.id, student.id).await.unwrap();
+        assert_eq!(current.status, BookingStatus::Confirmed);
+    }
+
+    #[actix_rt::test]
+    async fn overlapping_requests_are_rejected_until_the_slot_is_released() {
+        let repo = InMemoryRepository::new();
+        let (tutor, course) = open_tutor(&repo).await;
+        let first = insert_user(&repo, UserRole::Student).await;
+        let second = insert_user(&repo, UserRole::Student).await;
+
+        let booking = BookingService::create_booking(&repo, first.id, request(course.id, tomorrow_at(10)))
+            .await.unwrap();
+
+        let overlapping = request(course.id, tomorrow_at(10) + Duration::minutes(30));
+        let error = BookingService::create_booking(&repo, second.id, overlapping.clone()).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        // Back-to-back sessions are fine
+        BookingService::create_booking(&repo, second.id, request(course.id, tomorrow_at(11)))
+            .await.unwrap();
+
+        // Declining frees the time again
+        BookingService::decline_booking(&repo, booking.id, tutor.id, BookingActionRequest::default())
+            .await.unwrap();
+        let rebooked = BookingService::create_booking(&repo, second.id, request(course.id, tomorrow_at(9) + Duration::minutes(30)))
+            .await.unwrap();
+        assert_eq!(rebooked.status, BookingStatus::Requested);
+    }
+
+    #[actix_rt::test]
+    async fn bookings_must_fit_the_tutors_open_time() {
+        let repo = InMemoryRepository::new();
+        let (tutor, course) = open_tutor(&repo).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        // 23:30 runs past the end of the daily window
+        let error = BookingService::create_booking(&repo, student.id, request(course.id, tomorrow_at(23) + Duration::minutes(30)))
+            .await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        let error = BookingService::create_booking(&repo, student.id, request(course.id, Utc::now() - Duration::hours(1)))
+            .await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+
+        // Tutors cannot book sessions themselves
+        let error = BookingService::create_booking(&repo, tutor.id, request(course.id, tomorrow_at(10)))
+            .await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+    }
+
+    #[actix_rt::test]
+    async fn past_sessions_are_closed_by_the_tutor_only() {
+        let repo = InMemoryRepository::new();
+        let (tutor, course) = open_tutor(&repo).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        let stranger = insert_user(&repo, UserRole::Student).await;
+
+        let now = Utc::now();
+        let mut past = Booking {
+            id: Uuid::new_v4(),
+            course_id: course.id,
+            tutor_id: course.tutor_id,
+            student_id: student.id,
+            starts_at: now - Duration::hours(2),
+            ends_at: now - Duration::hours(1),
+            status: BookingStatus::Confirmed,
+            note: None,
+            status_reason: None,
+            price: course.price,
+            cancellation_fee: None,
+            reschedule_count: 0,
+            created_at: now,
+            updated_at: now,
+        };
+        repo.insert_booking(&past).await.unwrap();
+        past.id = Uuid::new_v4();
+        past.starts_at -= Duration::days(1);
+        past.ends_at -= Duration::days(1);
+        repo.insert_booking(&past).await.unwrap();
+
+        let error = BookingService::get_booking(&repo, past.id, stranger.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        let error = BookingService::cancel_booking(&repo, past.id, student.id, BookingActionRequest::default())
+            .await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+
+        let no_show = BookingService::mark_no_show(&repo, past.id, tutor.id).await.unwrap();
+        assert_eq!(no_show.status, BookingStatus::NoShow);
+
+        let tutor_view = BookingService::list_tutor_bookings(&repo, tutor.id, BookingListQuery {
+            status: Some(BookingStatus::Confirmed),
+        }).await.unwrap();
+        let completed = BookingService::complete_booking(&repo, tutor_view[0].id, tutor.id).await.unwrap();
+        assert_eq!(completed.status, BookingStatus::Completed);
+    }
+
+    fn policy(free_cancel_hours: i32, max_reschedules: i32, reschedule_notice_hours: i32) -> SetBookingPolicyRequest {
+        SetBookingPolicyRequest {
+            free_cancel_hours,
+            late_cancel_fee_percent: 40,
+            max_reschedules,
+            reschedule_notice_hours,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn late_cancellations_by_students_are_charged() {
+        let repo = InMemoryRepository::new();
+        let (tutor, course) = open_tutor(&repo).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        BookingService::set_policy(&repo, tutor.id, policy(48, 2, 24)).await.unwrap();
+        assert_eq!(
+            BookingService::get_tutor_policy(&repo, course.tutor_id).await.unwrap().free_cancel_hours,
+            48
+        );
+
+        // Inside the 48 hour window
+        let soon = BookingService::create_booking(&repo, student.id, request(course.id, tomorrow_at(10)))
+            .await.unwrap();
+        BookingService::accept_booking(&repo, soon.id, tutor.id).await.unwrap();
+        let cancelled = BookingService::cancel_booking(&repo, soon.id, student.id, BookingActionRequest::default())
+            .await.unwrap();
+        assert_eq!(cancelled.cancellation_fee, Some(1200));
+
+        // Well ahead of the window
+        let later = BookingService::create_booking(&repo, student.id, request(course.id, tomorrow_at(10) + Duration::days(9)))
+            .await.unwrap();
+        BookingService::accept_booking(&repo, later.id, tutor.id).await.unwrap();
+        let cancelled = BookingService::cancel_booking(&repo, later.id, student.id, BookingActionRequest::default())
+            .await.unwrap();
+        assert_eq!(cancelled.cancellation_fee, Some(0));
+
+        // Tutors never charge for their own cancellations
+        let again = BookingService::create_booking(&repo, student.id, request(course.id, tomorrow_at(12)))
+            .await.unwrap();
+        BookingService::accept_booking(&repo, again.id, tutor.id).await.unwrap();
+        let cancelled = BookingService::cancel_booking(&repo, again.id, tutor.id, BookingActionRequest::default())
+            .await.unwrap();
+        assert_eq!(cancelled.cancellation_fee, Some(0));
+    }
+
+    #[actix_rt::test]
+    async fn a_cancellation_racing_a_confirmation_is_refused() {
+        let repo = InMemoryRepository::new();
+        let (tutor, course) = open_tutor(&repo).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        BookingService::set_policy(&repo, tutor.id, policy(48, 2, 24)).await.unwrap();
+
+        // The student reads the booking while it is still only requested and
+        // the tutor confirms it before the cancellation is written
+        let booking = BookingService::create_booking(&repo, student.id, request(course.id, tomorrow_at(10)))
+            .await.unwrap();
+        let (stale, party) = BookingService::find_participant_booking(&repo, booking.id, student.id).await.unwrap();
+        BookingService::accept_booking(&repo, booking.id, tutor.id).await.unwrap();
+
+        let error = BookingService::cancel(&repo, stale, party, None).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+        let current = BookingService::get_booking(&repo, booking.id, student.id).await.unwrap();
+        assert_eq!((current.status, current.cancellation_fee), (BookingStatus::Confirmed, None));
+
+        let cancelled = BookingService::cancel_booking(&repo, booking.id, student.id, BookingActionRequest::default())
+            .await.unwrap();
+        assert_eq!((cancelled.status, cancelled.cancellation_fee), (BookingStatus::Cancelled, Some(1200)));
+    }
+
+    #[actix_rt::test]
+    async fn reschedules_follow_the_tutors_limits() {
+        let repo = InMemoryRepository::new();
+        let (tutor, course) = open_tutor(&repo).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        BookingService::set_policy(&repo, tutor.id, policy(24, 1, 2)).await.unwrap();
+
+        let booking = BookingService::create_booking(&repo, student.id, request(course.id, tomorrow_at(10)))
+            .await.unwrap();
+        BookingService::accept_booking(&repo, booking.id, tutor.id).await.unwrap();
+
+        // Moving by half an hour overlaps only the booking itself
+        let moved = BookingService::reschedule_booking(&repo, booking.id, student.id, RescheduleBookingRequest {
+            starts_at: tomorrow_at(10) + Duration::minutes(30),
+            reason: None,
+        }).await.unwrap();
+        assert_eq!(moved.status, BookingStatus::Requested);
+        assert_eq!(moved.reschedule_count, 1);
+        assert_eq!(moved.ends_at, tomorrow_at(11) + Duration::minutes(30));
+
+        let error = BookingService::reschedule_booking(&repo, booking.id, tutor.id, RescheduleBookingRequest {
+            starts_at: tomorrow_at(14),
+            reason: None,
+        }).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        // A stricter notice period rules out moving tomorrow's sessions
+        BookingService::set_policy(&repo, tutor.id, policy(24, 3, 48)).await.unwrap();
+        let error = BookingService::reschedule_booking(&repo, booking.id, student.id, RescheduleBookingRequest {
+            starts_at: tomorrow_at(14),
+            reason: None,
+        }).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+    }
+}
@@ -0,0 +1,502 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    BookingStatus, BookingWithDetails, CalendarFeed, CalendarFeedResponse, UpdateCalendarFeedRequest,
+};
+use crate::repositories::{
+    AvailabilityRepository, BookingRepository, CalendarRepository, TutorRepository, UserRepository,
+};
+use crate::services::availability::{parse_time_zone, Interval, SchedulePlan};
+use crate::services::AuthService;
+use chrono::{DateTime, Duration, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz, TzOffset};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use uuid::Uuid;
+use validator::Validate;
+
+const TOKEN_LENGTH: usize = 40;
+// Past sessions stay in the feed this long so late changes still sync
+const FEED_HISTORY_DAYS: i64 = 30;
+// How far ahead a tutor's open time is published
+const AVAILABILITY_HORIZON_DAYS: i64 = 28;
+const PRODUCT_ID: &str = "-//EzyTutor//Sessions//EN";
+const UID_DOMAIN: &str = "ezytutor";
+// RFC 5545 limit for a content line before it has to be folded
+const MAX_LINE_OCTETS: usize = 75;
+
+// Per-user iCalendar subscriptions. The secret token in the feed URL is the
+// only credential, because calendar apps cannot send a bearer token.
+pub struct CalendarService;
+
+impl CalendarService {
+    // Returns the user's feed, creating it on first use
+    pub async fn get_feed<R>(repo: &R, user_id: Uuid, base_url: &str) -> AppResult<CalendarFeedResponse>
+    where
+        R: UserRepository + TutorRepository + AvailabilityRepository + CalendarRepository,
+    {
+        let feed = Self::own_feed(repo, user_id).await?;
+        Ok(feed_response(feed, base_url))
+    }
+
+    pub async fn set_time_zone<R>(
+        repo: &R,
+        user_id: Uuid,
+        request: UpdateCalendarFeedRequest,
+        base_url: &str,
+    ) -> AppResult<CalendarFeedResponse>
+    where
+        R: UserRepository + TutorRepository + AvailabilityRepository + CalendarRepository,
+    {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        parse_time_zone(&request.time_zone)?;
+
+        let mut feed = Self::own_feed(repo, user_id).await?;
+        feed.time_zone = request.time_zone;
+        feed.updated_at = Utc::now();
+
+        repo.upsert_calendar_feed(&feed).await?;
+        Ok(feed_response(feed, base_url))
+    }
+
+    // Issues a new secret URL; subscriptions to the old one stop updating
+    pub async fn rotate_feed<R>(repo: &R, user_id: Uuid, base_url: &str) -> AppResult<CalendarFeedResponse>
+    where
+        R: UserRepository + TutorRepository + AvailabilityRepository + CalendarRepository,
+    {
+        let mut feed = Self::own_feed(repo, user_id).await?;
+        feed.token = new_token();
+        feed.updated_at = Utc::now();
+
+        repo.upsert_calendar_feed(&feed).await?;
+        Ok(feed_response(feed, base_url))
+    }
+
+    // The feed as an RFC 5545 document: the user's recent and upcoming
+    // sessions on either side, plus a tutor's open time for the next weeks
+    pub async fn render_feed<R>(repo: &R, token: &str) -> AppResult<String>
+    where
+        R: UserRepository + TutorRepository + AvailabilityRepository + BookingRepository + CalendarRepository,
+    {
+        let not_found = || AppError::NotFound("Calendar feed not found".to_string());
+        let feed = repo.find_calendar_feed_by_token(token).await?.ok_or_else(not_found)?;
+        let user = repo.find_user_by_id(feed.user_id)
+            .await?
+            .filter(|user| user.is_active)
+            .ok_or_else(not_found)?;
+        let zone = parse_time_zone(&feed.time_zone)?;
+
+        let now = Utc::now();
+        let since = now - Duration::days(FEED_HISTORY_DAYS);
+        let mut events: Vec<CalendarEvent> = repo.list_bookings_for_student(user.id, None)
+            .await?
+            .into_iter()
+            .filter(|row| row.booking.ends_at >= since)
+            .map(|row| booking_event(row, false))
+            .collect();
+
+        if let Some(tutor) = repo.find_tutor_by_user_id(user.id).await? {
+            let tutor_id = tutor.tutor.id;
+            events.extend(
+                repo.list_bookings_for_tutor(tutor_id, None)
+                    .await?
+                    .into_iter()
+                    .filter(|row| row.booking.ends_at >= since)
+                    .map(|row| booking_event(row, true)),
+            );
+
+            let schedule = repo.find_schedule(tutor_id).await?;
+            if let Some(schedule) = schedule.filter(|_| tutor.tutor.is_available) {
+                let range = (now, now + Duration::days(AVAILABILITY_HORIZON_DAYS));
+                let mut plan = SchedulePlan::load(repo, &schedule, range).await?;
+                // Sessions are listed as events of their own, so the blocks
+                // show the published hours and keep their UIDs as slots fill
+                plan.busy.clear();
+                events.extend(
+                    plan.open_intervals(range)
+                        .into_iter()
+                        .filter(|&(start, end)| end > range.0 && start < range.1)
+                        .map(|(start, end)| CalendarEvent {
+                            uid: format!(
+                                "availability-{}-{}@{}", tutor_id, start.format("%Y%m%dT%H%M%SZ"), UID_DOMAIN
+                            ),
+                            start,
+                            end,
+                            stamp: schedule.updated_at,
+                            summary: "Open for bookings".to_string(),
+                            description: None,
+                            status: "CONFIRMED",
+                            transparent: true,
+                        }),
+                );
+            }
+        }
+
+        events.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.uid.cmp(&b.uid)));
+        let name = format!("EzyTutor: {} {}", user.first_name, user.last_name);
+        Ok(render_calendar(&name, zone, &events, now))
+    }
+
+    async fn own_feed<R>(repo: &R, user_id: Uuid) -> AppResult<CalendarFeed>
+    where
+        R: UserRepository + TutorRepository + AvailabilityRepository + CalendarRepository,
+    {
+        if let Some(feed) = repo.find_calendar_feed(user_id).await? {
+            return Ok(feed);
+        }
+
+        AuthService::get_user_by_id(repo, user_id).await?;
+        // Tutors start out in the zone they teach in
+        let schedule = match repo.find_tutor_by_user_id(user_id).await? {
+            Some(tutor) => repo.find_schedule(tutor.tutor.id).await?,
+            None => None,
+        };
+        let now = Utc::now();
+        let feed = CalendarFeed {
+            user_id,
+            token: new_token(),
+            time_zone: schedule.map(|schedule| schedule.time_zone).unwrap_or_else(|| "UTC".to_string()),
+            created_at: now,
+            updated_at: now,
+        };
+
+        repo.upsert_calendar_feed(&feed).await?;
+        Ok(feed)
+    }
+}
+
+struct CalendarEvent {
+    uid: String,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    // When the event last changed
+    stamp: DateTime<Utc>,
+    summary: String,
+    description: Option<String>,
+    status: &'static str,
+    // Open time does not make the tutor look busy to other calendars
+    transparent: bool,
+}
+
+fn new_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+fn feed_response(feed: CalendarFeed, base_url: &str) -> CalendarFeedResponse {
+    CalendarFeedResponse {
+        url: format!("{}/calendar/{}.ics", base_url.trim_end_matches('/'), feed.token),
+        time_zone: feed.time_zone,
+        updated_at: feed.updated_at,
+    }
+}
+
+fn booking_event(row: BookingWithDetails, as_tutor: bool) -> CalendarEvent {
+    let booking = row.booking;
+    let other_party = if as_tutor {
+        format!("{} {}", row.student_first_name, row.student_last_name)
+    } else {
+        format!("{} {}", row.tutor_first_name, row.tutor_last_name)
+    };
+    let mut summary = format!("{} with {}", row.course_title, other_party);
+    let status = match booking.status {
+        BookingStatus::Requested => "TENTATIVE",
+        BookingStatus::Cancelled => {
+            summary.insert_str(0, "Cancelled: ");
+            "CANCELLED"
+        }
+        BookingStatus::Confirmed | BookingStatus::Completed | BookingStatus::NoShow => "CONFIRMED",
+    };
+    let description = [booking.note, booking.status_reason]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    CalendarEvent {
+        uid: format!("booking-{}@{}", booking.id, UID_DOMAIN),
+        start: booking.starts_at,
+        end: booking.ends_at,
+        stamp: booking.updated_at,
+        summary,
+        description: Some(description).filter(|text| !text.is_empty()),
+        status,
+        transparent: false,
+    }
+}
+
+fn render_calendar(name: &str, zone: Tz, events: &[CalendarEvent], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODUCT_ID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+        format!("X-WR-TIMEZONE:{}", zone.name()),
+    ];
+
+    let span = (
+        events.iter().map(|event| event.start).min().unwrap_or(now),
+        events.iter().map(|event| event.end).max().unwrap_or(now),
+    );
+    lines.extend(time_zone_lines(zone, span));
+
+    let local = |instant: DateTime<Utc>| instant.with_timezone(&zone).format("%Y%m%dT%H%M%S");
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", event.stamp.format("%Y%m%dT%H%M%SZ")));
+        lines.push(format!("DTSTART;TZID={}:{}", zone.name(), local(event.start)));
+        lines.push(format!("DTEND;TZID={}:{}", zone.name(), local(event.end)));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push(format!("STATUS:{}", event.status));
+        lines.push(format!("TRANSP:{}", if event.transparent { "TRANSPARENT" } else { "OPAQUE" }));
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut document = String::new();
+    for line in lines {
+        document.push_str(&fold(&line));
+        document.push_str("\r\n");
+    }
+    document
+}
+
+// VTIMEZONE listing the offset in force at the start of `span` and every
+// change up to its end, found by scanning a day at a time and then narrowing
+// each change down to the minute
+fn time_zone_lines(zone: Tz, span: Interval) -> Vec<String> {
+    let mut lines = vec!["BEGIN:VTIMEZONE".to_string(), format!("TZID:{}", zone.name())];
+    let offset_at = |instant: DateTime<Utc>| zone.offset_from_utc_datetime(&instant.naive_utc());
+    let changed = |a: &TzOffset, b: &TzOffset| a.fix() != b.fix() || a.dst_offset() != b.dst_offset();
+
+    let mut offset = offset_at(span.0);
+    lines.extend(observance(&offset, &offset, span.0));
+
+    let mut cursor = span.0;
+    while cursor < span.1 {
+        let next = cursor + Duration::days(1);
+        if !changed(&offset, &offset_at(next)) {
+            cursor = next;
+            continue;
+        }
+
+        let (mut before, mut after) = (0, Duration::days(1).num_minutes());
+        while after - before > 1 {
+            let middle = (before + after) / 2;
+            if changed(&offset, &offset_at(cursor + Duration::minutes(middle))) {
+                after = middle;
+            } else {
+                before = middle;
+            }
+        }
+        let onset = cursor + Duration::minutes(after);
+        let new_offset = offset_at(onset);
+        lines.extend(observance(&offset, &new_offset, onset));
+        offset = new_offset;
+        cursor = onset;
+    }
+
+    lines.push("END:VTIMEZONE".to_string());
+    lines
+}
+
+fn observance(from: &TzOffset, to: &TzOffset, onset: DateTime<Utc>) -> Vec<String> {
+    let kind = if to.dst_offset() == Duration::zero() { "STANDARD" } else { "DAYLIGHT" };
+    // The onset is written in the local time that was in force before it
+    let local_onset = onset.naive_utc() + Duration::seconds(from.fix().local_minus_utc() as i64);
+
+    let mut lines = vec![
+        format!("BEGIN:{}", kind),
+        format!("DTSTART:{}", local_onset.format("%Y%m%dT%H%M%S")),
+        format!("TZOFFSETFROM:{}", format_offset(from.fix().local_minus_utc())),
+        format!("TZOFFSETTO:{}", format_offset(to.fix().local_minus_utc())),
+    ];
+    if let Some(abbreviation) = to.abbreviation() {
+        lines.push(format!("TZNAME:{}", abbreviation));
+    }
+    lines.push(format!("END:{}", kind));
+    lines
+}
+
+fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    match seconds % 60 {
+        0 => format!("{}{:02}{:02}", sign, seconds / 3600, seconds % 3600 / 60),
+        rest => format!("{}{:02}{:02}{:02}", sign, seconds / 3600, seconds % 3600 / 60, rest),
+    }
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Splits long lines into 75-octet pieces without cutting a UTF-8 character;
+// continuation lines start with a space
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / MAX_LINE_OCTETS * 3);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        BookingActionRequest, CreateBookingRequest, CreateCourseRequest, DifficultyLevel,
+        RequestContext, SetScheduleRequest, UserRole, WeeklyWindow,
+    };
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use crate::services::{AvailabilityService, BookingService, CourseService};
+    use chrono::{NaiveTime, Weekday};
+
+    const BASE_URL: &str = "https://api.ezytutor.test/api/v1";
+
+    fn token_of(url: &str) -> &str {
+        url.trim_start_matches(BASE_URL)
+            .trim_start_matches("/calendar/")
+            .trim_end_matches(".ics")
+    }
+
+    #[actix_rt::test]
+    async fn feeds_list_sessions_for_both_sides() {
+        let repo = InMemoryRepository::new();
+        let (tutor, _) = insert_tutor(&repo, &["Piano"]).await;
+        let windows = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun]
+            .into_iter()
+            .map(|weekday| WeeklyWindow {
+                weekday,
+                start_time: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+                end_time: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+            })
+            .collect();
+        AvailabilityService::set_schedule(&repo, tutor.id, SetScheduleRequest {
+            time_zone: "America/New_York".to_string(),
+            slot_minutes: None,
+            windows,
+        }).await.unwrap();
+        let course = CourseService::create_course(&repo, tutor.id, CreateCourseRequest {
+            title: "Jazz Piano".to_string(),
+            description: "Voicings, comping and improvisation".to_string(),
+            price: 4000,
+            duration_minutes: 60,
+            category: "Music".to_string(),
+            difficulty_level: DifficultyLevel::Advanced,
+            max_students: None,
+        }, &RequestContext::default()).await.unwrap();
+
+        let student = insert_user(&repo, UserRole::Student).await;
+        let starts_at = (Utc::now() + Duration::days(2)).date_naive().and_hms_opt(15, 0, 0).unwrap().and_utc();
+        let booking = BookingService::create_booking(&repo, student.id, CreateBookingRequest {
+            course_id: course.id,
+            starts_at,
+            note: Some("Bring the lead sheet, please".to_string()),
+        }).await.unwrap();
+        BookingService::cancel_booking(&repo, booking.id, student.id, BookingActionRequest::default())
+            .await.unwrap();
+
+        // Tutors default to their schedule's zone, students to UTC
+        let tutor_feed = CalendarService::get_feed(&repo, tutor.id, BASE_URL).await.unwrap();
+        assert_eq!(tutor_feed.time_zone, "America/New_York");
+        let student_feed = CalendarService::get_feed(&repo, student.id, BASE_URL).await.unwrap();
+        assert_eq!(student_feed.time_zone, "UTC");
+
+        let uid = format!("UID:booking-{}@ezytutor", booking.id);
+        let tutor_ics = CalendarService::render_feed(&repo, token_of(&tutor_feed.url)).await.unwrap();
+        assert!(tutor_ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(tutor_ics.contains(&uid));
+        assert!(tutor_ics.contains("SUMMARY:Cancelled: Jazz Piano with Test Student"));
+        assert!(tutor_ics.contains("STATUS:CANCELLED"));
+        assert!(tutor_ics.contains("DESCRIPTION:Bring the lead sheet\\, please"));
+        assert!(tutor_ics.contains("TZID:America/New_York"));
+        assert!(tutor_ics.contains("SUMMARY:Open for bookings"));
+        assert!(tutor_ics.contains("DTSTART;TZID=America/New_York:"));
+
+        let student_ics = CalendarService::render_feed(&repo, token_of(&student_feed.url)).await.unwrap();
+        assert!(student_ics.contains(&uid));
+        assert!(!student_ics.contains("Open for bookings"));
+        assert!(student_ics.contains(&format!("DTSTART;TZID=UTC:{}", starts_at.format("%Y%m%dT%H%M%S"))));
+
+        // UIDs stay the same between fetches
+        let again = CalendarService::render_feed(&repo, token_of(&tutor_feed.url)).await.unwrap();
+        let uids = |ics: &str| ics.lines().filter(|line| line.starts_with("UID:")).map(str::to_string).collect::<Vec<_>>();
+        assert_eq!(uids(&tutor_ics), uids(&again));
+    }
+
+    #[actix_rt::test]
+    async fn rotating_the_secret_revokes_the_old_url() {
+        let repo = InMemoryRepository::new();
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        let feed = CalendarService::get_feed(&repo, student.id, BASE_URL).await.unwrap();
+        assert_eq!(CalendarService::get_feed(&repo, student.id, BASE_URL).await.unwrap().url, feed.url);
+        let rotated = CalendarService::rotate_feed(&repo, student.id, BASE_URL).await.unwrap();
+        assert_ne!(rotated.url, feed.url);
+
+        let error = CalendarService::render_feed(&repo, token_of(&feed.url)).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        assert!(CalendarService::render_feed(&repo, token_of(&rotated.url)).await.is_ok());
+
+        let error = CalendarService::set_time_zone(&repo, student.id, UpdateCalendarFeedRequest {
+            time_zone: "Mars/Olympus_Mons".to_string(),
+        }, BASE_URL).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+    }
+
+    #[test]
+    fn time_zones_list_each_offset_change() {
+        let zone: Tz = "Europe/London".parse().unwrap();
+        let span = (
+            Utc.with_ymd_and_hms(2030, 3, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2030, 11, 30, 0, 0, 0).unwrap(),
+        );
+        let lines = time_zone_lines(zone, span).join("\n");
+        assert!(lines.contains(
+            "BEGIN:DAYLIGHT\nDTSTART:20300331T010000\nTZOFFSETFROM:+0000\nTZOFFSETTO:+0100\nTZNAME:BST"
+        ));
+        assert!(lines.contains(
+            "BEGIN:STANDARD\nDTSTART:20301027T020000\nTZOFFSETFROM:+0100\nTZOFFSETTO:+0000\nTZNAME:GMT"
+        ));
+        assert_eq!(format_offset(-(5 * 3600 + 30 * 60)), "-0530");
+    }
+
+    #[test]
+    fn long_lines_are_folded_on_character_boundaries() {
+        let line = format!("DESCRIPTION:{}", "é".repeat(60));
+        let folded = fold(&line);
+        for piece in folded.split("\r\n") {
+            assert!(piece.len() <= MAX_LINE_OCTETS);
+        }
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
@@ -0,0 +1,435 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{Certificate, CertificateResponse, CourseProgress};
+use crate::repositories::{CertificateRepository, CourseRepository, NotificationRepository, ProgressRepository};
+use crate::services::NotificationService;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use uuid::Uuid;
+
+// No 0/O or 1/I so codes survive being read aloud or typed from paper
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_GROUPS: usize = 3;
+const CODE_GROUP_LENGTH: usize = 4;
+const VERIFY_PATH: &str = "/api/v1/certificates";
+
+// A4 landscape, in points
+const PAGE_WIDTH: f64 = 842.0;
+const PAGE_HEIGHT: f64 = 595.0;
+// Lines wider than this are set in a smaller size
+const MAX_LINE_WIDTH: f64 = 700.0;
+
+// Students who complete every lesson of a course get a certificate with a
+// code anyone can check. The names and title are copied at issue time, so
+// the certificate reads the same after renames or the course's deletion.
+pub struct CertificateService;
+
+impl CertificateService {
+    // Returns the certificate and whether it was issued by this call
+    pub async fn issue<R>(repo: &R, course_id: Uuid, student_id: Uuid) -> AppResult<(CertificateResponse, bool)>
+    where
+        R: CourseRepository + ProgressRepository + CertificateRepository + NotificationRepository,
+    {
+        if let Some(existing) = repo.find_certificate(course_id, student_id).await? {
+            return Ok((CertificateResponse::from(existing), false));
+        }
+
+        let progress = Self::find_progress(repo, course_id, student_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Enrollment not found".to_string()))?;
+        if !is_complete(&progress) {
+            return Err(AppError::Conflict(
+                "Complete every lesson of the course to earn its certificate".to_string(),
+            ));
+        }
+
+        let (certificate, created) = Self::create(repo, progress).await?;
+        Ok((CertificateResponse::from(certificate), created))
+    }
+
+    // Called after each lesson completion so the last one issues the certificate
+    pub(crate) async fn issue_if_complete<R>(repo: &R, course_id: Uuid, student_id: Uuid) -> AppResult<()>
+    where
+        R: CourseRepository + ProgressRepository + CertificateRepository + NotificationRepository,
+    {
+        if repo.find_certificate(course_id, student_id).await?.is_some() {
+            return Ok(());
+        }
+
+        if let Some(progress) = Self::find_progress(repo, course_id, student_id).await?.filter(is_complete) {
+            Self::create(repo, progress).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn list_for_student<R: CertificateRepository>(
+        repo: &R,
+        student_id: Uuid,
+    ) -> AppResult<Vec<CertificateResponse>> {
+        let certificates = repo.list_certificates_for_student(student_id).await?;
+        Ok(certificates.into_iter().map(CertificateResponse::from).collect())
+    }
+
+    // Public lookup; codes are accepted in any case
+    pub async fn verify<R: CertificateRepository>(repo: &R, code: &str) -> AppResult<CertificateResponse> {
+        Self::find_by_code(repo, code).await.map(CertificateResponse::from)
+    }
+
+    // Returns the file name and the PDF bytes
+    pub async fn render_pdf<R: CertificateRepository>(repo: &R, code: &str) -> AppResult<(String, Vec<u8>)> {
+        let certificate = Self::find_by_code(repo, code).await?;
+        Ok((format!("certificate-{}.pdf", certificate.code), render_certificate(&certificate)))
+    }
+
+    async fn find_by_code<R: CertificateRepository>(repo: &R, code: &str) -> AppResult<Certificate> {
+        repo.find_certificate_by_code(&code.trim().to_ascii_uppercase())
+            .await?
+            .ok_or_else(|| AppError::NotFound("Certificate not found".to_string()))
+    }
+
+    async fn find_progress<R: ProgressRepository>(
+        repo: &R,
+        course_id: Uuid,
+        student_id: Uuid,
+    ) -> AppResult<Option<CourseProgress>> {
+        Ok(repo.list_student_progress(student_id)
+            .await?
+            .into_iter()
+            .find(|row| row.course_id == course_id))
+    }
+
+    async fn create<R>(repo: &R, progress: CourseProgress) -> AppResult<(Certificate, bool)>
+    where
+        R: CourseRepository + CertificateRepository + NotificationRepository,
+    {
+        let course = repo.find_course_by_id(progress.course_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?;
+
+        let now = Utc::now();
+        let id = Uuid::new_v4();
+        let certificate = repo.insert_certificate(&Certificate {
+            id,
+            code: new_code(),
+            course_id: Some(progress.course_id),
+            student_id: progress.student_id,
+            student_name: format!("{} {}", progress.student_first_name, progress.student_last_name),
+            course_title: progress.course_title,
+            tutor_name: format!("{} {}", course.tutor_first_name, course.tutor_last_name),
+            completed_at: progress.last_completed_at.unwrap_or(now),
+            issued_at: now,
+        }).await?;
+
+        // Someone else may have issued it first
+        let created = certificate.id == id;
+        if created {
+            NotificationService::notify(
+                repo,
+                certificate.student_id,
+                "certificate_issued",
+                format!("Certificate for {}", certificate.course_title),
+                format!("You completed {}. Your verification code is {}.", certificate.course_title, certificate.code),
+                Some(format!("{}/{}", VERIFY_PATH, certificate.code)),
+            ).await?;
+        }
+
+        Ok((certificate, created))
+    }
+}
+
+fn is_complete(progress: &CourseProgress) -> bool {
+    progress.total_lessons > 0 && progress.completed_lessons >= progress.total_lessons
+}
+
+// XXXX-XXXX-XXXX
+fn new_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..CODE_GROUPS)
+        .map(|_| {
+            (0..CODE_GROUP_LENGTH)
+                .map(|_| char::from(CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())]))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+
+    // Advance widths from the Helvetica AFM files, in 1/1000 em, for ' '..='~'
+    fn widths(self) -> &'static [u16; 95] {
+        match self {
+            Font::Regular => &HELVETICA_WIDTHS,
+            Font::Bold => &HELVETICA_BOLD_WIDTHS,
+        }
+    }
+
+    // Accented letters fall back to an average width, close enough for centering
+    fn text_width(self, text: &[u8], size: f64) -> f64 {
+        let units: u32 = text
+            .iter()
+            .map(|&byte| match byte {
+                b' '..=b'~' => u32::from(self.widths()[usize::from(byte - b' ')]),
+                _ => 556,
+            })
+            .sum();
+        f64::from(units) * size / 1000.0
+    }
+}
+
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+// A one-page PDF using the standard Helvetica fonts, so nothing is embedded
+fn render_certificate(certificate: &Certificate) -> Vec<u8> {
+    let completed_on = certificate.completed_at.format("%B %-d, %Y").to_string();
+    let lines = [
+        (Font::Bold, 36.0, 460.0, "Certificate of Completion".to_string()),
+        (Font::Regular, 16.0, 400.0, "This certifies that".to_string()),
+        (Font::Bold, 30.0, 355.0, certificate.student_name.clone()),
+        (Font::Regular, 16.0, 310.0, "has completed the course".to_string()),
+        (Font::Bold, 24.0, 265.0, certificate.course_title.clone()),
+        (Font::Regular, 16.0, 225.0, format!("taught by {}", certificate.tutor_name)),
+        (Font::Regular, 14.0, 170.0, format!("Completed on {}", completed_on)),
+        (Font::Regular, 10.0, 80.0, format!("Verification code: {}", certificate.code)),
+        (Font::Regular, 10.0, 64.0, format!("Verify at {}/{}", VERIFY_PATH, certificate.code)),
+    ];
+
+    // Double border
+    let mut content = String::from("0.15 0.3 0.5 RG\n3 w 30 30 782 535 re S\n1 w 40 40 762 515 re S\n0 g\n");
+    for (font, size, y, text) in lines {
+        let encoded = win_ansi(&text);
+        let mut size: f64 = size;
+        let width = font.text_width(&encoded, size);
+        if width > MAX_LINE_WIDTH {
+            size *= MAX_LINE_WIDTH / width;
+        }
+        let x = (PAGE_WIDTH - font.text_width(&encoded, size)) / 2.0;
+        content.push_str(&format!(
+            "BT /{} {:.2} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+            font.resource(),
+            size,
+            x,
+            y,
+            escape_string(&encoded),
+        ));
+    }
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 4 0 R /F2 5 0 R >> >> /Contents 6 0 R >>",
+            PAGE_WIDTH, PAGE_HEIGHT,
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+        format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content),
+        format!(
+            "<< /Title ({}) /Producer (EzyTutor) /CreationDate ({}) >>",
+            escape_string(&win_ansi(&format!("Certificate {}", certificate.code))),
+            pdf_date(certificate.issued_at),
+        ),
+    ];
+    write_pdf(&objects)
+}
+
+// Numbers the objects from 1 in order; the first is the catalog and the last the info dictionary
+fn write_pdf(objects: &[String]) -> Vec<u8> {
+    // The binary comment tells transfer tools not to treat the file as text
+    let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+    }
+
+    let xref_offset = pdf.len();
+    let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        trailer.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    trailer.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        objects.len(),
+        xref_offset,
+    ));
+    pdf.extend_from_slice(trailer.as_bytes());
+    pdf
+}
+
+// WinAnsiEncoding matches Latin-1 for printable characters; anything else becomes '?'
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match u32::from(c) {
+            code @ (0x20..=0x7E | 0xA0..=0xFF) => code as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+// Literal string body, kept to ASCII with octal escapes
+fn escape_string(bytes: &[u8]) -> String {
+    let mut escaped = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        match byte {
+            b'(' | b')' | b'\\' => {
+                escaped.push('\\');
+                escaped.push(char::from(byte));
+            }
+            b' '..=b'~' => escaped.push(char::from(byte)),
+            _ => escaped.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    escaped
+}
+
+fn pdf_date(at: DateTime<Utc>) -> String {
+    at.format("D:%Y%m%d%H%M%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        CompleteLessonRequest, CreateCourseRequest, CreateLessonRequest, DifficultyLevel, ModuleRequest,
+        NotificationListQuery, RequestContext, UserRole,
+    };
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use crate::services::retention::RETENTION_DAYS;
+    use crate::services::{CourseService, CurriculumService, EnrollmentService, ProgressService, RetentionService};
+    use chrono::TimeZone;
+
+    #[test]
+    fn pdf_has_a_valid_cross_reference_table() {
+        let certificate = Certificate {
+            id: Uuid::new_v4(),
+            code: "ABCD-EFGH-JKLM".to_string(),
+            course_id: None,
+            student_id: Uuid::new_v4(),
+            student_name: "Zoë (Zo) Müller".to_string(),
+            course_title: "A course title long enough that it has to be set in a smaller size to fit".to_string(),
+            tutor_name: "Łukasz Nowak".to_string(),
+            completed_at: Utc.with_ymd_and_hms(2026, 3, 7, 15, 0, 0).unwrap(),
+            issued_at: Utc.with_ymd_and_hms(2026, 3, 8, 9, 30, 0).unwrap(),
+        };
+        let pdf = render_certificate(&certificate);
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        // Every xref entry points at the start of its object
+        let text = String::from_utf8_lossy(&pdf);
+        let startxref: usize = text.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert!(pdf[startxref..].starts_with(b"xref\n0 8\n"));
+        let xref = String::from_utf8(pdf[startxref..].to_vec()).unwrap();
+        let entries: Vec<&str> = xref.lines().skip(3).take(7).collect();
+        for (index, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj\n", index + 1).as_bytes()));
+        }
+
+        // Escaped parentheses, Latin-1 as octal, other characters replaced
+        assert!(text.contains("(Zo\\353 \\(Zo\\) M\\374ller) Tj"));
+        assert!(text.contains("(taught by ?ukasz Nowak) Tj"));
+        assert!(text.contains("(Completed on March 7, 2026) Tj"));
+        assert!(text.contains("/CreationDate (D:20260308093000Z)"));
+
+        // The long title is shrunk to fit between the borders
+        let title_line = text.lines().find(|line| line.contains("A course title")).unwrap();
+        let x: f64 = title_line.split_whitespace().nth(4).unwrap().parse().unwrap();
+        assert!(x >= (PAGE_WIDTH - MAX_LINE_WIDTH) / 2.0 - 0.01);
+    }
+
+    #[actix_rt::test]
+    async fn finishing_the_last_lesson_issues_a_verifiable_certificate() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (tutor, _) = insert_tutor(&repo, &["History"]).await;
+        let course = CourseService::create_course(&repo, tutor.id, CreateCourseRequest {
+            title: "Roman Britain".to_string(),
+            description: "From Claudius to the end of Roman rule".to_string(),
+            price: 2500,
+            duration_minutes: 60,
+            category: "History".to_string(),
+            difficulty_level: DifficultyLevel::Beginner,
+            max_students: None,
+        }, &context).await.unwrap();
+        let module = CurriculumService::add_module(&repo, course.id, tutor.id, ModuleRequest {
+            title: "Conquest".to_string(),
+        }).await.unwrap();
+        let mut lessons = Vec::new();
+        for title in ["Invasion", "Boudica"] {
+            let lesson = CurriculumService::add_lesson(&repo, module.id, tutor.id, CreateLessonRequest {
+                title: title.to_string(),
+                content: String::new(),
+                estimated_minutes: 15,
+                resources: Vec::new(),
+            }).await.unwrap();
+            lessons.push(lesson.id);
+        }
+
+        let student = insert_user(&repo, UserRole::Student).await;
+        let error = CertificateService::issue(&repo, course.id, student.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        EnrollmentService::enroll(&repo, course.id, student.id).await.unwrap();
+        ProgressService::complete_lesson(&repo, lessons[0], student.id, CompleteLessonRequest::default()).await.unwrap();
+        let error = CertificateService::issue(&repo, course.id, student.id).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        ProgressService::complete_lesson(&repo, lessons[1], student.id, CompleteLessonRequest::default()).await.unwrap();
+        let mine = CertificateService::list_for_student(&repo, student.id).await.unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].course_title, "Roman Britain");
+        assert_eq!(mine[0].tutor_name, format!("{} {}", tutor.first_name, tutor.last_name));
+
+        // Asking again returns the same certificate
+        let (again, created) = CertificateService::issue(&repo, course.id, student.id).await.unwrap();
+        assert!(!created);
+        assert_eq!(again.code, mine[0].code);
+
+        let notifications = NotificationService::list(&repo, student.id, NotificationListQuery { unread: false })
+            .await
+            .unwrap();
+        assert_eq!(notifications.iter().filter(|n| n.kind == "certificate_issued").count(), 1);
+
+        // Codes are checked case-insensitively and outlive the course
+        EnrollmentService::drop_enrollment(&repo, course.id, student.id).await.unwrap();
+        CourseService::delete_course(&repo, course.id, tutor.id, &context).await.unwrap();
+        let expired = Utc::now() + chrono::Duration::days(RETENTION_DAYS + 1);
+        RetentionService::purge_expired(&repo, expired).await.unwrap();
+        let verified = CertificateService::verify(&repo, &format!(" {} ", mine[0].code.to_lowercase())).await.unwrap();
+        assert_eq!((verified.course_id, verified.student_name), (None, mine[0].student_name.clone()));
+        let error = CertificateService::verify(&repo, "AAAA-AAAA-AAAA").await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+    }
+}
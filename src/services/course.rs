@@ -1,291 +1,632 @@
-use crate::database::DbPool;
 use crate::errors::{AppError, AppResult};
-use crate::models::{Course, CreateCourseRequest, UpdateCourseRequest, CourseResponse};
+use crate::models::{
+    Course, CourseWithTutor, CreateCourseRequest, UpdateCourseRequest, CourseResponse, DeletedCourseResponse, Precondition,
+    RequestContext
+};
+use crate::repositories::{AuditRepository, CourseRepository, EnrollmentRepository, TutorRepository};
+use crate::services::audit::{self, AuditService, AuditTarget};
+use crate::services::RetentionService;
 use chrono::Utc;
-use sqlx::Row;
 use uuid::Uuid;
 use validator::Validate;
 
 pub struct CourseService;
 
 impl CourseService {
-    pub async fn create_course(
-        pool: &DbPool,
+    pub async fn create_course<R: TutorRepository + CourseRepository + AuditRepository>(
+        repo: &R,
         tutor_id: Uuid,
         request: CreateCourseRequest,
+        context: &RequestContext,
     ) -> AppResult<CourseResponse> {
         // Validate input
         request.validate()
             .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
 
         // Verify tutor exists
-        let tutor_exists = sqlx::query("SELECT id FROM tutors WHERE user_id = $1")
-            .bind(tutor_id)
-            .fetch_optional(pool)
-            .await?;
-
-        let tutor_db_id = tutor_exists
-            .ok_or_else(|| AppError::NotFound("Tutor profile not found".to_string()))?
-            .get::<Uuid, _>("id");
+        let tutor = repo.find_tutor_by_user_id(tutor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor profile not found".to_string()))?;
 
         // Insert course
-        let course_id = Uuid::new_v4();
         let now = Utc::now();
-
-        sqlx::query(
-            r#"
-            INSERT INTO courses (id, title, description, tutor_id, price, duration_minutes, category, difficulty_level, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-            "#
-        )
-        .bind(course_id)
-        .bind(&request.title)
-        .bind(&request.description)
-        .bind(tutor_db_id)
-        .bind(request.price)
-        .bind(request.duration_minutes)
-        .bind(&request.category)
-        .bind(&request.difficulty_level)
-        .bind(now)
-        .bind(now)
-        .execute(pool)
-        .await?;
-
-        // Return course response
-        let mut course_response = CourseResponse {
-            id: course_id,
+        let course = Course {
+            id: Uuid::new_v4(),
             title: request.title,
             description: request.description,
-            tutor_id: tutor_db_id,
-            tutor_name: None,
+            tutor_id: tutor.tutor.id,
             price: request.price,
             duration_minutes: request.duration_minutes,
             category: request.category,
             difficulty_level: request.difficulty_level,
             is_active: true,
+            max_students: request.max_students,
+            taken_down_at: None,
+            deleted_at: None,
             created_at: now,
             updated_at: now,
         };
 
-        // Get tutor name
-        if let Ok(tutor_name) = Self::get_tutor_name(pool, tutor_db_id).await {
-            course_response.tutor_name = Some(tutor_name);
-        }
+        repo.insert_course(&course).await?;
+        let changes = audit::changes(None, Some(&course));
+        let target = AuditTarget::Course(course.id);
+        AuditService::record(repo, context, Some(tutor_id), "course.created", target, changes, None).await?;
 
-        Ok(course_response)
+        // Return course response
+        Ok(CourseResponse::from(CourseWithTutor {
+            course,
+            tutor_first_name: tutor.first_name,
+            tutor_last_name: tutor.last_name,
+            tutor_rating: tutor.tutor.rating,
+            tutor_is_verified: tutor.tutor.is_verified,
+            enrolled_students: 0,
+        }))
     }
 
-    pub async fn get_all_courses(pool: &DbPool) -> AppResult<Vec<CourseResponse>> {
-        let courses = sqlx::query_as::<_, Course>(
-            r#"
-            SELECT c.id, c.title, c.description, c.tutor_id, c.price, c.duration_minutes, 
-                   c.category, c.difficulty_level, c.is_active, c.created_at, c.updated_at
-            FROM courses c
-            WHERE c.is_active = true
-            ORDER BY c.created_at DESC
-            "#
-        )
-        .fetch_all(pool)
-        .await?;
-
-        let mut course_responses = Vec::new();
-        for course in courses {
-            let mut course_response = CourseResponse::from(course);
-            
-            // Get tutor name
-            if let Ok(tutor_name) = Self::get_tutor_name(pool, course_response.tutor_id).await {
-                course_response.tutor_name = Some(tutor_name);
-            }
-            
-            course_responses.push(course_response);
-        }
-
-        Ok(course_responses)
+    pub async fn get_all_courses<R: CourseRepository>(repo: &R) -> AppResult<Vec<CourseResponse>> {
+        let courses = repo.list_active_courses().await?;
+        Ok(courses.into_iter().map(CourseResponse::from).collect())
     }
 
-    pub async fn get_course_by_id(pool: &DbPool, course_id: Uuid) -> AppResult<CourseResponse> {
-        let course = sqlx::query_as::<_, Course>(
-            "SELECT id, title, description, tutor_id, price, duration_minutes, category, difficulty_level, is_active, created_at, updated_at FROM courses WHERE id = $1"
-        )
-        .bind(course_id)
-        .fetch_optional(pool)
-        .await?
-        .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?;
-
-        let mut course_response = CourseResponse::from(course);
-        
-        // Get tutor name
-        if let Ok(tutor_name) = Self::get_tutor_name(pool, course_response.tutor_id).await {
-            course_response.tutor_name = Some(tutor_name);
-        }
+    // Courses taken down by an admin are hidden; their tutor still sees them
+    // among their own courses
+    pub async fn get_course_by_id<R: CourseRepository>(repo: &R, course_id: Uuid) -> AppResult<CourseResponse> {
+        let course = repo.find_course_by_id(course_id)
+            .await?
+            .filter(|course| course.course.taken_down_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?;
 
-        Ok(course_response)
+        Ok(CourseResponse::from(course))
     }
 
-    pub async fn update_course(
-        pool: &DbPool,
+    pub async fn update_course<R: TutorRepository + CourseRepository + EnrollmentRepository + AuditRepository>(
+        repo: &R,
         course_id: Uuid,
         tutor_id: Uuid,
         request: UpdateCourseRequest,
+        if_match: &Precondition,
+        context: &RequestContext,
     ) -> AppResult<CourseResponse> {
         // Validate input
         request.validate()
             .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
 
         // Check if course exists and belongs to tutor
-        let existing_course = sqlx::query(
-            "SELECT c.id FROM courses c JOIN tutors t ON c.tutor_id = t.id WHERE c.id = $1 AND t.user_id = $2"
-        )
-        .bind(course_id)
-        .bind(tutor_id)
-        .fetch_optional(pool)
-        .await?;
-
-        if existing_course.is_none() {
-            return Err(AppError::NotFound("Course not found or access denied".to_string()));
-        }
-
-        // Build update query dynamically
-        let mut query_parts = Vec::new();
-        let mut bind_count = 1;
-
-        if request.title.is_some() {
-            query_parts.push(format!("title = ${}", bind_count));
-            bind_count += 1;
-        }
-        if request.description.is_some() {
-            query_parts.push(format!("description = ${}", bind_count));
-            bind_count += 1;
-        }
-        if request.price.is_some() {
-            query_parts.push(format!("price = ${}", bind_count));
-            bind_count += 1;
-        }
-        if request.duration_minutes.is_some() {
-            query_parts.push(format!("duration_minutes = ${}", bind_count));
-            bind_count += 1;
-        }
-        if request.category.is_some() {
-            query_parts.push(format!("category = ${}", bind_count));
-            bind_count += 1;
+        let mut course = Self::find_owned_course(repo, course_id, tutor_id).await?;
+        if course.taken_down_at.is_some() {
+            return Err(AppError::Conflict("Course was taken down by an administrator".to_string()));
         }
-        if request.difficulty_level.is_some() {
-            query_parts.push(format!("difficulty_level = ${}", bind_count));
-            bind_count += 1;
-        }
-        if request.is_active.is_some() {
-            query_parts.push(format!("is_active = ${}", bind_count));
-            bind_count += 1;
+        if !if_match.matches(course.updated_at) {
+            return Err(AppError::PreconditionFailed("Course has changed since it was read".to_string()));
         }
 
-        if query_parts.is_empty() {
+        if request.title.is_none()
+            && request.description.is_none()
+            && request.price.is_none()
+            && request.duration_minutes.is_none()
+            && request.category.is_none()
+            && request.difficulty_level.is_none()
+            && request.is_active.is_none()
+            && request.max_students.is_none()
+        {
             return Err(AppError::BadRequest("No fields to update".to_string()));
         }
+        let before = course.clone();
 
-        query_parts.push(format!("updated_at = ${}", bind_count));
-        let query = format!(
-            "UPDATE courses SET {} WHERE id = ${}",
-            query_parts.join(", "),
-            bind_count + 1
-        );
-
-        let mut query_builder = sqlx::query(&query);
-
-        // Bind values in the same order as the query parts
-        if let Some(title) = &request.title {
-            query_builder = query_builder.bind(title);
+        if let Some(title) = request.title {
+            course.title = title;
         }
-        if let Some(description) = &request.description {
-            query_builder = query_builder.bind(description);
+        if let Some(description) = request.description {
+            course.description = description;
         }
         if let Some(price) = request.price {
-            query_builder = query_builder.bind(price);
+            course.price = price;
         }
         if let Some(duration_minutes) = request.duration_minutes {
-            query_builder = query_builder.bind(duration_minutes);
+            course.duration_minutes = duration_minutes;
         }
-        if let Some(category) = &request.category {
-            query_builder = query_builder.bind(category);
+        if let Some(category) = request.category {
+            course.category = category;
         }
-        if let Some(difficulty_level) = &request.difficulty_level {
-            query_builder = query_builder.bind(difficulty_level);
+        if let Some(difficulty_level) = request.difficulty_level {
+            course.difficulty_level = difficulty_level;
         }
         if let Some(is_active) = request.is_active {
-            query_builder = query_builder.bind(is_active);
+            course.is_active = is_active;
         }
+        // The validator cannot see inside the nested option
+        let capacity_changed = match request.max_students {
+            Some(Some(max_students)) if !(1..=10000).contains(&max_students) => {
+                return Err(AppError::Validation("Validation failed: max_students must be between 1 and 10000".to_string()));
+            }
+            Some(max_students) => {
+                let changed = max_students != course.max_students;
+                course.max_students = max_students;
+                changed
+            }
+            None => false,
+        };
+        course.updated_at = Utc::now();
 
-        query_builder = query_builder.bind(Utc::now()).bind(course_id);
+        // Someone else may have saved between the check above and this write
+        if !repo.update_course(&course, before.updated_at).await? {
+            return Err(AppError::PreconditionFailed("Course has changed since it was read".to_string()));
+        }
+        let changes = audit::changes(Some(&before), Some(&course));
+        let target = AuditTarget::Course(course_id);
+        AuditService::record(repo, context, Some(tutor_id), "course.updated", target, changes, None).await?;
 
-        query_builder.execute(pool).await?;
+        // Raising or removing the limit frees seats for the waitlist
+        if capacity_changed {
+            repo.promote_waitlisted(course_id, course.updated_at).await?;
+        }
 
         // Return updated course
-        Self::get_course_by_id(pool, course_id).await
+        Self::get_course_by_id(repo, course_id).await
     }
 
-    pub async fn delete_course(
-        pool: &DbPool,
+    // Soft delete: the course disappears from every listing but its tutor
+    // can restore it until the retention window passes
+    pub async fn delete_course<R: TutorRepository + CourseRepository + EnrollmentRepository + AuditRepository>(
+        repo: &R,
         course_id: Uuid,
         tutor_id: Uuid,
+        context: &RequestContext,
     ) -> AppResult<()> {
-        let result = sqlx::query(
-            "DELETE FROM courses WHERE id = $1 AND tutor_id = (SELECT id FROM tutors WHERE user_id = $2)"
-        )
-        .bind(course_id)
-        .bind(tutor_id)
-        .execute(pool)
-        .await?;
+        let mut course = Self::find_owned_course(repo, course_id, tutor_id).await?;
 
-        if result.rows_affected() == 0 {
+        if repo.count_active_enrollments(course_id).await? > 0 {
+            return Err(AppError::Conflict(
+                "Course has enrolled students; deactivate it instead".to_string(),
+            ));
+        }
+        let before = course.clone();
+        let deleted_at = Utc::now();
+        course.deleted_at = Some(deleted_at);
+
+        // The repository repeats the enrollment check atomically with the delete
+        if !repo.delete_course(course_id, deleted_at).await? {
+            if repo.count_active_enrollments(course_id).await? > 0 {
+                return Err(AppError::Conflict(
+                    "Course has enrolled students; deactivate it instead".to_string(),
+                ));
+            }
             return Err(AppError::NotFound("Course not found or access denied".to_string()));
         }
+        let changes = audit::changes(Some(&before), Some(&course));
+        let target = AuditTarget::Course(course_id);
+        AuditService::record(repo, context, Some(tutor_id), "course.deleted", target, changes, None).await?;
 
         Ok(())
     }
 
-    pub async fn get_courses_by_tutor(pool: &DbPool, tutor_id: Uuid) -> AppResult<Vec<CourseResponse>> {
-        let courses = sqlx::query_as::<_, Course>(
-            r#"
-            SELECT c.id, c.title, c.description, c.tutor_id, c.price, c.duration_minutes, 
-                   c.category, c.difficulty_level, c.is_active, c.created_at, c.updated_at
-            FROM courses c
-            JOIN tutors t ON c.tutor_id = t.id
-            WHERE t.user_id = $1
-            ORDER BY c.created_at DESC
-            "#
-        )
-        .bind(tutor_id)
-        .fetch_all(pool)
-        .await?;
-
-        let mut course_responses = Vec::new();
-        for course in courses {
-            let mut course_response = CourseResponse::from(course);
-            
-            // Get tutor name
-            if let Ok(tutor_name) = Self::get_tutor_name(pool, course_response.tutor_id).await {
-                course_response.tutor_name = Some(tutor_name);
+    // The tutor's deleted courses that can still be restored
+    pub async fn list_deleted_courses<R: TutorRepository + CourseRepository>(
+        repo: &R,
+        tutor_id: Uuid,
+    ) -> AppResult<Vec<DeletedCourseResponse>> {
+        let tutor = repo.find_tutor_by_user_id(tutor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor profile not found".to_string()))?;
+        let now = Utc::now();
+
+        Ok(repo.list_deleted_courses(tutor.tutor.id)
+            .await?
+            .into_iter()
+            .filter_map(|course| {
+                let deleted_at = course.deleted_at.filter(|deleted_at| RetentionService::is_restorable(*deleted_at, now))?;
+                Some(DeletedCourseResponse {
+                    id: course.id,
+                    title: course.title,
+                    deleted_at,
+                    restorable_until: RetentionService::restorable_until(deleted_at),
+                })
+            })
+            .collect())
+    }
+
+    pub async fn restore_course<R: TutorRepository + CourseRepository + AuditRepository>(
+        repo: &R,
+        course_id: Uuid,
+        tutor_id: Uuid,
+        context: &RequestContext,
+    ) -> AppResult<CourseResponse> {
+        let not_found = || AppError::NotFound("Deleted course not found or access denied".to_string());
+
+        let tutor = repo.find_tutor_by_user_id(tutor_id).await?.ok_or_else(not_found)?;
+        let mut course = repo.list_deleted_courses(tutor.tutor.id)
+            .await?
+            .into_iter()
+            .find(|course| course.id == course_id)
+            .ok_or_else(not_found)?;
+
+        let Some(deleted_at) = course.deleted_at else {
+            return Err(not_found());
+        };
+        if !RetentionService::is_restorable(deleted_at, Utc::now()) {
+            return Err(AppError::Conflict("Course can no longer be restored".to_string()));
+        }
+        let before = course.clone();
+        course.deleted_at = None;
+
+        if !repo.restore_course(course_id).await? {
+            return Err(not_found());
+        }
+        let changes = audit::changes(Some(&before), Some(&course));
+        let target = AuditTarget::Course(course_id);
+        AuditService::record(repo, context, Some(tutor_id), "course.restored", target, changes, None).await?;
+
+        let course = repo.find_course_by_id(course_id).await?.ok_or_else(not_found)?;
+        Ok(CourseResponse::from(course))
+    }
+
+    pub async fn get_courses_by_tutor<R: CourseRepository>(repo: &R, tutor_id: Uuid) -> AppResult<Vec<CourseResponse>> {
+        let courses = repo.list_courses_by_tutor_user(tutor_id).await?;
+        Ok(courses.into_iter().map(CourseResponse::from).collect())
+    }
+
+    // Loads a course, treating courses owned by another tutor as missing
+    pub(crate) async fn find_owned_course<R: TutorRepository + CourseRepository>(
+        repo: &R,
+        course_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Course> {
+        let not_found = || AppError::NotFound("Course not found or access denied".to_string());
+
+        let tutor = repo.find_tutor_by_user_id(user_id).await?.ok_or_else(not_found)?;
+        let course = repo.find_course_by_id(course_id).await?.ok_or_else(not_found)?;
+
+        if course.course.tutor_id != tutor.tutor.id {
+            return Err(not_found());
+        }
+
+        Ok(course.course)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::DbPool;
+    use crate::models::{DifficultyLevel, UserRole};
+    use crate::repositories::{InMemoryRepository, PgRepository};
+    use crate::services::retention::RETENTION_DAYS;
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use crate::services::EnrollmentService;
+    use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+    use sqlx::ConnectOptions;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Instant;
+
+    static QUERY_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    // sqlx logs every executed statement under the `sqlx::query` target
+    struct QueryCounter;
+
+    impl log::Log for QueryCounter {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.target() == "sqlx::query"
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                QUERY_COUNT.fetch_add(1, Ordering::SeqCst);
             }
-            
-            course_responses.push(course_response);
         }
 
-        Ok(course_responses)
+        fn flush(&self) {}
     }
 
-    async fn get_tutor_name(pool: &DbPool, tutor_id: Uuid) -> AppResult<String> {
-        let row = sqlx::query(
-            "SELECT u.first_name, u.last_name FROM users u JOIN tutors t ON u.id = t.user_id WHERE t.id = $1"
+    static QUERY_COUNTER: QueryCounter = QueryCounter;
+
+    async fn seed_tutor_with_courses(pool: &DbPool, course_count: i32) -> Uuid {
+        let user_id = Uuid::new_v4();
+        let tutor_id = Uuid::new_v4();
+
+        sqlx::query(
+            "INSERT INTO users (id, email, password_hash, first_name, last_name, role) VALUES ($1, $2, 'x', 'Bench', 'Tutor', 'tutor')"
+        )
+        .bind(user_id)
+        .bind(format!("bench-{}@ezytutor.test", user_id))
+        .execute(pool)
+        .await
+        .unwrap();
+
+        sqlx::query("INSERT INTO tutors (id, user_id, bio, hourly_rate) VALUES ($1, $2, 'Benchmark tutor', 2500)")
+            .bind(tutor_id)
+            .bind(user_id)
+            .execute(pool)
+            .await
+            .unwrap();
+
+        sqlx::query(
+            r#"
+            INSERT INTO courses (title, description, tutor_id, price, duration_minutes, category, difficulty_level)
+            SELECT 'Course ' || n, 'Benchmark course', $1, 1000, 60, 'Benchmark', 'beginner'
+            FROM generate_series(1, $2) AS n
+            "#
         )
         .bind(tutor_id)
-        .fetch_optional(pool)
-        .await?;
-
-        if let Some(row) = row {
-            let first_name: String = row.get("first_name");
-            let last_name: String = row.get("last_name");
-            Ok(format!("{} {}", first_name, last_name))
-        } else {
-            Err(AppError::NotFound("Tutor not found".to_string()))
+        .bind(course_count)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    async fn count_queries<F, Fut>(run: F) -> (usize, usize, u128)
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = AppResult<Vec<CourseResponse>>>,
+    {
+        let started = Instant::now();
+        QUERY_COUNT.store(0, Ordering::SeqCst);
+        let courses = run().await.unwrap();
+        let queries = QUERY_COUNT.load(Ordering::SeqCst);
+        (queries, courses.len(), started.elapsed().as_millis())
+    }
+
+    // Query-count benchmark for the course listings. It needs a live database:
+    // DATABASE_URL=postgresql://localhost/ezytutor cargo test --release -- --ignored --nocapture
+    #[actix_rt::test]
+    #[ignore = "requires DATABASE_URL pointing at a disposable Postgres database"]
+    async fn course_listing_query_count_is_constant() {
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        log::set_logger(&QUERY_COUNTER).unwrap();
+        log::set_max_level(log::LevelFilter::Info);
+
+        let options = PgConnectOptions::from_str(&database_url)
+            .unwrap()
+            .log_statements(log::LevelFilter::Info);
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .unwrap();
+        crate::database::run_migrations(&pool).await.unwrap();
+        let repo = PgRepository::new(pool.clone());
+
+        let small_tutor = seed_tutor_with_courses(&pool, 10).await;
+        let large_tutor = seed_tutor_with_courses(&pool, 5000).await;
+
+        // Warm up so statement preparation and enum type lookups are not counted
+        CourseService::get_courses_by_tutor(&repo, small_tutor).await.unwrap();
+        CourseService::get_all_courses(&repo).await.unwrap();
+
+        let (small_queries, small_rows, small_ms) =
+            count_queries(|| CourseService::get_courses_by_tutor(&repo, small_tutor)).await;
+        let (large_queries, large_rows, large_ms) =
+            count_queries(|| CourseService::get_courses_by_tutor(&repo, large_tutor)).await;
+        let (all_queries, all_rows, all_ms) =
+            count_queries(|| CourseService::get_all_courses(&repo)).await;
+
+        println!("get_courses_by_tutor: {} courses -> {} queries in {}ms", small_rows, small_queries, small_ms);
+        println!("get_courses_by_tutor: {} courses -> {} queries in {}ms", large_rows, large_queries, large_ms);
+        println!("get_all_courses: {} courses -> {} queries in {}ms", all_rows, all_queries, all_ms);
+
+        sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+            .bind(vec![small_tutor, large_tutor])
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(large_rows, 5000);
+        assert_eq!(small_queries, 1);
+        assert_eq!(large_queries, 1);
+        assert_eq!(all_queries, 1);
+    }
+
+    fn course_request(title: &str) -> CreateCourseRequest {
+        CreateCourseRequest {
+            title: title.to_string(),
+            description: "Limits, derivatives and integrals".to_string(),
+            price: 4900,
+            duration_minutes: 60,
+            category: "Mathematics".to_string(),
+            difficulty_level: DifficultyLevel::Beginner,
+            max_students: None,
+        }
+    }
+
+    fn empty_update() -> UpdateCourseRequest {
+        UpdateCourseRequest {
+            title: None,
+            description: None,
+            price: None,
+            duration_minutes: None,
+            category: None,
+            difficulty_level: None,
+            is_active: None,
+            max_students: None,
         }
     }
+
+    #[actix_rt::test]
+    async fn create_course_requires_tutor_profile() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        let error = CourseService::create_course(&repo, student.id, course_request("Calculus"), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+    }
+
+    #[actix_rt::test]
+    async fn create_course_validates_input() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (user, _) = insert_tutor(&repo, &["Math"]).await;
+        let mut request = course_request("");
+        request.duration_minutes = 5;
+
+        let error = CourseService::create_course(&repo, user.id, request, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+    }
+
+    #[actix_rt::test]
+    async fn created_course_embeds_tutor_summary() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (user, tutor) = insert_tutor(&repo, &["Math"]).await;
+
+        let course = CourseService::create_course(&repo, user.id, course_request("Calculus"), &context).await.unwrap();
+        assert_eq!(course.tutor_id, tutor.id);
+        assert_eq!(course.tutor.name, format!("{} {}", user.first_name, user.last_name));
+        assert!(!course.tutor.is_verified);
+
+        let fetched = CourseService::get_course_by_id(&repo, course.id).await.unwrap();
+        assert_eq!(fetched.title, "Calculus");
+    }
+
+    #[actix_rt::test]
+    async fn listings_hide_inactive_courses_from_public_but_not_owner() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (user, _) = insert_tutor(&repo, &["Math"]).await;
+        let course = CourseService::create_course(&repo, user.id, course_request("Algebra"), &context).await.unwrap();
+        CourseService::create_course(&repo, user.id, course_request("Geometry"), &context).await.unwrap();
+
+        let mut update = empty_update();
+        update.is_active = Some(false);
+        CourseService::update_course(&repo, course.id, user.id, update, &Precondition::Any, &context).await.unwrap();
+
+        let public = CourseService::get_all_courses(&repo).await.unwrap();
+        assert_eq!(public.len(), 1);
+        assert_eq!(public[0].title, "Geometry");
+
+        let own = CourseService::get_courses_by_tutor(&repo, user.id).await.unwrap();
+        assert_eq!(own.len(), 2);
+    }
+
+    #[actix_rt::test]
+    async fn update_course_applies_only_provided_fields() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (user, _) = insert_tutor(&repo, &["Math"]).await;
+        let course = CourseService::create_course(&repo, user.id, course_request("Calculus"), &context).await.unwrap();
+
+        let error = CourseService::update_course(&repo, course.id, user.id, empty_update(), &Precondition::Any, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+
+        let mut update = empty_update();
+        update.price = Some(9900);
+        let updated = CourseService::update_course(&repo, course.id, user.id, update, &Precondition::Any, &context).await.unwrap();
+        assert_eq!(updated.price, 9900);
+        assert_eq!(updated.title, "Calculus");
+    }
+
+    #[actix_rt::test]
+    async fn other_tutors_cannot_modify_course() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (owner, _) = insert_tutor(&repo, &["Math"]).await;
+        let (intruder, _) = insert_tutor(&repo, &["Physics"]).await;
+        let course = CourseService::create_course(&repo, owner.id, course_request("Calculus"), &context).await.unwrap();
+
+        let mut update = empty_update();
+        update.title = Some("Hijacked".to_string());
+        let error = CourseService::update_course(&repo, course.id, intruder.id, update, &Precondition::Any, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        let error = CourseService::delete_course(&repo, course.id, intruder.id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        CourseService::delete_course(&repo, course.id, owner.id, &context).await.unwrap();
+        let error = CourseService::get_course_by_id(&repo, course.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+    }
+
+    #[actix_rt::test]
+    async fn deleted_courses_can_be_restored_until_they_are_purged() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (owner, tutor) = insert_tutor(&repo, &["Math"]).await;
+        let (intruder, _) = insert_tutor(&repo, &["Physics"]).await;
+        let algebra = CourseService::create_course(&repo, owner.id, course_request("Algebra"), &context).await.unwrap();
+        let geometry = CourseService::create_course(&repo, owner.id, course_request("Geometry"), &context).await.unwrap();
+
+        CourseService::delete_course(&repo, algebra.id, owner.id, &context).await.unwrap();
+        let error = CourseService::get_course_by_id(&repo, algebra.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        assert_eq!(CourseService::get_all_courses(&repo).await.unwrap().len(), 1);
+        assert_eq!(CourseService::get_courses_by_tutor(&repo, owner.id).await.unwrap().len(), 1);
+
+        let deleted = CourseService::list_deleted_courses(&repo, owner.id).await.unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].restorable_until, RetentionService::restorable_until(deleted[0].deleted_at));
+
+        let error = CourseService::restore_course(&repo, algebra.id, intruder.id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        let restored = CourseService::restore_course(&repo, algebra.id, owner.id, &context).await.unwrap();
+        assert_eq!(restored.title, "Algebra");
+        assert_eq!(CourseService::get_courses_by_tutor(&repo, owner.id).await.unwrap().len(), 2);
+
+        // Past the retention window a course can only be purged
+        let long_ago = Utc::now() - chrono::Duration::days(RETENTION_DAYS + 1);
+        repo.delete_course(geometry.id, long_ago).await.unwrap();
+        assert!(CourseService::list_deleted_courses(&repo, owner.id).await.unwrap().is_empty());
+        let error = CourseService::restore_course(&repo, geometry.id, owner.id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        let report = RetentionService::purge_expired(&repo, Utc::now()).await.unwrap();
+        assert_eq!(report.course_ids, vec![geometry.id]);
+        assert!(repo.list_deleted_courses(tutor.id).await.unwrap().is_empty());
+        let error = CourseService::restore_course(&repo, geometry.id, owner.id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+    }
+
+    #[actix_rt::test]
+    async fn deletes_and_enrollments_do_not_interleave() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (owner, _) = insert_tutor(&repo, &["Math"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        let algebra = CourseService::create_course(&repo, owner.id, course_request("Algebra"), &context).await.unwrap();
+        let geometry = CourseService::create_course(&repo, owner.id, course_request("Geometry"), &context).await.unwrap();
+
+        // An enrollment that lands after the service's check still stops the delete
+        repo.enroll_student(algebra.id, student.id, Utc::now()).await.unwrap();
+        assert!(!repo.delete_course(algebra.id, Utc::now()).await.unwrap());
+        let error = CourseService::delete_course(&repo, algebra.id, owner.id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        // and a delete that lands after the enrollment's check stops the enrollment
+        assert!(repo.delete_course(geometry.id, Utc::now()).await.unwrap());
+        let error = repo.enroll_student(geometry.id, student.id, Utc::now()).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        let error = EnrollmentService::enroll(&repo, geometry.id, student.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        assert_eq!(repo.count_active_enrollments(geometry.id).await.unwrap(), 0);
+    }
+
+    #[actix_rt::test]
+    async fn updates_must_name_the_current_version() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (user, _) = insert_tutor(&repo, &["Math"]).await;
+        let course = CourseService::create_course(&repo, user.id, course_request("Calculus"), &context).await.unwrap();
+        let loaded = Precondition::Tags(vec![Precondition::entity_tag(course.updated_at)]);
+
+        let stale = Precondition::from_header(&Precondition::entity_tag(course.updated_at - chrono::Duration::seconds(1)));
+        let mut update = empty_update();
+        update.price = Some(5900);
+        let error = CourseService::update_course(&repo, course.id, user.id, update.clone(), &stale, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::PreconditionFailed(_)));
+
+        // If-Match compares strongly, so a weak tag never matches
+        let weak = Precondition::from_header(&format!("W/{}", Precondition::entity_tag(course.updated_at)));
+        let error = CourseService::update_course(&repo, course.id, user.id, update.clone(), &weak, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::PreconditionFailed(_)));
+
+        let updated = CourseService::update_course(&repo, course.id, user.id, update, &loaded, &context).await.unwrap();
+        assert_eq!(updated.price, 5900);
+
+        // A write racing the check only lands on the version it was checked against
+        let mut stored = repo.find_course_by_id(course.id).await.unwrap().unwrap().course;
+        stored.price = 100;
+        assert!(!repo.update_course(&stored, course.updated_at).await.unwrap());
+        assert!(repo.update_course(&stored, updated.updated_at).await.unwrap());
+    }
 }
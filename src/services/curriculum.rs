@@ -0,0 +1,385 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    CourseModule, CourseOutlineResponse, CreateLessonRequest, EnrollmentStatus, Lesson, LessonResponse,
+    LessonSummary, ModuleOutline, ModuleRequest, ModuleResponse, ReorderCurriculumRequest,
+    UpdateLessonRequest,
+};
+use crate::repositories::{CourseRepository, CurriculumRepository, EnrollmentRepository, TutorRepository};
+use crate::services::CourseService;
+use chrono::Utc;
+use sqlx::types::Json;
+use uuid::Uuid;
+use validator::Validate;
+
+// Courses are split into ordered modules of ordered lessons. The outline is
+// public; lesson content is for the course's tutor and its active students.
+pub struct CurriculumService;
+
+impl CurriculumService {
+    pub async fn get_outline<R: CourseRepository + CurriculumRepository>(
+        repo: &R,
+        course_id: Uuid,
+    ) -> AppResult<CourseOutlineResponse> {
+        let course = repo.find_course_by_id(course_id)
+            .await?
+            .filter(|course| course.course.taken_down_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?
+            .course;
+
+        let modules = repo.list_modules(course_id).await?;
+        let lessons = repo.list_lessons(course_id).await?;
+
+        let modules: Vec<ModuleOutline> = modules.into_iter()
+            .map(|module| {
+                let lessons: Vec<LessonSummary> = lessons.iter()
+                    .filter(|lesson| lesson.module_id == module.id)
+                    .map(|lesson| LessonSummary {
+                        id: lesson.id,
+                        title: lesson.title.clone(),
+                        position: lesson.position,
+                        estimated_minutes: lesson.estimated_minutes,
+                    })
+                    .collect();
+                ModuleOutline {
+                    id: module.id,
+                    title: module.title,
+                    position: module.position,
+                    estimated_minutes: lessons.iter().map(|lesson| lesson.estimated_minutes).sum(),
+                    lessons,
+                }
+            })
+            .collect();
+
+        Ok(CourseOutlineResponse {
+            course_id: course.id,
+            title: course.title,
+            lesson_count: lessons.len(),
+            estimated_minutes: modules.iter().map(|module| module.estimated_minutes).sum(),
+            modules,
+        })
+    }
+
+    pub async fn get_lesson<R>(repo: &R, lesson_id: Uuid, user_id: Uuid) -> AppResult<LessonResponse>
+    where
+        R: TutorRepository + CourseRepository + EnrollmentRepository + CurriculumRepository,
+    {
+        let lesson = repo.find_lesson(lesson_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Lesson not found".to_string()))?;
+
+        match CourseService::find_owned_course(repo, lesson.course_id, user_id).await {
+            Ok(_) => return Ok(LessonResponse::from(lesson)),
+            Err(AppError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        let enrolled = repo.find_enrollment(lesson.course_id, user_id)
+            .await?
+            .is_some_and(|row| row.enrollment.status == EnrollmentStatus::Active);
+        if !enrolled {
+            return Err(AppError::Authorization("Enroll in this course to view its lessons".to_string()));
+        }
+
+        Ok(LessonResponse::from(lesson))
+    }
+
+    pub async fn add_module<R: TutorRepository + CourseRepository + CurriculumRepository>(
+        repo: &R,
+        course_id: Uuid,
+        tutor_id: Uuid,
+        request: ModuleRequest,
+    ) -> AppResult<ModuleResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        CourseService::find_owned_course(repo, course_id, tutor_id).await?;
+
+        let now = Utc::now();
+        let module = repo.append_module(&CourseModule {
+            id: Uuid::new_v4(),
+            course_id,
+            title: request.title,
+            position: 0,
+            created_at: now,
+            updated_at: now,
+        }).await?;
+
+        Ok(ModuleResponse::from(module))
+    }
+
+    pub async fn rename_module<R: TutorRepository + CourseRepository + CurriculumRepository>(
+        repo: &R,
+        module_id: Uuid,
+        tutor_id: Uuid,
+        request: ModuleRequest,
+    ) -> AppResult<ModuleResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let mut module = Self::find_owned_module(repo, module_id, tutor_id).await?;
+        module.title = request.title;
+        module.updated_at = Utc::now();
+        repo.update_module(&module).await?;
+
+        Ok(ModuleResponse::from(module))
+    }
+
+    // Deletes the module together with its lessons
+    pub async fn delete_module<R: TutorRepository + CourseRepository + CurriculumRepository>(
+        repo: &R,
+        module_id: Uuid,
+        tutor_id: Uuid,
+    ) -> AppResult<()> {
+        Self::find_owned_module(repo, module_id, tutor_id).await?;
+
+        if !repo.delete_module(module_id).await? {
+            return Err(AppError::NotFound("Module not found or access denied".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn add_lesson<R: TutorRepository + CourseRepository + CurriculumRepository>(
+        repo: &R,
+        module_id: Uuid,
+        tutor_id: Uuid,
+        request: CreateLessonRequest,
+    ) -> AppResult<LessonResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let module = Self::find_owned_module(repo, module_id, tutor_id).await?;
+
+        let now = Utc::now();
+        let lesson = repo.append_lesson(&Lesson {
+            id: Uuid::new_v4(),
+            course_id: module.course_id,
+            module_id,
+            title: request.title,
+            content: request.content,
+            estimated_minutes: request.estimated_minutes,
+            resources: Json(request.resources),
+            position: 0,
+            created_at: now,
+            updated_at: now,
+        }).await?;
+
+        Ok(LessonResponse::from(lesson))
+    }
+
+    pub async fn update_lesson<R: TutorRepository + CourseRepository + CurriculumRepository>(
+        repo: &R,
+        lesson_id: Uuid,
+        tutor_id: Uuid,
+        request: UpdateLessonRequest,
+    ) -> AppResult<LessonResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let mut lesson = Self::find_owned_lesson(repo, lesson_id, tutor_id).await?;
+
+        if request.title.is_none()
+            && request.content.is_none()
+            && request.estimated_minutes.is_none()
+            && request.resources.is_none()
+        {
+            return Err(AppError::BadRequest("No fields to update".to_string()));
+        }
+
+        if let Some(title) = request.title {
+            lesson.title = title;
+        }
+        if let Some(content) = request.content {
+            lesson.content = content;
+        }
+        if let Some(estimated_minutes) = request.estimated_minutes {
+            lesson.estimated_minutes = estimated_minutes;
+        }
+        if let Some(resources) = request.resources {
+            lesson.resources = Json(resources);
+        }
+        lesson.updated_at = Utc::now();
+
+        repo.update_lesson(&lesson).await?;
+
+        Ok(LessonResponse::from(lesson))
+    }
+
+    pub async fn delete_lesson<R: TutorRepository + CourseRepository + CurriculumRepository>(
+        repo: &R,
+        lesson_id: Uuid,
+        tutor_id: Uuid,
+    ) -> AppResult<()> {
+        Self::find_owned_lesson(repo, lesson_id, tutor_id).await?;
+
+        if !repo.delete_lesson(lesson_id).await? {
+            return Err(AppError::NotFound("Lesson not found or access denied".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Replaces the whole order at once, so clients never see a half-moved curriculum
+    pub async fn reorder<R: TutorRepository + CourseRepository + CurriculumRepository>(
+        repo: &R,
+        course_id: Uuid,
+        tutor_id: Uuid,
+        request: ReorderCurriculumRequest,
+    ) -> AppResult<CourseOutlineResponse> {
+        CourseService::find_owned_course(repo, course_id, tutor_id).await?;
+
+        repo.reorder_curriculum(course_id, &request.modules).await?;
+
+        Self::get_outline(repo, course_id).await
+    }
+
+    // Loads a module, treating modules of another tutor's course as missing
+    async fn find_owned_module<R: TutorRepository + CourseRepository + CurriculumRepository>(
+        repo: &R,
+        module_id: Uuid,
+        tutor_id: Uuid,
+    ) -> AppResult<CourseModule> {
+        let not_found = || AppError::NotFound("Module not found or access denied".to_string());
+
+        let module = repo.find_module(module_id).await?.ok_or_else(not_found)?;
+        CourseService::find_owned_course(repo, module.course_id, tutor_id)
+            .await
+            .map_err(|e| match e {
+                AppError::NotFound(_) => not_found(),
+                e => e,
+            })?;
+
+        Ok(module)
+    }
+
+    pub(crate) async fn find_owned_lesson<R: TutorRepository + CourseRepository + CurriculumRepository>(
+        repo: &R,
+        lesson_id: Uuid,
+        tutor_id: Uuid,
+    ) -> AppResult<Lesson> {
+        let not_found = || AppError::NotFound("Lesson not found or access denied".to_string());
+
+        let lesson = repo.find_lesson(lesson_id).await?.ok_or_else(not_found)?;
+        CourseService::find_owned_course(repo, lesson.course_id, tutor_id)
+            .await
+            .map_err(|e| match e {
+                AppError::NotFound(_) => not_found(),
+                e => e,
+            })?;
+
+        Ok(lesson)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateCourseRequest, DifficultyLevel, LessonResource, ModuleOrder, RequestContext, UserRole};
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use crate::services::EnrollmentService;
+
+    async fn course(repo: &InMemoryRepository) -> (Uuid, Uuid) {
+        let (user, _) = insert_tutor(repo, &["History"]).await;
+        let course = CourseService::create_course(repo, user.id, CreateCourseRequest {
+            title: "Modern Europe".to_string(),
+            description: "From 1789 to 1989".to_string(),
+            price: 2500,
+            duration_minutes: 60,
+            category: "History".to_string(),
+            difficulty_level: DifficultyLevel::Intermediate,
+            max_students: None,
+        }, &RequestContext::default()).await.unwrap();
+        (user.id, course.id)
+    }
+
+    fn module(title: &str) -> ModuleRequest {
+        ModuleRequest { title: title.to_string() }
+    }
+
+    fn lesson(title: &str, minutes: i32) -> CreateLessonRequest {
+        CreateLessonRequest {
+            title: title.to_string(),
+            content: format!("# {}", title),
+            estimated_minutes: minutes,
+            resources: vec![LessonResource {
+                title: "Reading list".to_string(),
+                url: "https://example.com/reading".to_string(),
+            }],
+        }
+    }
+
+    #[actix_rt::test]
+    async fn outline_follows_the_reordered_curriculum() {
+        let repo = InMemoryRepository::new();
+        let (tutor_id, course_id) = course(&repo).await;
+
+        let first = CurriculumService::add_module(&repo, course_id, tutor_id, module("Revolutions")).await.unwrap();
+        let second = CurriculumService::add_module(&repo, course_id, tutor_id, module("Empires")).await.unwrap();
+        assert_eq!((first.position, second.position), (1, 2));
+
+        let a = CurriculumService::add_lesson(&repo, first.id, tutor_id, lesson("1789", 30)).await.unwrap();
+        let b = CurriculumService::add_lesson(&repo, first.id, tutor_id, lesson("1848", 45)).await.unwrap();
+        let c = CurriculumService::add_lesson(&repo, second.id, tutor_id, lesson("Scramble for Africa", 20)).await.unwrap();
+        assert_eq!(b.position, 2);
+
+        // Swap the modules and move 1848 into the second one, ahead of its lesson
+        let outline = CurriculumService::reorder(&repo, course_id, tutor_id, ReorderCurriculumRequest {
+            modules: vec![
+                ModuleOrder { id: second.id, lessons: vec![b.id, c.id] },
+                ModuleOrder { id: first.id, lessons: vec![a.id] },
+            ],
+        }).await.unwrap();
+        assert_eq!(outline.lesson_count, 3);
+        assert_eq!(outline.estimated_minutes, 95);
+        assert_eq!(outline.modules[0].title, "Empires");
+        assert_eq!(outline.modules[0].estimated_minutes, 65);
+        let titles: Vec<&str> = outline.modules[0].lessons.iter().map(|l| l.title.as_str()).collect();
+        assert_eq!(titles, ["1848", "Scramble for Africa"]);
+
+        // Leaving out a lesson rejects the whole reorder
+        let error = CurriculumService::reorder(&repo, course_id, tutor_id, ReorderCurriculumRequest {
+            modules: vec![
+                ModuleOrder { id: first.id, lessons: vec![a.id] },
+                ModuleOrder { id: second.id, lessons: vec![c.id] },
+            ],
+        }).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+        assert_eq!(CurriculumService::get_outline(&repo, course_id).await.unwrap().modules[0].title, "Empires");
+
+        // Deleting a module closes the gap
+        CurriculumService::delete_module(&repo, second.id, tutor_id).await.unwrap();
+        let outline = CurriculumService::get_outline(&repo, course_id).await.unwrap();
+        assert_eq!(outline.modules.len(), 1);
+        assert_eq!(outline.modules[0].position, 1);
+        assert_eq!(outline.lesson_count, 1);
+    }
+
+    #[actix_rt::test]
+    async fn lesson_content_is_for_the_tutor_and_enrolled_students() {
+        let repo = InMemoryRepository::new();
+        let (tutor_id, course_id) = course(&repo).await;
+        let (other_tutor, _) = insert_tutor(&repo, &["Geography"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        let module = CurriculumService::add_module(&repo, course_id, tutor_id, module("Revolutions")).await.unwrap();
+        let error = CurriculumService::add_lesson(&repo, module.id, other_tutor.id, lesson("1789", 30)).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        let lesson = CurriculumService::add_lesson(&repo, module.id, tutor_id, lesson("1789", 30)).await.unwrap();
+
+        assert_eq!(CurriculumService::get_lesson(&repo, lesson.id, tutor_id).await.unwrap().content, "# 1789");
+        let error = CurriculumService::get_lesson(&repo, lesson.id, student.id).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+
+        EnrollmentService::enroll(&repo, course_id, student.id).await.unwrap();
+        let seen = CurriculumService::get_lesson(&repo, lesson.id, student.id).await.unwrap();
+        assert_eq!(seen.resources.len(), 1);
+
+        EnrollmentService::drop_enrollment(&repo, course_id, student.id).await.unwrap();
+        assert!(CurriculumService::get_lesson(&repo, lesson.id, student.id).await.is_err());
+    }
+}
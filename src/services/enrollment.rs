@@ -0,0 +1,190 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{EnrollmentListQuery, EnrollmentResponse, EnrollmentStatus, UserRole};
+use crate::repositories::{CourseRepository, EnrollmentRepository, TutorRepository, UserRepository};
+use crate::services::{AuthService, CourseService};
+use chrono::Utc;
+use uuid::Uuid;
+
+// Students join courses; once a course's seats are taken, later students
+// queue on a waitlist and are promoted in order as seats free up.
+pub struct EnrollmentService;
+
+impl EnrollmentService {
+    pub async fn enroll<R>(repo: &R, course_id: Uuid, student_id: Uuid) -> AppResult<EnrollmentResponse>
+    where
+        R: UserRepository + CourseRepository + EnrollmentRepository,
+    {
+        if !AuthService::verify_user_role(repo, student_id, UserRole::Student).await? {
+            return Err(AppError::Authorization("Only students can enroll in courses".to_string()));
+        }
+
+        repo.find_course_by_id(course_id)
+            .await?
+            .filter(|course| course.course.is_active)
+            .ok_or_else(|| AppError::NotFound("Course not found".to_string()))?;
+
+        if let Some(existing) = repo.find_enrollment(course_id, student_id).await? {
+            match existing.enrollment.status {
+                EnrollmentStatus::Active => {
+                    return Err(AppError::Conflict("Already enrolled in this course".to_string()));
+                }
+                EnrollmentStatus::Waitlisted => {
+                    return Err(AppError::Conflict("Already on the waitlist for this course".to_string()));
+                }
+                EnrollmentStatus::Dropped => {}
+            }
+        }
+
+        repo.enroll_student(course_id, student_id, Utc::now()).await?;
+        Self::find_current(repo, course_id, student_id).await
+    }
+
+    // Leaves the course or the waitlist; a freed seat goes to the next student in line
+    pub async fn drop_enrollment<R: EnrollmentRepository>(
+        repo: &R,
+        course_id: Uuid,
+        student_id: Uuid,
+    ) -> AppResult<()> {
+        let enrollment = Self::find_current(repo, course_id, student_id).await?;
+        repo.withdraw_enrollment(enrollment.id, Utc::now()).await?;
+        Ok(())
+    }
+
+    pub async fn list_student_enrollments<R: EnrollmentRepository>(
+        repo: &R,
+        student_id: Uuid,
+        query: EnrollmentListQuery,
+    ) -> AppResult<Vec<EnrollmentResponse>> {
+        let enrollments = repo.list_enrollments_for_student(student_id, query.status).await?;
+        Ok(enrollments.into_iter().map(EnrollmentResponse::from).collect())
+    }
+
+    // Roster and waitlist for the owning tutor
+    pub async fn list_course_enrollments<R>(
+        repo: &R,
+        course_id: Uuid,
+        tutor_id: Uuid,
+        query: EnrollmentListQuery,
+    ) -> AppResult<Vec<EnrollmentResponse>>
+    where
+        R: TutorRepository + CourseRepository + EnrollmentRepository,
+    {
+        CourseService::find_owned_course(repo, course_id, tutor_id).await?;
+
+        let enrollments = repo.list_enrollments_for_course(course_id, query.status).await?;
+        Ok(enrollments.into_iter().map(EnrollmentResponse::from).collect())
+    }
+
+    // Active or waitlisted enrollment; dropped ones count as missing
+    async fn find_current<R: EnrollmentRepository>(
+        repo: &R,
+        course_id: Uuid,
+        student_id: Uuid,
+    ) -> AppResult<EnrollmentResponse> {
+        repo.find_enrollment(course_id, student_id)
+            .await?
+            .filter(|row| row.enrollment.status != EnrollmentStatus::Dropped)
+            .map(EnrollmentResponse::from)
+            .ok_or_else(|| AppError::NotFound("Enrollment not found".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateCourseRequest, DifficultyLevel, Precondition, RequestContext, UpdateCourseRequest};
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::{insert_tutor, insert_user};
+
+    async fn course_with_capacity(repo: &InMemoryRepository, max_students: Option<i32>) -> (Uuid, Uuid) {
+        let (user, _) = insert_tutor(repo, &["Physics"]).await;
+        let course = CourseService::create_course(repo, user.id, CreateCourseRequest {
+            title: "Classical Mechanics".to_string(),
+            description: "Newton's laws and energy".to_string(),
+            price: 3500,
+            duration_minutes: 60,
+            category: "Science".to_string(),
+            difficulty_level: DifficultyLevel::Beginner,
+            max_students,
+        }, &RequestContext::default()).await.unwrap();
+        (user.id, course.id)
+    }
+
+    fn all() -> EnrollmentListQuery {
+        EnrollmentListQuery { status: None }
+    }
+
+    #[actix_rt::test]
+    async fn full_courses_waitlist_and_promote_in_order() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (tutor_id, course_id) = course_with_capacity(&repo, Some(1)).await;
+        let first = insert_user(&repo, UserRole::Student).await;
+        let second = insert_user(&repo, UserRole::Student).await;
+        let third = insert_user(&repo, UserRole::Student).await;
+
+        let seat = EnrollmentService::enroll(&repo, course_id, first.id).await.unwrap();
+        assert_eq!(seat.status, EnrollmentStatus::Active);
+        let queued = EnrollmentService::enroll(&repo, course_id, second.id).await.unwrap();
+        assert_eq!((queued.status, queued.waitlist_position), (EnrollmentStatus::Waitlisted, Some(1)));
+        let last = EnrollmentService::enroll(&repo, course_id, third.id).await.unwrap();
+        assert_eq!(last.waitlist_position, Some(2));
+
+        let error = EnrollmentService::enroll(&repo, course_id, second.id).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        EnrollmentService::drop_enrollment(&repo, course_id, first.id).await.unwrap();
+        let roster = EnrollmentService::list_course_enrollments(&repo, course_id, tutor_id, all()).await.unwrap();
+        let status_of = |id: Uuid| roster.iter().find(|e| e.student_id == id).map(|e| (e.status, e.waitlist_position));
+        assert_eq!(status_of(first.id), Some((EnrollmentStatus::Dropped, None)));
+        assert_eq!(status_of(second.id), Some((EnrollmentStatus::Active, None)));
+        assert_eq!(status_of(third.id), Some((EnrollmentStatus::Waitlisted, Some(1))));
+
+        // Removing the limit lets everyone in
+        let update = UpdateCourseRequest {
+            title: None,
+            description: None,
+            price: None,
+            duration_minutes: None,
+            category: None,
+            difficulty_level: None,
+            is_active: None,
+            max_students: Some(None),
+        };
+        let course = CourseService::update_course(&repo, course_id, tutor_id, update, &Precondition::Any, &context).await.unwrap();
+        assert_eq!(course.enrolled_students, 2);
+
+        // A dropped student rejoins at the back of the line
+        let rejoined = EnrollmentService::enroll(&repo, course_id, first.id).await.unwrap();
+        assert_eq!(rejoined.status, EnrollmentStatus::Active);
+        assert!(rejoined.requested_at > seat.requested_at);
+    }
+
+    #[actix_rt::test]
+    async fn only_students_enroll_and_only_owners_see_rosters() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (tutor_id, course_id) = course_with_capacity(&repo, None).await;
+        let (other_tutor, _) = insert_tutor(&repo, &["Chemistry"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        let error = EnrollmentService::enroll(&repo, course_id, tutor_id).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+
+        EnrollmentService::enroll(&repo, course_id, student.id).await.unwrap();
+        let error = EnrollmentService::list_course_enrollments(&repo, course_id, other_tutor.id, all()).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        let mine = EnrollmentService::list_student_enrollments(&repo, student.id, EnrollmentListQuery {
+            status: Some(EnrollmentStatus::Active),
+        }).await.unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].course_title, "Classical Mechanics");
+
+        // Courses with students cannot be deleted out from under them
+        let error = CourseService::delete_course(&repo, course_id, tutor_id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+        EnrollmentService::drop_enrollment(&repo, course_id, student.id).await.unwrap();
+        CourseService::delete_course(&repo, course_id, tutor_id, &context).await.unwrap();
+    }
+}
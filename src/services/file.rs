@@ -0,0 +1,295 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{DownloadQuery, FilePurpose, StoredFile, StoredFileResponse};
+use crate::repositories::FileRepository;
+use crate::storage::{content_hash, content_key, FileStore};
+use chrono::Utc;
+use uuid::Uuid;
+
+pub const MAX_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+
+// Types accepted by the general upload endpoint
+pub const UPLOAD_CONTENT_TYPES: &[&str] = &[
+    "application/pdf",
+    "application/zip",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "image/gif",
+    "image/jpeg",
+    "image/png",
+    "text/markdown",
+    "text/plain",
+];
+
+// A file as received, before it is checked and stored
+#[derive(Debug, Clone)]
+pub struct FileUpload {
+    pub file_name: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+// Uploaded files: metadata in the repository, content in the file store
+// keyed by its hash. Owners manage their files; anyone holding a signed
+// download link can fetch the file until the link expires.
+pub struct FileService;
+
+impl FileService {
+    pub async fn upload<R: FileRepository>(
+        repo: &R,
+        files: &FileStore,
+        owner_id: Uuid,
+        upload: FileUpload,
+        base_url: &str,
+    ) -> AppResult<StoredFileResponse> {
+        let file = Self::store(repo, files, owner_id, FilePurpose::Upload, upload, UPLOAD_CONTENT_TYPES).await?;
+        Ok(Self::response(files, file, base_url))
+    }
+
+    pub async fn get_file<R: FileRepository>(
+        repo: &R,
+        files: &FileStore,
+        file_id: Uuid,
+        user_id: Uuid,
+        base_url: &str,
+    ) -> AppResult<StoredFileResponse> {
+        let file = Self::find_owned_file(repo, file_id, user_id).await?;
+        Ok(Self::response(files, file, base_url))
+    }
+
+    pub async fn list_files<R: FileRepository>(
+        repo: &R,
+        files: &FileStore,
+        user_id: Uuid,
+        base_url: &str,
+    ) -> AppResult<Vec<StoredFileResponse>> {
+        let stored = repo.list_stored_files(user_id).await?;
+        Ok(stored.into_iter().map(|file| Self::response(files, file, base_url)).collect())
+    }
+
+    pub async fn delete_file<R: FileRepository>(
+        repo: &R,
+        files: &FileStore,
+        file_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<()> {
+        let file = Self::find_owned_file(repo, file_id, user_id).await?;
+        Self::remove(repo, files, &file).await
+    }
+
+    // Public: the signature in the link is the credential
+    pub async fn download<R: FileRepository>(
+        repo: &R,
+        files: &FileStore,
+        file_id: Uuid,
+        query: &DownloadQuery,
+    ) -> AppResult<(StoredFile, Vec<u8>)> {
+        if !files.verify_download(file_id, query.expires, &query.signature, Utc::now()) {
+            return Err(AppError::Authorization("Download link is invalid or has expired".to_string()));
+        }
+
+        let file = repo.find_stored_file(file_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
+        let content = Self::content(files, &file).await?;
+        Ok((file, content))
+    }
+
+    // Checks an upload against `accepted` types and the size limit, then
+    // stores it; content already in the store is not written again
+    pub(crate) async fn store<R: FileRepository>(
+        repo: &R,
+        files: &FileStore,
+        owner_id: Uuid,
+        purpose: FilePurpose,
+        upload: FileUpload,
+        accepted: &[&str],
+    ) -> AppResult<StoredFile> {
+        // Browsers may send a full path; keep only the last component
+        let file_name = upload.file_name.rsplit(['/', '\\']).next().unwrap_or_default().trim();
+        if file_name.is_empty() || file_name.len() > 255 {
+            return Err(AppError::Validation("File name must be 1 to 255 characters".to_string()));
+        }
+        if !accepted.contains(&upload.content_type.as_str()) {
+            return Err(AppError::Validation(format!("Files of type {} are not accepted", upload.content_type)));
+        }
+        if upload.content.is_empty() || upload.content.len() > MAX_UPLOAD_BYTES {
+            return Err(AppError::Validation(format!(
+                "Files must be between 1 byte and {} MB",
+                MAX_UPLOAD_BYTES / (1024 * 1024)
+            )));
+        }
+        if !content_matches_type(&upload.content_type, &upload.content) {
+            return Err(AppError::Validation(format!("File content is not valid {}", upload.content_type)));
+        }
+
+        let sha256 = content_hash(&upload.content);
+        let key = content_key(&sha256);
+        let file = StoredFile {
+            id: Uuid::new_v4(),
+            owner_id,
+            purpose,
+            file_name: file_name.to_string(),
+            content_type: upload.content_type.clone(),
+            size_bytes: upload.content.len() as i64,
+            sha256,
+            created_at: Utc::now(),
+        };
+        // The file is recorded before its content is written, and both happen
+        // under the content lock so a concurrent `remove` sees this file
+        let _lock = files.lock_content(&file.sha256).await;
+        repo.insert_stored_file(&file).await?;
+        let written = match files.backend().exists(&key).await {
+            Ok(true) => Ok(()),
+            Ok(false) => files.backend().put(&key, upload.content, &upload.content_type).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
+            repo.delete_stored_file(file.id).await?;
+            return Err(e);
+        }
+
+        Ok(file)
+    }
+
+    pub(crate) async fn content(files: &FileStore, file: &StoredFile) -> AppResult<Vec<u8>> {
+        files.backend()
+            .get(&content_key(&file.sha256))
+            .await?
+            .ok_or_else(|| AppError::Internal(format!("Content of file {} is missing from storage", file.id)))
+    }
+
+    // The content goes once no other file shares it
+    pub(crate) async fn remove<R: FileRepository>(repo: &R, files: &FileStore, file: &StoredFile) -> AppResult<()> {
+        let _lock = files.lock_content(&file.sha256).await;
+        repo.delete_stored_file(file.id).await?;
+        if repo.count_stored_files_with_hash(&file.sha256).await? == 0 {
+            files.backend().delete(&content_key(&file.sha256)).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn response(files: &FileStore, file: StoredFile, base_url: &str) -> StoredFileResponse {
+        let (query, expires_at) = files.sign_download(file.id, Utc::now());
+        StoredFileResponse {
+            download_url: format!("{}/downloads/{}?{}", base_url.trim_end_matches('/'), file.id, query),
+            download_url_expires_at: expires_at,
+            id: file.id,
+            file_name: file.file_name,
+            content_type: file.content_type,
+            size_bytes: file.size_bytes,
+            sha256: file.sha256,
+            created_at: file.created_at,
+        }
+    }
+
+    async fn find_owned_file<R: FileRepository>(repo: &R, file_id: Uuid, user_id: Uuid) -> AppResult<StoredFile> {
+        repo.find_stored_file(file_id)
+            .await?
+            .filter(|file| file.owner_id == user_id && file.purpose == FilePurpose::Upload)
+            .ok_or_else(|| AppError::NotFound("File not found or access denied".to_string()))
+    }
+}
+
+// The declared type must agree with the file's leading bytes, so a renamed
+// executable cannot pass as a PDF; text has to be UTF-8
+fn content_matches_type(content_type: &str, content: &[u8]) -> bool {
+    match content_type {
+        "application/pdf" => content.starts_with(b"%PDF-"),
+        "application/zip" | "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => {
+            content.starts_with(b"PK\x03\x04")
+        }
+        "image/gif" => content.starts_with(b"GIF87a") || content.starts_with(b"GIF89a"),
+        "image/jpeg" => content.starts_with(b"\xFF\xD8\xFF"),
+        "image/png" => content.starts_with(b"\x89PNG\r\n\x1A\n"),
+        "text/markdown" | "text/plain" => std::str::from_utf8(content).is_ok(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserRole;
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::insert_user;
+    use crate::storage::MemoryStorage;
+
+    const BASE_URL: &str = "http://localhost:8080/api/v1";
+
+    fn upload(file_name: &str, content_type: &str, content: &[u8]) -> FileUpload {
+        FileUpload {
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+            content: content.to_vec(),
+        }
+    }
+
+    fn signature(response: &StoredFileResponse) -> DownloadQuery {
+        let query = response.download_url.split_once('?').unwrap().1;
+        let (expires, signature) = query.split_once('&').unwrap();
+        DownloadQuery {
+            expires: expires.trim_start_matches("expires=").parse().unwrap(),
+            signature: signature.trim_start_matches("signature=").to_string(),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn identical_uploads_share_stored_content() {
+        let repo = InMemoryRepository::new();
+        let storage = MemoryStorage::new();
+        let files = FileStore::new(storage.clone(), b"secret");
+        let owner = insert_user(&repo, UserRole::Tutor).await;
+        let other = insert_user(&repo, UserRole::Student).await;
+
+        let notes = upload("C:\\Users\\me\\notes.md", "text/markdown", b"# Week 1");
+        let first = FileService::upload(&repo, &files, owner.id, notes.clone(), BASE_URL).await.unwrap();
+        let second = FileService::upload(&repo, &files, other.id, notes, BASE_URL).await.unwrap();
+        assert_eq!(first.file_name, "notes.md");
+        assert_eq!(first.sha256, second.sha256);
+        assert_ne!(first.id, second.id);
+        assert_eq!(storage.object_count(), 1);
+        assert!(first.download_url.starts_with(&format!("{}/downloads/{}?expires=", BASE_URL, first.id)));
+
+        let (file, content) = FileService::download(&repo, &files, first.id, &signature(&first)).await.unwrap();
+        assert_eq!((file.content_type.as_str(), content.as_slice()), ("text/markdown", &b"# Week 1"[..]));
+
+        // Another file's link does not open this one
+        let error = FileService::download(&repo, &files, first.id, &signature(&second)).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+
+        // Only the owner manages a file; the content stays until its last file goes
+        let error = FileService::delete_file(&repo, &files, first.id, other.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        FileService::delete_file(&repo, &files, first.id, owner.id).await.unwrap();
+        assert_eq!(storage.object_count(), 1);
+        FileService::delete_file(&repo, &files, second.id, other.id).await.unwrap();
+        assert_eq!(storage.object_count(), 0);
+        assert!(FileService::list_files(&repo, &files, owner.id, BASE_URL).await.unwrap().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn uploads_are_checked_against_their_declared_type() {
+        let repo = InMemoryRepository::new();
+        let files = FileStore::new(MemoryStorage::new(), b"secret");
+        let owner = insert_user(&repo, UserRole::Student).await;
+
+        let rejected = [
+            upload("setup.exe", "application/octet-stream", b"MZ\x90\x00"),
+            upload("paper.pdf", "application/pdf", b"MZ\x90\x00"),
+            upload("notes.txt", "text/plain", b"\xFF\xFE\x00"),
+            upload("empty.txt", "text/plain", b""),
+            upload("   ", "text/plain", b"hello"),
+            upload("big.txt", "text/plain", &vec![b'a'; MAX_UPLOAD_BYTES + 1]),
+        ];
+        for bad in rejected {
+            let error = FileService::upload(&repo, &files, owner.id, bad, BASE_URL).await.unwrap_err();
+            assert!(matches!(error, AppError::Validation(_)));
+        }
+
+        let photo = FileService::upload(&repo, &files, owner.id, upload("me.png", "image/png", b"\x89PNG\r\n\x1A\n...."), BASE_URL)
+            .await
+            .unwrap();
+        assert_eq!(photo.size_bytes, 12);
+        let mine = FileService::list_files(&repo, &files, owner.id, BASE_URL).await.unwrap();
+        assert_eq!(mine.len(), 1);
+    }
+}
@@ -1,7 +1,42 @@
+pub mod admin;
+pub mod assignment;
+pub mod audit;
 pub mod auth;
+pub mod availability;
+pub mod avatar;
+pub mod booking;
+pub mod calendar;
+pub mod certificate;
 pub mod course;
+pub mod curriculum;
+pub mod enrollment;
+pub mod file;
+pub mod notification;
+pub mod progress;
+pub mod quiz;
+pub mod retention;
 pub mod tutor;
+pub mod verification;
 
+pub use admin::AdminService;
+pub use assignment::AssignmentService;
+pub use audit::{AuditService, AuditTarget};
 pub use auth::AuthService;
+pub use availability::AvailabilityService;
+pub use avatar::AvatarService;
+pub use booking::BookingService;
+pub use calendar::CalendarService;
+pub use certificate::CertificateService;
 pub use course::CourseService;
+pub use curriculum::CurriculumService;
+pub use enrollment::EnrollmentService;
+pub use file::FileService;
+pub use notification::NotificationService;
+pub use progress::ProgressService;
+pub use quiz::QuizService;
+pub use retention::RetentionService;
 pub use tutor::TutorService;
+pub use verification::VerificationService;
+
+#[cfg(test)]
+mod test_support;
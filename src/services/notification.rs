@@ -0,0 +1,47 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{Notification, NotificationListQuery, NotificationResponse};
+use crate::repositories::NotificationRepository;
+use chrono::Utc;
+use uuid::Uuid;
+
+// In-app notifications. Other services call `notify`; users read them from
+// their inbox and mark them read.
+pub struct NotificationService;
+
+impl NotificationService {
+    pub async fn notify<R: NotificationRepository>(
+        repo: &R,
+        user_id: Uuid,
+        kind: &str,
+        title: String,
+        body: String,
+        link: Option<String>,
+    ) -> AppResult<()> {
+        repo.insert_notification(&Notification {
+            id: Uuid::new_v4(),
+            user_id,
+            kind: kind.to_string(),
+            title,
+            body,
+            link,
+            read_at: None,
+            created_at: Utc::now(),
+        }).await
+    }
+
+    pub async fn list<R: NotificationRepository>(
+        repo: &R,
+        user_id: Uuid,
+        query: NotificationListQuery,
+    ) -> AppResult<Vec<NotificationResponse>> {
+        let notifications = repo.list_notifications(user_id, query.unread).await?;
+        Ok(notifications.into_iter().map(NotificationResponse::from).collect())
+    }
+
+    pub async fn mark_read<R: NotificationRepository>(repo: &R, notification_id: Uuid, user_id: Uuid) -> AppResult<()> {
+        if !repo.mark_notification_read(notification_id, user_id, Utc::now()).await? {
+            return Err(AppError::NotFound("Notification not found".to_string()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,248 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    CompleteLessonRequest, CourseProgressDetailResponse, CourseProgressResponse, EnrollmentStatus,
+    LessonCompletion, LessonCompletionResponse, LessonProgress,
+};
+use crate::repositories::{
+    CertificateRepository, CourseRepository, CurriculumRepository, EnrollmentRepository, NotificationRepository,
+    ProgressRepository, TutorRepository,
+};
+use crate::services::{CertificateService, CourseService};
+use chrono::Utc;
+use std::collections::HashMap;
+use uuid::Uuid;
+use validator::Validate;
+
+// Enrolled students mark lessons complete as they go. Completion percentage
+// counts the course's current lessons, so it drops when the tutor adds one.
+// Completing the last lesson issues the course certificate.
+pub struct ProgressService;
+
+impl ProgressService {
+    pub async fn complete_lesson<R>(
+        repo: &R,
+        lesson_id: Uuid,
+        student_id: Uuid,
+        request: CompleteLessonRequest,
+    ) -> AppResult<LessonCompletionResponse>
+    where
+        R: EnrollmentRepository + CurriculumRepository + ProgressRepository + CourseRepository
+            + CertificateRepository + NotificationRepository,
+    {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let lesson = repo.find_lesson(lesson_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Lesson not found".to_string()))?;
+        Self::check_enrolled(repo, lesson.course_id, student_id).await?;
+
+        let now = Utc::now();
+        let completion = repo.record_lesson_completion(&LessonCompletion {
+            id: Uuid::new_v4(),
+            lesson_id,
+            course_id: lesson.course_id,
+            student_id,
+            minutes_spent: request.minutes_spent.unwrap_or(lesson.estimated_minutes),
+            completed_at: now,
+            created_at: now,
+            updated_at: now,
+        }).await?;
+        // The completion is already recorded; a certificate that fails to
+        // issue is retried the next time a lesson in the course is completed
+        if let Err(e) = CertificateService::issue_if_complete(repo, lesson.course_id, student_id).await {
+            log::error!("Issuing certificate for course {} to student {} failed: {}", lesson.course_id, student_id, e);
+        }
+
+        Ok(LessonCompletionResponse::from(completion))
+    }
+
+    // Marks a lesson as not done, discarding the time recorded for it
+    pub async fn reset_lesson<R>(repo: &R, lesson_id: Uuid, student_id: Uuid) -> AppResult<()>
+    where
+        R: EnrollmentRepository + CurriculumRepository + ProgressRepository,
+    {
+        let lesson = repo.find_lesson(lesson_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Lesson not found".to_string()))?;
+        Self::check_enrolled(repo, lesson.course_id, student_id).await?;
+
+        if !repo.delete_lesson_completion(lesson_id, student_id).await? {
+            return Err(AppError::NotFound("Lesson has not been completed".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Student dashboard: every course the student is actively enrolled in
+    pub async fn student_dashboard<R: ProgressRepository>(
+        repo: &R,
+        student_id: Uuid,
+    ) -> AppResult<Vec<CourseProgressResponse>> {
+        let progress = repo.list_student_progress(student_id).await?;
+        Ok(progress.into_iter().map(CourseProgressResponse::from).collect())
+    }
+
+    pub async fn student_course_progress<R>(
+        repo: &R,
+        course_id: Uuid,
+        student_id: Uuid,
+    ) -> AppResult<CourseProgressDetailResponse>
+    where
+        R: CurriculumRepository + ProgressRepository,
+    {
+        let progress = repo.list_student_progress(student_id)
+            .await?
+            .into_iter()
+            .find(|row| row.course_id == course_id)
+            .ok_or_else(|| AppError::NotFound("Enrollment not found".to_string()))?;
+
+        let completions: HashMap<Uuid, LessonCompletion> = repo.list_lesson_completions(course_id, student_id)
+            .await?
+            .into_iter()
+            .map(|completion| (completion.lesson_id, completion))
+            .collect();
+        let lessons = repo.list_lessons(course_id)
+            .await?
+            .into_iter()
+            .map(|lesson| {
+                let completion = completions.get(&lesson.id);
+                LessonProgress {
+                    lesson_id: lesson.id,
+                    module_id: lesson.module_id,
+                    title: lesson.title,
+                    estimated_minutes: lesson.estimated_minutes,
+                    completed_at: completion.map(|c| c.completed_at),
+                    minutes_spent: completion.map(|c| c.minutes_spent),
+                }
+            })
+            .collect();
+
+        Ok(CourseProgressDetailResponse {
+            progress: CourseProgressResponse::from(progress),
+            lessons,
+        })
+    }
+
+    // Tutor dashboard: one row per actively enrolled student of an own course
+    pub async fn course_dashboard<R>(
+        repo: &R,
+        course_id: Uuid,
+        tutor_id: Uuid,
+    ) -> AppResult<Vec<CourseProgressResponse>>
+    where
+        R: TutorRepository + CourseRepository + ProgressRepository,
+    {
+        CourseService::find_owned_course(repo, course_id, tutor_id).await?;
+
+        let progress = repo.list_course_progress(course_id).await?;
+        Ok(progress.into_iter().map(CourseProgressResponse::from).collect())
+    }
+
+    async fn check_enrolled<R: EnrollmentRepository>(repo: &R, course_id: Uuid, student_id: Uuid) -> AppResult<()> {
+        let enrolled = repo.find_enrollment(course_id, student_id)
+            .await?
+            .is_some_and(|row| row.enrollment.status == EnrollmentStatus::Active);
+        if !enrolled {
+            return Err(AppError::Authorization("Enroll in this course to track progress".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        CreateCourseRequest, CreateLessonRequest, DifficultyLevel, ModuleRequest, RequestContext, UserRole,
+    };
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use crate::services::{CurriculumService, EnrollmentService};
+
+    // A course with three lessons of 10, 20 and 30 minutes
+    async fn course_with_lessons(repo: &InMemoryRepository) -> (Uuid, Uuid, Vec<Uuid>) {
+        let (user, _) = insert_tutor(repo, &["Languages"]).await;
+        let course = CourseService::create_course(repo, user.id, CreateCourseRequest {
+            title: "Spanish A1".to_string(),
+            description: "First steps".to_string(),
+            price: 2000,
+            duration_minutes: 60,
+            category: "Languages".to_string(),
+            difficulty_level: DifficultyLevel::Beginner,
+            max_students: None,
+        }, &RequestContext::default()).await.unwrap();
+        let module = CurriculumService::add_module(repo, course.id, user.id, ModuleRequest {
+            title: "Basics".to_string(),
+        }).await.unwrap();
+
+        let mut lessons = Vec::new();
+        for minutes in [10, 20, 30] {
+            let lesson = CurriculumService::add_lesson(repo, module.id, user.id, CreateLessonRequest {
+                title: format!("Lesson {}", minutes),
+                content: String::new(),
+                estimated_minutes: minutes,
+                resources: Vec::new(),
+            }).await.unwrap();
+            lessons.push(lesson.id);
+        }
+        (user.id, course.id, lessons)
+    }
+
+    fn minutes(minutes_spent: Option<i32>) -> CompleteLessonRequest {
+        CompleteLessonRequest { minutes_spent }
+    }
+
+    #[actix_rt::test]
+    async fn completions_add_up_to_course_progress() {
+        let repo = InMemoryRepository::new();
+        let (tutor_id, course_id, lessons) = course_with_lessons(&repo).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        let error = ProgressService::complete_lesson(&repo, lessons[0], student.id, minutes(None)).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+        EnrollmentService::enroll(&repo, course_id, student.id).await.unwrap();
+
+        // Defaults to the estimate; completing again adds the extra time
+        let first = ProgressService::complete_lesson(&repo, lessons[0], student.id, minutes(None)).await.unwrap();
+        assert_eq!(first.minutes_spent, 10);
+        let again = ProgressService::complete_lesson(&repo, lessons[0], student.id, minutes(Some(5))).await.unwrap();
+        assert_eq!((again.minutes_spent, again.completed_at), (15, first.completed_at));
+        ProgressService::complete_lesson(&repo, lessons[1], student.id, minutes(Some(25))).await.unwrap();
+
+        let dashboard = ProgressService::student_dashboard(&repo, student.id).await.unwrap();
+        assert_eq!(dashboard.len(), 1);
+        assert_eq!((dashboard[0].completed_lessons, dashboard[0].total_lessons), (2, 3));
+        assert_eq!(dashboard[0].completion_percent, 66);
+        assert_eq!((dashboard[0].minutes_spent, dashboard[0].estimated_minutes), (40, 60));
+
+        let detail = ProgressService::student_course_progress(&repo, course_id, student.id).await.unwrap();
+        let done: Vec<bool> = detail.lessons.iter().map(|lesson| lesson.completed_at.is_some()).collect();
+        assert_eq!(done, [true, true, false]);
+
+        ProgressService::reset_lesson(&repo, lessons[1], student.id).await.unwrap();
+        let roster = ProgressService::course_dashboard(&repo, course_id, tutor_id).await.unwrap();
+        assert_eq!((roster[0].student_id, roster[0].completion_percent), (student.id, 33));
+    }
+
+    #[actix_rt::test]
+    async fn only_the_owner_sees_the_course_dashboard() {
+        let repo = InMemoryRepository::new();
+        let (_, course_id, lessons) = course_with_lessons(&repo).await;
+        let (other_tutor, _) = insert_tutor(&repo, &["Music"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        EnrollmentService::enroll(&repo, course_id, student.id).await.unwrap();
+        ProgressService::complete_lesson(&repo, lessons[2], student.id, minutes(None)).await.unwrap();
+
+        let error = ProgressService::course_dashboard(&repo, course_id, other_tutor.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        // Dropping hides the course from the dashboard but keeps the history
+        EnrollmentService::drop_enrollment(&repo, course_id, student.id).await.unwrap();
+        assert!(ProgressService::student_dashboard(&repo, student.id).await.unwrap().is_empty());
+        EnrollmentService::enroll(&repo, course_id, student.id).await.unwrap();
+        let dashboard = ProgressService::student_dashboard(&repo, student.id).await.unwrap();
+        assert_eq!(dashboard[0].completed_lessons, 1);
+    }
+}
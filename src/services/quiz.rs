@@ -0,0 +1,649 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    EnrollmentStatus, QuestionKind, QuestionRequest, QuestionResult, Quiz, QuizAnswer, QuizAttempt,
+    QuizAttemptResponse, QuizQuestion, QuizRequest, QuizResponse, QuizSheetResponse, QuizSummary,
+    SubmitQuizRequest,
+};
+use crate::repositories::{
+    CourseRepository, CurriculumRepository, EnrollmentRepository, QuizRepository, TutorRepository,
+};
+use crate::services::{CourseService, CurriculumService};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::types::Json;
+use std::collections::HashSet;
+use uuid::Uuid;
+use validator::Validate;
+
+// Submissions this late still count, to allow for network delay
+const SUBMIT_GRACE_SECONDS: i64 = 30;
+
+// Tutors attach quizzes to lessons; enrolled students take them and are
+// graded on submission. Attempts past their time limit score no answers.
+pub struct QuizService;
+
+impl QuizService {
+    pub async fn create_quiz<R: TutorRepository + CourseRepository + CurriculumRepository + QuizRepository>(
+        repo: &R,
+        lesson_id: Uuid,
+        tutor_id: Uuid,
+        request: QuizRequest,
+    ) -> AppResult<QuizResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        Self::check_questions(&request.questions)?;
+
+        let lesson = CurriculumService::find_owned_lesson(repo, lesson_id, tutor_id).await?;
+
+        let now = Utc::now();
+        let quiz = Quiz {
+            id: Uuid::new_v4(),
+            lesson_id,
+            course_id: lesson.course_id,
+            title: request.title,
+            instructions: request.instructions,
+            max_attempts: request.max_attempts,
+            time_limit_minutes: request.time_limit_minutes,
+            questions: Json(Self::build_questions(request.questions, &[])),
+            created_at: now,
+            updated_at: now,
+        };
+        repo.insert_quiz(&quiz).await?;
+
+        Ok(QuizResponse::from(quiz))
+    }
+
+    // Replaces the quiz; questions sent with an existing id keep it. Graded
+    // attempts are unaffected since they store their own results.
+    pub async fn update_quiz<R: TutorRepository + CourseRepository + QuizRepository>(
+        repo: &R,
+        quiz_id: Uuid,
+        tutor_id: Uuid,
+        request: QuizRequest,
+    ) -> AppResult<QuizResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        Self::check_questions(&request.questions)?;
+
+        let mut quiz = Self::find_owned_quiz(repo, quiz_id, tutor_id).await?;
+        quiz.title = request.title;
+        quiz.instructions = request.instructions;
+        quiz.max_attempts = request.max_attempts;
+        quiz.time_limit_minutes = request.time_limit_minutes;
+        quiz.questions = Json(Self::build_questions(request.questions, &quiz.questions));
+        quiz.updated_at = Utc::now();
+
+        repo.update_quiz(&quiz).await?;
+
+        Ok(QuizResponse::from(quiz))
+    }
+
+    pub async fn delete_quiz<R: TutorRepository + CourseRepository + QuizRepository>(
+        repo: &R,
+        quiz_id: Uuid,
+        tutor_id: Uuid,
+    ) -> AppResult<()> {
+        Self::find_owned_quiz(repo, quiz_id, tutor_id).await?;
+
+        if !repo.delete_quiz(quiz_id).await? {
+            return Err(AppError::NotFound("Quiz not found or access denied".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_answer_key<R: TutorRepository + CourseRepository + QuizRepository>(
+        repo: &R,
+        quiz_id: Uuid,
+        tutor_id: Uuid,
+    ) -> AppResult<QuizResponse> {
+        let quiz = Self::find_owned_quiz(repo, quiz_id, tutor_id).await?;
+        Ok(QuizResponse::from(quiz))
+    }
+
+    pub async fn list_lesson_quizzes<R>(repo: &R, lesson_id: Uuid, user_id: Uuid) -> AppResult<Vec<QuizSummary>>
+    where
+        R: TutorRepository + CourseRepository + EnrollmentRepository + CurriculumRepository + QuizRepository,
+    {
+        let lesson = repo.find_lesson(lesson_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Lesson not found".to_string()))?;
+        Self::check_access(repo, lesson.course_id, user_id).await?;
+
+        let quizzes = repo.list_quizzes_for_lesson(lesson_id).await?;
+        Ok(quizzes.into_iter().map(QuizSummary::from).collect())
+    }
+
+    pub async fn get_quiz_sheet<R>(repo: &R, quiz_id: Uuid, user_id: Uuid) -> AppResult<QuizSheetResponse>
+    where
+        R: TutorRepository + CourseRepository + EnrollmentRepository + QuizRepository,
+    {
+        let quiz = Self::find_quiz(repo, quiz_id).await?;
+        Self::check_access(repo, quiz.course_id, user_id).await?;
+
+        Ok(QuizSheetResponse::from(quiz))
+    }
+
+    // Returns the student's open attempt if there is one, otherwise starts the next
+    pub async fn start_attempt<R>(repo: &R, quiz_id: Uuid, student_id: Uuid) -> AppResult<QuizAttemptResponse>
+    where
+        R: EnrollmentRepository + QuizRepository,
+    {
+        let quiz = Self::find_quiz(repo, quiz_id).await?;
+        let enrolled = repo.find_enrollment(quiz.course_id, student_id)
+            .await?
+            .is_some_and(|row| row.enrollment.status == EnrollmentStatus::Active);
+        if !enrolled {
+            return Err(AppError::Authorization("Enroll in this course to take its quizzes".to_string()));
+        }
+
+        let now = Utc::now();
+        let open = repo.list_quiz_attempts(quiz_id, Some(student_id))
+            .await?
+            .into_iter()
+            .find(|attempt| attempt.submitted_at.is_none());
+        if let Some(attempt) = open {
+            if !Self::is_expired(&attempt, now) {
+                return Ok(QuizAttemptResponse::from(attempt));
+            }
+            Self::expire(repo, &quiz, attempt, now).await?;
+        }
+
+        let attempt = repo.start_quiz_attempt(&QuizAttempt {
+            id: Uuid::new_v4(),
+            quiz_id,
+            student_id,
+            attempt_number: 0,
+            started_at: now,
+            deadline_at: quiz.time_limit_minutes.map(|minutes| now + Duration::minutes(minutes as i64)),
+            submitted_at: None,
+            timed_out: false,
+            score: None,
+            max_score: quiz.total_points(),
+            answers: Json(Vec::new()),
+            results: Json(Vec::new()),
+            created_at: now,
+            updated_at: now,
+        }, quiz.max_attempts).await?;
+
+        Ok(QuizAttemptResponse::from(attempt))
+    }
+
+    pub async fn submit_attempt<R: QuizRepository>(
+        repo: &R,
+        attempt_id: Uuid,
+        student_id: Uuid,
+        request: SubmitQuizRequest,
+    ) -> AppResult<QuizAttemptResponse> {
+        let attempt = repo.find_quiz_attempt(attempt_id)
+            .await?
+            .filter(|attempt| attempt.student_id == student_id)
+            .ok_or_else(|| AppError::NotFound("Attempt not found".to_string()))?;
+        if attempt.submitted_at.is_some() {
+            return Err(AppError::Conflict("Attempt has already been submitted".to_string()));
+        }
+        let quiz = Self::find_quiz(repo, attempt.quiz_id).await?;
+
+        let now = Utc::now();
+        if Self::is_expired(&attempt, now) {
+            let attempt = Self::expire(repo, &quiz, attempt, now).await?;
+            return Ok(QuizAttemptResponse::from(attempt));
+        }
+
+        let attempt = Self::finish(repo, &quiz, attempt, request.answers, false, now).await?;
+        Ok(QuizAttemptResponse::from(attempt))
+    }
+
+    // The student who made the attempt or the course's tutor
+    pub async fn get_attempt<R>(repo: &R, attempt_id: Uuid, user_id: Uuid) -> AppResult<QuizAttemptResponse>
+    where
+        R: TutorRepository + CourseRepository + QuizRepository,
+    {
+        let not_found = || AppError::NotFound("Attempt not found".to_string());
+
+        let attempt = repo.find_quiz_attempt(attempt_id).await?.ok_or_else(not_found)?;
+        let quiz = Self::find_quiz(repo, attempt.quiz_id).await?;
+        if attempt.student_id != user_id && !Self::is_owner(repo, quiz.course_id, user_id).await? {
+            return Err(not_found());
+        }
+
+        let attempt = Self::settle(repo, &quiz, attempt).await?;
+        Ok(QuizAttemptResponse::from(attempt))
+    }
+
+    // Result history: every student's attempts for the tutor, otherwise the caller's own
+    pub async fn list_attempts<R>(repo: &R, quiz_id: Uuid, user_id: Uuid) -> AppResult<Vec<QuizAttemptResponse>>
+    where
+        R: TutorRepository + CourseRepository + QuizRepository,
+    {
+        let quiz = Self::find_quiz(repo, quiz_id).await?;
+        let student_id = if Self::is_owner(repo, quiz.course_id, user_id).await? {
+            None
+        } else {
+            Some(user_id)
+        };
+
+        let mut attempts = Vec::new();
+        for attempt in repo.list_quiz_attempts(quiz_id, student_id).await? {
+            attempts.push(QuizAttemptResponse::from(Self::settle(repo, &quiz, attempt).await?));
+        }
+
+        Ok(attempts)
+    }
+
+    // Scores answers against the key. Multi-select earns partial credit, with
+    // each wrong pick cancelling a right one.
+    pub fn grade(questions: &[QuizQuestion], answers: &[QuizAnswer]) -> (Vec<QuestionResult>, i32) {
+        let results: Vec<QuestionResult> = questions.iter()
+            .map(|question| {
+                let answer = answers.iter().find(|answer| answer.question_id == question.id);
+                let points_awarded = answer.map_or(0, |answer| Self::score(question, answer));
+                QuestionResult {
+                    question_id: question.id,
+                    correct: points_awarded == question.points,
+                    points_awarded,
+                    points_possible: question.points,
+                    feedback: question.explanation.clone(),
+                }
+            })
+            .collect();
+        let score = results.iter().map(|result| result.points_awarded).sum();
+
+        (results, score)
+    }
+
+    fn score(question: &QuizQuestion, answer: &QuizAnswer) -> i32 {
+        match &question.kind {
+            QuestionKind::MultipleChoice { correct, .. } => {
+                if answer.choices == [*correct] { question.points } else { 0 }
+            }
+            QuestionKind::MultiSelect { correct, .. } => {
+                let chosen: HashSet<usize> = answer.choices.iter().copied().collect();
+                let hits = chosen.iter().filter(|choice| correct.contains(choice)).count();
+                let misses = chosen.len() - hits;
+                let net = hits.saturating_sub(misses) as i32;
+                question.points * net / correct.len() as i32
+            }
+            QuestionKind::Numeric { answer: expected, tolerance } => {
+                match answer.number {
+                    Some(number) if (number - expected).abs() <= *tolerance => question.points,
+                    _ => 0,
+                }
+            }
+            QuestionKind::ShortText { accepted, case_sensitive } => {
+                let normalize = |text: &str| {
+                    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                    if *case_sensitive { text } else { text.to_lowercase() }
+                };
+                match &answer.text {
+                    Some(text) if accepted.iter().any(|option| normalize(option) == normalize(text)) => {
+                        question.points
+                    }
+                    _ => 0,
+                }
+            }
+        }
+    }
+
+    fn check_questions(questions: &[QuestionRequest]) -> AppResult<()> {
+        for (index, question) in questions.iter().enumerate() {
+            let problem = match &question.kind {
+                QuestionKind::MultipleChoice { options, correct } => Self::check_options(options)
+                    .or_else(|| (*correct >= options.len()).then_some("correct option is out of range")),
+                QuestionKind::MultiSelect { options, correct } => Self::check_options(options)
+                    .or_else(|| correct.is_empty().then_some("at least one option must be correct"))
+                    .or_else(|| correct.iter().any(|&choice| choice >= options.len())
+                        .then_some("correct option is out of range"))
+                    .or_else(|| (correct.iter().collect::<HashSet<_>>().len() != correct.len())
+                        .then_some("correct options must be distinct")),
+                QuestionKind::Numeric { answer, tolerance } => {
+                    (!answer.is_finite() || !tolerance.is_finite() || *tolerance < 0.0)
+                        .then_some("answer and tolerance must be finite, tolerance not negative")
+                }
+                QuestionKind::ShortText { accepted, .. } => {
+                    (accepted.is_empty() || accepted.len() > 20 || accepted.iter().any(|text| text.trim().is_empty()))
+                        .then_some("between 1 and 20 non-blank accepted answers are required")
+                }
+            };
+            if let Some(problem) = problem {
+                return Err(AppError::Validation(format!(
+                    "Validation failed: question {}: {}",
+                    index + 1,
+                    problem
+                )));
+            }
+        }
+
+        let ids: Vec<Uuid> = questions.iter().filter_map(|question| question.id).collect();
+        if ids.iter().collect::<HashSet<_>>().len() != ids.len() {
+            return Err(AppError::Validation("Validation failed: question ids must be distinct".to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn check_options(options: &[String]) -> Option<&'static str> {
+        if !(2..=10).contains(&options.len()) {
+            return Some("between 2 and 10 options are required");
+        }
+        if options.iter().any(|option| option.trim().is_empty() || option.len() > 500) {
+            return Some("options must be non-blank and at most 500 characters");
+        }
+        None
+    }
+
+    // Ids of questions the quiz already had are kept; anything else gets a fresh one
+    fn build_questions(requests: Vec<QuestionRequest>, existing: &[QuizQuestion]) -> Vec<QuizQuestion> {
+        requests.into_iter()
+            .map(|request| QuizQuestion {
+                id: request.id
+                    .filter(|id| existing.iter().any(|question| question.id == *id))
+                    .unwrap_or_else(Uuid::new_v4),
+                prompt: request.prompt,
+                points: request.points,
+                kind: request.kind,
+                explanation: request.explanation,
+            })
+            .collect()
+    }
+
+    fn is_expired(attempt: &QuizAttempt, now: DateTime<Utc>) -> bool {
+        attempt.deadline_at
+            .is_some_and(|deadline| now > deadline + Duration::seconds(SUBMIT_GRACE_SECONDS))
+    }
+
+    // Closes an abandoned attempt once its time is up, so history shows its score
+    async fn settle<R: QuizRepository>(repo: &R, quiz: &Quiz, attempt: QuizAttempt) -> AppResult<QuizAttempt> {
+        let now = Utc::now();
+        if attempt.submitted_at.is_none() && Self::is_expired(&attempt, now) {
+            return Self::expire(repo, quiz, attempt, now).await;
+        }
+        Ok(attempt)
+    }
+
+    async fn expire<R: QuizRepository>(
+        repo: &R,
+        quiz: &Quiz,
+        attempt: QuizAttempt,
+        now: DateTime<Utc>,
+    ) -> AppResult<QuizAttempt> {
+        let submitted_at = attempt.deadline_at.unwrap_or(now);
+        Self::finish(repo, quiz, attempt, Vec::new(), true, submitted_at).await
+    }
+
+    async fn finish<R: QuizRepository>(
+        repo: &R,
+        quiz: &Quiz,
+        mut attempt: QuizAttempt,
+        answers: Vec<QuizAnswer>,
+        timed_out: bool,
+        submitted_at: DateTime<Utc>,
+    ) -> AppResult<QuizAttempt> {
+        // Keep the first answer to each of the quiz's questions
+        let mut answered = HashSet::new();
+        let answers: Vec<QuizAnswer> = answers.into_iter()
+            .filter(|answer| quiz.questions.iter().any(|question| question.id == answer.question_id))
+            .filter(|answer| answered.insert(answer.question_id))
+            .collect();
+        let (results, score) = Self::grade(&quiz.questions, &answers);
+
+        attempt.submitted_at = Some(submitted_at);
+        attempt.timed_out = timed_out;
+        attempt.score = Some(score);
+        attempt.max_score = quiz.total_points();
+        attempt.answers = Json(answers);
+        attempt.results = Json(results);
+        attempt.updated_at = Utc::now();
+
+        if !repo.finish_quiz_attempt(&attempt).await? {
+            return Err(AppError::Conflict("Attempt has already been submitted".to_string()));
+        }
+
+        Ok(attempt)
+    }
+
+    async fn find_quiz<R: QuizRepository>(repo: &R, quiz_id: Uuid) -> AppResult<Quiz> {
+        repo.find_quiz(quiz_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Quiz not found".to_string()))
+    }
+
+    // Loads a quiz, treating quizzes of another tutor's course as missing
+    async fn find_owned_quiz<R: TutorRepository + CourseRepository + QuizRepository>(
+        repo: &R,
+        quiz_id: Uuid,
+        tutor_id: Uuid,
+    ) -> AppResult<Quiz> {
+        let not_found = || AppError::NotFound("Quiz not found or access denied".to_string());
+
+        let quiz = repo.find_quiz(quiz_id).await?.ok_or_else(not_found)?;
+        CourseService::find_owned_course(repo, quiz.course_id, tutor_id)
+            .await
+            .map_err(|e| match e {
+                AppError::NotFound(_) => not_found(),
+                e => e,
+            })?;
+
+        Ok(quiz)
+    }
+
+    async fn is_owner<R: TutorRepository + CourseRepository>(repo: &R, course_id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        match CourseService::find_owned_course(repo, course_id, user_id).await {
+            Ok(_) => Ok(true),
+            Err(AppError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    // The course's tutor or one of its active students
+    async fn check_access<R>(repo: &R, course_id: Uuid, user_id: Uuid) -> AppResult<()>
+    where
+        R: TutorRepository + CourseRepository + EnrollmentRepository,
+    {
+        if Self::is_owner(repo, course_id, user_id).await? {
+            return Ok(());
+        }
+
+        let enrolled = repo.find_enrollment(course_id, user_id)
+            .await?
+            .is_some_and(|row| row.enrollment.status == EnrollmentStatus::Active);
+        if !enrolled {
+            return Err(AppError::Authorization("Enroll in this course to take its quizzes".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        CreateCourseRequest, CreateLessonRequest, DifficultyLevel, ModuleRequest, RequestContext, UserRole,
+    };
+    use crate::repositories::InMemoryRepository;
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use crate::services::EnrollmentService;
+
+    fn question(points: i32, kind: QuestionKind) -> QuestionRequest {
+        QuestionRequest {
+            id: None,
+            prompt: "?".to_string(),
+            points,
+            kind,
+            explanation: Some("Because".to_string()),
+        }
+    }
+
+    fn quiz_request(max_attempts: Option<i32>, time_limit_minutes: Option<i32>) -> QuizRequest {
+        let options = |n: usize| (0..n).map(|i| format!("Option {}", i)).collect::<Vec<_>>();
+        QuizRequest {
+            title: "Check-in".to_string(),
+            instructions: String::new(),
+            max_attempts,
+            time_limit_minutes,
+            questions: vec![
+                question(2, QuestionKind::MultipleChoice { options: options(3), correct: 1 }),
+                question(4, QuestionKind::MultiSelect { options: options(4), correct: vec![0, 2] }),
+                question(1, QuestionKind::Numeric { answer: 9.81, tolerance: 0.01 }),
+                question(1, QuestionKind::ShortText {
+                    accepted: vec!["Isaac Newton".to_string()],
+                    case_sensitive: false,
+                }),
+            ],
+        }
+    }
+
+    // A lesson in a course of the returned tutor, with one quiz on it
+    async fn lesson_quiz(repo: &InMemoryRepository, request: QuizRequest) -> (Uuid, Uuid, QuizResponse) {
+        let (user, _) = insert_tutor(repo, &["Physics"]).await;
+        let course = CourseService::create_course(repo, user.id, CreateCourseRequest {
+            title: "Mechanics".to_string(),
+            description: "Forces".to_string(),
+            price: 2000,
+            duration_minutes: 60,
+            category: "Science".to_string(),
+            difficulty_level: DifficultyLevel::Beginner,
+            max_students: None,
+        }, &RequestContext::default()).await.unwrap();
+        let module = CurriculumService::add_module(repo, course.id, user.id, ModuleRequest {
+            title: "Gravity".to_string(),
+        }).await.unwrap();
+        let lesson = CurriculumService::add_lesson(repo, module.id, user.id, CreateLessonRequest {
+            title: "Free fall".to_string(),
+            content: String::new(),
+            estimated_minutes: 15,
+            resources: Vec::new(),
+        }).await.unwrap();
+        let quiz = QuizService::create_quiz(repo, lesson.id, user.id, request).await.unwrap();
+        (user.id, course.id, quiz)
+    }
+
+    fn answer(question: &QuizQuestion) -> QuizAnswer {
+        QuizAnswer { question_id: question.id, ..QuizAnswer::default() }
+    }
+
+    #[test]
+    fn grading_awards_partial_credit_for_multi_select() {
+        let questions = QuizService::build_questions(quiz_request(None, None).questions, &[]);
+        let answers = vec![
+            QuizAnswer { choices: vec![1], ..answer(&questions[0]) },
+            // One right pick of two
+            QuizAnswer { choices: vec![2], ..answer(&questions[1]) },
+            QuizAnswer { number: Some(9.8), ..answer(&questions[2]) },
+            QuizAnswer { text: Some("  isaac   NEWTON ".to_string()), ..answer(&questions[3]) },
+        ];
+
+        let (results, score) = QuizService::grade(&questions, &answers);
+        let awarded: Vec<i32> = results.iter().map(|result| result.points_awarded).collect();
+        assert_eq!(awarded, [2, 2, 1, 1]);
+        assert_eq!(score, 6);
+        assert!(!results[1].correct);
+        assert_eq!(results[0].feedback.as_deref(), Some("Because"));
+
+        // A wrong pick cancels a right one; unanswered questions score nothing
+        let answers = vec![QuizAnswer { choices: vec![0, 1, 2, 3], ..answer(&questions[1]) }];
+        let (results, score) = QuizService::grade(&questions, &answers);
+        assert_eq!((results[1].points_awarded, score), (0, 0));
+    }
+
+    #[actix_rt::test]
+    async fn attempts_are_limited_and_late_submissions_score_nothing() {
+        let repo = InMemoryRepository::new();
+        let (_, course_id, quiz) = lesson_quiz(&repo, quiz_request(Some(2), Some(10))).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        EnrollmentService::enroll(&repo, course_id, student.id).await.unwrap();
+
+        // Starting again resumes the open attempt
+        let first = QuizService::start_attempt(&repo, quiz.id, student.id).await.unwrap();
+        let resumed = QuizService::start_attempt(&repo, quiz.id, student.id).await.unwrap();
+        assert_eq!((resumed.id, resumed.attempt_number), (first.id, 1));
+        assert!(first.deadline_at.is_some());
+
+        let answers = vec![QuizAnswer { choices: vec![1], ..answer(&quiz.questions[0]) }];
+        let graded = QuizService::submit_attempt(&repo, first.id, student.id, SubmitQuizRequest { answers })
+            .await
+            .unwrap();
+        assert_eq!((graded.score, graded.max_score, graded.percent), (Some(2), 8, Some(25)));
+        let error = QuizService::submit_attempt(&repo, first.id, student.id, SubmitQuizRequest { answers: vec![] })
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        // An attempt whose time ran out is graded without its answers
+        let started_at = Utc::now() - Duration::minutes(15);
+        let late = repo.start_quiz_attempt(&QuizAttempt {
+            id: Uuid::new_v4(),
+            quiz_id: quiz.id,
+            student_id: student.id,
+            attempt_number: 0,
+            started_at,
+            deadline_at: Some(started_at + Duration::minutes(10)),
+            submitted_at: None,
+            timed_out: false,
+            score: None,
+            max_score: 8,
+            answers: Json(Vec::new()),
+            results: Json(Vec::new()),
+            created_at: started_at,
+            updated_at: started_at,
+        }, Some(2)).await.unwrap();
+        let answers = vec![QuizAnswer { choices: vec![1], ..answer(&quiz.questions[0]) }];
+        let graded = QuizService::submit_attempt(&repo, late.id, student.id, SubmitQuizRequest { answers })
+            .await
+            .unwrap();
+        assert_eq!((graded.attempt_number, graded.timed_out, graded.score), (2, true, Some(0)));
+
+        let error = QuizService::start_attempt(&repo, quiz.id, student.id).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+        let history = QuizService::list_attempts(&repo, quiz.id, student.id).await.unwrap();
+        let scores: HashSet<(i32, Option<i32>)> = history.iter()
+            .map(|attempt| (attempt.attempt_number, attempt.score))
+            .collect();
+        assert_eq!(scores, HashSet::from([(1, Some(2)), (2, Some(0))]));
+    }
+
+    #[actix_rt::test]
+    async fn quizzes_are_for_the_tutor_and_enrolled_students() {
+        let repo = InMemoryRepository::new();
+        let (tutor_id, course_id, quiz) = lesson_quiz(&repo, quiz_request(None, None)).await;
+        let (other_tutor, _) = insert_tutor(&repo, &["Music"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        let classmate = insert_user(&repo, UserRole::Student).await;
+
+        let error = QuizService::get_quiz_sheet(&repo, quiz.id, student.id).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+        let error = QuizService::get_answer_key(&repo, quiz.id, other_tutor.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        let mut invalid = quiz_request(None, None);
+        invalid.questions[0].kind = QuestionKind::MultipleChoice { options: vec!["Only".to_string()], correct: 0 };
+        let error = QuizService::update_quiz(&repo, quiz.id, tutor_id, invalid).await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+
+        EnrollmentService::enroll(&repo, course_id, student.id).await.unwrap();
+        EnrollmentService::enroll(&repo, course_id, classmate.id).await.unwrap();
+        let sheet = QuizService::get_quiz_sheet(&repo, quiz.id, student.id).await.unwrap();
+        assert_eq!((sheet.questions[0].kind.as_str(), sheet.total_points), ("multiple_choice", 8));
+
+        let attempt = QuizService::start_attempt(&repo, quiz.id, student.id).await.unwrap();
+        assert!(attempt.deadline_at.is_none());
+        let error = QuizService::get_attempt(&repo, attempt.id, classmate.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        let error = QuizService::submit_attempt(&repo, attempt.id, classmate.id, SubmitQuizRequest { answers: vec![] })
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        QuizService::start_attempt(&repo, quiz.id, classmate.id).await.unwrap();
+        assert_eq!(QuizService::list_attempts(&repo, quiz.id, tutor_id).await.unwrap().len(), 2);
+        assert_eq!(QuizService::get_attempt(&repo, attempt.id, tutor_id).await.unwrap().student_id, student.id);
+
+        // Replacing the quiz keeps the ids of questions sent back with theirs
+        let mut request = quiz_request(None, None);
+        request.questions[0].id = Some(quiz.questions[0].id);
+        let updated = QuizService::update_quiz(&repo, quiz.id, tutor_id, request).await.unwrap();
+        assert_eq!(updated.questions[0].id, quiz.questions[0].id);
+        assert_ne!(updated.questions[1].id, quiz.questions[1].id);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::errors::AppResult;
+use crate::models::RequestContext;
+use crate::repositories::{AuditRepository, CourseRepository, TutorRepository};
+use crate::services::audit::{AuditService, AuditTarget};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+// How long deleted courses and tutor profiles stay restorable
+pub const RETENTION_DAYS: i64 = 30;
+
+// What one purge removed
+#[derive(Debug, Default)]
+pub struct PurgeReport {
+    pub tutor_ids: Vec<Uuid>,
+    pub course_ids: Vec<Uuid>,
+}
+
+pub struct RetentionService;
+
+impl RetentionService {
+    // Last moment a record deleted at `deleted_at` can be restored
+    pub fn restorable_until(deleted_at: DateTime<Utc>) -> DateTime<Utc> {
+        deleted_at + Duration::days(RETENTION_DAYS)
+    }
+
+    pub fn is_restorable(deleted_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        now <= Self::restorable_until(deleted_at)
+    }
+
+    // Removes the courses and tutor profiles whose retention window has
+    // passed. Profiles go first; their courses go with them.
+    pub async fn purge_expired<R: TutorRepository + CourseRepository + AuditRepository>(
+        repo: &R,
+        now: DateTime<Utc>,
+    ) -> AppResult<PurgeReport> {
+        let deleted_before = now - Duration::days(RETENTION_DAYS);
+        let context = RequestContext::default();
+
+        let tutor_ids = repo.purge_deleted_tutors(deleted_before).await?;
+        for tutor_id in &tutor_ids {
+            let target = AuditTarget::Tutor(*tutor_id);
+            AuditService::record(repo, &context, None, "tutor.purged", target, None, None).await?;
+        }
+
+        let course_ids = repo.purge_deleted_courses(deleted_before).await?;
+        for course_id in &course_ids {
+            let target = AuditTarget::Course(*course_id);
+            AuditService::record(repo, &context, None, "course.purged", target, None, None).await?;
+        }
+
+        Ok(PurgeReport { tutor_ids, course_ids })
+    }
+}
@@ -0,0 +1,51 @@
+// Fixtures shared by the service tests. They write straight to the in-memory
+// repository so tests only pay for bcrypt when they exercise authentication.
+use chrono::Utc;
+use uuid::Uuid;
+use crate::models::{TutorProfile, User, UserRole};
+use crate::repositories::{InMemoryRepository, TutorRepository, UserRepository};
+
+pub const TUTOR_BIO: &str = "Experienced mathematics tutor who loves helping students build confidence.";
+
+pub async fn insert_user(repo: &InMemoryRepository, role: UserRole) -> User {
+    let now = Utc::now();
+    let id = Uuid::new_v4();
+    let user = User {
+        id,
+        email: format!("{}@ezytutor.test", id),
+        password_hash: "not-a-real-hash".to_string(),
+        first_name: "Test".to_string(),
+        last_name: format!("{:?}", role),
+        role,
+        is_active: true,
+        avatar_updated_at: None,
+        token_version: 0,
+        created_at: now,
+        updated_at: now,
+    };
+    repo.insert_user(&user).await.unwrap();
+    user
+}
+
+pub async fn insert_tutor(repo: &InMemoryRepository, specializations: &[&str]) -> (User, TutorProfile) {
+    let user = insert_user(repo, UserRole::Tutor).await;
+    let now = Utc::now();
+    let tutor = TutorProfile {
+        id: Uuid::new_v4(),
+        user_id: user.id,
+        bio: TUTOR_BIO.to_string(),
+        specializations: specializations.iter().map(|s| s.to_string()).collect(),
+        hourly_rate: 2500,
+        years_experience: 3,
+        rating: None,
+        total_reviews: 0,
+        rating_sum: 0,
+        is_verified: false,
+        is_available: true,
+        deleted_at: None,
+        created_at: now,
+        updated_at: now,
+    };
+    repo.insert_tutor(&tutor).await.unwrap();
+    (user, tutor)
+}
@@ -1,414 +1,758 @@
-use crate::database::DbPool;
 use crate::errors::{AppError, AppResult};
 use crate::models::{
     TutorProfile, CreateTutorProfileRequest, UpdateTutorProfileRequest, TutorResponse,
-    TutorReview, CreateReviewRequest, ReviewResponse, UserRole
+    TutorReview, CreateReviewRequest, UpdateReviewRequest, ReviewEdit, ReviewReply, ReviewReplyRequest,
+    ReviewResponse, UserRole, AvatarUrls, Precondition, RequestContext
 };
-use chrono::Utc;
-use sqlx::Row;
+use crate::repositories::{
+    AuditRepository, CourseRepository, NotificationRepository, ReviewRepository, TutorRepository, UserRepository
+};
+use crate::services::audit::{self, AuditService, AuditTarget};
+use crate::services::{AuthService, NotificationService, RetentionService};
+use chrono::{Duration, Utc};
 use uuid::Uuid;
 use validator::Validate;
 
+// How long after posting a review its author may still edit or delete it
+pub const REVIEW_EDIT_WINDOW_DAYS: i64 = 14;
+
 pub struct TutorService;
 
 impl TutorService {
-    pub async fn create_tutor_profile(
-        pool: &DbPool,
+    pub async fn create_tutor_profile<R: UserRepository + TutorRepository + CourseRepository + AuditRepository>(
+        repo: &R,
         user_id: Uuid,
         request: CreateTutorProfileRequest,
+        context: &RequestContext,
     ) -> AppResult<TutorResponse> {
         // Validate input
         request.validate()
             .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
 
         // Check if user exists and is a tutor
-        let user = sqlx::query(
-            "SELECT first_name, last_name, email, role FROM users WHERE id = $1"
-        )
-        .bind(user_id)
-        .fetch_optional(pool)
-        .await?
-        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
-
-        let role: UserRole = user.get("role");
-        if role != UserRole::Tutor && role != UserRole::Admin {
+        if !AuthService::verify_user_role(repo, user_id, UserRole::Tutor).await? {
             return Err(AppError::Authorization("Only tutors can create tutor profiles".to_string()));
         }
 
         // Check if tutor profile already exists
-        let existing_profile = sqlx::query("SELECT id FROM tutors WHERE user_id = $1")
-            .bind(user_id)
-            .fetch_optional(pool)
-            .await?;
+        let existing_profile = repo.find_tutor_by_user_id(user_id).await?;
 
         if existing_profile.is_some() {
             return Err(AppError::BadRequest("Tutor profile already exists".to_string()));
         }
 
-        // Insert tutor profile
-        let tutor_id = Uuid::new_v4();
+        // A deleted profile is restored rather than replaced while it can be;
+        // once expired it is purged to make way for the new one
         let now = Utc::now();
+        if let Some(deleted) = repo.find_deleted_tutor_by_user_id(user_id).await? {
+            if deleted.deleted_at.is_some_and(|deleted_at| RetentionService::is_restorable(deleted_at, now)) {
+                return Err(AppError::Conflict(
+                    "A deleted tutor profile can still be restored; restore it instead".to_string(),
+                ));
+            }
+            RetentionService::purge_expired(repo, now).await?;
+        }
 
-        sqlx::query(
-            r#"
-            INSERT INTO tutors (id, user_id, bio, specializations, hourly_rate, years_experience, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-            "#
-        )
-        .bind(tutor_id)
-        .bind(user_id)
-        .bind(&request.bio)
-        .bind(&request.specializations)
-        .bind(request.hourly_rate)
-        .bind(request.years_experience)
-        .bind(now)
-        .bind(now)
-        .execute(pool)
-        .await?;
-
-        // Return tutor response
-        Ok(TutorResponse {
-            id: tutor_id,
+        // Insert tutor profile
+        let tutor = TutorProfile {
+            id: Uuid::new_v4(),
             user_id,
-            first_name: user.get("first_name"),
-            last_name: user.get("last_name"),
-            email: user.get("email"),
             bio: request.bio,
             specializations: request.specializations,
             hourly_rate: request.hourly_rate,
             years_experience: request.years_experience,
             rating: None,
             total_reviews: 0,
+            rating_sum: 0,
             is_verified: false,
             is_available: true,
+            deleted_at: None,
             created_at: now,
             updated_at: now,
-        })
+        };
+
+        repo.insert_tutor(&tutor).await?;
+        let changes = audit::changes(None, Some(&tutor));
+        let target = AuditTarget::Tutor(tutor.id);
+        AuditService::record(repo, context, Some(user_id), "tutor.created", target, changes, None).await?;
+
+        // Return tutor response
+        Self::get_tutor_by_id(repo, tutor.id).await
     }
 
-    pub async fn get_all_tutors(pool: &DbPool) -> AppResult<Vec<TutorResponse>> {
-        let tutors = sqlx::query(
-            r#"
-            SELECT t.*, u.first_name, u.last_name, u.email
-            FROM tutors t
-            JOIN users u ON t.user_id = u.id
-            WHERE t.is_available = true
-            ORDER BY t.rating DESC NULLS LAST, t.created_at DESC
-            "#
-        )
-        .fetch_all(pool)
-        .await?;
-
-        let mut tutor_responses = Vec::new();
-        for row in tutors {
-            tutor_responses.push(TutorResponse {
-                id: row.get("id"),
-                user_id: row.get("user_id"),
-                first_name: row.get("first_name"),
-                last_name: row.get("last_name"),
-                email: row.get("email"),
-                bio: row.get("bio"),
-                specializations: row.get("specializations"),
-                hourly_rate: row.get("hourly_rate"),
-                years_experience: row.get("years_experience"),
-                rating: row.get("rating"),
-                total_reviews: row.get("total_reviews"),
-                is_verified: row.get("is_verified"),
-                is_available: row.get("is_available"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            });
-        }
-
-        Ok(tutor_responses)
+    pub async fn get_all_tutors<R: TutorRepository>(repo: &R) -> AppResult<Vec<TutorResponse>> {
+        let tutors = repo.list_available_tutors().await?;
+        Ok(tutors.into_iter().map(TutorResponse::from).collect())
     }
 
-    pub async fn get_tutor_by_id(pool: &DbPool, tutor_id: Uuid) -> AppResult<TutorResponse> {
-        let tutor = sqlx::query(
-            r#"
-            SELECT t.*, u.first_name, u.last_name, u.email
-            FROM tutors t
-            JOIN users u ON t.user_id = u.id
-            WHERE t.id = $1
-            "#
-        )
-        .bind(tutor_id)
-        .fetch_optional(pool)
-        .await?
-        .ok_or_else(|| AppError::NotFound("Tutor not found".to_string()))?;
-
-        Ok(TutorResponse {
-            id: tutor.get("id"),
-            user_id: tutor.get("user_id"),
-            first_name: tutor.get("first_name"),
-            last_name: tutor.get("last_name"),
-            email: tutor.get("email"),
-            bio: tutor.get("bio"),
-            specializations: tutor.get("specializations"),
-            hourly_rate: tutor.get("hourly_rate"),
-            years_experience: tutor.get("years_experience"),
-            rating: tutor.get("rating"),
-            total_reviews: tutor.get("total_reviews"),
-            is_verified: tutor.get("is_verified"),
-            is_available: tutor.get("is_available"),
-            created_at: tutor.get("created_at"),
-            updated_at: tutor.get("updated_at"),
-        })
+    pub async fn get_tutor_by_id<R: TutorRepository>(repo: &R, tutor_id: Uuid) -> AppResult<TutorResponse> {
+        let tutor = repo.find_tutor_by_id(tutor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor not found".to_string()))?;
+
+        Ok(TutorResponse::from(tutor))
     }
 
-    pub async fn update_tutor_profile(
-        pool: &DbPool,
+    pub async fn update_tutor_profile<R: TutorRepository + AuditRepository>(
+        repo: &R,
         user_id: Uuid,
         request: UpdateTutorProfileRequest,
+        if_match: &Precondition,
+        context: &RequestContext,
     ) -> AppResult<TutorResponse> {
         // Validate input
         request.validate()
             .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
 
         // Check if tutor profile exists
-        let existing_tutor = sqlx::query("SELECT id FROM tutors WHERE user_id = $1")
-            .bind(user_id)
-            .fetch_optional(pool)
+        let mut tutor = repo.find_tutor_by_user_id(user_id)
             .await?
-            .ok_or_else(|| AppError::NotFound("Tutor profile not found".to_string()))?;
-
-        let tutor_id: Uuid = existing_tutor.get("id");
-
-        // Build update query dynamically
-        let mut query_parts = Vec::new();
-        let mut bind_count = 1;
-
-        if request.bio.is_some() {
-            query_parts.push(format!("bio = ${}", bind_count));
-            bind_count += 1;
-        }
-        if request.specializations.is_some() {
-            query_parts.push(format!("specializations = ${}", bind_count));
-            bind_count += 1;
-        }
-        if request.hourly_rate.is_some() {
-            query_parts.push(format!("hourly_rate = ${}", bind_count));
-            bind_count += 1;
-        }
-        if request.years_experience.is_some() {
-            query_parts.push(format!("years_experience = ${}", bind_count));
-            bind_count += 1;
-        }
-        if request.is_available.is_some() {
-            query_parts.push(format!("is_available = ${}", bind_count));
-            bind_count += 1;
+            .ok_or_else(|| AppError::NotFound("Tutor profile not found".to_string()))?
+            .tutor;
+        if !if_match.matches(tutor.updated_at) {
+            return Err(AppError::PreconditionFailed("Tutor profile has changed since it was read".to_string()));
         }
 
-        if query_parts.is_empty() {
+        if request.bio.is_none()
+            && request.specializations.is_none()
+            && request.hourly_rate.is_none()
+            && request.years_experience.is_none()
+            && request.is_available.is_none()
+        {
             return Err(AppError::BadRequest("No fields to update".to_string()));
         }
+        let before = tutor.clone();
 
-        query_parts.push(format!("updated_at = ${}", bind_count));
-        let query = format!(
-            "UPDATE tutors SET {} WHERE user_id = ${}",
-            query_parts.join(", "),
-            bind_count + 1
-        );
-
-        let mut query_builder = sqlx::query(&query);
-
-        // Bind values in the same order as the query parts
-        if let Some(bio) = &request.bio {
-            query_builder = query_builder.bind(bio);
+        if let Some(bio) = request.bio {
+            tutor.bio = bio;
         }
-        if let Some(specializations) = &request.specializations {
-            query_builder = query_builder.bind(specializations);
+        if let Some(specializations) = request.specializations {
+            tutor.specializations = specializations;
         }
         if let Some(hourly_rate) = request.hourly_rate {
-            query_builder = query_builder.bind(hourly_rate);
+            tutor.hourly_rate = hourly_rate;
         }
         if let Some(years_experience) = request.years_experience {
-            query_builder = query_builder.bind(years_experience);
+            tutor.years_experience = years_experience;
         }
         if let Some(is_available) = request.is_available {
-            query_builder = query_builder.bind(is_available);
+            tutor.is_available = is_available;
         }
+        tutor.updated_at = Utc::now();
 
-        query_builder = query_builder.bind(Utc::now()).bind(user_id);
-
-        query_builder.execute(pool).await?;
+        // Someone else may have saved between the check above and this write
+        if !repo.update_tutor(&tutor, before.updated_at).await? {
+            return Err(AppError::PreconditionFailed("Tutor profile has changed since it was read".to_string()));
+        }
+        let changes = audit::changes(Some(&before), Some(&tutor));
+        let target = AuditTarget::Tutor(tutor.id);
+        AuditService::record(repo, context, Some(user_id), "tutor.updated", target, changes, None).await?;
 
         // Return updated tutor
-        Self::get_tutor_by_id(pool, tutor_id).await
+        Self::get_tutor_by_id(repo, tutor.id).await
+    }
+
+    // Soft delete: the profile, its courses and its reviews disappear from
+    // every listing until the tutor restores the profile or it is purged
+    pub async fn delete_tutor_profile<R: TutorRepository + CourseRepository + AuditRepository>(
+        repo: &R,
+        user_id: Uuid,
+        context: &RequestContext,
+    ) -> AppResult<()> {
+        let mut tutor = repo.find_tutor_by_user_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor profile not found".to_string()))?
+            .tutor;
+
+        let courses = repo.list_courses_by_tutor_user(user_id).await?;
+        if courses.iter().any(|course| course.enrolled_students > 0) {
+            return Err(AppError::Conflict(
+                "Courses have enrolled students; mark the profile unavailable instead".to_string(),
+            ));
+        }
+        let before = tutor.clone();
+        let deleted_at = Utc::now();
+        tutor.deleted_at = Some(deleted_at);
+
+        // The repository repeats the enrollment check atomically with the delete
+        if !repo.delete_tutor(tutor.id, deleted_at).await? {
+            let courses = repo.list_courses_by_tutor_user(user_id).await?;
+            if courses.iter().any(|course| course.enrolled_students > 0) {
+                return Err(AppError::Conflict(
+                    "Courses have enrolled students; mark the profile unavailable instead".to_string(),
+                ));
+            }
+            return Err(AppError::NotFound("Tutor profile not found".to_string()));
+        }
+        let changes = audit::changes(Some(&before), Some(&tutor));
+        let target = AuditTarget::Tutor(tutor.id);
+        AuditService::record(repo, context, Some(user_id), "tutor.deleted", target, changes, None).await?;
+
+        Ok(())
+    }
+
+    pub async fn restore_tutor_profile<R: TutorRepository + AuditRepository>(
+        repo: &R,
+        user_id: Uuid,
+        context: &RequestContext,
+    ) -> AppResult<TutorResponse> {
+        let mut tutor = repo.find_deleted_tutor_by_user_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Deleted tutor profile not found".to_string()))?;
+
+        let Some(deleted_at) = tutor.deleted_at else {
+            return Err(AppError::NotFound("Deleted tutor profile not found".to_string()));
+        };
+        if !RetentionService::is_restorable(deleted_at, Utc::now()) {
+            return Err(AppError::Conflict("Tutor profile can no longer be restored".to_string()));
+        }
+        let before = tutor.clone();
+        tutor.deleted_at = None;
+
+        if !repo.restore_tutor(tutor.id).await? {
+            return Err(AppError::NotFound("Deleted tutor profile not found".to_string()));
+        }
+        let changes = audit::changes(Some(&before), Some(&tutor));
+        let target = AuditTarget::Tutor(tutor.id);
+        AuditService::record(repo, context, Some(user_id), "tutor.restored", target, changes, None).await?;
+
+        Self::get_tutor_by_id(repo, tutor.id).await
     }
 
-    pub async fn create_review(
-        pool: &DbPool,
+    pub async fn create_review<R: UserRepository + TutorRepository + ReviewRepository + AuditRepository>(
+        repo: &R,
         tutor_id: Uuid,
         student_id: Uuid,
         request: CreateReviewRequest,
+        context: &RequestContext,
     ) -> AppResult<ReviewResponse> {
         // Validate input
         request.validate()
             .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
 
         // Check if tutor exists
-        let tutor_exists = sqlx::query("SELECT id FROM tutors WHERE id = $1")
-            .bind(tutor_id)
-            .fetch_optional(pool)
-            .await?;
+        let tutor_exists = repo.find_tutor_by_id(tutor_id).await?;
 
         if tutor_exists.is_none() {
             return Err(AppError::NotFound("Tutor not found".to_string()));
         }
 
         // Check if review already exists
-        let existing_review = sqlx::query(
-            "SELECT id FROM tutor_reviews WHERE tutor_id = $1 AND student_id = $2"
-        )
-        .bind(tutor_id)
-        .bind(student_id)
-        .fetch_optional(pool)
-        .await?;
+        let existing_review = repo.find_review(tutor_id, student_id).await?;
 
         if existing_review.is_some() {
             return Err(AppError::BadRequest("Review already exists for this tutor".to_string()));
         }
 
-        // Insert review
-        let review_id = Uuid::new_v4();
-        let now = Utc::now();
+        // Get student name for response
+        let student = AuthService::get_user_by_id(repo, student_id).await?;
 
-        sqlx::query(
-            "INSERT INTO tutor_reviews (id, tutor_id, student_id, rating, comment, created_at) VALUES ($1, $2, $3, $4, $5, $6)"
-        )
-        .bind(review_id)
-        .bind(tutor_id)
-        .bind(student_id)
-        .bind(request.rating)
-        .bind(&request.comment)
-        .bind(now)
-        .execute(pool)
-        .await?;
-
-        // Update tutor rating and review count
-        Self::update_tutor_rating(pool, tutor_id).await?;
+        // Insert review
+        let review = TutorReview {
+            id: Uuid::new_v4(),
+            tutor_id,
+            student_id,
+            rating: request.rating,
+            comment: request.comment,
+            created_at: Utc::now(),
+            edited_at: None,
+        };
 
-        // Get student name for response
-        let student = sqlx::query("SELECT first_name, last_name FROM users WHERE id = $1")
-            .bind(student_id)
-            .fetch_one(pool)
-            .await?;
+        // The tutor's rating moves with the insert; a concurrent duplicate
+        // loses to the unique constraint
+        if !repo.insert_review(&review).await? {
+            return Err(AppError::BadRequest("Review already exists for this tutor".to_string()));
+        }
 
-        let student_name = format!("{} {}", 
-            student.get::<String, _>("first_name"),
-            student.get::<String, _>("last_name")
-        );
+        let changes = audit::changes(None, Some(&review));
+        let target = AuditTarget::Review(review.id);
+        AuditService::record(repo, context, Some(student_id), "review.created", target, changes, None).await?;
 
         Ok(ReviewResponse {
-            id: review_id,
+            id: review.id,
             tutor_id,
-            student_name,
-            rating: request.rating,
-            comment: request.comment,
-            created_at: now,
+            student_name: format!("{} {}", student.first_name, student.last_name),
+            student_avatar: student.avatar_updated_at.map(|at| AvatarUrls::new(student.id, at)),
+            rating: review.rating,
+            comment: review.comment,
+            created_at: review.created_at,
+            edited_at: None,
+            reply: None,
         })
     }
 
-    pub async fn get_tutor_reviews(pool: &DbPool, tutor_id: Uuid) -> AppResult<Vec<ReviewResponse>> {
-        let reviews = sqlx::query(
-            r#"
-            SELECT r.id, r.tutor_id, r.rating, r.comment, r.created_at,
-                   u.first_name, u.last_name
-            FROM tutor_reviews r
-            JOIN users u ON r.student_id = u.id
-            WHERE r.tutor_id = $1
-            ORDER BY r.created_at DESC
-            "#
-        )
-        .bind(tutor_id)
-        .fetch_all(pool)
-        .await?;
-
-        let mut review_responses = Vec::new();
-        for row in reviews {
-            let student_name = format!("{} {}", 
-                row.get::<String, _>("first_name"),
-                row.get::<String, _>("last_name")
-            );
-
-            review_responses.push(ReviewResponse {
-                id: row.get("id"),
-                tutor_id: row.get("tutor_id"),
-                student_name,
-                rating: row.get("rating"),
-                comment: row.get("comment"),
-                created_at: row.get("created_at"),
-            });
-        }
-
-        Ok(review_responses)
+    pub async fn get_tutor_reviews<R: ReviewRepository>(repo: &R, tutor_id: Uuid) -> AppResult<Vec<ReviewResponse>> {
+        let reviews = repo.list_reviews_for_tutor(tutor_id).await?;
+        Ok(reviews.into_iter().map(ReviewResponse::from).collect())
+    }
+
+    pub async fn update_review<R: TutorRepository + ReviewRepository + AuditRepository>(
+        repo: &R,
+        review_id: Uuid,
+        student_id: Uuid,
+        request: UpdateReviewRequest,
+        context: &RequestContext,
+    ) -> AppResult<ReviewResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        if request.rating.is_none() && request.comment.is_none() {
+            return Err(AppError::BadRequest("No fields to update".to_string()));
+        }
+
+        let before = Self::find_own_review(repo, review_id, student_id).await?;
+        let mut review = before.clone();
+        if let Some(rating) = request.rating {
+            review.rating = rating;
+        }
+        if let Some(comment) = request.comment {
+            review.comment = comment;
+        }
+        review.edited_at = Some(Utc::now());
+
+        // Keeps the replaced version and moves the tutor's rating in one step
+        if !repo.update_review(&review).await? {
+            return Err(AppError::NotFound("Review not found".to_string()));
+        }
+
+        let changes = audit::changes(Some(&before), Some(&review));
+        let target = AuditTarget::Review(review.id);
+        AuditService::record(repo, context, Some(student_id), "review.updated", target, changes, None).await?;
+
+        Self::get_review(repo, review_id).await
     }
 
-    async fn update_tutor_rating(pool: &DbPool, tutor_id: Uuid) -> AppResult<()> {
-        // Calculate new rating and review count
-        let stats = sqlx::query(
-            "SELECT AVG(rating) as avg_rating, COUNT(*) as total_reviews FROM tutor_reviews WHERE tutor_id = $1"
-        )
-        .bind(tutor_id)
-        .fetch_one(pool)
-        .await?;
-
-        let avg_rating: Option<f64> = stats.get("avg_rating");
-        let total_reviews: i64 = stats.get("total_reviews");
-
-        // Update tutor record
-        sqlx::query(
-            "UPDATE tutors SET rating = $1, total_reviews = $2, updated_at = $3 WHERE id = $4"
-        )
-        .bind(avg_rating.map(|r| r as f32))
-        .bind(total_reviews as i32)
-        .bind(Utc::now())
-        .bind(tutor_id)
-        .execute(pool)
-        .await?;
+    pub async fn delete_review<R: TutorRepository + ReviewRepository + AuditRepository>(
+        repo: &R,
+        review_id: Uuid,
+        student_id: Uuid,
+        context: &RequestContext,
+    ) -> AppResult<()> {
+        let review = Self::find_own_review(repo, review_id, student_id).await?;
+        if !repo.delete_review(review_id).await? {
+            return Err(AppError::NotFound("Review not found".to_string()));
+        }
 
-        Ok(())
+        let changes = audit::changes(Some(&review), None);
+        let target = AuditTarget::Review(review_id);
+        AuditService::record(repo, context, Some(student_id), "review.deleted", target, changes, None).await
     }
 
-    pub async fn search_tutors_by_specialization(
-        pool: &DbPool,
+    // Versions of the review its author has replaced, oldest first
+    pub async fn get_review_edits<R: TutorRepository + ReviewRepository>(repo: &R, review_id: Uuid) -> AppResult<Vec<ReviewEdit>> {
+        Self::find_live_review(repo, review_id).await?;
+        repo.list_review_edits(review_id).await
+    }
+
+    pub async fn reply_to_review<R: TutorRepository + ReviewRepository + NotificationRepository + AuditRepository>(
+        repo: &R,
+        review_id: Uuid,
+        user_id: Uuid,
+        request: ReviewReplyRequest,
+        context: &RequestContext,
+    ) -> AppResult<ReviewResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let review = Self::find_live_review(repo, review_id).await?;
+        let tutor = repo.find_tutor_by_user_id(user_id).await?;
+        if tutor.map(|tutor| tutor.tutor.id) != Some(review.tutor_id) {
+            return Err(AppError::Authorization("Only the reviewed tutor can reply to a review".to_string()));
+        }
+
+        let reply = ReviewReply {
+            review_id,
+            comment: request.comment,
+            created_at: Utc::now(),
+        };
+        if !repo.insert_review_reply(&reply).await? {
+            return Err(AppError::Conflict("This review already has a reply".to_string()));
+        }
+
+        let changes = audit::changes(None, Some(&reply));
+        let target = AuditTarget::Review(review_id);
+        AuditService::record(repo, context, Some(user_id), "review.replied", target, changes, None).await?;
+        NotificationService::notify(
+            repo,
+            review.student_id,
+            "review_replied",
+            "Your review has a reply".to_string(),
+            reply.comment.clone(),
+            Some(format!("/api/v1/tutors/{}/reviews", review.tutor_id)),
+        ).await?;
+
+        Self::get_review(repo, review_id).await
+    }
+
+    // Reviews of a deleted tutor are hidden with the tutor
+    async fn find_live_review<R: TutorRepository + ReviewRepository>(repo: &R, review_id: Uuid) -> AppResult<TutorReview> {
+        let review = repo.find_review_by_id(review_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Review not found".to_string()))?;
+        if repo.find_tutor_by_id(review.tutor_id).await?.is_none() {
+            return Err(AppError::NotFound("Review not found".to_string()));
+        }
+        Ok(review)
+    }
+
+    // The caller's review, while it can still be changed
+    async fn find_own_review<R: TutorRepository + ReviewRepository>(
+        repo: &R,
+        review_id: Uuid,
+        student_id: Uuid,
+    ) -> AppResult<TutorReview> {
+        let review = Self::find_live_review(repo, review_id).await?;
+        if review.student_id != student_id {
+            return Err(AppError::Authorization("You can only change your own reviews".to_string()));
+        }
+        if Utc::now() > review.created_at + Duration::days(REVIEW_EDIT_WINDOW_DAYS) {
+            return Err(AppError::Conflict(format!(
+                "Reviews can only be changed within {} days of posting",
+                REVIEW_EDIT_WINDOW_DAYS
+            )));
+        }
+        Ok(review)
+    }
+
+    async fn get_review<R: ReviewRepository>(repo: &R, review_id: Uuid) -> AppResult<ReviewResponse> {
+        repo.find_review_with_student(review_id)
+            .await?
+            .map(ReviewResponse::from)
+            .ok_or_else(|| AppError::NotFound("Review not found".to_string()))
+    }
+
+    pub async fn search_tutors_by_specialization<R: TutorRepository>(
+        repo: &R,
         specialization: &str,
     ) -> AppResult<Vec<TutorResponse>> {
-        let tutors = sqlx::query(
-            r#"
-            SELECT t.*, u.first_name, u.last_name, u.email
-            FROM tutors t
-            JOIN users u ON t.user_id = u.id
-            WHERE t.is_available = true AND $1 = ANY(t.specializations)
-            ORDER BY t.rating DESC NULLS LAST, t.created_at DESC
-            "#
-        )
-        .bind(specialization)
-        .fetch_all(pool)
-        .await?;
-
-        let mut tutor_responses = Vec::new();
-        for row in tutors {
-            tutor_responses.push(TutorResponse {
-                id: row.get("id"),
-                user_id: row.get("user_id"),
-                first_name: row.get("first_name"),
-                last_name: row.get("last_name"),
-                email: row.get("email"),
-                bio: row.get("bio"),
-                specializations: row.get("specializations"),
-                hourly_rate: row.get("hourly_rate"),
-                years_experience: row.get("years_experience"),
-                rating: row.get("rating"),
-                total_reviews: row.get("total_reviews"),
-                is_verified: row.get("is_verified"),
-                is_available: row.get("is_available"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            });
-        }
-
-        Ok(tutor_responses)
+        let tutors = repo.search_tutors_by_specialization(specialization).await?;
+        Ok(tutors.into_iter().map(TutorResponse::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateCourseRequest, DifficultyLevel};
+    use crate::repositories::{EnrollmentRepository, InMemoryRepository};
+    use crate::services::retention::RETENTION_DAYS;
+    use crate::services::test_support::{insert_tutor, insert_user, TUTOR_BIO};
+    use crate::services::{CourseService, EnrollmentService};
+    use chrono::Duration;
+
+    fn profile_request() -> CreateTutorProfileRequest {
+        CreateTutorProfileRequest {
+            bio: TUTOR_BIO.to_string(),
+            specializations: vec!["Math".to_string()],
+            hourly_rate: 3000,
+            years_experience: 5,
+        }
+    }
+
+    fn empty_update() -> UpdateTutorProfileRequest {
+        UpdateTutorProfileRequest {
+            bio: None,
+            specializations: None,
+            hourly_rate: None,
+            years_experience: None,
+            is_available: None,
+        }
+    }
+
+    fn course_request() -> CreateCourseRequest {
+        CreateCourseRequest {
+            title: "Trigonometry".to_string(),
+            description: "Angles, identities and the unit circle".to_string(),
+            price: 4900,
+            duration_minutes: 60,
+            category: "Mathematics".to_string(),
+            difficulty_level: DifficultyLevel::Beginner,
+            max_students: None,
+        }
+    }
+
+    fn review(rating: i32) -> CreateReviewRequest {
+        CreateReviewRequest {
+            rating,
+            comment: Some("Very patient".to_string()),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn only_tutors_and_admins_can_create_profiles() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let student = insert_user(&repo, UserRole::Student).await;
+        let error = TutorService::create_tutor_profile(&repo, student.id, profile_request(), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+
+        let admin = insert_user(&repo, UserRole::Admin).await;
+        let tutor = TutorService::create_tutor_profile(&repo, admin.id, profile_request(), &context).await.unwrap();
+        assert_eq!(tutor.user_id, admin.id);
+        assert_eq!(tutor.email, admin.email);
+    }
+
+    #[actix_rt::test]
+    async fn create_profile_rejects_duplicates_and_short_bios() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (user, _) = insert_tutor(&repo, &["Math"]).await;
+        let error = TutorService::create_tutor_profile(&repo, user.id, profile_request(), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+
+        let newcomer = insert_user(&repo, UserRole::Tutor).await;
+        let mut request = profile_request();
+        request.bio = "Too short".to_string();
+        let error = TutorService::create_tutor_profile(&repo, newcomer.id, request, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+    }
+
+    #[actix_rt::test]
+    async fn update_profile_requires_existing_profile_and_fields() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let student = insert_user(&repo, UserRole::Student).await;
+        let mut update = empty_update();
+        update.hourly_rate = Some(4000);
+        let error = TutorService::update_tutor_profile(&repo, student.id, update, &Precondition::Any, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        let (user, _) = insert_tutor(&repo, &["Math"]).await;
+        let error = TutorService::update_tutor_profile(&repo, user.id, empty_update(), &Precondition::Any, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+
+        let mut update = empty_update();
+        update.hourly_rate = Some(4000);
+        let updated = TutorService::update_tutor_profile(&repo, user.id, update, &Precondition::Any, &context).await.unwrap();
+        assert_eq!(updated.hourly_rate, 4000);
+        assert_eq!(updated.bio, TUTOR_BIO);
+    }
+
+    #[actix_rt::test]
+    async fn listings_hide_unavailable_tutors_and_filter_by_specialization() {
+        let repo = InMemoryRepository::new();
+        let (math, _) = insert_tutor(&repo, &["Math"]).await;
+        insert_tutor(&repo, &["Physics", "Math"]).await;
+        let (away, _) = insert_tutor(&repo, &["Math"]).await;
+
+        let mut update = empty_update();
+        update.is_available = Some(false);
+        TutorService::update_tutor_profile(&repo, away.id, update, &Precondition::Any, &RequestContext::default()).await.unwrap();
+
+        assert_eq!(TutorService::get_all_tutors(&repo).await.unwrap().len(), 2);
+        assert_eq!(TutorService::search_tutors_by_specialization(&repo, "Math").await.unwrap().len(), 2);
+
+        let physics = TutorService::search_tutors_by_specialization(&repo, "Physics").await.unwrap();
+        assert_eq!(physics.len(), 1);
+        assert_ne!(physics[0].user_id, math.id);
+    }
+
+    #[actix_rt::test]
+    async fn reviews_update_rating_and_ranking() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (_, first) = insert_tutor(&repo, &["Math"]).await;
+        let (_, second) = insert_tutor(&repo, &["Math"]).await;
+
+        for rating in [5, 4] {
+            let student = insert_user(&repo, UserRole::Student).await;
+            TutorService::create_review(&repo, second.id, student.id, review(rating), &context).await.unwrap();
+        }
+        let student = insert_user(&repo, UserRole::Student).await;
+        TutorService::create_review(&repo, first.id, student.id, review(3), &context).await.unwrap();
+
+        let tutor = TutorService::get_tutor_by_id(&repo, second.id).await.unwrap();
+        assert_eq!(tutor.rating, Some(4.5));
+        assert_eq!(tutor.total_reviews, 2);
+
+        let ranked = TutorService::get_all_tutors(&repo).await.unwrap();
+        assert_eq!(ranked[0].id, second.id);
+        assert_eq!(TutorService::get_tutor_reviews(&repo, second.id).await.unwrap().len(), 2);
+    }
+
+    #[actix_rt::test]
+    async fn create_review_rejects_unknown_tutor_duplicates_and_bad_ratings() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (_, tutor) = insert_tutor(&repo, &["Math"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        let error = TutorService::create_review(&repo, Uuid::new_v4(), student.id, review(5), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        let error = TutorService::create_review(&repo, tutor.id, student.id, review(6), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+
+        let created = TutorService::create_review(&repo, tutor.id, student.id, review(5), &context).await.unwrap();
+        assert_eq!(created.student_name, format!("{} {}", student.first_name, student.last_name));
+
+        let error = TutorService::create_review(&repo, tutor.id, student.id, review(4), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+    }
+
+    #[actix_rt::test]
+    async fn authors_edit_and_delete_reviews_within_the_window() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (_, tutor) = insert_tutor(&repo, &["Math"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        let created = TutorService::create_review(&repo, tutor.id, student.id, review(5), &context).await.unwrap();
+
+        let edit = |rating: Option<i32>, comment: Option<Option<&str>>| UpdateReviewRequest {
+            rating,
+            comment: comment.map(|comment| comment.map(str::to_string)),
+        };
+        let intruder = insert_user(&repo, UserRole::Student).await;
+        let error = TutorService::update_review(&repo, created.id, intruder.id, edit(Some(1), None), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+        let error = TutorService::update_review(&repo, created.id, student.id, edit(None, None), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::BadRequest(_)));
+        let error = TutorService::update_review(&repo, created.id, student.id, edit(Some(0), None), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+
+        let updated = TutorService::update_review(&repo, created.id, student.id, edit(Some(3), Some(None)), &context)
+            .await
+            .unwrap();
+        assert_eq!((updated.rating, updated.comment.as_deref()), (3, None));
+        assert!(updated.edited_at.is_some());
+        TutorService::update_review(&repo, created.id, student.id, edit(None, Some(Some("Clear"))), &context)
+            .await
+            .unwrap();
+
+        let edits = TutorService::get_review_edits(&repo, created.id).await.unwrap();
+        let versions: Vec<_> = edits.iter().map(|edit| (edit.rating, edit.comment.as_deref())).collect();
+        assert_eq!(versions, vec![(5, Some("Very patient")), (3, None)]);
+        assert_eq!(TutorService::get_tutor_by_id(&repo, tutor.id).await.unwrap().rating, Some(3.0));
+
+        // Past the window the review is frozen
+        let veteran = insert_user(&repo, UserRole::Student).await;
+        let old = TutorReview {
+            id: Uuid::new_v4(),
+            tutor_id: tutor.id,
+            student_id: veteran.id,
+            rating: 4,
+            comment: None,
+            created_at: Utc::now() - Duration::days(REVIEW_EDIT_WINDOW_DAYS + 1),
+            edited_at: None,
+        };
+        repo.insert_review(&old).await.unwrap();
+        let error = TutorService::update_review(&repo, old.id, veteran.id, edit(Some(5), None), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+        let error = TutorService::delete_review(&repo, old.id, veteran.id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        let error = TutorService::delete_review(&repo, created.id, intruder.id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+        TutorService::delete_review(&repo, created.id, student.id, &context).await.unwrap();
+        let tutor = TutorService::get_tutor_by_id(&repo, tutor.id).await.unwrap();
+        assert_eq!((tutor.rating, tutor.total_reviews), (Some(4.0), 1));
+        let error = TutorService::get_review_edits(&repo, created.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+    }
+
+    #[actix_rt::test]
+    async fn reviewed_tutors_reply_once() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (owner, tutor) = insert_tutor(&repo, &["Math"]).await;
+        let (rival, _) = insert_tutor(&repo, &["Math"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        let created = TutorService::create_review(&repo, tutor.id, student.id, review(4), &context).await.unwrap();
+        let reply = |comment: &str| ReviewReplyRequest { comment: comment.to_string() };
+
+        for user_id in [rival.id, student.id] {
+            let error = TutorService::reply_to_review(&repo, created.id, user_id, reply("Thanks!"), &context).await.unwrap_err();
+            assert!(matches!(error, AppError::Authorization(_)));
+        }
+        let error = TutorService::reply_to_review(&repo, created.id, owner.id, reply(""), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+
+        let replied = TutorService::reply_to_review(&repo, created.id, owner.id, reply("Thanks!"), &context).await.unwrap();
+        assert_eq!(replied.reply.map(|reply| reply.comment), Some("Thanks!".to_string()));
+        let error = TutorService::reply_to_review(&repo, created.id, owner.id, reply("Again"), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        let listed = TutorService::get_tutor_reviews(&repo, tutor.id).await.unwrap();
+        assert_eq!(listed[0].reply.as_ref().map(|reply| reply.comment.as_str()), Some("Thanks!"));
+        let notifications = repo.list_notifications(student.id, false).await.unwrap();
+        assert_eq!(notifications[0].kind, "review_replied");
+    }
+
+    #[actix_rt::test]
+    async fn review_writes_move_the_rating_aggregate() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (_, tutor) = insert_tutor(&repo, &["Math"]).await;
+        let aggregate = |tutor: TutorProfile| (tutor.rating, tutor.total_reviews, tutor.rating_sum);
+
+        let mut reviews = Vec::new();
+        for rating in [5, 2] {
+            let student = insert_user(&repo, UserRole::Student).await;
+            let created = TutorService::create_review(&repo, tutor.id, student.id, review(rating), &context).await.unwrap();
+            reviews.push(repo.find_review_by_id(created.id).await.unwrap().unwrap());
+        }
+        assert_eq!(aggregate(repo.find_tutor_by_id(tutor.id).await.unwrap().unwrap().tutor), (Some(3.5), 2, 7));
+
+        // A duplicate that slipped past the lookup leaves the aggregate alone
+        let duplicate = TutorReview { id: Uuid::new_v4(), ..reviews[0].clone() };
+        assert!(!repo.insert_review(&duplicate).await.unwrap());
+
+        let edited = TutorReview { rating: 4, ..reviews[1].clone() };
+        assert!(repo.update_review(&edited).await.unwrap());
+        assert_eq!(aggregate(repo.find_tutor_by_id(tutor.id).await.unwrap().unwrap().tutor), (Some(4.5), 2, 9));
+
+        assert!(repo.delete_review(reviews[0].id).await.unwrap());
+        assert_eq!(aggregate(repo.find_tutor_by_id(tutor.id).await.unwrap().unwrap().tutor), (Some(4.0), 1, 4));
+        assert!(repo.delete_review(edited.id).await.unwrap());
+        assert_eq!(aggregate(repo.find_tutor_by_id(tutor.id).await.unwrap().unwrap().tutor), (None, 0, 0));
+        assert!(repo.list_rating_drift().await.unwrap().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn deleted_profiles_hide_their_courses_until_restored() {
+        let repo = InMemoryRepository::new();
+        let context = RequestContext::default();
+        let (user, tutor) = insert_tutor(&repo, &["Math"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        let course = CourseService::create_course(&repo, user.id, course_request(), &context).await.unwrap();
+        TutorService::create_review(&repo, tutor.id, student.id, review(5), &context).await.unwrap();
+
+        EnrollmentService::enroll(&repo, course.id, student.id).await.unwrap();
+        let error = TutorService::delete_tutor_profile(&repo, user.id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+        // The store refuses too, for enrollments that land after the service's check
+        assert!(!repo.delete_tutor(tutor.id, Utc::now()).await.unwrap());
+        EnrollmentService::drop_enrollment(&repo, course.id, student.id).await.unwrap();
+
+        TutorService::delete_tutor_profile(&repo, user.id, &context).await.unwrap();
+        let error = repo.enroll_student(course.id, student.id, Utc::now()).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        let error = TutorService::get_tutor_by_id(&repo, tutor.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        assert!(TutorService::get_all_tutors(&repo).await.unwrap().is_empty());
+        assert!(TutorService::get_tutor_reviews(&repo, tutor.id).await.unwrap().is_empty());
+        assert!(CourseService::get_all_courses(&repo).await.unwrap().is_empty());
+
+        // A fresh profile would orphan the deleted one while it can be restored
+        let error = TutorService::create_tutor_profile(&repo, user.id, profile_request(), &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        let restored = TutorService::restore_tutor_profile(&repo, user.id, &context).await.unwrap();
+        assert_eq!((restored.id, restored.total_reviews), (tutor.id, 1));
+        assert_eq!(CourseService::get_all_courses(&repo).await.unwrap().len(), 1);
+        let error = TutorService::restore_tutor_profile(&repo, user.id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        // Once expired, the old profile is purged with its courses and reviews
+        repo.delete_tutor(tutor.id, Utc::now() - Duration::days(RETENTION_DAYS + 1)).await.unwrap();
+        let error = TutorService::restore_tutor_profile(&repo, user.id, &context).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        let created = TutorService::create_tutor_profile(&repo, user.id, profile_request(), &context).await.unwrap();
+        assert_ne!(created.id, tutor.id);
+        assert_eq!(created.total_reviews, 0);
+        assert!(CourseService::get_all_courses(&repo).await.unwrap().is_empty());
+        assert!(repo.find_review(tutor.id, student.id).await.unwrap().is_none());
     }
 }
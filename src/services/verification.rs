@@ -0,0 +1,558 @@
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    FilePurpose, SubmitVerificationRequest, TutorResponse, TutorVerificationResponse, UserRole, VerificationAction,
+    VerificationDecisionRequest, VerificationDocument, VerificationDocumentResponse, VerificationEvent,
+    VerificationQueueQuery, VerificationRequest, VerificationRequestResponse, VerificationRequestWithTutor,
+    VerificationStatus,
+};
+use crate::repositories::{FileRepository, NotificationRepository, TutorRepository, UserRepository, VerificationRepository};
+use crate::services::{AuthService, FileService, NotificationService, TutorService};
+use crate::storage::FileStore;
+use chrono::Utc;
+use std::collections::HashSet;
+use uuid::Uuid;
+use validator::Validate;
+
+// Credentials are scans or photos of documents
+pub const CREDENTIAL_CONTENT_TYPES: &[&str] = &["application/pdf", "image/jpeg", "image/png"];
+
+const MY_VERIFICATION_PATH: &str = "/api/v1/my/verification";
+
+// Tutors send credential documents they have uploaded through the files API;
+// admins work the queue of pending requests and approve or reject each one.
+// Every change to a tutor's verified flag is logged, so the history survives
+// later decisions and revocations.
+pub struct VerificationService;
+
+impl VerificationService {
+    pub async fn submit<R: TutorRepository + FileRepository + VerificationRepository>(
+        repo: &R,
+        files: &FileStore,
+        user_id: Uuid,
+        request: SubmitVerificationRequest,
+        base_url: &str,
+    ) -> AppResult<VerificationRequestResponse> {
+        // Validate input
+        request.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+
+        let tutor = repo.find_tutor_by_user_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor profile not found".to_string()))?
+            .tutor;
+        if tutor.is_verified {
+            return Err(AppError::Conflict("Tutor is already verified".to_string()));
+        }
+        let requests = repo.list_tutor_verification_requests(tutor.id).await?;
+        if requests.iter().any(|row| row.request.status == VerificationStatus::Pending) {
+            return Err(AppError::Conflict("A verification request is already waiting for review".to_string()));
+        }
+
+        let mut seen = HashSet::new();
+        for document in &request.documents {
+            if !seen.insert(document.file_id) {
+                return Err(AppError::Validation("Each document may be attached once".to_string()));
+            }
+            let file = repo.find_stored_file(document.file_id)
+                .await?
+                .filter(|file| file.owner_id == user_id && file.purpose == FilePurpose::Upload)
+                .ok_or_else(|| AppError::NotFound(format!("File {} not found or access denied", document.file_id)))?;
+            if !CREDENTIAL_CONTENT_TYPES.contains(&file.content_type.as_str()) {
+                return Err(AppError::Validation(format!(
+                    "{} is not a PDF, JPEG or PNG document",
+                    file.file_name
+                )));
+            }
+        }
+
+        let now = Utc::now();
+        let verification = VerificationRequest {
+            id: Uuid::new_v4(),
+            tutor_id: tutor.id,
+            status: VerificationStatus::Pending,
+            note: request.note.map(|note| note.trim().to_string()).filter(|note| !note.is_empty()),
+            submitted_at: now,
+            reviewed_by: None,
+            reviewed_at: None,
+            decision_reason: None,
+        };
+        let documents: Vec<VerificationDocument> = request.documents
+            .iter()
+            .enumerate()
+            .map(|(position, document)| VerificationDocument {
+                id: Uuid::new_v4(),
+                request_id: verification.id,
+                kind: document.kind,
+                file_id: document.file_id,
+                position: position as i32,
+            })
+            .collect();
+        let event = VerificationEvent {
+            id: Uuid::new_v4(),
+            tutor_id: tutor.id,
+            request_id: Some(verification.id),
+            action: VerificationAction::Submitted,
+            actor_id: Some(user_id),
+            reason: None,
+            created_at: now,
+        };
+        repo.insert_verification_request(&verification, &documents, &event).await?;
+
+        Self::get_request_response(repo, files, verification.id, base_url).await
+    }
+
+    pub async fn get_own<R: TutorRepository + FileRepository + VerificationRepository>(
+        repo: &R,
+        files: &FileStore,
+        user_id: Uuid,
+        base_url: &str,
+    ) -> AppResult<TutorVerificationResponse> {
+        let tutor = repo.find_tutor_by_user_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor profile not found".to_string()))?
+            .tutor;
+
+        let mut requests = Vec::new();
+        for row in repo.list_tutor_verification_requests(tutor.id).await? {
+            requests.push(Self::response(repo, files, row, base_url).await?);
+        }
+        Ok(TutorVerificationResponse {
+            tutor_id: tutor.id,
+            is_verified: tutor.is_verified,
+            requests,
+            history: repo.list_verification_events(tutor.id).await?,
+        })
+    }
+
+    // Admins only
+    pub async fn list_queue<R: UserRepository + FileRepository + VerificationRepository>(
+        repo: &R,
+        files: &FileStore,
+        admin_id: Uuid,
+        query: VerificationQueueQuery,
+        base_url: &str,
+    ) -> AppResult<Vec<VerificationRequestResponse>> {
+        Self::require_admin(repo, admin_id).await?;
+
+        let status = query.status.unwrap_or(VerificationStatus::Pending);
+        let mut requests = Vec::new();
+        for row in repo.list_verification_requests(status).await? {
+            requests.push(Self::response(repo, files, row, base_url).await?);
+        }
+        Ok(requests)
+    }
+
+    // Admins only
+    pub async fn get_request<R: UserRepository + FileRepository + VerificationRepository>(
+        repo: &R,
+        files: &FileStore,
+        admin_id: Uuid,
+        request_id: Uuid,
+        base_url: &str,
+    ) -> AppResult<VerificationRequestResponse> {
+        Self::require_admin(repo, admin_id).await?;
+        Self::get_request_response(repo, files, request_id, base_url).await
+    }
+
+    // Admins only. Verifies the tutor.
+    pub async fn approve<R>(
+        repo: &R,
+        files: &FileStore,
+        admin_id: Uuid,
+        request_id: Uuid,
+        decision: VerificationDecisionRequest,
+        base_url: &str,
+    ) -> AppResult<VerificationRequestResponse>
+    where
+        R: UserRepository + FileRepository + VerificationRepository + NotificationRepository,
+    {
+        Self::decide(repo, files, admin_id, request_id, true, decision, base_url).await
+    }
+
+    // Admins only. The reason is required and sent to the tutor.
+    pub async fn reject<R>(
+        repo: &R,
+        files: &FileStore,
+        admin_id: Uuid,
+        request_id: Uuid,
+        decision: VerificationDecisionRequest,
+        base_url: &str,
+    ) -> AppResult<VerificationRequestResponse>
+    where
+        R: UserRepository + FileRepository + VerificationRepository + NotificationRepository,
+    {
+        Self::decide(repo, files, admin_id, request_id, false, decision, base_url).await
+    }
+
+    async fn decide<R>(
+        repo: &R,
+        files: &FileStore,
+        admin_id: Uuid,
+        request_id: Uuid,
+        approve: bool,
+        decision: VerificationDecisionRequest,
+        base_url: &str,
+    ) -> AppResult<VerificationRequestResponse>
+    where
+        R: UserRepository + FileRepository + VerificationRepository + NotificationRepository,
+    {
+        Self::require_admin(repo, admin_id).await?;
+        // Validate input
+        decision.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        let reason = decision.reason.map(|reason| reason.trim().to_string()).filter(|reason| !reason.is_empty());
+        if !approve && reason.is_none() {
+            return Err(AppError::Validation("A reason is required to reject a request".to_string()));
+        }
+
+        let row = repo.find_verification_request(request_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Verification request not found".to_string()))?;
+        if row.request.status != VerificationStatus::Pending {
+            return Err(AppError::Conflict("Verification request has already been decided".to_string()));
+        }
+
+        let now = Utc::now();
+        let (status, action) = if approve {
+            (VerificationStatus::Approved, VerificationAction::Approved)
+        } else {
+            (VerificationStatus::Rejected, VerificationAction::Rejected)
+        };
+        let decided = VerificationRequest {
+            status,
+            reviewed_by: Some(admin_id),
+            reviewed_at: Some(now),
+            decision_reason: reason.clone(),
+            ..row.request
+        };
+        let event = VerificationEvent {
+            id: Uuid::new_v4(),
+            tutor_id: decided.tutor_id,
+            request_id: Some(decided.id),
+            action,
+            actor_id: Some(admin_id),
+            reason: reason.clone(),
+            created_at: now,
+        };
+        if !repo.decide_verification_request(&decided, &event).await? {
+            return Err(AppError::Conflict("Verification request has already been decided".to_string()));
+        }
+
+        let (kind, title, body) = if approve {
+            (
+                "verification_approved",
+                "You are now a verified tutor".to_string(),
+                "Your credentials were approved. Your profile shows the verified badge.".to_string(),
+            )
+        } else {
+            (
+                "verification_rejected",
+                "Verification request rejected".to_string(),
+                format!("Your credentials were not approved: {}", reason.unwrap_or_default()),
+            )
+        };
+        NotificationService::notify(repo, row.tutor_user_id, kind, title, body, Some(MY_VERIFICATION_PATH.to_string())).await?;
+
+        Self::get_request_response(repo, files, request_id, base_url).await
+    }
+
+    // Admins only. Takes the badge away from a verified tutor, e.g. when a
+    // credential turns out to be forged.
+    pub async fn revoke<R>(
+        repo: &R,
+        admin_id: Uuid,
+        tutor_id: Uuid,
+        decision: VerificationDecisionRequest,
+    ) -> AppResult<TutorResponse>
+    where
+        R: UserRepository + TutorRepository + VerificationRepository + NotificationRepository,
+    {
+        Self::require_admin(repo, admin_id).await?;
+        // Validate input
+        decision.validate()
+            .map_err(|e| AppError::Validation(format!("Validation failed: {}", e)))?;
+        let reason = decision.reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty())
+            .ok_or_else(|| AppError::Validation("A reason is required to revoke verification".to_string()))?;
+
+        let tutor = repo.find_tutor_by_id(tutor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tutor not found".to_string()))?;
+        if !tutor.tutor.is_verified {
+            return Err(AppError::Conflict("Tutor is not verified".to_string()));
+        }
+
+        let event = VerificationEvent {
+            id: Uuid::new_v4(),
+            tutor_id,
+            request_id: None,
+            action: VerificationAction::Revoked,
+            actor_id: Some(admin_id),
+            reason: Some(reason.clone()),
+            created_at: Utc::now(),
+        };
+        repo.set_tutor_verified(tutor_id, false, &event).await?;
+        NotificationService::notify(
+            repo,
+            tutor.tutor.user_id,
+            "verification_revoked",
+            "Verification revoked".to_string(),
+            format!("Your verified badge was removed: {}", reason),
+            Some(MY_VERIFICATION_PATH.to_string()),
+        ).await?;
+
+        TutorService::get_tutor_by_id(repo, tutor_id).await
+    }
+
+    // Admins only
+    pub async fn history<R: UserRepository + TutorRepository + VerificationRepository>(
+        repo: &R,
+        admin_id: Uuid,
+        tutor_id: Uuid,
+    ) -> AppResult<Vec<VerificationEvent>> {
+        Self::require_admin(repo, admin_id).await?;
+        if repo.find_tutor_by_id(tutor_id).await?.is_none() {
+            return Err(AppError::NotFound("Tutor not found".to_string()));
+        }
+        repo.list_verification_events(tutor_id).await
+    }
+
+    async fn require_admin<R: UserRepository>(repo: &R, user_id: Uuid) -> AppResult<()> {
+        if !AuthService::verify_user_role(repo, user_id, UserRole::Admin).await? {
+            return Err(AppError::Authorization("Only admins can review tutor verification".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn get_request_response<R: FileRepository + VerificationRepository>(
+        repo: &R,
+        files: &FileStore,
+        request_id: Uuid,
+        base_url: &str,
+    ) -> AppResult<VerificationRequestResponse> {
+        let row = repo.find_verification_request(request_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Verification request not found".to_string()))?;
+        Self::response(repo, files, row, base_url).await
+    }
+
+    // Documents come with fresh download links for the reviewer
+    async fn response<R: FileRepository + VerificationRepository>(
+        repo: &R,
+        files: &FileStore,
+        row: VerificationRequestWithTutor,
+        base_url: &str,
+    ) -> AppResult<VerificationRequestResponse> {
+        let mut documents = Vec::new();
+        for document in repo.list_verification_documents(row.request.id).await? {
+            let file = repo.find_stored_file(document.file_id)
+                .await?
+                .ok_or_else(|| AppError::Internal(format!("Credential file {} is missing", document.file_id)))?;
+            documents.push(VerificationDocumentResponse {
+                kind: document.kind,
+                file: FileService::response(files, file, base_url),
+            });
+        }
+
+        let request = row.request;
+        Ok(VerificationRequestResponse {
+            id: request.id,
+            tutor_id: request.tutor_id,
+            tutor_name: format!("{} {}", row.tutor_first_name, row.tutor_last_name),
+            tutor_email: row.tutor_email,
+            status: request.status,
+            note: request.note,
+            documents,
+            submitted_at: request.submitted_at,
+            reviewed_by: request.reviewed_by,
+            reviewed_at: request.reviewed_at,
+            decision_reason: request.decision_reason,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CredentialDocumentRequest, CredentialKind, TutorReview};
+    use crate::repositories::{InMemoryRepository, ReviewRepository};
+    use crate::services::file::FileUpload;
+    use crate::services::test_support::{insert_tutor, insert_user};
+    use crate::storage::MemoryStorage;
+
+    const BASE_URL: &str = "http://localhost:8080/api/v1";
+
+    async fn upload_pdf(repo: &InMemoryRepository, files: &FileStore, owner_id: Uuid, name: &str) -> Uuid {
+        let upload = FileUpload {
+            file_name: name.to_string(),
+            content_type: "application/pdf".to_string(),
+            content: format!("%PDF-1.4 {}", name).into_bytes(),
+        };
+        FileService::upload(repo, files, owner_id, upload, BASE_URL).await.unwrap().id
+    }
+
+    fn submission(documents: &[(CredentialKind, Uuid)]) -> SubmitVerificationRequest {
+        SubmitVerificationRequest {
+            documents: documents
+                .iter()
+                .map(|&(kind, file_id)| CredentialDocumentRequest { kind, file_id })
+                .collect(),
+            note: Some("  Degree and passport attached  ".to_string()),
+        }
+    }
+
+    fn reason(text: &str) -> VerificationDecisionRequest {
+        VerificationDecisionRequest { reason: Some(text.to_string()) }
+    }
+
+    #[actix_rt::test]
+    async fn requests_are_reviewed_and_every_decision_is_kept() {
+        let repo = InMemoryRepository::new();
+        let files = FileStore::new(MemoryStorage::new(), b"secret");
+        let (user, tutor) = insert_tutor(&repo, &["Chemistry"]).await;
+        let admin = insert_user(&repo, UserRole::Admin).await;
+
+        let degree = upload_pdf(&repo, &files, user.id, "degree.pdf").await;
+        let first = VerificationService::submit(&repo, &files, user.id, submission(&[(CredentialKind::Degree, degree)]), BASE_URL)
+            .await
+            .unwrap();
+        assert_eq!(first.note.as_deref(), Some("Degree and passport attached"));
+        assert_eq!(first.documents[0].file.file_name, "degree.pdf");
+
+        // Attached documents leave the tutor's uploads and can no longer be deleted
+        assert!(FileService::list_files(&repo, &files, user.id, BASE_URL).await.unwrap().is_empty());
+        let error = FileService::delete_file(&repo, &files, degree, user.id).await.unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+
+        let passport = upload_pdf(&repo, &files, user.id, "passport.pdf").await;
+        let again = submission(&[(CredentialKind::Identity, passport)]);
+        let error = VerificationService::submit(&repo, &files, user.id, again.clone(), BASE_URL).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        // Only admins see the queue; rejections need a reason
+        let error = VerificationService::list_queue(&repo, &files, user.id, VerificationQueueQuery { status: None }, BASE_URL)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Authorization(_)));
+        let queue = VerificationService::list_queue(&repo, &files, admin.id, VerificationQueueQuery { status: None }, BASE_URL)
+            .await
+            .unwrap();
+        assert_eq!(queue.iter().map(|request| request.id).collect::<Vec<_>>(), vec![first.id]);
+        let error = VerificationService::reject(&repo, &files, admin.id, first.id, VerificationDecisionRequest::default(), BASE_URL)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+
+        let rejected = VerificationService::reject(&repo, &files, admin.id, first.id, reason("Scan is unreadable"), BASE_URL)
+            .await
+            .unwrap();
+        assert_eq!((rejected.status, rejected.reviewed_by), (VerificationStatus::Rejected, Some(admin.id)));
+        let error = VerificationService::approve(&repo, &files, admin.id, first.id, VerificationDecisionRequest::default(), BASE_URL)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+
+        let second = VerificationService::submit(&repo, &files, user.id, again, BASE_URL).await.unwrap();
+        VerificationService::approve(&repo, &files, admin.id, second.id, VerificationDecisionRequest::default(), BASE_URL)
+            .await
+            .unwrap();
+
+        let own = VerificationService::get_own(&repo, &files, user.id, BASE_URL).await.unwrap();
+        assert!(own.is_verified);
+        let statuses: Vec<_> = own.requests.iter().map(|request| request.status).collect();
+        assert_eq!(statuses, vec![VerificationStatus::Approved, VerificationStatus::Rejected]);
+        let actions: Vec<_> = own.history.iter().map(|event| event.action).collect();
+        assert_eq!(actions, vec![
+            VerificationAction::Submitted,
+            VerificationAction::Rejected,
+            VerificationAction::Submitted,
+            VerificationAction::Approved,
+        ]);
+        assert_eq!(own.history[1].reason.as_deref(), Some("Scan is unreadable"));
+
+        let kinds: Vec<_> = repo.list_notifications(user.id, false).await.unwrap().into_iter().map(|n| n.kind).collect();
+        assert!(kinds.contains(&"verification_rejected".to_string()));
+        assert!(kinds.contains(&"verification_approved".to_string()));
+
+        let error = VerificationService::submit(&repo, &files, user.id, submission(&[]), BASE_URL).await.unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+        let fresh = upload_pdf(&repo, &files, user.id, "award.pdf").await;
+        let error = VerificationService::submit(&repo, &files, user.id, submission(&[(CredentialKind::Other, fresh)]), BASE_URL)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)));
+        assert_eq!(VerificationService::history(&repo, admin.id, tutor.id).await.unwrap().len(), 4);
+    }
+
+    #[actix_rt::test]
+    async fn only_admins_can_decide_requests() {
+        let repo = InMemoryRepository::new();
+        let files = FileStore::new(MemoryStorage::new(), b"secret");
+        let (user, _) = insert_tutor(&repo, &["Physics"]).await;
+        let (other_tutor, _) = insert_tutor(&repo, &["Physics"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+
+        let degree = upload_pdf(&repo, &files, user.id, "degree.pdf").await;
+        let request = VerificationService::submit(&repo, &files, user.id, submission(&[(CredentialKind::Degree, degree)]), BASE_URL)
+            .await
+            .unwrap();
+
+        for actor in [user.id, other_tutor.id, student.id] {
+            let error = VerificationService::approve(&repo, &files, actor, request.id, VerificationDecisionRequest::default(), BASE_URL)
+                .await
+                .unwrap_err();
+            assert!(matches!(error, AppError::Authorization(_)));
+            let error = VerificationService::reject(&repo, &files, actor, request.id, reason("Not convincing"), BASE_URL)
+                .await
+                .unwrap_err();
+            assert!(matches!(error, AppError::Authorization(_)));
+        }
+
+        let own = VerificationService::get_own(&repo, &files, user.id, BASE_URL).await.unwrap();
+        assert!(!own.is_verified);
+        assert_eq!(own.requests[0].status, VerificationStatus::Pending);
+    }
+
+    #[actix_rt::test]
+    async fn verified_tutors_rank_first_until_revoked() {
+        let repo = InMemoryRepository::new();
+        let admin = insert_user(&repo, UserRole::Admin).await;
+        let (_, popular) = insert_tutor(&repo, &["Biology"]).await;
+        let (_, newcomer) = insert_tutor(&repo, &["Biology"]).await;
+        let student = insert_user(&repo, UserRole::Student).await;
+        repo.insert_review(&TutorReview {
+            id: Uuid::new_v4(),
+            tutor_id: popular.id,
+            student_id: student.id,
+            rating: 5,
+            comment: None,
+            created_at: Utc::now(),
+            edited_at: None,
+        }).await.unwrap();
+
+        let ranking = |tutors: Vec<TutorResponse>| tutors.into_iter().map(|tutor| tutor.id).collect::<Vec<_>>();
+        assert_eq!(ranking(TutorService::get_all_tutors(&repo).await.unwrap()), vec![popular.id, newcomer.id]);
+
+        let event = VerificationEvent {
+            id: Uuid::new_v4(),
+            tutor_id: newcomer.id,
+            request_id: None,
+            action: VerificationAction::Approved,
+            actor_id: None,
+            reason: None,
+            created_at: Utc::now(),
+        };
+        repo.set_tutor_verified(newcomer.id, true, &event).await.unwrap();
+        let search = TutorService::search_tutors_by_specialization(&repo, "Biology").await.unwrap();
+        assert_eq!(ranking(search), vec![newcomer.id, popular.id]);
+
+        let error = VerificationService::revoke(&repo, admin.id, newcomer.id, VerificationDecisionRequest::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+        let revoked = VerificationService::revoke(&repo, admin.id, newcomer.id, reason("Forged certificate")).await.unwrap();
+        assert!(!revoked.is_verified);
+        assert_eq!(ranking(TutorService::get_all_tutors(&repo).await.unwrap()), vec![popular.id, newcomer.id]);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::errors::{AppError, AppResult};
+use crate::storage::Storage;
+use async_trait::async_trait;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use tokio::fs;
+use uuid::Uuid;
+
+// Objects are files under `root`, one per key
+#[derive(Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalStorage { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> AppResult<PathBuf> {
+        // Keys never contain "." so they cannot climb out of the root
+        let valid = !key.is_empty()
+            && !key.starts_with('/')
+            && key.bytes().all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'/');
+        if !valid {
+            return Err(AppError::Internal(format!("Invalid storage key {:?}", key)));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+fn io_error(error: std::io::Error) -> AppError {
+    AppError::Internal(format!("File storage error: {}", error))
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, content: Vec<u8>, _content_type: &str) -> AppResult<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await.map_err(io_error)?;
+        }
+
+        // Written aside and renamed so readers never see a partial file
+        let partial = path.with_file_name(format!(".{}.partial", Uuid::new_v4()));
+        fs::write(&partial, content).await.map_err(io_error)?;
+        if let Err(error) = fs::rename(&partial, &path).await {
+            let _ = fs::remove_file(&partial).await;
+            return Err(io_error(error));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        match fs::read(self.path(key)?).await {
+            Ok(content) => Ok(Some(content)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(io_error(error)),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> AppResult<bool> {
+        fs::try_exists(self.path(key)?).await.map_err(io_error)
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(error) if error.kind() != ErrorKind::NotFound => Err(io_error(error)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{content_hash, content_key};
+
+    #[actix_rt::test]
+    async fn objects_round_trip_through_the_file_system() {
+        let root = std::env::temp_dir().join(format!("ezytutor-storage-{}", Uuid::new_v4()));
+        let storage = LocalStorage::new(&root);
+        let key = content_key(&content_hash(b"hello"));
+
+        assert_eq!(storage.get(&key).await.unwrap(), None);
+        storage.put(&key, b"hello".to_vec(), "text/plain").await.unwrap();
+        assert!(storage.exists(&key).await.unwrap());
+        assert_eq!(storage.get(&key).await.unwrap().as_deref(), Some(&b"hello"[..]));
+        assert!(root.join(&key).is_file());
+
+        storage.delete(&key).await.unwrap();
+        storage.delete(&key).await.unwrap();
+        assert!(!storage.exists(&key).await.unwrap());
+
+        assert!(storage.get("../etc/passwd").await.is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
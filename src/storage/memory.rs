@@ -0,0 +1,54 @@
+use crate::errors::AppResult;
+use crate::storage::Storage;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+// Keeps objects in process memory, for the test server and tests
+#[derive(Clone, Default)]
+pub struct MemoryStorage {
+    objects: Arc<RwLock<HashMap<String, Vec<u8>>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn object_count(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn clear(&self) {
+        self.write().clear();
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, Vec<u8>>> {
+        self.objects.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<String, Vec<u8>>> {
+        self.objects.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+    async fn put(&self, key: &str, content: Vec<u8>, _content_type: &str) -> AppResult<()> {
+        self.write().insert(key.to_string(), content);
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        Ok(self.read().get(key).cloned())
+    }
+
+    async fn exists(&self, key: &str) -> AppResult<bool> {
+        Ok(self.read().contains_key(key))
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        self.write().remove(key);
+        Ok(())
+    }
+}
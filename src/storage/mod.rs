@@ -0,0 +1,138 @@
+pub mod local;
+pub mod memory;
+pub mod s3;
+
+pub use local::LocalStorage;
+pub use memory::MemoryStorage;
+pub use s3::S3Storage;
+
+use crate::config::StorageConfig;
+use crate::errors::AppResult;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tokio::sync::{Mutex, MutexGuard};
+use uuid::Uuid;
+
+// How long a signed download URL stays valid
+pub const DOWNLOAD_URL_TTL_MINUTES: i64 = 15;
+
+// A flat key/value blob store. Keys are ASCII paths made of lowercase
+// letters, digits and '/', as produced by `content_key`.
+#[async_trait]
+pub trait Storage: Send + Sync + 'static {
+    async fn put(&self, key: &str, content: Vec<u8>, content_type: &str) -> AppResult<()>;
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>>;
+    async fn exists(&self, key: &str) -> AppResult<bool>;
+    // Deleting a missing key is not an error
+    async fn delete(&self, key: &str) -> AppResult<()>;
+}
+
+// Content is stored under its SHA-256, so identical uploads share one object
+pub fn content_hash(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+pub fn content_key(hash: &str) -> String {
+    format!("{}/{}", &hash[..2], hash)
+}
+
+// Signing key for download URLs taken from another secret, so a signature
+// made for one purpose is never valid for the other
+pub fn derive_download_key(secret: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(b"ezytutor download links");
+    mac.finalize().into_bytes().to_vec()
+}
+
+// The configured backend plus the key used to sign download URLs. Handlers
+// get it as app data next to the repository.
+#[derive(Clone)]
+pub struct FileStore {
+    backend: Arc<dyn Storage>,
+    signing_key: Arc<[u8]>,
+    // One lock per leading hash byte; see `lock_content`
+    content_locks: Arc<[Mutex<()>]>,
+}
+
+impl FileStore {
+    pub fn new(backend: impl Storage, signing_key: &[u8]) -> Self {
+        FileStore {
+            backend: Arc::new(backend),
+            signing_key: Arc::from(signing_key),
+            content_locks: (0..256).map(|_| Mutex::new(())).collect(),
+        }
+    }
+
+    pub fn from_config(config: &StorageConfig, signing_key: &[u8]) -> AppResult<Self> {
+        Ok(match config {
+            StorageConfig::Local { root } => FileStore::new(LocalStorage::new(root), signing_key),
+            StorageConfig::S3 { endpoint, bucket, region, access_key, secret_key } => FileStore::new(
+                S3Storage::new(endpoint, bucket, region, access_key, secret_key)?,
+                signing_key,
+            ),
+        })
+    }
+
+    pub fn backend(&self) -> &dyn Storage {
+        self.backend.as_ref()
+    }
+
+    // Held while a file claims or releases the content with this hash, so a
+    // removal never deletes content a new file has just started to share
+    pub async fn lock_content(&self, hash: &str) -> MutexGuard<'_, ()> {
+        let stripe = u8::from_str_radix(&hash[..2], 16).unwrap_or_default();
+        self.content_locks[stripe as usize].lock().await
+    }
+
+    // Query string for a download link to `file_id` that expires after the TTL
+    pub fn sign_download(&self, file_id: Uuid, now: DateTime<Utc>) -> (String, DateTime<Utc>) {
+        let expires_at = now + Duration::minutes(DOWNLOAD_URL_TTL_MINUTES);
+        let expires = expires_at.timestamp();
+        let signature = hex::encode(self.mac(file_id, expires).finalize().into_bytes());
+        (format!("expires={}&signature={}", expires, signature), expires_at)
+    }
+
+    pub fn verify_download(&self, file_id: Uuid, expires: i64, signature: &str, now: DateTime<Utc>) -> bool {
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+        // Checked even for expired links so timing does not reveal which failed
+        let valid = self.mac(file_id, expires).verify_slice(&signature).is_ok();
+        valid && now.timestamp() <= expires
+    }
+
+    fn mac(&self, file_id: Uuid, expires: i64) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.signing_key).expect("HMAC accepts keys of any length");
+        mac.update(format!("{}:{}", file_id, expires).as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn download_signatures_expire_and_bind_the_file() {
+        let store = FileStore::new(MemoryStorage::new(), b"secret");
+        let file_id = Uuid::new_v4();
+        let now = Utc::now();
+        let (query, expires_at) = store.sign_download(file_id, now);
+        let signature = query.split("signature=").nth(1).unwrap();
+        let expires = expires_at.timestamp();
+
+        assert!(store.verify_download(file_id, expires, signature, now));
+        assert!(!store.verify_download(file_id, expires + 60, signature, now));
+        assert!(!store.verify_download(Uuid::new_v4(), expires, signature, now));
+        assert!(!store.verify_download(file_id, expires, "not-hex", now));
+        assert!(!store.verify_download(file_id, expires, signature, expires_at + Duration::seconds(1)));
+
+        let other = FileStore::new(MemoryStorage::new(), b"other secret");
+        assert!(!other.verify_download(file_id, expires, signature, now));
+        let derived = FileStore::new(MemoryStorage::new(), &derive_download_key(b"secret"));
+        assert!(!derived.verify_download(file_id, expires, signature, now));
+    }
+}